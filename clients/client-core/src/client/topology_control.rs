@@ -1,7 +1,10 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crypto::asymmetric::identity;
+use futures::future::join_all;
 use log::*;
+use mixnet_contract_common::{GatewayBond, MixNodeBond};
 use nymsphinx::addressing::clients::Recipient;
 use nymsphinx::params::DEFAULT_NUM_MIX_HOPS;
 use rand::seq::SliceRandom;
@@ -14,6 +17,8 @@ use tokio::sync::{RwLock, RwLockReadGuard};
 use tokio::task::JoinHandle;
 use topology::{nym_topology_from_bonds, NymTopology};
 use url::Url;
+use validator_client::validator_api::signed_topology::{agreed_topology, verify_topology_snapshot};
+use validator_client::validator_api::Versioned;
 
 // I'm extremely curious why compiler NEVER complained about lack of Debug here before
 #[derive(Debug)]
@@ -130,6 +135,9 @@ pub struct TopologyRefresherConfig {
     validator_api_urls: Vec<Url>,
     refresh_rate: time::Duration,
     client_version: String,
+    trusted_validator_api_keys: Vec<identity::PublicKey>,
+    required_topology_signatures: usize,
+    max_topology_snapshot_age: time::Duration,
 }
 
 impl TopologyRefresherConfig {
@@ -137,17 +145,55 @@ impl TopologyRefresherConfig {
         validator_api_urls: Vec<Url>,
         refresh_rate: time::Duration,
         client_version: String,
+        trusted_validator_api_keys: Vec<identity::PublicKey>,
+        required_topology_signatures: usize,
+        max_topology_snapshot_age: time::Duration,
     ) -> Self {
         TopologyRefresherConfig {
             validator_api_urls,
             refresh_rate,
             client_version,
+            trusted_validator_api_keys,
+            required_topology_signatures,
+            max_topology_snapshot_age,
         }
     }
 }
 
+// most recently retrieved bonds alongside their entity tag, so that they would not need to be
+// downloaded again if they have not changed
+struct CachedBonds<T> {
+    etag: Option<String>,
+    bonds: Vec<T>,
+}
+
+fn cached_etag<T>(cached: &Option<CachedBonds<T>>) -> Option<&str> {
+    cached.as_ref().and_then(|cached| cached.etag.as_deref())
+}
+
+fn resolve_bonds<T: Clone>(
+    cached: &mut Option<CachedBonds<T>>,
+    response: Versioned<Vec<T>>,
+) -> Vec<T> {
+    match response {
+        Versioned::Changed { value, etag } => {
+            *cached = Some(CachedBonds {
+                etag,
+                bonds: value.clone(),
+            });
+            value
+        }
+        Versioned::Unchanged => cached
+            .as_ref()
+            .map(|cached| cached.bonds.clone())
+            .unwrap_or_default(),
+    }
+}
+
 pub struct TopologyRefresher {
     validator_client: validator_client::ApiClient,
+    cached_mixnodes: Option<CachedBonds<MixNodeBond>>,
+    cached_gateways: Option<CachedBonds<GatewayBond>>,
     client_version: String,
 
     validator_api_urls: Vec<Url>,
@@ -156,20 +202,51 @@ pub struct TopologyRefresher {
 
     currently_used_api: usize,
     was_latest_valid: bool,
+
+    trusted_validator_api_keys: Vec<identity::PublicKey>,
+    /// Number of distinct validator APIs that have to agree on a node before it's put in
+    /// the topology. If 0, the topology is retrieved from a single validator API without
+    /// any verification.
+    required_topology_signatures: usize,
+    max_topology_snapshot_age: Duration,
+    /// The most recent epoch seen in an accepted set of topology snapshots. Snapshots from
+    /// earlier epochs are rejected so that a stale view of the network could not be replayed.
+    latest_topology_epoch: Option<u32>,
 }
 
 impl TopologyRefresher {
     pub fn new(mut cfg: TopologyRefresherConfig, topology_accessor: TopologyAccessor) -> Self {
         cfg.validator_api_urls.shuffle(&mut thread_rng());
 
+        // otherwise snapshots signed by arbitrary keys would have been accepted
+        assert!(
+            cfg.required_topology_signatures == 0 || !cfg.trusted_validator_api_keys.is_empty(),
+            "{} topology signatures are required, but no trusted validator API keys were provided",
+            cfg.required_topology_signatures
+        );
+
+        if cfg.required_topology_signatures > cfg.validator_api_urls.len() {
+            warn!(
+                "{} topology signatures are required, but only {} validator APIs are known - it won't be possible to obtain a valid topology",
+                cfg.required_topology_signatures,
+                cfg.validator_api_urls.len()
+            );
+        }
+
         TopologyRefresher {
             validator_client: validator_client::ApiClient::new(cfg.validator_api_urls[0].clone()),
+            cached_mixnodes: None,
+            cached_gateways: None,
             client_version: cfg.client_version,
             validator_api_urls: cfg.validator_api_urls,
             topology_accessor,
             refresh_rate: cfg.refresh_rate,
             currently_used_api: 0,
             was_latest_valid: true,
+            trusted_validator_api_keys: cfg.trusted_validator_api_keys,
+            required_topology_signatures: cfg.required_topology_signatures,
+            max_topology_snapshot_age: cfg.max_topology_snapshot_age,
+            latest_topology_epoch: None,
         }
     }
 
@@ -243,25 +320,107 @@ impl TopologyRefresher {
         true
     }
 
-    async fn get_current_compatible_topology(&self) -> Option<NymTopology> {
+    async fn get_current_bonds(&mut self) -> Option<(Vec<MixNodeBond>, Vec<GatewayBond>)> {
         // TODO: optimization for the future:
         // only refresh mixnodes on timer and refresh gateways only when
         // we have to send to a new, unknown, gateway
 
-        let mixnodes = match self.validator_client.get_cached_active_mixnodes().await {
+        let mixnodes = match self
+            .validator_client
+            .get_cached_active_mixnodes_if_changed(cached_etag(&self.cached_mixnodes))
+            .await
+        {
             Err(err) => {
                 error!("failed to get network mixnodes - {}", err);
                 return None;
             }
-            Ok(mixes) => mixes,
+            Ok(mixes) => resolve_bonds(&mut self.cached_mixnodes, mixes),
         };
 
-        let gateways = match self.validator_client.get_cached_gateways().await {
+        let gateways = match self
+            .validator_client
+            .get_cached_gateways_if_changed(cached_etag(&self.cached_gateways))
+            .await
+        {
             Err(err) => {
                 error!("failed to get network gateways - {}", err);
                 return None;
             }
-            Ok(gateways) => gateways,
+            Ok(gateways) => resolve_bonds(&mut self.cached_gateways, gateways),
+        };
+
+        Some((mixnodes, gateways))
+    }
+
+    /// Retrieves signed topology snapshots from all known validator APIs and returns only
+    /// the active mixnodes and gateways that at least `required_topology_signatures` distinct
+    /// signers agree on.
+    async fn get_agreed_bonds(&mut self) -> Option<(Vec<MixNodeBond>, Vec<GatewayBond>)> {
+        let responses = join_all(self.validator_api_urls.iter().map(|url| async move {
+            let client = validator_client::ApiClient::new(url.clone());
+            (url, client.get_signed_topology().await)
+        }))
+        .await;
+
+        let mut signers = Vec::new();
+        let mut snapshots = Vec::new();
+        for (url, response) in responses {
+            let signed = match response {
+                Ok(signed) => signed,
+                Err(err) => {
+                    warn!("failed to get signed topology from {} - {}", url, err);
+                    continue;
+                }
+            };
+
+            match verify_topology_snapshot(
+                &signed,
+                &self.trusted_validator_api_keys,
+                self.max_topology_snapshot_age,
+                self.latest_topology_epoch,
+            ) {
+                Ok((signer, _)) if signers.contains(&signer) => {
+                    warn!(
+                        "{} has served a topology snapshot signed by an already seen key {}",
+                        url, signer
+                    );
+                }
+                Ok((signer, snapshot)) => {
+                    signers.push(signer);
+                    snapshots.push(snapshot);
+                }
+                Err(err) => warn!("rejecting topology snapshot from {} - {}", url, err),
+            }
+        }
+
+        if snapshots.len() < self.required_topology_signatures {
+            error!(
+                "only {} validator APIs have provided valid topology snapshots while {} are required",
+                snapshots.len(),
+                self.required_topology_signatures
+            );
+            return None;
+        }
+
+        let latest_epoch = snapshots
+            .iter()
+            .filter_map(|snapshot| snapshot.epoch.map(|epoch| epoch.id()))
+            .max();
+        if latest_epoch > self.latest_topology_epoch {
+            self.latest_topology_epoch = latest_epoch;
+        }
+
+        Some(agreed_topology(
+            &snapshots,
+            self.required_topology_signatures,
+        ))
+    }
+
+    async fn get_current_compatible_topology(&mut self) -> Option<NymTopology> {
+        let (mixnodes, gateways) = if self.required_topology_signatures > 0 {
+            self.get_agreed_bonds().await?
+        } else {
+            self.get_current_bonds().await?
         };
 
         let mixnodes_count = mixnodes.len();
@@ -3,6 +3,8 @@
 
 use config::defaults::*;
 use config::NymConfig;
+use crypto::asymmetric::identity;
+use log::warn;
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 use std::path::PathBuf;
@@ -22,6 +24,7 @@ const DEFAULT_MESSAGE_STREAM_AVERAGE_DELAY: Duration = Duration::from_millis(20)
 const DEFAULT_AVERAGE_PACKET_DELAY: Duration = Duration::from_millis(50);
 const DEFAULT_TOPOLOGY_REFRESH_RATE: Duration = Duration::from_secs(5 * 60); // every 5min
 const DEFAULT_TOPOLOGY_RESOLUTION_TIMEOUT: Duration = Duration::from_millis(5_000);
+const DEFAULT_MAX_TOPOLOGY_SNAPSHOT_AGE: Duration = Duration::from_secs(5 * 60);
 // Set this to a high value for now, so that we don't risk sporadic timeouts that might cause
 // bought bandwidth tokens to not have time to be spent; Once we remove the gateway from the
 // bandwidth bridging protocol, we can come back to a smaller timeout value
@@ -194,6 +197,34 @@ impl<T: NymConfig> Config<T> {
         self.client.validator_api_urls.clone()
     }
 
+    pub fn get_trusted_validator_api_keys(&self) -> Vec<identity::PublicKey> {
+        self.client
+            .trusted_validator_api_keys
+            .iter()
+            .filter_map(|key| match identity::PublicKey::from_base58_string(key) {
+                Ok(key) => Some(key),
+                Err(err) => {
+                    warn!(
+                        "Ignoring malformed trusted validator API key {} - {}",
+                        key, err
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn get_required_topology_signatures(&self) -> usize {
+        self.client.required_topology_signatures
+    }
+
+    /// Checks whether the topology verification can actually be performed, i.e. if signed
+    /// topology snapshots are required, there has to be at least one valid trusted key.
+    pub fn has_valid_topology_verification(&self) -> bool {
+        self.get_required_topology_signatures() == 0
+            || !self.get_trusted_validator_api_keys().is_empty()
+    }
+
     pub fn get_gateway_id(&self) -> String {
         self.client.gateway_endpoint.gateway_id.clone()
     }
@@ -257,6 +288,10 @@ impl<T: NymConfig> Config<T> {
         self.debug.topology_resolution_timeout
     }
 
+    pub fn get_max_topology_snapshot_age(&self) -> Duration {
+        self.debug.max_topology_snapshot_age
+    }
+
     pub fn get_version(&self) -> &str {
         &self.client.version
     }
@@ -302,6 +337,16 @@ pub struct Client<T> {
     /// Addresses to APIs running on validator from which the client gets the view of the network.
     validator_api_urls: Vec<Url>,
 
+    /// Base58 encoded identity keys of the validator APIs whose signed topology snapshots are
+    /// trusted. It must not be empty if `required_topology_signatures` is non-zero.
+    #[serde(default)]
+    trusted_validator_api_keys: Vec<String>,
+
+    /// Number of validator APIs that have to agree on the details of a node before it is used
+    /// in the topology. 0 disables the verification of the signed topology snapshots.
+    #[serde(default)]
+    required_topology_signatures: usize,
+
     /// Path to file containing private identity key.
     private_identity_key_file: PathBuf,
 
@@ -356,6 +401,8 @@ impl<T: NymConfig> Default for Client<T> {
             id: "".to_string(),
             disabled_credentials_mode: true,
             validator_api_urls: default_api_endpoints(),
+            trusted_validator_api_keys: Vec::new(),
+            required_topology_signatures: 0,
             private_identity_key_file: Default::default(),
             public_identity_key_file: Default::default(),
             private_encryption_key_file: Default::default(),
@@ -467,6 +514,10 @@ pub struct Debug {
     /// did not reach its destination.
     #[serde(with = "humantime_serde")]
     topology_resolution_timeout: Duration,
+
+    /// Maximum age of a signed topology snapshot for it to still be accepted.
+    #[serde(with = "humantime_serde")]
+    max_topology_snapshot_age: Duration,
 }
 
 impl Default for Debug {
@@ -481,6 +532,7 @@ impl Default for Debug {
             gateway_response_timeout: DEFAULT_GATEWAY_RESPONSE_TIMEOUT,
             topology_refresh_rate: DEFAULT_TOPOLOGY_REFRESH_RATE,
             topology_resolution_timeout: DEFAULT_TOPOLOGY_RESOLUTION_TIMEOUT,
+            max_topology_snapshot_age: DEFAULT_MAX_TOPOLOGY_SNAPSHOT_AGE,
         }
     }
 }
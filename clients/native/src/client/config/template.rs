@@ -30,6 +30,18 @@ validator_api_urls = [
     {{/each}}
 ]
 
+# Base58 encoded identity keys of the validator APIs whose signed topology snapshots are trusted.
+# It must not be empty if `required_topology_signatures` is non-zero.
+trusted_validator_api_keys = [
+    {{#each client.trusted_validator_api_keys }}
+        '{{this}}',
+    {{/each}}
+]
+
+# Number of validator APIs that have to agree on the details of a node before it is used
+# in the topology. 0 disables the verification of the signed topology snapshots.
+required_topology_signatures = {{ client.required_topology_signatures }}
+
 # Path to file containing private identity key.
 private_identity_key_file = '{{ client.private_identity_key_file }}'
 
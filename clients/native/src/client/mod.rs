@@ -217,6 +217,9 @@ impl NymClient {
             self.config.get_base().get_validator_api_endpoints(),
             self.config.get_base().get_topology_refresh_rate(),
             env!("CARGO_PKG_VERSION").to_string(),
+            self.config.get_base().get_trusted_validator_api_keys(),
+            self.config.get_base().get_required_topology_signatures(),
+            self.config.get_base().get_max_topology_snapshot_age(),
         );
         let mut topology_refresher =
             TopologyRefresher::new(topology_refresher_config, topology_accessor);
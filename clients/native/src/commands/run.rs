@@ -102,5 +102,10 @@ pub async fn execute(matches: ArgMatches<'static>) {
         return;
     }
 
+    if !config.get_base().has_valid_topology_verification() {
+        error!("signed topology snapshots are required, but no valid trusted validator API keys were provided - please set `trusted_validator_api_keys` or set `required_topology_signatures` to 0");
+        return;
+    }
+
     NymClient::new(config).run_forever().await;
 }
@@ -66,6 +66,11 @@ impl Config {
         self
     }
 
+    pub fn with_provider_connection_status(mut self, reports_status: bool) -> Self {
+        self.socks5.provider_reports_connection_status = reports_status;
+        self
+    }
+
     // getters
     pub fn get_config_file_save_location(&self) -> PathBuf {
         self.config_directory().join(Self::config_file_name())
@@ -87,6 +92,10 @@ impl Config {
     pub fn get_listening_port(&self) -> u16 {
         self.socks5.listening_port
     }
+
+    pub fn get_provider_reports_connection_status(&self) -> bool {
+        self.socks5.provider_reports_connection_status
+    }
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
@@ -97,6 +106,11 @@ pub struct Socks5 {
 
     /// The mix address of the provider to which all requests are going to be sent.
     provider_mix_address: String,
+
+    /// Whether the provider is able to tell us if it managed to establish the requested
+    /// connection. If it isn't, we optimistically assume every connection succeeds.
+    #[serde(default)]
+    provider_reports_connection_status: bool,
 }
 
 impl Socks5 {
@@ -104,6 +118,7 @@ impl Socks5 {
         Socks5 {
             listening_port: DEFAULT_SOCKS5_LISTENING_PORT,
             provider_mix_address: provider_mix_address.into(),
+            provider_reports_connection_status: false,
         }
     }
 }
@@ -113,6 +128,7 @@ impl Default for Socks5 {
         Socks5 {
             listening_port: DEFAULT_SOCKS5_LISTENING_PORT,
             provider_mix_address: "".into(),
+            provider_reports_connection_status: false,
         }
     }
 }
@@ -30,6 +30,18 @@ validator_api_urls = [
     {{/each}}
 ]
 
+# Base58 encoded identity keys of the validator APIs whose signed topology snapshots are trusted.
+# It must not be empty if `required_topology_signatures` is non-zero.
+trusted_validator_api_keys = [
+    {{#each client.trusted_validator_api_keys }}
+        '{{this}}',
+    {{/each}}
+]
+
+# Number of validator APIs that have to agree on the details of a node before it is used
+# in the topology. 0 disables the verification of the signed topology snapshots.
+required_topology_signatures = {{ client.required_topology_signatures }}
+
 # Path to file containing private identity key.
 private_identity_key_file = '{{ client.private_identity_key_file }}'
 
@@ -88,6 +100,11 @@ gateway_listener = '{{ client.gateway_endpoint.gateway_listener }}'
 # The mix address of the provider to which all requests are going to be sent.
 provider_mix_address = '{{ socks5.provider_mix_address }}'
 
+# Whether the provider reports if it managed to establish the requested connections.
+# Only enable it if the provider is running a network requester that supports it,
+# otherwise all connections are going to time out.
+provider_reports_connection_status = {{ socks5.provider_reports_connection_status }}
+
 # The port on which the client will be listening for incoming requests
 listening_port = {{ socks5.listening_port }}
 
@@ -205,6 +205,9 @@ impl NymClient {
             self.config.get_base().get_validator_api_endpoints(),
             self.config.get_base().get_topology_refresh_rate(),
             env!("CARGO_PKG_VERSION").to_string(),
+            self.config.get_base().get_trusted_validator_api_keys(),
+            self.config.get_base().get_required_topology_signatures(),
+            self.config.get_base().get_max_topology_snapshot_age(),
         );
         let mut topology_refresher =
             TopologyRefresher::new(topology_refresher_config, topology_accessor);
@@ -252,6 +255,7 @@ impl NymClient {
             self.config.get_listening_port(),
             authenticator,
             self.config.get_provider_mix_address(),
+            self.config.get_provider_reports_connection_status(),
             self.as_mix_recipient(),
         );
         tokio::spawn(async move { sphinx_socks.serve(msg_input, buffer_requester).await });
@@ -18,7 +18,7 @@ use topology::{filter::VersionFilterable, gateway};
 use url::Url;
 
 use crate::client::config::Config;
-use crate::commands::override_config;
+use crate::commands::{override_config, PROVIDER_REPORTS_STATUS_ARG_NAME};
 #[cfg(feature = "eth")]
 #[cfg(not(feature = "coconut"))]
 use crate::commands::{
@@ -41,6 +41,10 @@ pub fn command_args<'a, 'b>() -> clap::App<'a, 'b> {
             .takes_value(true)
             .required(true)
         )
+        .arg(Arg::with_name(PROVIDER_REPORTS_STATUS_ARG_NAME)
+            .long(PROVIDER_REPORTS_STATUS_ARG_NAME)
+            .help("Wait for the provider to report whether it managed to establish each connection. Only use it with providers that support it.")
+        )
         .arg(Arg::with_name("gateway")
             .long("gateway")
             .help("Id of the gateway we are going to connect to.")
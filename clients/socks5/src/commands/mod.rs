@@ -10,6 +10,7 @@ pub(crate) mod run;
 pub(crate) mod upgrade;
 
 pub(crate) const ENABLED_CREDENTIALS_MODE_ARG_NAME: &str = "enabled-credentials-mode";
+pub(crate) const PROVIDER_REPORTS_STATUS_ARG_NAME: &str = "provider-reports-status";
 #[cfg(not(feature = "coconut"))]
 pub(crate) const ETH_ENDPOINT_ARG_NAME: &str = "eth_endpoint";
 #[cfg(not(feature = "coconut"))]
@@ -51,6 +52,10 @@ pub(crate) fn override_config(mut config: Config, matches: &ArgMatches<'_>) -> C
         config = config.with_port(port.unwrap());
     }
 
+    if matches.is_present(PROVIDER_REPORTS_STATUS_ARG_NAME) {
+        config = config.with_provider_connection_status(true);
+    }
+
     #[cfg(not(feature = "coconut"))]
     if let Some(eth_endpoint) = matches.value_of(ETH_ENDPOINT_ARG_NAME) {
         config.get_base_mut().with_eth_endpoint(eth_endpoint);
@@ -3,7 +3,7 @@
 
 use crate::client::config::Config;
 use crate::client::NymClient;
-use crate::commands::override_config;
+use crate::commands::{override_config, PROVIDER_REPORTS_STATUS_ARG_NAME};
 #[cfg(feature = "eth")]
 #[cfg(not(feature = "coconut"))]
 use crate::commands::{
@@ -34,6 +34,10 @@ pub fn command_args<'a, 'b>() -> clap::App<'a, 'b> {
             .help("Address of the socks5 provider to send messages to.")
             .takes_value(true)
         )
+        .arg(Arg::with_name(PROVIDER_REPORTS_STATUS_ARG_NAME)
+            .long(PROVIDER_REPORTS_STATUS_ARG_NAME)
+            .help("Wait for the provider to report whether it managed to establish each connection. Only use it with providers that support it.")
+        )
         .arg(Arg::with_name("validators")
                 .long("validators")
                 .help("Comma separated list of rest endpoints of the validators")
@@ -108,5 +112,10 @@ pub async fn execute(matches: ArgMatches<'static>) {
         return;
     }
 
+    if !config.get_base().has_valid_topology_verification() {
+        error!("signed topology snapshots are required, but no valid trusted validator API keys were provided - please set `trusted_validator_api_keys` or set `required_topology_signatures` to 0");
+        return;
+    }
+
     NymClient::new(config).run_forever().await;
 }
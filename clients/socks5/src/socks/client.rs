@@ -1,6 +1,7 @@
 #![forbid(unsafe_code)]
 
 use super::authentication::{AuthenticationMethods, Authenticator, User};
+use super::mixnet_responses::PendingConnections;
 use super::request::{SocksCommand, SocksRequest};
 use super::types::{ResponseCode, SocksProxyError};
 use super::{RESERVED, SOCKS_VERSION};
@@ -20,9 +21,14 @@ use socks5_requests::{ConnectionId, Message, RemoteAddress, Request};
 use std::io;
 use std::net::SocketAddr;
 use std::pin::Pin;
+use std::time::Duration;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
 use tokio::{self, net::TcpStream};
 
+// how long we're willing to wait for the network requester to tell us whether it managed to
+// establish the requested connection before giving up on it
+const CONNECTION_RESULT_TIMEOUT: Duration = Duration::from_secs(60);
+
 #[pin_project(project = StateProject)]
 enum StreamState {
     Available(TcpStream),
@@ -131,6 +137,7 @@ impl AsyncWrite for StreamState {
 /// SphinxSocksServer.
 pub(crate) struct SocksClient {
     controller_sender: ControllerSender,
+    pending_connections: PendingConnections,
     stream: StreamState,
     auth_nmethods: u8,
     authenticator: Authenticator,
@@ -138,6 +145,7 @@ pub(crate) struct SocksClient {
     input_sender: InputMessageSender,
     connection_id: ConnectionId,
     service_provider: Recipient,
+    provider_reports_status: bool,
     self_address: Recipient,
     started_proxy: bool,
 }
@@ -145,6 +153,7 @@ pub(crate) struct SocksClient {
 impl Drop for SocksClient {
     fn drop(&mut self) {
         debug!("Connection {} is getting closed", self.connection_id);
+        self.pending_connections.remove(self.connection_id);
         // if we never managed to start a proxy, the entry will not exist in the controller
         if self.started_proxy {
             self.controller_sender
@@ -161,12 +170,15 @@ impl SocksClient {
         authenticator: Authenticator,
         input_sender: InputMessageSender,
         service_provider: Recipient,
+        provider_reports_status: bool,
         controller_sender: ControllerSender,
+        pending_connections: PendingConnections,
         self_address: Recipient,
     ) -> Self {
         let connection_id = Self::generate_random();
         SocksClient {
             controller_sender,
+            pending_connections,
             connection_id,
             stream: StreamState::Available(stream),
             auth_nmethods: 0,
@@ -174,6 +186,7 @@ impl SocksClient {
             authenticator,
             input_sender,
             service_provider,
+            provider_reports_status,
             self_address,
             started_proxy: false,
         }
@@ -223,17 +236,62 @@ impl SocksClient {
     }
 
     async fn send_connect_to_mixnet(&mut self, remote_address: RemoteAddress) {
-        let req = Request::new_connect(self.connection_id, remote_address, self.self_address);
+        let req = if self.provider_reports_status {
+            Request::new_connect_with_status(self.connection_id, remote_address, self.self_address)
+        } else {
+            Request::new_connect(self.connection_id, remote_address, self.self_address)
+        };
         let msg = Message::Request(req);
 
         let input_message = InputMessage::new_fresh(self.service_provider, msg.into_bytes(), false);
         self.input_sender.unbounded_send(input_message).unwrap();
     }
 
-    async fn run_proxy(&mut self, conn_receiver: ConnectionReceiver, remote_proxy_target: String) {
-        self.send_connect_to_mixnet(remote_proxy_target.clone())
-            .await;
+    // closes the connection on the network requester side, if it ever got established
+    fn send_close_to_mixnet(&mut self) {
+        let req = Request::new_send(self.connection_id, Vec::new(), true);
+        let msg = Message::Request(req);
+
+        let input_message = InputMessage::new_fresh(self.service_provider, msg.into_bytes(), false);
+        self.input_sender.unbounded_send(input_message).unwrap();
+    }
+
+    /// Requests the network requester to connect to the remote address and waits for the result
+    /// of that attempt, so that it could be relayed to the application.
+    /// Providers that can't report the result are optimistically assumed to always succeed,
+    /// if they don't, they are going to close the connection straight away instead.
+    async fn connect_through_mixnet(&mut self, remote_address: RemoteAddress) -> ResponseCode {
+        if !self.provider_reports_status {
+            self.send_connect_to_mixnet(remote_address).await;
+            return ResponseCode::Success;
+        }
+
+        let connection_result = self.pending_connections.register(self.connection_id);
+        self.send_connect_to_mixnet(remote_address.clone()).await;
 
+        match tokio::time::timeout(CONNECTION_RESULT_TIMEOUT, connection_result).await {
+            Ok(Ok(Ok(()))) => ResponseCode::Success,
+            Ok(Ok(Err(err))) => {
+                info!(
+                    "Failed to connect to {} (id: {}) - {}",
+                    remote_address, self.connection_id, err
+                );
+                ResponseCode::from(err.kind)
+            }
+            Ok(Err(_)) => ResponseCode::Failure,
+            Err(_) => {
+                warn!(
+                    "Timed out while waiting for the connection to {} (id: {})",
+                    remote_address, self.connection_id
+                );
+                self.pending_connections.remove(self.connection_id);
+                self.send_close_to_mixnet();
+                ResponseCode::TtlExpired
+            }
+        }
+    }
+
+    async fn run_proxy(&mut self, conn_receiver: ConnectionReceiver, remote_proxy_target: String) {
         let stream = self.stream.run_proxy();
         let local_stream_remote = stream
             .peer_addr()
@@ -276,13 +334,21 @@ impl SocksClient {
             // Use the Proxy to connect to the specified addr/port
             SocksCommand::Connect => {
                 trace!("Connecting to: {:?}", remote_address.clone());
-                self.acknowledge_socks5().await;
 
+                // register the connection before the connect goes out, so that we wouldn't miss
+                // the provider closing it straight away
                 self.started_proxy = true;
                 self.controller_sender
                     .unbounded_send(ControllerCommand::Insert(self.connection_id, mix_sender))
                     .unwrap();
 
+                let response_code = self.connect_through_mixnet(remote_address.clone()).await;
+                let is_connected = matches!(response_code, ResponseCode::Success);
+                self.reply_socks5(response_code).await?;
+                if !is_connected {
+                    return Ok(());
+                }
+
                 info!(
                     "Starting proxy for {} (id: {})",
                     remote_address.clone(),
@@ -302,13 +368,13 @@ impl SocksClient {
         Ok(())
     }
 
-    /// Writes a Socks5 header back to the requesting client's TCP stream,
-    /// basically saying "I acknowledge your request and am dealing with it".
-    async fn acknowledge_socks5(&mut self) {
+    /// Writes a Socks5 reply back to the requesting client's TCP stream, telling it whether
+    /// the connection to the remote has been established.
+    async fn reply_socks5(&mut self, response_code: ResponseCode) -> Result<(), SocksProxyError> {
         self.stream
             .write_all(&[
                 SOCKS_VERSION,
-                ResponseCode::Success as u8,
+                response_code as u8,
                 RESERVED,
                 1,
                 127,
@@ -318,8 +384,8 @@ impl SocksClient {
                 0,
                 0,
             ])
-            .await
-            .unwrap();
+            .await?;
+        Ok(())
     }
 
     /// Authenticate the incoming request. Each request is checked for its
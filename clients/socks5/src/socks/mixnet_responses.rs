@@ -1,16 +1,54 @@
+use super::types::ResponseCode;
 use client_core::client::received_buffer::ReconstructedMessagesReceiver;
 use client_core::client::received_buffer::{ReceivedBufferMessage, ReceivedBufferRequestSender};
-use futures::channel::mpsc;
+use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
 use log::*;
 use nymsphinx::receiver::ReconstructedMessage;
 use proxy_helpers::connection_controller::{ControllerCommand, ControllerSender};
-use socks5_requests::Response;
+use socks5_requests::{ConnectionError, ConnectionId, Message, Response};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Result of the connection attempt made by the network requester on behalf of the client.
+pub(crate) type ConnectionResult = Result<(), ConnectionError>;
+
+/// Connections still waiting for the network requester to report whether it managed to
+/// establish them.
+#[derive(Clone, Default)]
+pub(crate) struct PendingConnections {
+    inner: Arc<Mutex<HashMap<ConnectionId, oneshot::Sender<ConnectionResult>>>>,
+}
+
+impl PendingConnections {
+    pub(crate) fn register(&self, conn_id: ConnectionId) -> oneshot::Receiver<ConnectionResult> {
+        let (result_sender, result_receiver) = oneshot::channel();
+        self.inner.lock().unwrap().insert(conn_id, result_sender);
+        result_receiver
+    }
+
+    pub(crate) fn remove(&self, conn_id: ConnectionId) {
+        self.inner.lock().unwrap().remove(&conn_id);
+    }
+
+    // returns the result back if nobody was waiting for it
+    fn resolve(
+        &self,
+        conn_id: ConnectionId,
+        result: ConnectionResult,
+    ) -> Result<(), ConnectionResult> {
+        match self.inner.lock().unwrap().remove(&conn_id) {
+            Some(result_sender) => result_sender.send(result),
+            None => Err(result),
+        }
+    }
+}
 
 pub(crate) struct MixnetResponseListener {
     buffer_requester: ReceivedBufferRequestSender,
     mix_response_receiver: ReconstructedMessagesReceiver,
     controller_sender: ControllerSender,
+    pending_connections: PendingConnections,
 }
 
 impl Drop for MixnetResponseListener {
@@ -25,6 +63,7 @@ impl MixnetResponseListener {
     pub(crate) fn new(
         buffer_requester: ReceivedBufferRequestSender,
         controller_sender: ControllerSender,
+        pending_connections: PendingConnections,
     ) -> Self {
         let (mix_response_sender, mix_response_receiver) = mpsc::unbounded();
         buffer_requester
@@ -35,6 +74,7 @@ impl MixnetResponseListener {
             buffer_requester,
             mix_response_receiver,
             controller_sender,
+            pending_connections,
         }
     }
 
@@ -44,14 +84,16 @@ impl MixnetResponseListener {
             warn!("this message had a surb - we didn't do anything with it");
         }
 
-        let response = match Response::try_from_bytes(&raw_message) {
-            Err(err) => {
-                warn!("failed to parse received response - {:?}", err);
-                return;
-            }
-            Ok(data) => data,
-        };
+        match Message::try_from_bytes(&raw_message) {
+            Err(err) => warn!("failed to parse received response - {}", err),
+            Ok(Message::Response(response)) => self.on_response(response),
+            Ok(Message::ConnectionError(error)) => self.on_connection_error(error),
+            Ok(Message::ConnectionEstablished(conn_id)) => self.on_connection_established(conn_id),
+            Ok(Message::Request(_)) => warn!("received a request instead of a response"),
+        }
+    }
 
+    fn on_response(&self, response: Response) {
         self.controller_sender
             .unbounded_send(ControllerCommand::Send(
                 response.connection_id,
@@ -61,6 +103,40 @@ impl MixnetResponseListener {
             .unwrap();
     }
 
+    fn on_connection_established(&self, conn_id: ConnectionId) {
+        if self.pending_connections.resolve(conn_id, Ok(())).is_err() {
+            debug!(
+                "Connection {} got established, but nobody is waiting for it anymore",
+                conn_id
+            );
+        }
+    }
+
+    fn on_connection_error(&self, error: ConnectionError) {
+        warn!(
+            "Network requester failed to establish connection {} ({}) - {}",
+            error.connection_id,
+            ResponseCode::from(error.kind),
+            error
+        );
+
+        // the client is still waiting for the result, it will reply to the application and
+        // close the connection itself
+        let conn_id = error.connection_id;
+        if self
+            .pending_connections
+            .resolve(conn_id, Err(error))
+            .is_ok()
+        {
+            return;
+        }
+
+        // the connection never got established on the other side, so close our end
+        self.controller_sender
+            .unbounded_send(ControllerCommand::Send(conn_id, Vec::new(), true))
+            .unwrap();
+    }
+
     pub(crate) async fn run(&mut self) {
         while let Some(received_responses) = self.mix_response_receiver.next().await {
             for reconstructed_message in received_responses {
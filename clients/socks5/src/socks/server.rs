@@ -1,7 +1,7 @@
 use super::authentication::Authenticator;
 use super::client::SocksClient;
 use super::{
-    mixnet_responses::MixnetResponseListener,
+    mixnet_responses::{MixnetResponseListener, PendingConnections},
     types::{ResponseCode, SocksProxyError},
 };
 use client_core::client::{
@@ -18,6 +18,7 @@ pub struct SphinxSocksServer {
     authenticator: Authenticator,
     listening_address: SocketAddr,
     service_provider: Recipient,
+    provider_reports_status: bool,
     self_address: Recipient,
 }
 
@@ -27,6 +28,7 @@ impl SphinxSocksServer {
         port: u16,
         authenticator: Authenticator,
         service_provider: Recipient,
+        provider_reports_status: bool,
         self_address: Recipient,
     ) -> Self {
         // hardcode ip as we (presumably) ONLY want to listen locally. If we change it, we can
@@ -37,6 +39,7 @@ impl SphinxSocksServer {
             authenticator,
             listening_address: format!("{}:{}", ip, port).parse().unwrap(),
             service_provider,
+            provider_reports_status,
             self_address,
         }
     }
@@ -57,9 +60,15 @@ impl SphinxSocksServer {
             active_streams_controller.run().await;
         });
 
+        // connections waiting for the network requester to establish them
+        let pending_connections = PendingConnections::default();
+
         // listener for mix messages
-        let mut mixnet_response_listener =
-            MixnetResponseListener::new(buffer_requester, controller_sender.clone());
+        let mut mixnet_response_listener = MixnetResponseListener::new(
+            buffer_requester,
+            controller_sender.clone(),
+            pending_connections.clone(),
+        );
 
         tokio::spawn(async move {
             mixnet_response_listener.run().await;
@@ -73,7 +82,9 @@ impl SphinxSocksServer {
                     self.authenticator.clone(),
                     input_sender.clone(),
                     self.service_provider,
+                    self.provider_reports_status,
                     controller_sender.clone(),
+                    pending_connections.clone(),
                     self.self_address,
                 );
 
@@ -1,4 +1,6 @@
 use snafu::Snafu;
+use socks5_requests::ConnectionErrorKind;
+
 #[derive(Debug, Snafu)]
 /// Possible SOCKS5 Response Codes
 pub(crate) enum ResponseCode {
@@ -21,6 +23,19 @@ pub(crate) enum ResponseCode {
     AddrTypeNotSupported = 0x08,
 }
 
+impl From<ConnectionErrorKind> for ResponseCode {
+    fn from(kind: ConnectionErrorKind) -> Self {
+        match kind {
+            ConnectionErrorKind::Filtered => ResponseCode::RuleFailure,
+            ConnectionErrorKind::DnsFailure => ResponseCode::HostUnreachable,
+            ConnectionErrorKind::ConnectionRefused => ResponseCode::ConnectionRefused,
+            ConnectionErrorKind::Timeout => ResponseCode::TtlExpired,
+            ConnectionErrorKind::RateLimited => ResponseCode::RuleFailure,
+            ConnectionErrorKind::Other => ResponseCode::Failure,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum SocksProxyError {
     GenericError(Box<dyn std::error::Error + Send + Sync>),
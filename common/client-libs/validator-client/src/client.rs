@@ -1,6 +1,8 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::validator_api::events::ValidatorCacheEventStream;
+use crate::validator_api::Versioned;
 use crate::{validator_api, ValidatorClientError};
 use coconut_interface::{BlindSignRequestBody, BlindedSignatureResponse, VerificationKeyResponse};
 use mixnet_contract_common::{GatewayBond, IdentityKeyRef, MixNodeBond};
@@ -10,7 +12,7 @@ use url::Url;
 use validator_api_requests::models::UptimeResponse;
 use validator_api_requests::models::{
     CoreNodeStatusResponse, MixnodeStatusResponse, RewardEstimationResponse,
-    StakeSaturationResponse,
+    SignedTopologySnapshot, StakeSaturationResponse,
 };
 
 #[cfg(feature = "nymd-client")]
@@ -640,6 +642,35 @@ impl ApiClient {
         Ok(self.validator_api.get_mixnodes().await?)
     }
 
+    pub async fn get_cached_active_mixnodes_if_changed(
+        &self,
+        etag: Option<&str>,
+    ) -> Result<Versioned<Vec<MixNodeBond>>, ValidatorClientError> {
+        Ok(self
+            .validator_api
+            .get_active_mixnodes_if_changed(etag)
+            .await?)
+    }
+
+    pub async fn get_cached_gateways_if_changed(
+        &self,
+        etag: Option<&str>,
+    ) -> Result<Versioned<Vec<GatewayBond>>, ValidatorClientError> {
+        Ok(self.validator_api.get_gateways_if_changed(etag).await?)
+    }
+
+    pub async fn get_signed_topology(
+        &self,
+    ) -> Result<SignedTopologySnapshot, ValidatorClientError> {
+        Ok(self.validator_api.get_signed_topology().await?)
+    }
+
+    pub async fn subscribe_to_cache_events(
+        &self,
+    ) -> Result<ValidatorCacheEventStream, ValidatorClientError> {
+        Ok(self.validator_api.subscribe_to_cache_events().await?)
+    }
+
     pub async fn get_cached_gateways(&self) -> Result<Vec<GatewayBond>, ValidatorClientError> {
         Ok(self.validator_api.get_gateways().await?)
     }
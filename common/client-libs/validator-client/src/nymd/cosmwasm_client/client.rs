@@ -1,7 +1,9 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::nymd::cosmwasm_client::helpers::{create_pagination, next_page_key};
+use crate::nymd::cosmwasm_client::helpers::{
+    collect_paged_results, create_pagination, next_page_key,
+};
 use crate::nymd::cosmwasm_client::types::{
     Account, Code, CodeDetails, Contract, ContractCodeHistoryEntry, ContractCodeId,
     SequenceResponse, SimulateResponse,
@@ -218,29 +220,16 @@ pub trait CosmWasmClient: rpc::Client {
         // so let's attempt to use the maximum
         let per_page = 100;
 
-        let mut results = Vec::new();
-        let mut page = 1;
-
-        loop {
-            let mut res = self
-                .tx_search(query.clone(), false, page, 100, Order::Ascending)
-                .await?;
-
-            results.append(&mut res.txs);
-            // sanity check for if tendermint's maximum per_page was modified -
-            // we don't want to accidentally be stuck in an infinite loop
-            if res.total_count == 0 || res.txs.is_empty() {
-                break;
-            }
-
-            if res.total_count >= per_page {
-                page += 1
-            } else {
-                break;
+        collect_paged_results(|page| {
+            let query = query.clone();
+            async move {
+                let res = self
+                    .tx_search(query, false, page, per_page, Order::Ascending)
+                    .await?;
+                Ok::<_, NymdError>((res.txs, res.total_count))
             }
-        }
-
-        Ok(results)
+        })
+        .await
     }
 
     /// Broadcast a transaction, returning immediately.
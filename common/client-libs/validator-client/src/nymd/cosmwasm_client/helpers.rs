@@ -8,6 +8,7 @@ use cosmrs::rpc::endpoint::broadcast;
 use cosmrs::Coin;
 use flate2::write::GzEncoder;
 use flate2::Compression;
+use std::future::Future;
 use std::io::Write;
 
 pub(crate) trait CheckResponse: Sized {
@@ -83,6 +84,35 @@ pub(crate) fn next_page_key(pagination_info: Option<PageResponse>) -> Option<Vec
     None
 }
 
+/// Keeps requesting subsequent pages, starting with page 1, until all of the `total_count` results,
+/// as reported alongside each page, have been retrieved.
+pub(crate) async fn collect_paged_results<T, E, F, Fut>(mut fetch_page: F) -> Result<Vec<T>, E>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, u32), E>>,
+{
+    let mut results = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let (mut page_results, total_count) = fetch_page(page).await?;
+
+        // sanity check for if the reported total count was inconsistent with the actual results -
+        // we don't want to accidentally be stuck in an infinite loop
+        if page_results.is_empty() {
+            break;
+        }
+        results.append(&mut page_results);
+
+        if results.len() >= total_count as usize {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(results)
+}
+
 pub(crate) fn parse_proto_coin_vec(value: Vec<ProtoCoin>) -> Result<Vec<Coin>, NymdError> {
     value
         .into_iter()
@@ -93,3 +123,67 @@ pub(crate) fn parse_proto_coin_vec(value: Vec<ProtoCoin>) -> Result<Vec<Coin>, N
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::cell::RefCell;
+
+    fn paged_source(
+        total: u32,
+        per_page: u32,
+        requested_pages: &RefCell<Vec<u32>>,
+    ) -> impl FnMut(u32) -> futures::future::Ready<Result<(Vec<u32>, u32), ()>> + '_ {
+        move |page| {
+            requested_pages.borrow_mut().push(page);
+            let start = (page - 1) * per_page;
+            let end = (start + per_page).min(total);
+            futures::future::ready(Ok(((start..end).collect(), total)))
+        }
+    }
+
+    #[test]
+    fn collecting_results_spanning_multiple_pages() {
+        let requested_pages = RefCell::new(Vec::new());
+        let results = block_on(collect_paged_results(paged_source(
+            250,
+            100,
+            &requested_pages,
+        )))
+        .unwrap();
+
+        assert_eq!(results, (0..250).collect::<Vec<_>>());
+        assert_eq!(*requested_pages.borrow(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn collecting_results_filling_exact_pages() {
+        let requested_pages = RefCell::new(Vec::new());
+        let results = block_on(collect_paged_results(paged_source(
+            200,
+            100,
+            &requested_pages,
+        )))
+        .unwrap();
+
+        // there's no need to request the non-existent third page
+        assert_eq!(results.len(), 200);
+        assert_eq!(*requested_pages.borrow(), vec![1, 2]);
+    }
+
+    #[test]
+    fn collecting_results_stops_on_empty_page() {
+        let requested_pages = RefCell::new(Vec::new());
+        let results = block_on(collect_paged_results(|page| {
+            requested_pages.borrow_mut().push(page);
+            // claims more results than it actually has
+            let page_results = if page == 1 { vec![1, 2, 3] } else { Vec::new() };
+            futures::future::ready(Ok::<_, ()>((page_results, 1000)))
+        }))
+        .unwrap();
+
+        assert_eq!(results, vec![1, 2, 3]);
+        assert_eq!(*requested_pages.borrow(), vec![1, 2]);
+    }
+}
@@ -20,6 +20,16 @@ pub struct Log {
     events: Vec<cosmwasm_std::Event>,
 }
 
+impl Log {
+    pub fn msg_index(&self) -> usize {
+        self.msg_index
+    }
+
+    pub fn events(&self) -> &[cosmwasm_std::Event] {
+        &self.events
+    }
+}
+
 /// Searches in logs for the first event of the given event type and in that event
 /// for the first attribute with the given attribute key.
 pub(crate) fn find_attribute<'a>(
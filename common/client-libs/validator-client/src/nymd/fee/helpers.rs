@@ -24,11 +24,15 @@ pub enum Operation {
     BondMixnodeOnBehalf,
     UnbondMixnode,
     UnbondMixnodeOnBehalf,
+    DecreaseMixnodePledge,
     UpdateMixnodeConfig,
+    UnjailMixnode,
     DelegateToMixnode,
     DelegateToMixnodeOnBehalf,
     UndelegateFromMixnode,
     UndelegateFromMixnodeOnBehalf,
+    PartiallyUndelegateFromMixnode,
+    RedelegateFromMixnode,
 
     BondGateway,
     BondGatewayOnBehalf,
@@ -45,6 +49,7 @@ pub enum Operation {
     WithdrawVestedCoins,
     TrackUndelegation,
     CreatePeriodicVestingAccount,
+    RevokeUnvestedCoins,
 
     AdvanceCurrentInterval,
     AdvanceCurrentEpoch,
@@ -53,6 +58,10 @@ pub enum Operation {
     UpdateMixnetAddress,
     CheckpointMixnodes,
     ReconcileDelegations,
+
+    MultisigPropose,
+    MultisigVote,
+    MultisigExecute,
 }
 
 pub(crate) fn calculate_fee(gas_price: &GasPrice, gas_limit: Gas) -> Coin {
@@ -70,7 +79,9 @@ impl fmt::Display for Operation {
             Operation::BondMixnode => f.write_str("BondMixnode"),
             Operation::BondMixnodeOnBehalf => f.write_str("BondMixnodeOnBehalf"),
             Operation::UnbondMixnode => f.write_str("UnbondMixnode"),
+            Operation::DecreaseMixnodePledge => f.write_str("DecreaseMixnodePledge"),
             Operation::UpdateMixnodeConfig => f.write_str("UpdateMixnodeConfig"),
+            Operation::UnjailMixnode => f.write_str("UnjailMixnode"),
             Operation::UnbondMixnodeOnBehalf => f.write_str("UnbondMixnodeOnBehalf"),
             Operation::BondGateway => f.write_str("BondGateway"),
             Operation::BondGatewayOnBehalf => f.write_str("BondGatewayOnBehalf"),
@@ -82,6 +93,10 @@ impl fmt::Display for Operation {
             Operation::UndelegateFromMixnodeOnBehalf => {
                 f.write_str("UndelegateFromMixnodeOnBehalf")
             }
+            Operation::PartiallyUndelegateFromMixnode => {
+                f.write_str("PartiallyUndelegateFromMixnode")
+            }
+            Operation::RedelegateFromMixnode => f.write_str("RedelegateFromMixnode"),
             Operation::UpdateContractSettings => f.write_str("UpdateContractSettings"),
             Operation::BeginMixnodeRewarding => f.write_str("BeginMixnodeRewarding"),
             Operation::FinishMixnodeRewarding => f.write_str("FinishMixnodeRewarding"),
@@ -90,6 +105,7 @@ impl fmt::Display for Operation {
             Operation::WithdrawVestedCoins => f.write_str("WithdrawVestedCoins"),
             Operation::TrackUndelegation => f.write_str("TrackUndelegation"),
             Operation::CreatePeriodicVestingAccount => f.write_str("CreatePeriodicVestingAccount"),
+            Operation::RevokeUnvestedCoins => f.write_str("RevokeUnvestedCoins"),
             Operation::AdvanceCurrentInterval => f.write_str("AdvanceCurrentInterval"),
             Operation::WriteRewardedSet => f.write_str("WriteRewardedSet"),
             Operation::ClearRewardedSet => f.write_str("ClearRewardedSet"),
@@ -97,6 +113,9 @@ impl fmt::Display for Operation {
             Operation::CheckpointMixnodes => f.write_str("CheckpointMixnodes"),
             Operation::ReconcileDelegations => f.write_str("ReconcileDelegations"),
             Operation::AdvanceCurrentEpoch => f.write_str("AdvanceCurrentEpoch"),
+            Operation::MultisigPropose => f.write_str("MultisigPropose"),
+            Operation::MultisigVote => f.write_str("MultisigVote"),
+            Operation::MultisigExecute => f.write_str("MultisigExecute"),
         }
     }
 }
@@ -115,11 +134,15 @@ impl Operation {
             Operation::BondMixnodeOnBehalf => 200_000u64.into(),
             Operation::UnbondMixnode => 175_000u64.into(),
             Operation::UnbondMixnodeOnBehalf => 175_000u64.into(),
+            Operation::DecreaseMixnodePledge => 200_000u64.into(),
             Operation::UpdateMixnodeConfig => 175_000u64.into(),
+            Operation::UnjailMixnode => 175_000u64.into(),
             Operation::DelegateToMixnode => 175_000u64.into(),
             Operation::DelegateToMixnodeOnBehalf => 175_000u64.into(),
             Operation::UndelegateFromMixnode => 175_000u64.into(),
             Operation::UndelegateFromMixnodeOnBehalf => 175_000u64.into(),
+            Operation::PartiallyUndelegateFromMixnode => 200_000u64.into(),
+            Operation::RedelegateFromMixnode => 250_000u64.into(),
 
             Operation::BondGateway => 175_000u64.into(),
             Operation::BondGatewayOnBehalf => 200_000u64.into(),
@@ -134,6 +157,7 @@ impl Operation {
             Operation::WithdrawVestedCoins => 175_000u64.into(),
             Operation::TrackUndelegation => 175_000u64.into(),
             Operation::CreatePeriodicVestingAccount => 175_000u64.into(),
+            Operation::RevokeUnvestedCoins => 175_000u64.into(),
             Operation::AdvanceCurrentInterval => 175_000u64.into(),
             Operation::WriteRewardedSet => 175_000u64.into(),
             Operation::ClearRewardedSet => 175_000u64.into(),
@@ -141,6 +165,9 @@ impl Operation {
             Operation::CheckpointMixnodes => 175_000u64.into(),
             Operation::ReconcileDelegations => 500_000u64.into(),
             Operation::AdvanceCurrentEpoch => 175_000u64.into(),
+            Operation::MultisigPropose => 250_000u64.into(),
+            Operation::MultisigVote => 175_000u64.into(),
+            Operation::MultisigExecute => 250_000u64.into(),
         }
     }
 
@@ -15,11 +15,12 @@ pub use fee::gas_price::GasPrice;
 use fee::helpers::Operation;
 use mixnet_contract_common::mixnode::DelegationEvent;
 use mixnet_contract_common::{
-    ContractStateParams, Delegation, ExecuteMsg, Gateway, GatewayBond, GatewayOwnershipResponse,
-    IdentityKey, Interval, LayerDistribution, MixNode, MixNodeBond, MixOwnershipResponse,
-    MixnetContractVersion, MixnodeRewardingStatusResponse, PagedDelegatorDelegationsResponse,
-    PagedGatewayResponse, PagedMixDelegationsResponse, PagedMixnodeResponse,
-    PagedRewardedSetResponse, QueryMsg, RewardedSetUpdateDetails,
+    ContractStateParams, Delegation, ExecuteMsg, FamilyMembersResponse, Gateway, GatewayBond,
+    GatewayOwnershipResponse, IdentityKey, Interval, LayerDistribution, MixNode, MixNodeBond,
+    MixOwnershipResponse, MixnetContractVersion, MixnodeRewardingStatusResponse,
+    PagedDelegatorDelegationsResponse, PagedFamiliesResponse, PagedGatewayResponse,
+    PagedMixDelegationsResponse, PagedMixnodeResponse, PagedRewardedSetResponse,
+    PendingCostParamsChange, QueryMsg, RewardedSetUpdateDetails,
 };
 use serde::Serialize;
 use std::collections::HashMap;
@@ -32,6 +33,7 @@ use crate::nymd::fee::DEFAULT_SIMULATED_GAS_MULTIPLIER;
 pub use cosmrs::bank::MsgSend;
 pub use cosmrs::rpc::endpoint::tx::Response as TxResponse;
 pub use cosmrs::rpc::endpoint::validators::Response as ValidatorResponse;
+pub use cosmrs::rpc::query::Query;
 pub use cosmrs::rpc::HttpClient as QueryNymdClient;
 pub use cosmrs::rpc::Paging;
 pub use cosmrs::tendermint::abci::responses::{DeliverTx, Event};
@@ -44,7 +46,7 @@ pub use cosmrs::tx::{self, Gas};
 pub use cosmrs::Coin as CosmosCoin;
 pub use cosmrs::{AccountId, Decimal, Denom};
 pub use signing_client::Client as SigningNymdClient;
-pub use traits::{VestingQueryClient, VestingSigningClient};
+pub use traits::{MultisigSigningClient, VestingQueryClient, VestingSigningClient};
 
 pub mod cosmwasm_client;
 pub mod error;
@@ -371,6 +373,50 @@ impl<C> NymdClient<C> {
             .await
     }
 
+    /// Gets the profit margin and operating cost change of the mixnode that is yet to take effect.
+    pub async fn get_pending_cost_params_change(
+        &self,
+        mix_identity: IdentityKey,
+    ) -> Result<Option<PendingCostParamsChange>, NymdError>
+    where
+        C: CosmWasmClient + Sync,
+    {
+        let request = QueryMsg::GetPendingCostParamsChange { mix_identity };
+        self.client
+            .query_contract_smart(self.mixnet_contract_address()?, &request)
+            .await
+    }
+
+    pub async fn get_families_paged(
+        &self,
+        start_after: Option<IdentityKey>,
+        page_limit: Option<u32>,
+    ) -> Result<PagedFamiliesResponse, NymdError>
+    where
+        C: CosmWasmClient + Sync,
+    {
+        let request = QueryMsg::GetFamiliesPaged {
+            start_after,
+            limit: page_limit,
+        };
+        self.client
+            .query_contract_smart(self.mixnet_contract_address()?, &request)
+            .await
+    }
+
+    pub async fn get_family_members(
+        &self,
+        family_head: IdentityKey,
+    ) -> Result<FamilyMembersResponse, NymdError>
+    where
+        C: CosmWasmClient + Sync,
+    {
+        let request = QueryMsg::GetFamilyMembers { family_head };
+        self.client
+            .query_contract_smart(self.mixnet_contract_address()?, &request)
+            .await
+    }
+
     pub async fn get_mixnet_contract_version(&self) -> Result<MixnetContractVersion, NymdError>
     where
         C: CosmWasmClient + Sync,
@@ -913,10 +959,55 @@ impl<C> NymdClient<C> {
             .await
     }
 
-    /// Update the configuration of a mixnode. Right now, only possible for profit margin.
+    /// Withdraws part of the mixnode pledge, keeping the node bonded. The remaining pledge can't
+    /// go below the minimum pledge defined in the contract.
+    pub async fn decrease_mixnode_pledge(&self, amount: &Coin) -> Result<ExecuteResult, NymdError>
+    where
+        C: SigningCosmWasmClient + Sync,
+    {
+        let fee = self.operation_fee(Operation::DecreaseMixnodePledge);
+
+        let req = ExecuteMsg::DecreaseMixnodePledge {
+            amount: amount.clone(),
+        };
+        self.client
+            .execute(
+                self.address(),
+                self.mixnet_contract_address()?,
+                &req,
+                fee,
+                "Decreasing mixnode pledge from rust!",
+                Vec::new(),
+            )
+            .await
+    }
+
+    /// Unjails the mixnode of the sender once the jail cooldown has passed.
+    pub async fn unjail_mixnode(&self) -> Result<ExecuteResult, NymdError>
+    where
+        C: SigningCosmWasmClient + Sync,
+    {
+        let fee = self.operation_fee(Operation::UnjailMixnode);
+
+        let req = ExecuteMsg::UnjailMixnode {};
+        self.client
+            .execute(
+                self.address(),
+                self.mixnet_contract_address()?,
+                &req,
+                fee,
+                "Unjailing mixnode from rust!",
+                Vec::new(),
+            )
+            .await
+    }
+
+    /// Request an update of the profit margin and, optionally, the operating cost of a mixnode.
+    /// The change only takes effect after the notice period defined in the contract.
     pub async fn update_mixnode_config(
         &self,
         profit_margin_percent: u8,
+        operating_cost: Option<Uint128>,
     ) -> Result<ExecuteResult, NymdError>
     where
         C: SigningCosmWasmClient + Sync,
@@ -925,6 +1016,7 @@ impl<C> NymdClient<C> {
 
         let req = ExecuteMsg::UpdateMixnodeConfig {
             profit_margin_percent,
+            operating_cost,
         };
         self.client
             .execute(
@@ -1082,6 +1174,64 @@ impl<C> NymdClient<C> {
             .await
     }
 
+    /// Removes specified amount of stake delegation from a particular mixnode.
+    /// The remainder of the delegation, including any rewards not withdrawn, stays on the node.
+    pub async fn partially_remove_mixnode_delegation(
+        &self,
+        mix_identity: &str,
+        amount: &Coin,
+    ) -> Result<ExecuteResult, NymdError>
+    where
+        C: SigningCosmWasmClient + Sync,
+    {
+        let fee = self.operation_fee(Operation::PartiallyUndelegateFromMixnode);
+
+        let req = ExecuteMsg::PartiallyUndelegateFromMixnode {
+            mix_identity: mix_identity.to_string(),
+            amount: amount.clone(),
+        };
+        self.client
+            .execute(
+                self.address(),
+                self.mixnet_contract_address()?,
+                &req,
+                fee,
+                "Partially removing mixnode delegation from rust!",
+                Vec::new(),
+            )
+            .await
+    }
+
+    /// Moves specified amount of stake from one mixnode to another without going through
+    /// the usual undelegation delay.
+    pub async fn redelegate_from_mixnode(
+        &self,
+        from: &str,
+        to: &str,
+        amount: &Coin,
+    ) -> Result<ExecuteResult, NymdError>
+    where
+        C: SigningCosmWasmClient + Sync,
+    {
+        let fee = self.operation_fee(Operation::RedelegateFromMixnode);
+
+        let req = ExecuteMsg::RedelegateFromMixnode {
+            from: from.to_string(),
+            to: to.to_string(),
+            amount: amount.clone(),
+        };
+        self.client
+            .execute(
+                self.address(),
+                self.mixnet_contract_address()?,
+                &req,
+                fee,
+                "Redelegating stake between mixnodes from rust!",
+                Vec::new(),
+            )
+            .await
+    }
+
     /// Announce a gateway, paying a fee.
     pub async fn bond_gateway(
         &self,
@@ -1241,6 +1391,49 @@ impl<C> NymdClient<C> {
             .await
     }
 
+    /// Proposes handing over the ownership of the mixnet contract, for example to a multisig contract.
+    /// The ownership is only transferred once the proposed owner accepts it.
+    pub async fn propose_contract_owner(&self, address: &str) -> Result<ExecuteResult, NymdError>
+    where
+        C: SigningCosmWasmClient + Sync,
+    {
+        let fee = self.operation_fee(Operation::UpdateContractSettings);
+
+        let req = ExecuteMsg::ProposeContractOwner {
+            address: address.to_string(),
+        };
+        self.client
+            .execute(
+                self.address(),
+                self.mixnet_contract_address()?,
+                &req,
+                fee,
+                "Proposing contract owner from rust!",
+                Vec::new(),
+            )
+            .await
+    }
+
+    /// Accepts the ownership of the mixnet contract previously proposed by its current owner.
+    pub async fn accept_contract_ownership(&self) -> Result<ExecuteResult, NymdError>
+    where
+        C: SigningCosmWasmClient + Sync,
+    {
+        let fee = self.operation_fee(Operation::UpdateContractSettings);
+
+        let req = ExecuteMsg::AcceptContractOwnership {};
+        self.client
+            .execute(
+                self.address(),
+                self.mixnet_contract_address()?,
+                &req,
+                fee,
+                "Accepting contract ownership from rust!",
+                Vec::new(),
+            )
+            .await
+    }
+
     pub async fn advance_current_epoch(&self) -> Result<ExecuteResult, NymdError>
     where
         C: SigningCosmWasmClient + Sync,
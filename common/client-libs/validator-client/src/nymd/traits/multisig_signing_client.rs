@@ -0,0 +1,147 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nymd::cosmwasm_client::signing_client::SigningCosmWasmClient;
+use crate::nymd::cosmwasm_client::types::ExecuteResult;
+use crate::nymd::error::NymdError;
+use crate::nymd::fee::helpers::Operation;
+use crate::nymd::{AccountId, NymdClient};
+use async_trait::async_trait;
+use mixnet_contract_common::{ContractStateParams, ExecuteMsg as MixnetExecuteMsg};
+use multisig_contract_common::msg::{ExecuteMsg as MultisigExecuteMsg, Expiration, Vote};
+
+/// Interactions with a cw3 multisig contract that has been made the owner of the mixnet contract.
+#[async_trait]
+pub trait MultisigSigningClient {
+    async fn propose_contract_state_params_update(
+        &self,
+        multisig_address: &AccountId,
+        params: ContractStateParams,
+        title: String,
+        description: String,
+        expiration: Option<Expiration>,
+    ) -> Result<ExecuteResult, NymdError>;
+
+    /// Proposes accepting the ownership of the mixnet contract, after it got offered to the multisig.
+    async fn propose_contract_ownership_acceptance(
+        &self,
+        multisig_address: &AccountId,
+        title: String,
+        description: String,
+        expiration: Option<Expiration>,
+    ) -> Result<ExecuteResult, NymdError>;
+
+    async fn vote_on_proposal(
+        &self,
+        multisig_address: &AccountId,
+        proposal_id: u64,
+        vote: Vote,
+    ) -> Result<ExecuteResult, NymdError>;
+
+    async fn execute_proposal(
+        &self,
+        multisig_address: &AccountId,
+        proposal_id: u64,
+    ) -> Result<ExecuteResult, NymdError>;
+}
+
+#[async_trait]
+impl<C: SigningCosmWasmClient + Sync + Send> MultisigSigningClient for NymdClient<C> {
+    async fn propose_contract_state_params_update(
+        &self,
+        multisig_address: &AccountId,
+        params: ContractStateParams,
+        title: String,
+        description: String,
+        expiration: Option<Expiration>,
+    ) -> Result<ExecuteResult, NymdError> {
+        let fee = self.operation_fee(Operation::MultisigPropose);
+        let req = MultisigExecuteMsg::propose_contract_execution(
+            title,
+            description,
+            self.mixnet_contract_address()?.to_string(),
+            &MixnetExecuteMsg::UpdateContractStateParams(params),
+            expiration,
+        )
+        .map_err(|err| NymdError::SerializationError(err.to_string()))?;
+
+        self.client
+            .execute(
+                self.address(),
+                multisig_address,
+                &req,
+                fee,
+                "Multisig::Propose",
+                vec![],
+            )
+            .await
+    }
+
+    async fn propose_contract_ownership_acceptance(
+        &self,
+        multisig_address: &AccountId,
+        title: String,
+        description: String,
+        expiration: Option<Expiration>,
+    ) -> Result<ExecuteResult, NymdError> {
+        let fee = self.operation_fee(Operation::MultisigPropose);
+        let req = MultisigExecuteMsg::propose_contract_execution(
+            title,
+            description,
+            self.mixnet_contract_address()?.to_string(),
+            &MixnetExecuteMsg::AcceptContractOwnership {},
+            expiration,
+        )
+        .map_err(|err| NymdError::SerializationError(err.to_string()))?;
+
+        self.client
+            .execute(
+                self.address(),
+                multisig_address,
+                &req,
+                fee,
+                "Multisig::Propose",
+                vec![],
+            )
+            .await
+    }
+
+    async fn vote_on_proposal(
+        &self,
+        multisig_address: &AccountId,
+        proposal_id: u64,
+        vote: Vote,
+    ) -> Result<ExecuteResult, NymdError> {
+        let fee = self.operation_fee(Operation::MultisigVote);
+        let req = MultisigExecuteMsg::Vote { proposal_id, vote };
+        self.client
+            .execute(
+                self.address(),
+                multisig_address,
+                &req,
+                fee,
+                "Multisig::Vote",
+                vec![],
+            )
+            .await
+    }
+
+    async fn execute_proposal(
+        &self,
+        multisig_address: &AccountId,
+        proposal_id: u64,
+    ) -> Result<ExecuteResult, NymdError> {
+        let fee = self.operation_fee(Operation::MultisigExecute);
+        let req = MultisigExecuteMsg::Execute { proposal_id };
+        self.client
+            .execute(
+                self.address(),
+                multisig_address,
+                &req,
+                fee,
+                "Multisig::Execute",
+                vec![],
+            )
+            .await
+    }
+}
@@ -9,6 +9,7 @@ use cosmwasm_std::{Coin, Timestamp};
 use vesting_contract::vesting::Account;
 use vesting_contract_common::{
     messages::QueryMsg as VestingQueryMsg, OriginalVestingResponse, Period, PledgeData,
+    VestingScheduleResponse,
 };
 
 #[async_trait]
@@ -69,6 +70,10 @@ pub trait VestingQueryClient {
         &self,
         vesting_account_address: &str,
     ) -> Result<Period, NymdError>;
+    async fn get_vesting_schedule(
+        &self,
+        vesting_account_address: &str,
+    ) -> Result<VestingScheduleResponse, NymdError>;
 }
 
 #[async_trait]
@@ -224,4 +229,16 @@ impl<C: CosmWasmClient + Sync + Send> VestingQueryClient for NymdClient<C> {
             .query_contract_smart(self.vesting_contract_address()?, &request)
             .await
     }
+
+    async fn get_vesting_schedule(
+        &self,
+        vesting_account_address: &str,
+    ) -> Result<VestingScheduleResponse, NymdError> {
+        let request = VestingQueryMsg::GetVestingSchedule {
+            vesting_account_address: vesting_account_address.to_string(),
+        };
+        self.client
+            .query_contract_smart(self.vesting_contract_address()?, &request)
+            .await
+    }
 }
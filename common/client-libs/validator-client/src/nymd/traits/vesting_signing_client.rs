@@ -7,7 +7,7 @@ use crate::nymd::error::NymdError;
 use crate::nymd::fee::helpers::Operation;
 use crate::nymd::{cosmwasm_coin_to_cosmos_coin, NymdClient};
 use async_trait::async_trait;
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Coin, Uint128};
 use mixnet_contract_common::{Gateway, IdentityKey, IdentityKeyRef, MixNode};
 use vesting_contract_common::messages::{ExecuteMsg as VestingExecuteMsg, VestingSpecification};
 
@@ -16,6 +16,7 @@ pub trait VestingSigningClient {
     async fn vesting_update_mixnode_config(
         &self,
         profix_margin_percent: u8,
+        operating_cost: Option<Uint128>,
     ) -> Result<ExecuteResult, NymdError>;
 
     async fn update_mixnet_address(&self, address: &str) -> Result<ExecuteResult, NymdError>;
@@ -43,6 +44,11 @@ pub trait VestingSigningClient {
     ) -> Result<ExecuteResult, NymdError>;
     async fn vesting_unbond_mixnode(&self) -> Result<ExecuteResult, NymdError>;
 
+    async fn vesting_decrease_mixnode_pledge(
+        &self,
+        amount: Coin,
+    ) -> Result<ExecuteResult, NymdError>;
+
     async fn vesting_track_unbond_mixnode(
         &self,
         owner: &str,
@@ -69,6 +75,19 @@ pub trait VestingSigningClient {
         mix_identity: IdentityKeyRef<'a>,
     ) -> Result<ExecuteResult, NymdError>;
 
+    async fn vesting_partially_undelegate_from_mixnode<'a>(
+        &self,
+        mix_identity: IdentityKeyRef<'a>,
+        amount: Coin,
+    ) -> Result<ExecuteResult, NymdError>;
+
+    async fn vesting_redelegate_from_mixnode<'a>(
+        &self,
+        from: IdentityKeyRef<'a>,
+        to: IdentityKeyRef<'a>,
+        amount: Coin,
+    ) -> Result<ExecuteResult, NymdError>;
+
     async fn create_periodic_vesting_account(
         &self,
         owner_address: &str,
@@ -76,6 +95,8 @@ pub trait VestingSigningClient {
         vesting_spec: Option<VestingSpecification>,
         amount: Coin,
     ) -> Result<ExecuteResult, NymdError>;
+
+    async fn revoke_unvested_coins(&self, owner_address: &str) -> Result<ExecuteResult, NymdError>;
 }
 
 #[async_trait]
@@ -128,6 +149,7 @@ impl<C: SigningCosmWasmClient + Sync + Send> VestingSigningClient for NymdClient
         let req = VestingExecuteMsg::TrackUnbondGateway {
             owner: owner.to_string(),
             amount,
+            partial: false,
         };
         self.client
             .execute(
@@ -180,6 +202,24 @@ impl<C: SigningCosmWasmClient + Sync + Send> VestingSigningClient for NymdClient
             .await
     }
 
+    async fn vesting_decrease_mixnode_pledge(
+        &self,
+        amount: Coin,
+    ) -> Result<ExecuteResult, NymdError> {
+        let fee = self.operation_fee(Operation::DecreaseMixnodePledge);
+        let req = VestingExecuteMsg::DecreaseMixnodePledge { amount };
+        self.client
+            .execute(
+                self.address(),
+                self.vesting_contract_address()?,
+                &req,
+                fee,
+                "VestingContract::DecreaseMixnodePledge",
+                vec![],
+            )
+            .await
+    }
+
     async fn vesting_track_unbond_mixnode(
         &self,
         owner: &str,
@@ -228,6 +268,7 @@ impl<C: SigningCosmWasmClient + Sync + Send> VestingSigningClient for NymdClient
             owner: address.to_string(),
             mix_identity,
             amount,
+            partial: false,
         };
         self.client
             .execute(
@@ -280,6 +321,50 @@ impl<C: SigningCosmWasmClient + Sync + Send> VestingSigningClient for NymdClient
             )
             .await
     }
+    async fn vesting_partially_undelegate_from_mixnode<'a>(
+        &self,
+        mix_identity: IdentityKeyRef<'a>,
+        amount: Coin,
+    ) -> Result<ExecuteResult, NymdError> {
+        let fee = self.operation_fee(Operation::PartiallyUndelegateFromMixnode);
+        let req = VestingExecuteMsg::PartiallyUndelegateFromMixnode {
+            mix_identity: mix_identity.into(),
+            amount,
+        };
+        self.client
+            .execute(
+                self.address(),
+                self.vesting_contract_address()?,
+                &req,
+                fee,
+                "VestingContract::PartiallyUndelegateFromMixnode",
+                vec![],
+            )
+            .await
+    }
+    async fn vesting_redelegate_from_mixnode<'a>(
+        &self,
+        from: IdentityKeyRef<'a>,
+        to: IdentityKeyRef<'a>,
+        amount: Coin,
+    ) -> Result<ExecuteResult, NymdError> {
+        let fee = self.operation_fee(Operation::RedelegateFromMixnode);
+        let req = VestingExecuteMsg::RedelegateFromMixnode {
+            from: from.into(),
+            to: to.into(),
+            amount,
+        };
+        self.client
+            .execute(
+                self.address(),
+                self.vesting_contract_address()?,
+                &req,
+                fee,
+                "VestingContract::RedelegateFromMixnode",
+                vec![],
+            )
+            .await
+    }
     async fn create_periodic_vesting_account(
         &self,
         owner_address: &str,
@@ -305,6 +390,23 @@ impl<C: SigningCosmWasmClient + Sync + Send> VestingSigningClient for NymdClient
             .await
     }
 
+    async fn revoke_unvested_coins(&self, owner_address: &str) -> Result<ExecuteResult, NymdError> {
+        let fee = self.operation_fee(Operation::RevokeUnvestedCoins);
+        let req = VestingExecuteMsg::RevokeUnvestedCoins {
+            owner_address: owner_address.to_string(),
+        };
+        self.client
+            .execute(
+                self.address(),
+                self.vesting_contract_address()?,
+                &req,
+                fee,
+                "VestingContract::RevokeUnvestedCoins",
+                vec![],
+            )
+            .await
+    }
+
     async fn update_mixnet_address(&self, address: &str) -> Result<ExecuteResult, NymdError> {
         let fee = self.operation_fee(Operation::UpdateMixnetAddress);
         let req = VestingExecuteMsg::UpdateMixnetAddress {
@@ -325,10 +427,12 @@ impl<C: SigningCosmWasmClient + Sync + Send> VestingSigningClient for NymdClient
     async fn vesting_update_mixnode_config(
         &self,
         profit_margin_percent: u8,
+        operating_cost: Option<Uint128>,
     ) -> Result<ExecuteResult, NymdError> {
         let fee = self.operation_fee(Operation::UpdateMixnodeConfig);
         let req = VestingExecuteMsg::UpdateMixnodeConfig {
             profit_margin_percent,
+            operating_cost,
         };
         self.client
             .execute(
@@ -10,4 +10,30 @@ pub enum ValidatorAPIError {
 
     #[error("Request failed with error message - {0}")]
     GenericRequestFailure(String),
+
+    #[error("Received a malformed json response - {source}")]
+    MalformedJsonResponse {
+        #[from]
+        source: serde_json::Error,
+    },
+
+    #[error("Received a malformed binary response - {source}")]
+    MalformedBinaryResponse {
+        #[from]
+        source: bincode::Error,
+    },
+
+    #[error("The topology snapshot signature is invalid - {0}")]
+    InvalidTopologySignature(String),
+
+    #[error("The topology snapshot was signed by an untrusted key {0}")]
+    UntrustedTopologySigner(String),
+
+    #[error("The topology snapshot is {age_secs}s old while at most {max_age_secs}s is allowed")]
+    StaleTopologySnapshot { age_secs: i64, max_age_secs: u64 },
+
+    #[error(
+        "The topology snapshot comes from epoch {epoch:?} while at least {min_epoch} is required"
+    )]
+    OutdatedTopologyEpoch { epoch: Option<u32>, min_epoch: u32 },
 }
@@ -0,0 +1,74 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::validator_api::error::ValidatorAPIError;
+use validator_api_requests::models::ValidatorCacheEvent;
+
+/// Subscription to the server-sent events stream of the validator API, yielding changes of its
+/// view of the network as they happen.
+pub struct ValidatorCacheEventStream {
+    response: reqwest::Response,
+    buffer: Vec<u8>,
+}
+
+impl ValidatorCacheEventStream {
+    pub(crate) fn new(response: reqwest::Response) -> Self {
+        ValidatorCacheEventStream {
+            response,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Waits for the next event. Returns `None` once the validator API closes the stream.
+    pub async fn next(&mut self) -> Result<Option<ValidatorCacheEvent>, ValidatorAPIError> {
+        loop {
+            if let Some(data) = next_event_data(&mut self.buffer) {
+                return Ok(Some(serde_json::from_str(&data)?));
+            }
+
+            match self.response.chunk().await? {
+                Some(chunk) => self.buffer.extend_from_slice(&chunk),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+// extracts the data of the next complete event from the buffer, skipping anything without any
+// data, such as the heartbeat comments
+fn next_event_data(buffer: &mut Vec<u8>) -> Option<String> {
+    while let Some(end) = buffer.windows(2).position(|window| window == b"\n\n") {
+        let raw_event = buffer.drain(..end + 2).collect::<Vec<_>>();
+        let raw_event = String::from_utf8_lossy(&raw_event);
+
+        let data = raw_event
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(|data| data.strip_prefix(' ').unwrap_or(data))
+            .collect::<Vec<_>>();
+
+        if !data.is_empty() {
+            return Some(data.join("\n"));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracting_event_data() {
+        let mut buffer = b":\n\ndata:{\"a\":1}\n\ndata: foo\ndata: bar\n\ndata:incomplete".to_vec();
+
+        assert_eq!(next_event_data(&mut buffer), Some("{\"a\":1}".to_string()));
+        assert_eq!(next_event_data(&mut buffer), Some("foo\nbar".to_string()));
+        assert_eq!(next_event_data(&mut buffer), None);
+        assert_eq!(buffer, b"data:incomplete".to_vec());
+
+        buffer.extend_from_slice(b"\n\n");
+        assert_eq!(next_event_data(&mut buffer), Some("incomplete".to_string()));
+        assert!(buffer.is_empty());
+    }
+}
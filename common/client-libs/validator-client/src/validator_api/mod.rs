@@ -2,25 +2,53 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::validator_api::error::ValidatorAPIError;
+use crate::validator_api::events::ValidatorCacheEventStream;
 use crate::validator_api::routes::{CORE_STATUS_COUNT, SINCE_ARG};
 use coconut_interface::{BlindSignRequestBody, BlindedSignatureResponse, VerificationKeyResponse};
+use log::warn;
 use mixnet_contract_common::{GatewayBond, IdentityKeyRef, MixNodeBond};
+use reqwest::header::{ACCEPT, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use url::Url;
 use validator_api_requests::models::{
     CoreNodeStatusResponse, InclusionProbabilityResponse, MixnodeStatusResponse,
-    RewardEstimationResponse, StakeSaturationResponse, UptimeResponse,
+    RewardEstimationResponse, SignedTopologySnapshot, StakeSaturationResponse, UptimeResponse,
+    ValidatorApiIdentity,
 };
 
 pub mod error;
+pub mod events;
 pub mod routes;
+pub mod signed_topology;
+
+/// Result of a conditional request for data that might have not changed since it was last
+/// retrieved.
+#[derive(Debug)]
+pub enum Versioned<T> {
+    Changed {
+        value: T,
+        /// Entity tag of the returned value, to be used in the subsequent requests.
+        etag: Option<String>,
+    },
+    Unchanged,
+}
 
 type PathSegments<'a> = &'a [&'a str];
 type Params<'a, K, V> = &'a [(K, V)];
 
 const NO_PARAMS: Params<'_, &'_ str, &'_ str> = &[];
 
+const BINARY_CONTENT_TYPE: &str = "application/vnd.nym.topology.v1+bincode";
+const JSON_CONTENT_TYPE: &str = "application/json";
+
+// raw response body alongside the information about its encoding
+struct EncodedValue {
+    bytes: Vec<u8>,
+    is_binary: bool,
+}
+
 pub struct Client {
     url: Url,
     reqwest_client: reqwest::Client,
@@ -57,6 +85,88 @@ impl Client {
         Ok(self.reqwest_client.get(url).send().await?.json().await?)
     }
 
+    async fn get_if_changed(
+        &self,
+        url: Url,
+        accept: &str,
+        etag: Option<&str>,
+    ) -> Result<Versioned<EncodedValue>, ValidatorAPIError> {
+        let mut request = self.reqwest_client.get(url).header(ACCEPT, accept);
+        if let Some(etag) = etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+
+        let response = request.send().await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(Versioned::Unchanged);
+        }
+        if !response.status().is_success() {
+            return Err(ValidatorAPIError::GenericRequestFailure(
+                response.text().await?,
+            ));
+        }
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(|etag| etag.to_owned());
+        let is_binary = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .map(|content_type| content_type == BINARY_CONTENT_TYPE)
+            .unwrap_or_default();
+        let bytes = response.bytes().await?.to_vec();
+
+        Ok(Versioned::Changed {
+            value: EncodedValue { bytes, is_binary },
+            etag,
+        })
+    }
+
+    // requests the value in the compact binary encoding, unless the validator API does not support
+    // it, and only if it has changed since the provided entity tag. If the binary response can't
+    // be decoded, the value is requested again as json.
+    async fn query_validator_api_if_changed<T, K, V>(
+        &self,
+        path: PathSegments<'_>,
+        params: Params<'_, K, V>,
+        etag: Option<&str>,
+    ) -> Result<Versioned<T>, ValidatorAPIError>
+    where
+        for<'a> T: Deserialize<'a>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let url = create_api_url(&self.url, path, params);
+        let accept = format!("{}, {};q=0.9", BINARY_CONTENT_TYPE, JSON_CONTENT_TYPE);
+        let (encoded, etag) = match self.get_if_changed(url.clone(), &accept, etag).await? {
+            Versioned::Unchanged => return Ok(Versioned::Unchanged),
+            Versioned::Changed { value, etag } => (value, etag),
+        };
+
+        if !encoded.is_binary {
+            let value = serde_json::from_slice(&encoded.bytes)?;
+            return Ok(Versioned::Changed { value, etag });
+        }
+
+        match bincode::deserialize(&encoded.bytes) {
+            Ok(value) => return Ok(Versioned::Changed { value, etag }),
+            Err(err) => warn!(
+                "failed to decode the binary response from {} - {}. Falling back to json",
+                url, err
+            ),
+        }
+
+        match self.get_if_changed(url, JSON_CONTENT_TYPE, None).await? {
+            Versioned::Unchanged => Ok(Versioned::Unchanged),
+            Versioned::Changed { value, etag } => Ok(Versioned::Changed {
+                value: serde_json::from_slice(&value.bytes)?,
+                etag,
+            }),
+        }
+    }
+
     async fn post_validator_api<B, T, K, V>(
         &self,
         path: PathSegments<'_>,
@@ -90,6 +200,34 @@ impl Client {
             .await
     }
 
+    /// Subscribes to the changes of the validator API view of the network, such as nodes
+    /// getting (un)bonded, the rewarded set changing or the epoch advancing.
+    pub async fn subscribe_to_cache_events(
+        &self,
+    ) -> Result<ValidatorCacheEventStream, ValidatorAPIError> {
+        let url = create_api_url(&self.url, &[routes::API_VERSION, routes::EVENTS], NO_PARAMS);
+        let response = self.reqwest_client.get(url).send().await?;
+        if response.status().is_success() {
+            Ok(ValidatorCacheEventStream::new(response))
+        } else {
+            Err(ValidatorAPIError::GenericRequestFailure(
+                response.text().await?,
+            ))
+        }
+    }
+
+    pub async fn get_gateways_if_changed(
+        &self,
+        etag: Option<&str>,
+    ) -> Result<Versioned<Vec<GatewayBond>>, ValidatorAPIError> {
+        self.query_validator_api_if_changed(
+            &[routes::API_VERSION, routes::GATEWAYS],
+            NO_PARAMS,
+            etag,
+        )
+        .await
+    }
+
     pub async fn get_active_mixnodes(&self) -> Result<Vec<MixNodeBond>, ValidatorAPIError> {
         self.query_validator_api(
             &[routes::API_VERSION, routes::MIXNODES, routes::ACTIVE],
@@ -98,6 +236,34 @@ impl Client {
         .await
     }
 
+    pub async fn get_active_mixnodes_if_changed(
+        &self,
+        etag: Option<&str>,
+    ) -> Result<Versioned<Vec<MixNodeBond>>, ValidatorAPIError> {
+        self.query_validator_api_if_changed(
+            &[routes::API_VERSION, routes::MIXNODES, routes::ACTIVE],
+            NO_PARAMS,
+            etag,
+        )
+        .await
+    }
+
+    pub async fn get_signed_topology(&self) -> Result<SignedTopologySnapshot, ValidatorAPIError> {
+        self.query_validator_api(
+            &[routes::API_VERSION, routes::TOPOLOGY, routes::SIGNED],
+            NO_PARAMS,
+        )
+        .await
+    }
+
+    pub async fn get_identity(&self) -> Result<ValidatorApiIdentity, ValidatorAPIError> {
+        self.query_validator_api(
+            &[routes::API_VERSION, routes::TOPOLOGY, routes::IDENTITY],
+            NO_PARAMS,
+        )
+        .await
+    }
+
     pub async fn get_rewarded_mixnodes(&self) -> Result<Vec<MixNodeBond>, ValidatorAPIError> {
         self.query_validator_api(
             &[routes::API_VERSION, routes::MIXNODES, routes::REWARDED],
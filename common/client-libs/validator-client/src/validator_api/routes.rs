@@ -7,6 +7,12 @@ pub const API_VERSION: &str = VALIDATOR_API_VERSION;
 pub const MIXNODES: &str = "mixnodes";
 pub const GATEWAYS: &str = "gateways";
 
+pub const EVENTS: &str = "events";
+
+pub const TOPOLOGY: &str = "topology";
+pub const SIGNED: &str = "signed";
+pub const IDENTITY: &str = "identity";
+
 pub const ACTIVE: &str = "active";
 pub const REWARDED: &str = "rewarded";
 
@@ -0,0 +1,336 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::validator_api::error::ValidatorAPIError;
+use crypto::asymmetric::identity;
+use mixnet_contract_common::{GatewayBond, MixNodeBond};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use validator_api_requests::models::{SignedTopologySnapshot, TopologySnapshot};
+
+/// Verifies the signature on the provided snapshot, whether it was produced by one of the trusted
+/// keys and whether it's still fresh, i.e. it's not older than `max_age` and, if `min_epoch` is
+/// specified, it doesn't come from an earlier epoch. On success, returns the key of the signer
+/// alongside the deserialized snapshot.
+pub fn verify_topology_snapshot(
+    signed: &SignedTopologySnapshot,
+    trusted_keys: &[identity::PublicKey],
+    max_age: Duration,
+    min_epoch: Option<u32>,
+) -> Result<(identity::PublicKey, TopologySnapshot), ValidatorAPIError> {
+    let signer = identity::PublicKey::from_base58_string(&signed.identity_key)
+        .map_err(|err| ValidatorAPIError::InvalidTopologySignature(err.to_string()))?;
+
+    if !trusted_keys.contains(&signer) {
+        return Err(ValidatorAPIError::UntrustedTopologySigner(
+            signed.identity_key.clone(),
+        ));
+    }
+
+    let signature = identity::Signature::from_base58_string(&signed.signature)
+        .map_err(|err| ValidatorAPIError::InvalidTopologySignature(err.to_string()))?;
+    signer
+        .verify(signed.snapshot.as_bytes(), &signature)
+        .map_err(|err| ValidatorAPIError::InvalidTopologySignature(err.to_string()))?;
+
+    let snapshot: TopologySnapshot = serde_json::from_str(&signed.snapshot)?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("the system clock is set before the unix epoch")
+        .as_secs() as i64;
+    let age = now.saturating_sub(snapshot.timestamp);
+    if age > max_age.as_secs() as i64 {
+        return Err(ValidatorAPIError::StaleTopologySnapshot {
+            age_secs: age,
+            max_age_secs: max_age.as_secs(),
+        });
+    }
+
+    if let Some(min_epoch) = min_epoch {
+        let epoch = snapshot.epoch.map(|epoch| epoch.id());
+        if epoch.map(|epoch| epoch < min_epoch).unwrap_or(true) {
+            return Err(ValidatorAPIError::OutdatedTopologyEpoch { epoch, min_epoch });
+        }
+    }
+
+    Ok((signer, snapshot))
+}
+
+/// Determines the active mixnodes and gateways that at least `threshold` of the provided snapshots
+/// agree on, i.e. they contain a node with the same identity and exactly the same details.
+/// Note that the snapshots are assumed to come from distinct signers.
+pub fn agreed_topology(
+    snapshots: &[TopologySnapshot],
+    threshold: usize,
+) -> (Vec<MixNodeBond>, Vec<GatewayBond>) {
+    let active_mixnodes = snapshots.iter().map(|snapshot| {
+        let active_set = snapshot.active_set.iter().collect::<HashSet<_>>();
+        snapshot
+            .mixnodes
+            .iter()
+            .filter(|bond| active_set.contains(&bond.mix_node.identity_key))
+            .map(|bond| (bond.mix_node.identity_key.as_str(), bond))
+            .collect::<Vec<_>>()
+    });
+    let gateways = snapshots.iter().map(|snapshot| {
+        snapshot
+            .gateways
+            .iter()
+            .map(|bond| (bond.gateway.identity_key.as_str(), bond))
+            .collect::<Vec<_>>()
+    });
+
+    // the stake information is irrelevant for the topology and might legitimately differ
+    // if the snapshots were taken at slightly different block heights
+    let mixnodes = agreed_entries(active_mixnodes, threshold, |a, b| {
+        a.layer == b.layer && a.mix_node == b.mix_node
+    });
+    let gateways = agreed_entries(gateways, threshold, |a, b| a.gateway == b.gateway);
+
+    (mixnodes, gateways)
+}
+
+fn agreed_entries<'a, T, I, F>(views: I, threshold: usize, same: F) -> Vec<T>
+where
+    T: Clone + 'a,
+    I: IntoIterator<Item = Vec<(&'a str, &'a T)>>,
+    F: Fn(&T, &T) -> bool,
+{
+    // for each identity, all of its variants alongside the number of views containing them
+    let mut variants: HashMap<&str, Vec<(&T, usize)>> = HashMap::new();
+    for view in views {
+        // make sure a view listing the same node multiple times can't vote for it more than once
+        let mut seen = HashSet::new();
+        for (identity, entry) in view {
+            if !seen.insert(identity) {
+                continue;
+            }
+            let identity_variants = variants.entry(identity).or_default();
+            match identity_variants
+                .iter_mut()
+                .find(|(variant, _)| same(variant, entry))
+            {
+                Some((_, votes)) => *votes += 1,
+                None => identity_variants.push((entry, 1)),
+            }
+        }
+    }
+
+    variants
+        .into_values()
+        .flatten()
+        .filter(|(_, votes)| *votes >= threshold)
+        .map(|(entry, _)| entry.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mixnet_contract_common::{Addr, Coin, Gateway, Interval, Layer, MixNode};
+
+    const MAX_AGE: Duration = Duration::from_secs(60);
+
+    fn mixnode(identity: &str, host: &str) -> MixNodeBond {
+        MixNodeBond {
+            pledge_amount: Coin::new(100_000_000, "unym"),
+            total_delegation: Coin::new(0, "unym"),
+            owner: Addr::unchecked("owner"),
+            layer: Layer::One,
+            block_height: 1,
+            mix_node: MixNode {
+                host: host.to_string(),
+                mix_port: 1789,
+                verloc_port: 1790,
+                http_api_port: 8000,
+                sphinx_key: "sphinx".to_string(),
+                identity_key: identity.to_string(),
+                version: "1.0.0".to_string(),
+                profit_margin_percent: 10,
+                operating_cost: None,
+            },
+            proxy: None,
+            accumulated_rewards: None,
+            family: None,
+            jailed: None,
+        }
+    }
+
+    fn gateway(identity: &str, host: &str) -> GatewayBond {
+        GatewayBond {
+            pledge_amount: Coin::new(100_000_000, "unym"),
+            owner: Addr::unchecked("owner"),
+            block_height: 1,
+            gateway: Gateway {
+                host: host.to_string(),
+                mix_port: 1789,
+                clients_port: 9000,
+                location: "Earth".to_string(),
+                sphinx_key: "sphinx".to_string(),
+                identity_key: identity.to_string(),
+                version: "1.0.0".to_string(),
+            },
+            proxy: None,
+        }
+    }
+
+    fn epoch(id: u32) -> Interval {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "start": "2022-05-01T00:00:00Z",
+            "length": { "secs": 3600, "nanos": 0 },
+        }))
+        .unwrap()
+    }
+
+    fn now() -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64
+    }
+
+    fn snapshot(mixnodes: Vec<MixNodeBond>, gateways: Vec<GatewayBond>) -> TopologySnapshot {
+        TopologySnapshot {
+            timestamp: now(),
+            epoch: Some(epoch(3)),
+            active_set: mixnodes
+                .iter()
+                .map(|bond| bond.mix_node.identity_key.clone())
+                .collect(),
+            rewarded_set: Vec::new(),
+            mixnodes,
+            gateways,
+        }
+    }
+
+    fn keypair(seed: u8) -> identity::KeyPair {
+        let private_key = identity::PrivateKey::from_bytes(&[seed; 32]).unwrap();
+        let public_key = identity::PublicKey::from(&private_key);
+        identity::KeyPair::from_bytes(&private_key.to_bytes(), &public_key.to_bytes()).unwrap()
+    }
+
+    fn sign(keypair: &identity::KeyPair, snapshot: &TopologySnapshot) -> SignedTopologySnapshot {
+        let snapshot = serde_json::to_string(snapshot).unwrap();
+        SignedTopologySnapshot {
+            identity_key: keypair.public_key().to_base58_string(),
+            signature: keypair
+                .private_key()
+                .sign(snapshot.as_bytes())
+                .to_base58_string(),
+            snapshot,
+        }
+    }
+
+    #[test]
+    fn snapshot_verification() {
+        let keys = keypair(1);
+        let other_keys = keypair(2);
+        let trusted = [*keys.public_key()];
+        let snapshot = snapshot(vec![mixnode("a", "1.1.1.1")], vec![]);
+        let signed = sign(&keys, &snapshot);
+
+        let (signer, verified) =
+            verify_topology_snapshot(&signed, &trusted, MAX_AGE, None).unwrap();
+        assert_eq!(signer, *keys.public_key());
+        assert_eq!(verified.mixnodes, snapshot.mixnodes);
+
+        // an empty list of trusted keys doesn't mean any key is trusted
+        assert!(matches!(
+            verify_topology_snapshot(&signed, &[], MAX_AGE, None),
+            Err(ValidatorAPIError::UntrustedTopologySigner(_))
+        ));
+        assert!(matches!(
+            verify_topology_snapshot(&signed, &[*other_keys.public_key()], MAX_AGE, None),
+            Err(ValidatorAPIError::UntrustedTopologySigner(_))
+        ));
+
+        let mut tampered = signed.clone();
+        tampered.snapshot = tampered.snapshot.replace("1.1.1.1", "6.6.6.6");
+        assert!(matches!(
+            verify_topology_snapshot(&tampered, &trusted, MAX_AGE, None),
+            Err(ValidatorAPIError::InvalidTopologySignature(_))
+        ));
+
+        let mut impersonated = signed;
+        impersonated.identity_key = other_keys.public_key().to_base58_string();
+        assert!(matches!(
+            verify_topology_snapshot(&impersonated, &[*other_keys.public_key()], MAX_AGE, None),
+            Err(ValidatorAPIError::InvalidTopologySignature(_))
+        ));
+    }
+
+    #[test]
+    fn stale_snapshots_are_rejected() {
+        let keys = keypair(1);
+        let trusted = [*keys.public_key()];
+
+        let mut old = snapshot(vec![mixnode("a", "1.1.1.1")], vec![]);
+        old.timestamp = now() - 2 * MAX_AGE.as_secs() as i64;
+        assert!(matches!(
+            verify_topology_snapshot(&sign(&keys, &old), &trusted, MAX_AGE, None),
+            Err(ValidatorAPIError::StaleTopologySnapshot { .. })
+        ));
+
+        let current = sign(&keys, &snapshot(vec![mixnode("a", "1.1.1.1")], vec![]));
+        assert!(verify_topology_snapshot(&current, &trusted, MAX_AGE, Some(2)).is_ok());
+        assert!(verify_topology_snapshot(&current, &trusted, MAX_AGE, Some(3)).is_ok());
+        assert!(matches!(
+            verify_topology_snapshot(&current, &trusted, MAX_AGE, Some(4)),
+            Err(ValidatorAPIError::OutdatedTopologyEpoch {
+                epoch: Some(3),
+                min_epoch: 4
+            })
+        ));
+
+        let mut without_epoch = snapshot(vec![mixnode("a", "1.1.1.1")], vec![]);
+        without_epoch.epoch = None;
+        let without_epoch = sign(&keys, &without_epoch);
+        assert!(verify_topology_snapshot(&without_epoch, &trusted, MAX_AGE, None).is_ok());
+        assert!(matches!(
+            verify_topology_snapshot(&without_epoch, &trusted, MAX_AGE, Some(3)),
+            Err(ValidatorAPIError::OutdatedTopologyEpoch { epoch: None, .. })
+        ));
+    }
+
+    #[test]
+    fn only_nodes_agreed_on_by_threshold_of_snapshots_are_included() {
+        let snapshots = vec![
+            snapshot(
+                vec![mixnode("a", "1.1.1.1"), mixnode("b", "2.2.2.2")],
+                vec![gateway("g", "3.3.3.3")],
+            ),
+            snapshot(
+                vec![mixnode("a", "1.1.1.1"), mixnode("b", "6.6.6.6")],
+                vec![gateway("g", "3.3.3.3")],
+            ),
+            snapshot(
+                vec![mixnode("a", "1.1.1.1"), mixnode("c", "4.4.4.4")],
+                vec![gateway("g", "6.6.6.6"), gateway("h", "5.5.5.5")],
+            ),
+        ];
+
+        let (mixnodes, gateways) = agreed_topology(&snapshots, 2);
+        assert_eq!(mixnodes, vec![mixnode("a", "1.1.1.1")]);
+        assert_eq!(gateways, vec![gateway("g", "3.3.3.3")]);
+
+        let (mixnodes, gateways) = agreed_topology(&snapshots, 3);
+        assert_eq!(mixnodes, vec![mixnode("a", "1.1.1.1")]);
+        assert!(gateways.is_empty());
+    }
+
+    #[test]
+    fn inactive_and_duplicate_nodes_get_no_votes() {
+        let mut inactive = snapshot(vec![mixnode("a", "1.1.1.1")], vec![]);
+        inactive.active_set.clear();
+        let duplicated = snapshot(
+            vec![mixnode("b", "2.2.2.2"), mixnode("b", "2.2.2.2")],
+            vec![gateway("g", "3.3.3.3"), gateway("g", "3.3.3.3")],
+        );
+
+        let (mixnodes, gateways) = agreed_topology(&[inactive, duplicated], 2);
+        assert!(mixnodes.is_empty());
+        assert!(gateways.is_empty());
+    }
+}
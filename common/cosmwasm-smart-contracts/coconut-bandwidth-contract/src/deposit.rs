@@ -1,6 +1,7 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use cosmwasm_std::{Addr, Coin, Timestamp};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -32,3 +33,33 @@ impl DepositData {
         &self.encryption_key
     }
 }
+
+/// A deposit made towards obtaining a bandwidth credential.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Deposit {
+    pub owner: Addr,
+    pub amount: Coin,
+    pub created_at: Timestamp,
+    /// Signers that have confirmed issuing their share of the credential for this deposit.
+    pub issued_by: Vec<Addr>,
+    /// Set once a quorum of signers has confirmed issuing the credential. It's recorded
+    /// explicitly so that later changes to the signer set don't affect already issued deposits.
+    #[serde(default)]
+    pub issued: bool,
+}
+
+impl Deposit {
+    pub fn new(owner: Addr, amount: Coin, created_at: Timestamp) -> Self {
+        Deposit {
+            owner,
+            amount,
+            created_at,
+            issued_by: Vec::new(),
+            issued: false,
+        }
+    }
+
+    pub fn has_quorum(&self, signer_threshold: u32) -> bool {
+        self.issued_by.len() >= signer_threshold as usize
+    }
+}
@@ -3,9 +3,17 @@
 
 // event types
 pub const DEPOSITED_FUNDS_EVENT_TYPE: &str = "deposited-funds";
+pub const DEPOSIT_ISSUED_EVENT_TYPE: &str = "deposit-issued";
+pub const DEPOSIT_REFUNDED_EVENT_TYPE: &str = "deposit-refunded";
+pub const SIGNERS_UPDATED_EVENT_TYPE: &str = "signers-updated";
 
 // attributes that are used in multiple places
+pub const DEPOSIT_ID: &str = "deposit-id";
+pub const DEPOSIT_OWNER: &str = "deposit-owner";
+pub const DEPOSIT_SIGNER: &str = "deposit-signer";
 pub const DEPOSIT_VALUE: &str = "deposit-value";
 pub const DEPOSIT_INFO: &str = "deposit-info";
 pub const DEPOSIT_IDENTITY_KEY: &str = "deposit-identity-key";
 pub const DEPOSIT_ENCRYPTION_KEY: &str = "deposit-encryption-key";
+pub const SIGNERS: &str = "signers";
+pub const SIGNER_THRESHOLD: &str = "signer-threshold";
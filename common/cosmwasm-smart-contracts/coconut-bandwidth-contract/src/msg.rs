@@ -11,19 +11,49 @@ use crate::deposit::DepositData;
 pub struct InstantiateMsg {
     pub multisig_addr: String,
     pub pool_addr: String,
+    /// Addresses of the credential signers that are allowed to mark deposits as issued.
+    pub signers: Vec<String>,
+    /// Number of signers that have to mark a deposit as issued before it can no longer be refunded.
+    pub signer_threshold: u32,
+    /// Time (in seconds) after which a deposit that hasn't been issued can be refunded to its owner.
+    pub deposit_timeout_secs: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    DepositFunds { data: DepositData },
-    ReleaseFunds { funds: Coin },
+    DepositFunds {
+        data: DepositData,
+    },
+    ReleaseFunds {
+        funds: Coin,
+    },
+    MarkIssued {
+        deposit_id: u64,
+    },
+    RefundDeposit {
+        deposit_id: u64,
+    },
+    UpdateSigners {
+        signers: Vec<String>,
+        signer_threshold: u32,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub enum QueryMsg {}
+pub enum QueryMsg {
+    GetDeposit { deposit_id: u64 },
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// Credential signers to set, required when migrating from a version without a signer set.
+    #[serde(default)]
+    pub signers: Option<Vec<String>>,
+    #[serde(default)]
+    pub signer_threshold: Option<u32>,
+    #[serde(default)]
+    pub deposit_timeout_secs: Option<u64>,
+}
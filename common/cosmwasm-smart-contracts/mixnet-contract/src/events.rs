@@ -1,6 +1,6 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
-use crate::mixnode::NodeRewardResult;
+use crate::mixnode::{JailStatus, NodeRewardResult, PendingCostParamsChange};
 use crate::{ContractStateParams, IdentityKeyRef, Interval, Layer};
 use cosmwasm_std::{Addr, Coin, Event, Uint128};
 
@@ -12,11 +12,15 @@ pub const PENDING_DELEGATION_EVENT_TYPE: &str = "pending_delegation";
 pub const RECONCILE_DELEGATION_EVENT_TYPE: &str = "reconcile_delegation";
 pub const UNDELEGATION_EVENT_TYPE: &str = "undelegation";
 pub const PENDING_UNDELEGATION_EVENT_TYPE: &str = "pending_undelegation";
+pub const REDELEGATION_EVENT_TYPE: &str = "redelegation";
 pub const GATEWAY_BONDING_EVENT_TYPE: &str = "gateway_bonding";
 pub const GATEWAY_UNBONDING_EVENT_TYPE: &str = "gateway_unbonding";
 pub const MIXNODE_BONDING_EVENT_TYPE: &str = "mixnode_bonding";
 pub const MIXNODE_UNBONDING_EVENT_TYPE: &str = "mixnode_unbonding";
+pub const MIXNODE_PLEDGE_DECREASE_EVENT_TYPE: &str = "mixnode_pledge_decrease";
 pub const SETTINGS_UPDATE_EVENT_TYPE: &str = "settings_update";
+pub const CONTRACT_OWNER_PROPOSAL_EVENT_TYPE: &str = "contract_owner_proposal";
+pub const CONTRACT_OWNER_UPDATE_EVENT_TYPE: &str = "contract_owner_update";
 pub const OPERATOR_REWARDING_EVENT_TYPE: &str = "mix_rewarding";
 pub const MIX_DELEGATORS_REWARDING_EVENT_TYPE: &str = "mix_delegators_rewarding";
 pub const CHANGE_REWARDED_SET_EVENT_TYPE: &str = "change_rewarded_set";
@@ -25,6 +29,15 @@ pub const ADVANCE_EPOCH_EVENT_TYPE: &str = "advance_epoch";
 pub const COMPOUND_DELEGATOR_REWARD_EVENT_TYPE: &str = "compound_delegator_reward";
 pub const COMPOUND_OPERATOR_REWARD_EVENT_TYPE: &str = "compound_operator_reward";
 pub const SNAPSHOT_MIXNODES_EVENT: &str = "snapshot_mixnodes";
+pub const MIXNODE_COST_PARAMS_UPDATE_EVENT_TYPE: &str = "mixnode_cost_params_update";
+pub const PENDING_MIXNODE_COST_PARAMS_UPDATE_EVENT_TYPE: &str =
+    "pending_mixnode_cost_params_update";
+pub const MIXNODE_JAILING_EVENT_TYPE: &str = "mixnode_jailing";
+pub const MIXNODE_UNJAILING_EVENT_TYPE: &str = "mixnode_unjailing";
+pub const FAMILY_CREATION_EVENT_TYPE: &str = "family_creation";
+pub const FAMILY_JOIN_REQUEST_EVENT_TYPE: &str = "family_join_request";
+pub const FAMILY_JOIN_EVENT_TYPE: &str = "family_join";
+pub const FAMILY_LEAVE_EVENT_TYPE: &str = "family_leave";
 
 // attributes that are used in multiple places
 pub const OWNER_KEY: &str = "owner";
@@ -37,22 +50,49 @@ pub const PROXY_KEY: &str = "proxy";
 pub const DELEGATOR_KEY: &str = "delegator";
 pub const DELEGATION_TARGET_KEY: &str = "delegation_target";
 pub const DELEGATION_HEIGHT_KEY: &str = "delegation_latest_block_height";
+pub const REDELEGATION_SOURCE_KEY: &str = "redelegation_source";
 
 // bonding/unbonding
 pub const NODE_IDENTITY_KEY: &str = "identity";
 pub const ASSIGNED_LAYER_KEY: &str = "assigned_layer";
+pub const REMAINING_PLEDGE_KEY: &str = "remaining_pledge";
+
+// jailing
+pub const JAILED_AT_EPOCH_KEY: &str = "jailed_at_epoch";
+pub const UNJAIL_EPOCH_KEY: &str = "unjail_epoch";
+pub const NO_JAIL_REASON_KEY: &str = "no_jail_reason";
+pub const ALREADY_JAILED_VALUE: &str = "already_jailed";
 
 // settings change
+pub const OLD_OWNER_KEY: &str = "old_owner";
+pub const NEW_OWNER_KEY: &str = "new_owner";
+pub const PROPOSED_OWNER_KEY: &str = "proposed_owner";
 pub const OLD_MINIMUM_MIXNODE_PLEDGE_KEY: &str = "old_minimum_mixnode_pledge";
 pub const OLD_MINIMUM_GATEWAY_PLEDGE_KEY: &str = "old_minimum_gateway_pledge";
 pub const OLD_MIXNODE_REWARDED_SET_SIZE_KEY: &str = "old_mixnode_rewarded_set_size";
 pub const OLD_MIXNODE_ACTIVE_SET_SIZE_KEY: &str = "old_mixnode_active_set_size";
 pub const OLD_ACTIVE_SET_WORK_FACTOR_KEY: &str = "old_active_set_work_factor";
+pub const OLD_COST_PARAMS_CHANGE_NOTICE_EPOCHS_KEY: &str = "old_cost_params_change_notice_epochs";
+pub const OLD_MAXIMUM_MIXNODE_OPERATING_COST_KEY: &str = "old_maximum_mixnode_operating_cost";
+pub const OLD_JAIL_COOLDOWN_EPOCHS_KEY: &str = "old_jail_cooldown_epochs";
 
 pub const NEW_MINIMUM_MIXNODE_PLEDGE_KEY: &str = "new_minimum_mixnode_pledge";
 pub const NEW_MINIMUM_GATEWAY_PLEDGE_KEY: &str = "new_minimum_gateway_pledge";
 pub const NEW_MIXNODE_REWARDED_SET_SIZE_KEY: &str = "new_mixnode_rewarded_set_size";
 pub const NEW_MIXNODE_ACTIVE_SET_SIZE_KEY: &str = "new_mixnode_active_set_size";
+pub const NEW_COST_PARAMS_CHANGE_NOTICE_EPOCHS_KEY: &str = "new_cost_params_change_notice_epochs";
+pub const NEW_MAXIMUM_MIXNODE_OPERATING_COST_KEY: &str = "new_maximum_mixnode_operating_cost";
+pub const NEW_JAIL_COOLDOWN_EPOCHS_KEY: &str = "new_jail_cooldown_epochs";
+
+// cost params
+pub const PROFIT_MARGIN_PERCENT_KEY: &str = "profit_margin_percent";
+pub const OPERATING_COST_KEY: &str = "operating_cost";
+pub const EFFECTIVE_EPOCH_ID_KEY: &str = "effective_epoch_id";
+
+// families
+pub const FAMILY_HEAD_KEY: &str = "family_head";
+pub const FAMILY_LABEL_KEY: &str = "family_label";
+pub const FAMILY_MEMBER_KEY: &str = "family_member";
 
 // rewarding
 pub const INTERVAL_ID_KEY: &str = "interval_id";
@@ -68,6 +108,7 @@ pub const NO_REWARD_REASON_KEY: &str = "no_reward_reason";
 pub const BOND_NOT_FOUND_VALUE: &str = "bond_not_found";
 pub const BOND_TOO_FRESH_VALUE: &str = "bond_too_fresh";
 pub const ZERO_UPTIME_VALUE: &str = "zero_uptime";
+pub const INVALID_OPERATING_COST_VALUE: &str = "invalid_operating_cost";
 
 // rewarded set update
 pub const ACTIVE_SET_SIZE_KEY: &str = "active_set_size";
@@ -193,6 +234,7 @@ pub fn new_pending_undelegation_event(
     delegator: &Addr,
     proxy: &Option<Addr>,
     mix_identity: IdentityKeyRef<'_>,
+    amount: Option<Uint128>,
 ) -> Event {
     let mut event =
         Event::new(PENDING_UNDELEGATION_EVENT_TYPE).add_attribute(DELEGATOR_KEY, delegator);
@@ -201,10 +243,35 @@ pub fn new_pending_undelegation_event(
         event = event.add_attribute(PROXY_KEY, proxy)
     }
 
+    // the amount is only known upfront for partial undelegations
+    if let Some(amount) = amount {
+        event = event.add_attribute(AMOUNT_KEY, amount.to_string())
+    }
+
     // coin implements Display trait and we use that implementation here
     event.add_attribute(DELEGATION_TARGET_KEY, mix_identity)
 }
 
+pub fn new_redelegation_event(
+    delegator: &Addr,
+    proxy: &Option<Addr>,
+    amount: &Coin,
+    from_mix_identity: IdentityKeyRef<'_>,
+    to_mix_identity: IdentityKeyRef<'_>,
+) -> Event {
+    let mut event = Event::new(REDELEGATION_EVENT_TYPE).add_attribute(DELEGATOR_KEY, delegator);
+
+    if let Some(proxy) = proxy {
+        event = event.add_attribute(PROXY_KEY, proxy)
+    }
+
+    // coin implements Display trait and we use that implementation here
+    event
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+        .add_attribute(REDELEGATION_SOURCE_KEY, from_mix_identity)
+        .add_attribute(DELEGATION_TARGET_KEY, to_mix_identity)
+}
+
 pub fn new_gateway_bonding_event(
     owner: &Addr,
     proxy: &Option<Addr>,
@@ -280,6 +347,98 @@ pub fn new_mixnode_unbonding_event(
     event.add_attribute(AMOUNT_KEY, amount.to_string())
 }
 
+pub fn new_mixnode_pledge_decrease_event(
+    owner: &Addr,
+    proxy: &Option<Addr>,
+    amount: &Coin,
+    remaining_pledge: &Coin,
+    identity: IdentityKeyRef<'_>,
+) -> Event {
+    let mut event = Event::new(MIXNODE_PLEDGE_DECREASE_EVENT_TYPE)
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(NODE_IDENTITY_KEY, identity);
+
+    if let Some(proxy) = proxy {
+        event = event.add_attribute(PROXY_KEY, proxy)
+    }
+
+    event
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+        .add_attribute(REMAINING_PLEDGE_KEY, remaining_pledge.to_string())
+}
+
+pub fn new_mixnode_jailing_event(identity: IdentityKeyRef<'_>, jail_status: &JailStatus) -> Event {
+    Event::new(MIXNODE_JAILING_EVENT_TYPE)
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+        .add_attribute(
+            JAILED_AT_EPOCH_KEY,
+            jail_status.jailed_at_epoch_id.to_string(),
+        )
+        .add_attribute(UNJAIL_EPOCH_KEY, jail_status.unjail_epoch_id.to_string())
+}
+
+pub fn new_not_found_mixnode_jailing_event(identity: IdentityKeyRef<'_>) -> Event {
+    Event::new(MIXNODE_JAILING_EVENT_TYPE)
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+        .add_attribute(NO_JAIL_REASON_KEY, BOND_NOT_FOUND_VALUE)
+}
+
+pub fn new_already_jailed_mixnode_jailing_event(identity: IdentityKeyRef<'_>) -> Event {
+    Event::new(MIXNODE_JAILING_EVENT_TYPE)
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+        .add_attribute(NO_JAIL_REASON_KEY, ALREADY_JAILED_VALUE)
+}
+
+pub fn new_mixnode_unjailing_event(owner: &Addr, identity: IdentityKeyRef<'_>) -> Event {
+    Event::new(MIXNODE_UNJAILING_EVENT_TYPE)
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+}
+
+pub fn new_family_creation_event(
+    owner: &Addr,
+    family_head: IdentityKeyRef<'_>,
+    label: &str,
+) -> Event {
+    Event::new(FAMILY_CREATION_EVENT_TYPE)
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(FAMILY_HEAD_KEY, family_head)
+        .add_attribute(FAMILY_LABEL_KEY, label)
+}
+
+pub fn new_family_join_request_event(
+    owner: &Addr,
+    family_head: IdentityKeyRef<'_>,
+    member: IdentityKeyRef<'_>,
+) -> Event {
+    Event::new(FAMILY_JOIN_REQUEST_EVENT_TYPE)
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(FAMILY_HEAD_KEY, family_head)
+        .add_attribute(FAMILY_MEMBER_KEY, member)
+}
+
+pub fn new_family_join_event(
+    owner: &Addr,
+    family_head: IdentityKeyRef<'_>,
+    member: IdentityKeyRef<'_>,
+) -> Event {
+    Event::new(FAMILY_JOIN_EVENT_TYPE)
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(FAMILY_HEAD_KEY, family_head)
+        .add_attribute(FAMILY_MEMBER_KEY, member)
+}
+
+pub fn new_family_leave_event(
+    owner: &Addr,
+    family_head: IdentityKeyRef<'_>,
+    member: IdentityKeyRef<'_>,
+) -> Event {
+    Event::new(FAMILY_LEAVE_EVENT_TYPE)
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(FAMILY_HEAD_KEY, family_head)
+        .add_attribute(FAMILY_MEMBER_KEY, member)
+}
+
 pub fn new_settings_update_event(
     old_params: &ContractStateParams,
     new_params: &ContractStateParams,
@@ -334,9 +493,109 @@ pub fn new_settings_update_event(
             )
     }
 
+    if old_params.cost_params_change_notice_epochs != new_params.cost_params_change_notice_epochs {
+        event = event
+            .add_attribute(
+                OLD_COST_PARAMS_CHANGE_NOTICE_EPOCHS_KEY,
+                old_params.cost_params_change_notice_epochs.to_string(),
+            )
+            .add_attribute(
+                NEW_COST_PARAMS_CHANGE_NOTICE_EPOCHS_KEY,
+                new_params.cost_params_change_notice_epochs.to_string(),
+            )
+    }
+
+    if old_params.maximum_mixnode_operating_cost != new_params.maximum_mixnode_operating_cost {
+        event = event
+            .add_attribute(
+                OLD_MAXIMUM_MIXNODE_OPERATING_COST_KEY,
+                old_params.maximum_mixnode_operating_cost,
+            )
+            .add_attribute(
+                NEW_MAXIMUM_MIXNODE_OPERATING_COST_KEY,
+                new_params.maximum_mixnode_operating_cost,
+            )
+    }
+
+    if old_params.jail_cooldown_epochs != new_params.jail_cooldown_epochs {
+        event = event
+            .add_attribute(
+                OLD_JAIL_COOLDOWN_EPOCHS_KEY,
+                old_params.jail_cooldown_epochs.to_string(),
+            )
+            .add_attribute(
+                NEW_JAIL_COOLDOWN_EPOCHS_KEY,
+                new_params.jail_cooldown_epochs.to_string(),
+            )
+    }
+
     event
 }
 
+pub fn new_contract_owner_proposal_event(owner: &Addr, proposed_owner: &Addr) -> Event {
+    Event::new(CONTRACT_OWNER_PROPOSAL_EVENT_TYPE)
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(PROPOSED_OWNER_KEY, proposed_owner)
+}
+
+pub fn new_contract_owner_update_event(old_owner: &Addr, new_owner: &Addr) -> Event {
+    Event::new(CONTRACT_OWNER_UPDATE_EVENT_TYPE)
+        .add_attribute(OLD_OWNER_KEY, old_owner)
+        .add_attribute(NEW_OWNER_KEY, new_owner)
+}
+
+fn add_cost_params_attributes(
+    event: Event,
+    profit_margin_percent: u8,
+    operating_cost: Option<Uint128>,
+) -> Event {
+    let event = event.add_attribute(PROFIT_MARGIN_PERCENT_KEY, profit_margin_percent.to_string());
+    match operating_cost {
+        Some(operating_cost) => event.add_attribute(OPERATING_COST_KEY, operating_cost),
+        None => event,
+    }
+}
+
+pub fn new_pending_mixnode_cost_params_update_event(
+    owner: &Addr,
+    proxy: &Option<Addr>,
+    identity: IdentityKeyRef<'_>,
+    change: &PendingCostParamsChange,
+) -> Event {
+    let mut event = Event::new(PENDING_MIXNODE_COST_PARAMS_UPDATE_EVENT_TYPE)
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(NODE_IDENTITY_KEY, identity);
+
+    if let Some(proxy) = proxy {
+        event = event.add_attribute(PROXY_KEY, proxy)
+    }
+
+    add_cost_params_attributes(
+        event.add_attribute(
+            EFFECTIVE_EPOCH_ID_KEY,
+            change.effective_epoch_id.to_string(),
+        ),
+        change.profit_margin_percent,
+        change.operating_cost,
+    )
+}
+
+pub fn new_mixnode_cost_params_update_event(
+    identity: IdentityKeyRef<'_>,
+    change: &PendingCostParamsChange,
+) -> Event {
+    add_cost_params_attributes(
+        Event::new(MIXNODE_COST_PARAMS_UPDATE_EVENT_TYPE)
+            .add_attribute(NODE_IDENTITY_KEY, identity)
+            .add_attribute(
+                EFFECTIVE_EPOCH_ID_KEY,
+                change.effective_epoch_id.to_string(),
+            ),
+        change.profit_margin_percent,
+        change.operating_cost,
+    )
+}
+
 pub fn new_not_found_mix_operator_rewarding_event(
     interval_id: u32,
     identity: IdentityKeyRef<'_>,
@@ -367,6 +626,16 @@ pub fn new_zero_uptime_mix_operator_rewarding_event(
         .add_attribute(NO_REWARD_REASON_KEY, ZERO_UPTIME_VALUE)
 }
 
+pub fn new_invalid_operating_cost_mix_operator_rewarding_event(
+    interval_id: u32,
+    identity: IdentityKeyRef<'_>,
+) -> Event {
+    Event::new(OPERATOR_REWARDING_EVENT_TYPE)
+        .add_attribute(INTERVAL_ID_KEY, interval_id.to_string())
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+        .add_attribute(NO_REWARD_REASON_KEY, INVALID_OPERATING_COST_VALUE)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn new_mix_operator_rewarding_event(
     interval_id: u32,
@@ -0,0 +1,49 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::IdentityKey;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A group of mixnodes run by the same entity. The family is identified by the identity of
+/// the mixnode that created it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, JsonSchema)]
+pub struct Family {
+    pub head: IdentityKey,
+    pub label: String,
+}
+
+impl Family {
+    pub fn new(head: IdentityKey, label: String) -> Self {
+        Family { head, label }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, JsonSchema)]
+pub struct PagedFamiliesResponse {
+    pub families: Vec<Family>,
+    pub per_page: usize,
+    pub start_next_after: Option<IdentityKey>,
+}
+
+impl PagedFamiliesResponse {
+    pub fn new(
+        families: Vec<Family>,
+        per_page: usize,
+        start_next_after: Option<IdentityKey>,
+    ) -> Self {
+        PagedFamiliesResponse {
+            families,
+            per_page,
+            start_next_after,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, JsonSchema)]
+pub struct FamilyMembersResponse {
+    pub family: Option<Family>,
+    // note that the head itself is also included
+    pub members: Vec<IdentityKey>,
+    pub pending_members: Vec<IdentityKey>,
+}
@@ -4,6 +4,7 @@
 pub mod delegation;
 pub mod error;
 pub mod events;
+mod family;
 mod gateway;
 mod interval;
 pub mod mixnode;
@@ -18,10 +19,12 @@ pub use delegation::{
     Delegation, PagedAllDelegationsResponse, PagedDelegatorDelegationsResponse,
     PagedMixDelegationsResponse,
 };
+pub use family::{Family, FamilyMembersResponse, PagedFamiliesResponse};
 pub use gateway::{Gateway, GatewayBond, GatewayOwnershipResponse, PagedGatewayResponse};
 pub use interval::Interval;
 pub use mixnode::{
-    Layer, MixNode, MixNodeBond, MixOwnershipResponse, PagedMixnodeResponse, RewardedSetNodeStatus,
+    JailStatus, Layer, MixNode, MixNodeBond, MixOwnershipResponse, PagedMixnodeResponse,
+    PendingCostParamsChange, RewardedSetNodeStatus,
 };
 pub use msg::*;
 pub use types::*;
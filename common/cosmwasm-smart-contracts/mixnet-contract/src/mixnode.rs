@@ -8,6 +8,7 @@ use crate::{ONE, U128};
 use az::CheckedCast;
 use cosmwasm_std::{coin, Addr, Coin, Uint128};
 use log::error;
+use network_defaults::DEFAULT_OPERATOR_INTERVAL_COST;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
@@ -46,6 +47,9 @@ pub struct PendingUndelegate {
     delegate: Addr,
     proxy: Option<Addr>,
     block_height: u64,
+    // if not set, the entire delegation (alongside all accrued rewards) is withdrawn
+    #[serde(default)]
+    amount: Option<Uint128>,
 }
 
 impl PendingUndelegate {
@@ -54,12 +58,14 @@ impl PendingUndelegate {
         delegate: Addr,
         proxy: Option<Addr>,
         block_height: u64,
+        amount: Option<Uint128>,
     ) -> Self {
         Self {
             mix_identity,
             delegate,
             proxy,
             block_height,
+            amount,
         }
     }
 
@@ -79,6 +85,14 @@ impl PendingUndelegate {
         self.block_height
     }
 
+    pub fn amount(&self) -> Option<Uint128> {
+        self.amount
+    }
+
+    pub fn is_partial(&self) -> bool {
+        self.amount.is_some()
+    }
+
     pub fn proxy_storage_key(&self) -> Vec<u8> {
         if let Some(proxy) = &self.proxy {
             self.delegate()
@@ -125,6 +139,31 @@ pub struct MixNode {
     pub identity_key: IdentityKey,
     pub version: String,
     pub profit_margin_percent: u8,
+    /// Operating cost of the node per epoch, assuming 100% uptime. If not set,
+    /// the network default is used.
+    #[serde(default)]
+    #[cfg_attr(test, ts(type = "string | null"))]
+    pub operating_cost: Option<Uint128>,
+}
+
+/// Profit margin and operating cost change requested by the node operator. To give the delegators
+/// a chance to react, the change only takes effect at the beginning of the specified epoch.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, JsonSchema)]
+pub struct PendingCostParamsChange {
+    pub profit_margin_percent: u8,
+    pub operating_cost: Option<Uint128>,
+    /// Id of the epoch from which the new values are going to be used.
+    pub effective_epoch_id: u32,
+}
+
+/// Set by the rewarding validator on mixnodes with sustained poor performance. Jailed nodes
+/// are not eligible for the rewarded set until their operator unjails them.
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Serialize, JsonSchema)]
+pub struct JailStatus {
+    /// Id of the epoch during which the node got jailed.
+    pub jailed_at_epoch_id: u32,
+    /// Id of the first epoch during which the operator is allowed to unjail the node.
+    pub unjail_epoch_id: u32,
 }
 
 #[derive(
@@ -328,6 +367,11 @@ pub struct MixNodeBond {
     pub mix_node: MixNode,
     pub proxy: Option<Addr>,
     pub accumulated_rewards: Option<Uint128>,
+    // identity of the head of the family this node belongs to (if any)
+    #[serde(default)]
+    pub family: Option<IdentityKey>,
+    #[serde(default)]
+    pub jailed: Option<JailStatus>,
 }
 
 impl MixNodeBond {
@@ -348,6 +392,8 @@ impl MixNodeBond {
             mix_node,
             proxy,
             accumulated_rewards: None,
+            family: None,
+            jailed: None,
         }
     }
 
@@ -359,10 +405,29 @@ impl MixNodeBond {
         U128::from_num(self.mix_node.profit_margin_percent) / U128::from_num(100)
     }
 
+    pub fn operating_cost(&self) -> u128 {
+        self.mix_node
+            .operating_cost
+            .map(|cost| cost.u128())
+            .unwrap_or(DEFAULT_OPERATOR_INTERVAL_COST as u128)
+    }
+
+    pub fn operator_cost(&self, params: &RewardParams) -> U128 {
+        // the operating cost is bounded by the contract, so this should never saturate
+        params
+            .node
+            .operator_cost_for(self.operating_cost())
+            .unwrap_or(U128::MAX)
+    }
+
     pub fn identity(&self) -> &String {
         &self.mix_node.identity_key
     }
 
+    pub fn is_jailed(&self) -> bool {
+        self.jailed.is_some()
+    }
+
     pub fn pledge_amount(&self) -> Coin {
         self.pledge_amount.clone()
     }
@@ -462,10 +527,11 @@ impl MixNodeBond {
     }
 
     pub fn node_profit(&self, params: &RewardParams) -> U128 {
-        if self.reward(params).reward() < params.node.operator_cost() {
+        let operator_cost = self.operator_cost(params);
+        if self.reward(params).reward() < operator_cost {
             U128::from_num(0u128)
         } else {
-            self.reward(params).reward() - params.node.operator_cost()
+            self.reward(params).reward() - operator_cost
         }
     }
 
@@ -474,12 +540,13 @@ impl MixNodeBond {
         if reward.sigma == 0 {
             return 0;
         }
-        let profit = if reward.reward < params.node.operator_cost() {
+        let operator_cost = self.operator_cost(params);
+        let profit = if reward.reward < operator_cost {
             U128::from_num(0u128)
         } else {
-            reward.reward - params.node.operator_cost()
+            reward.reward - operator_cost
         };
-        let operator_base_reward = reward.reward.min(params.node.operator_cost());
+        let operator_base_reward = reward.reward.min(operator_cost);
         // Div by zero checked above
         let operator_reward = (self.profit_margin()
             + (ONE - self.profit_margin()) * reward.lambda / reward.sigma)
@@ -636,6 +703,7 @@ mod tests {
             identity_key: "identitykey".to_string(),
             version: "0.11.0".to_string(),
             profit_margin_percent: 10,
+            operating_cost: None,
         }
     }
 
@@ -654,6 +722,8 @@ mod tests {
             mix_node: mixnode_fixture(),
             proxy: None,
             accumulated_rewards: Some(Uint128::zero()),
+            family: None,
+            jailed: None,
         };
 
         let mix2 = MixNodeBond {
@@ -665,6 +735,8 @@ mod tests {
             mix_node: mixnode_fixture(),
             proxy: None,
             accumulated_rewards: Some(Uint128::zero()),
+            family: None,
+            jailed: None,
         };
 
         let mix3 = MixNodeBond {
@@ -676,6 +748,8 @@ mod tests {
             mix_node: mixnode_fixture(),
             proxy: None,
             accumulated_rewards: Some(Uint128::zero()),
+            family: None,
+            jailed: None,
         };
 
         let mix4 = MixNodeBond {
@@ -687,6 +761,8 @@ mod tests {
             mix_node: mixnode_fixture(),
             proxy: None,
             accumulated_rewards: Some(Uint128::zero()),
+            family: None,
+            jailed: None,
         };
 
         let mix5 = MixNodeBond {
@@ -698,6 +774,8 @@ mod tests {
             mix_node: mixnode_fixture(),
             proxy: None,
             accumulated_rewards: Some(Uint128::zero()),
+            family: None,
+            jailed: None,
         };
 
         // summary:
@@ -4,6 +4,7 @@
 use crate::reward_params::NodeRewardParams;
 use crate::ContractStateParams;
 use crate::{Gateway, IdentityKey, MixNode};
+use cosmwasm_std::{Coin, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +19,12 @@ pub enum ExecuteMsg {
     UpdateRewardingValidatorAddress {
         address: String,
     },
+    // proposes handing over the ownership of the contract, for example to a multisig contract.
+    // The ownership is only transferred once the proposed owner accepts it
+    ProposeContractOwner {
+        address: String,
+    },
+    AcceptContractOwnership {},
     InitEpoch {},
     ReconcileDelegations {},
     CheckpointMixnodes {},
@@ -37,11 +44,21 @@ pub enum ExecuteMsg {
         owner_signature: String,
     },
     UnbondMixnode {},
+    // withdraws part of the pledge, as long as the remaining amount is not below the minimum pledge
+    DecreaseMixnodePledge {
+        amount: Coin,
+    },
+    // the changes are only applied after the notice period has passed.
+    // if `operating_cost` is not set, the current one is kept.
     UpdateMixnodeConfig {
         profit_margin_percent: u8,
+        #[serde(default)]
+        operating_cost: Option<Uint128>,
     },
     UpdateMixnodeConfigOnBehalf {
         profit_margin_percent: u8,
+        #[serde(default)]
+        operating_cost: Option<Uint128>,
         owner: String,
     },
     BondGateway {
@@ -59,6 +76,20 @@ pub enum ExecuteMsg {
         mix_identity: IdentityKey,
     },
 
+    // similarly to the full undelegation, it's only applied once the pending events are reconciled
+    PartiallyUndelegateFromMixnode {
+        mix_identity: IdentityKey,
+        amount: Coin,
+    },
+
+    // moves the specified amount of the delegation (and any rewards accrued on it) from one mixnode to another
+    // without going through the pending undelegation and delegation events
+    RedelegateFromMixnode {
+        from: IdentityKey,
+        to: IdentityKey,
+        amount: Coin,
+    },
+
     RewardMixnode {
         identity: IdentityKey,
         // percentage value in range 0-100
@@ -77,6 +108,17 @@ pub enum ExecuteMsg {
         mix_identity: IdentityKey,
         delegate: String,
     },
+    PartiallyUndelegateFromMixnodeOnBehalf {
+        mix_identity: IdentityKey,
+        amount: Coin,
+        delegate: String,
+    },
+    RedelegateFromMixnodeOnBehalf {
+        from: IdentityKey,
+        to: IdentityKey,
+        amount: Coin,
+        delegate: String,
+    },
     BondMixnodeOnBehalf {
         mix_node: MixNode,
         owner: String,
@@ -85,6 +127,10 @@ pub enum ExecuteMsg {
     UnbondMixnodeOnBehalf {
         owner: String,
     },
+    DecreaseMixnodePledgeOnBehalf {
+        amount: Coin,
+        owner: String,
+    },
     BondGatewayOnBehalf {
         gateway: Gateway,
         owner: String,
@@ -93,6 +139,22 @@ pub enum ExecuteMsg {
     UnbondGatewayOnBehalf {
         owner: String,
     },
+    CreateFamily {
+        label: String,
+    },
+    // the signature is made with the identity key of the joining node over the identity of the family head
+    JoinFamily {
+        family_head: IdentityKey,
+        signature: String,
+    },
+    AcceptFamilyMember {
+        member: IdentityKey,
+    },
+    LeaveFamily {},
+    JailMixnode {
+        mix_identity: IdentityKey,
+    },
+    UnjailMixnode {},
     WriteRewardedSet {
         rewarded_set: Vec<IdentityKey>,
         expected_active_set_size: u32,
@@ -184,6 +246,18 @@ pub enum QueryMsg {
         mix_identity: IdentityKey,
         height: u64,
     },
+    // gets the profit margin and operating cost change of particular mixnode that is yet to take effect
+    GetPendingCostParamsChange {
+        mix_identity: IdentityKey,
+    },
+    GetFamiliesPaged {
+        start_after: Option<IdentityKey>,
+        limit: Option<u32>,
+    },
+    // gets the family, alongside all its accepted and pending members, headed by the particular mixnode
+    GetFamilyMembers {
+        family_head: IdentityKey,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -10,14 +10,28 @@ pub struct NodeEpochRewards {
     params: NodeRewardParams,
     result: StoredNodeRewardResult,
     epoch_id: u32,
+    // the cost parameters in effect during the epoch, they're optional as they were not recorded
+    // for the rewards distributed before the cost changes were introduced
+    #[serde(default)]
+    profit_margin_percent: Option<u8>,
+    #[serde(default)]
+    operating_cost: Option<Uint128>,
 }
 
 impl NodeEpochRewards {
-    pub fn new(params: NodeRewardParams, result: StoredNodeRewardResult, epoch_id: u32) -> Self {
+    pub fn new(
+        params: NodeRewardParams,
+        result: StoredNodeRewardResult,
+        epoch_id: u32,
+        profit_margin_percent: u8,
+        operating_cost: Uint128,
+    ) -> Self {
         Self {
             params,
             result,
             epoch_id,
+            profit_margin_percent: Some(profit_margin_percent),
+            operating_cost: Some(operating_cost),
         }
     }
 
@@ -25,6 +39,12 @@ impl NodeEpochRewards {
         self.epoch_id
     }
 
+    /// Profit margin of the node that was in effect during the rewarded epoch, if it was recorded.
+    pub fn profit_margin(&self) -> Option<U128> {
+        self.profit_margin_percent
+            .map(|percent| U128::from_num(percent) / U128::from_num(100))
+    }
+
     pub fn sigma(&self) -> U128 {
         self.result.sigma()
     }
@@ -42,7 +62,14 @@ impl NodeEpochRewards {
     }
 
     pub fn operator_cost(&self) -> U128 {
-        U128::from_num(self.params.uptime.u128() / 100u128 * DEFAULT_OPERATOR_INTERVAL_COST as u128)
+        let operating_cost = self
+            .operating_cost
+            .map(|cost| cost.u128())
+            .unwrap_or(DEFAULT_OPERATOR_INTERVAL_COST as u128);
+        // nodes whose operating cost can't be represented are never rewarded in the first place
+        self.params
+            .operator_cost_for(operating_cost)
+            .unwrap_or(U128::MAX)
     }
 
     pub fn node_profit(&self) -> U128 {
@@ -159,6 +186,14 @@ impl EpochRewardParams {
     pub fn epoch_reward_pool(&self) -> u128 {
         self.epoch_reward_pool.u128()
     }
+
+    pub fn sybil_resistance_percent(&self) -> u8 {
+        self.sybil_resistance_percent
+    }
+
+    pub fn active_set_work_factor(&self) -> u8 {
+        self.active_set_work_factor
+    }
 }
 
 #[derive(Debug, Clone, JsonSchema, PartialEq, Serialize, Deserialize, Copy)]
@@ -178,13 +213,26 @@ impl NodeRewardParams {
     }
 
     pub fn operator_cost(&self) -> U128 {
-        U128::from_num(self.uptime.u128() / 100u128 * DEFAULT_OPERATOR_INTERVAL_COST as u128)
+        self.operator_cost_for(DEFAULT_OPERATOR_INTERVAL_COST as u128)
+            .expect("the default operating cost is always representable")
+    }
+
+    /// Operating cost of the node scaled by its uptime. Returns `None` if the result
+    /// can't be represented.
+    pub fn operator_cost_for(&self, operating_cost: u128) -> Option<U128> {
+        (self.uptime.u128() / 100u128)
+            .checked_mul(operating_cost)
+            .and_then(U128::checked_from_num)
     }
 
     pub fn uptime(&self) -> u128 {
         self.uptime.u128()
     }
 
+    pub fn in_active_set(&self) -> bool {
+        self.in_active_set
+    }
+
     pub fn set_reward_blockstamp(&mut self, blockstamp: u64) {
         self.reward_blockstamp = blockstamp;
     }
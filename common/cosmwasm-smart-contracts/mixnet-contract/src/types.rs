@@ -27,6 +27,20 @@ impl LayerDistribution {
     }
 }
 
+/// Default upper bound on the per-epoch operating cost a mixnode operator can declare.
+pub const DEFAULT_MAXIMUM_MIXNODE_OPERATING_COST: Uint128 = Uint128::new(1_000_000_000);
+
+fn default_maximum_mixnode_operating_cost() -> Uint128 {
+    DEFAULT_MAXIMUM_MIXNODE_OPERATING_COST
+}
+
+/// Default number of epochs that have to pass before a jailed mixnode can be unjailed.
+pub const DEFAULT_JAIL_COOLDOWN_EPOCHS: u32 = 24;
+
+fn default_jail_cooldown_epochs() -> u32 {
+    DEFAULT_JAIL_COOLDOWN_EPOCHS
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ContractStateParams {
     // so currently interval_length is being unused and validator API performs rewarding
@@ -43,6 +57,19 @@ pub struct ContractStateParams {
     // subset of rewarded mixnodes that are actively receiving mix traffic
     // used to handle shorter-term (e.g. hourly) fluctuations of demand
     pub mixnode_active_set_size: u32,
+
+    // number of full epochs that have to pass before a profit margin or operating cost change
+    // requested by a mixnode operator takes effect
+    #[serde(default)]
+    pub cost_params_change_notice_epochs: u32,
+
+    // maximum operating cost (per epoch) a mixnode operator is allowed to declare
+    #[serde(default = "default_maximum_mixnode_operating_cost")]
+    pub maximum_mixnode_operating_cost: Uint128,
+
+    // number of epochs that have to pass before the operator of a jailed mixnode can unjail it
+    #[serde(default = "default_jail_cooldown_epochs")]
+    pub jail_cooldown_epochs: u32,
 }
 
 impl Display for ContractStateParams {
@@ -65,9 +92,20 @@ impl Display for ContractStateParams {
         )?;
         write!(
             f,
-            "mixnode active set size: {}",
+            "mixnode active set size: {}; ",
             self.mixnode_active_set_size
-        )
+        )?;
+        write!(
+            f,
+            "cost params change notice epochs: {}; ",
+            self.cost_params_change_notice_epochs
+        )?;
+        write!(
+            f,
+            "maximum mixnode operating cost: {}; ",
+            self.maximum_mixnode_operating_cost
+        )?;
+        write!(f, "jail cooldown epochs: {} ]", self.jail_cooldown_epochs)
     }
 }
 
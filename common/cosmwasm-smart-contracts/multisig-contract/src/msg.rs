@@ -0,0 +1,98 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+// Subset of the messages understood by `cw3-flex-multisig`, defined here so that clients do not need
+// to depend on the cw3 crates themselves. The serialized forms must match the cw-plus definitions.
+
+use cosmwasm_std::{to_binary, CosmosMsg, Empty, StdResult, Timestamp, WasmMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Vote {
+    Yes,
+    No,
+    Abstain,
+    Veto,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Expiration {
+    AtHeight(u64),
+    AtTime(Timestamp),
+    Never {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Propose {
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+        latest: Option<Expiration>,
+    },
+    Vote {
+        proposal_id: u64,
+        vote: Vote,
+    },
+    Execute {
+        proposal_id: u64,
+    },
+    Close {
+        proposal_id: u64,
+    },
+}
+
+impl ExecuteMsg {
+    /// Creates a proposal to execute the provided message against the specified contract
+    /// on behalf of the multisig.
+    pub fn propose_contract_execution<M: Serialize>(
+        title: String,
+        description: String,
+        contract_address: String,
+        msg: &M,
+        latest: Option<Expiration>,
+    ) -> StdResult<Self> {
+        let execution = WasmMsg::Execute {
+            contract_addr: contract_address,
+            msg: to_binary(msg)?,
+            funds: Vec::new(),
+        };
+
+        Ok(ExecuteMsg::Propose {
+            title,
+            description,
+            msgs: vec![execution.into()],
+            latest,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_match_cw3_format() {
+        let vote = ExecuteMsg::Vote {
+            proposal_id: 42,
+            vote: Vote::Yes,
+        };
+        assert_eq!(
+            serde_json::to_string(&vote).unwrap(),
+            r#"{"vote":{"proposal_id":42,"vote":"yes"}}"#
+        );
+
+        assert_eq!(
+            serde_json::to_string(&Expiration::Never {}).unwrap(),
+            r#"{"never":{}}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Expiration::AtHeight(100)).unwrap(),
+            r#"{"at_height":100}"#
+        );
+    }
+}
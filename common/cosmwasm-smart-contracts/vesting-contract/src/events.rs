@@ -8,18 +8,22 @@ pub const WITHDRAW_EVENT_TYPE: &str = "vested_coins_withdraw";
 pub const OWNERSHIP_TRANSFER_EVENT_TYPE: &str = "ownership_transfer";
 pub const STAKING_ADDRESS_UPDATE_EVENT_TYPE: &str = "staking_address_update";
 pub const NEW_PERIODIC_VESTING_ACCOUNT_EVENT_TYPE: &str = "new_periodic_vesting_account";
+pub const UNVESTED_COINS_REVOCATION_EVENT_TYPE: &str = "unvested_coins_revocation";
 
 pub const VESTING_DELEGATION_EVENT_TYPE: &str = "vesting_delegation";
 pub const VESTING_UNDELEGATION_EVENT_TYPE: &str = "vesting_undelegation";
+pub const VESTING_REDELEGATION_EVENT_TYPE: &str = "vesting_redelegation";
 pub const VESTING_GATEWAY_BONDING_EVENT_TYPE: &str = "vesting_gateway_bonding";
 pub const VESTING_GATEWAY_UNBONDING_EVENT_TYPE: &str = "vesting_gateway_unbonding";
 pub const VESTING_MIXNODE_BONDING_EVENT_TYPE: &str = "vesting_mixnode_bonding";
 pub const VESTING_MIXNODE_UNBONDING_EVENT_TYPE: &str = "vesting_mixnode_unbonding";
+pub const VESTING_MIXNODE_PLEDGE_DECREASE_EVENT_TYPE: &str = "vesting_mixnode_pledge_decrease";
 pub const VESTING_UPDATE_MIXNODE_CONFIG_EVENT_TYPE: &str = "vesting_update_mixnode_config";
 
 pub const TRACK_MIXNODE_UNBOND_EVENT_TYPE: &str = "track_mixnode_unbond";
 pub const TRACK_GATEWAY_UNBOND_EVENT_TYPE: &str = "track_gateway_unbond";
 pub const TRACK_UNDELEGATION_EVENT_TYPE: &str = "track_undelegation";
+pub const TRACK_REDELEGATION_EVENT_TYPE: &str = "track_redelegation";
 
 // attributes that are used in multiple places
 pub const OWNER_KEY: &str = "owner";
@@ -93,6 +97,12 @@ pub fn new_periodic_vesting_account_event(
     event.add_attribute(START_TIME_KEY, start_time.to_string())
 }
 
+pub fn new_unvested_coins_revocation_event(owner_address: &Addr, amount: &Coin) -> Event {
+    Event::new(UNVESTED_COINS_REVOCATION_EVENT_TYPE)
+        .add_attribute(OWNER_KEY, owner_address)
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+}
+
 // In most cases the events are rather barebone as there's no point in attaching
 // bunch of data to them as it would be redundant. It is because in most cases when the event is emitted
 // a call to the mixnet contract is made that throws another event with relevant attributes already attached.
@@ -117,6 +127,10 @@ pub fn new_vesting_mixnode_unbonding_event() -> Event {
     Event::new(VESTING_MIXNODE_UNBONDING_EVENT_TYPE)
 }
 
+pub fn new_vesting_mixnode_pledge_decrease_event() -> Event {
+    Event::new(VESTING_MIXNODE_PLEDGE_DECREASE_EVENT_TYPE)
+}
+
 pub fn new_vesting_delegation_event() -> Event {
     Event::new(VESTING_DELEGATION_EVENT_TYPE)
 }
@@ -125,6 +139,10 @@ pub fn new_vesting_undelegation_event() -> Event {
     Event::new(VESTING_UNDELEGATION_EVENT_TYPE)
 }
 
+pub fn new_vesting_redelegation_event() -> Event {
+    Event::new(VESTING_REDELEGATION_EVENT_TYPE)
+}
+
 pub fn new_track_mixnode_unbond_event() -> Event {
     Event::new(TRACK_MIXNODE_UNBOND_EVENT_TYPE)
 }
@@ -136,3 +154,7 @@ pub fn new_track_gateway_unbond_event() -> Event {
 pub fn new_track_undelegation_event() -> Event {
     Event::new(TRACK_UNDELEGATION_EVENT_TYPE)
 }
+
+pub fn new_track_redelegation_event() -> Event {
+    Event::new(TRACK_REDELEGATION_EVENT_TYPE)
+}
@@ -72,3 +72,34 @@ impl OriginalVestingResponse {
         }
     }
 }
+
+// note that all amounts are calculated based on the current state of the account, i.e. its current
+// delegations, pledges and withdrawals, so for the future periods they're merely a projection
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingPeriodSummary {
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    // amount of coins that vest in this particular period, taking the cliff and revocation into account
+    pub tranche: Coin,
+    // amount of coins withdrawn during this particular period (withdrawals made after the vesting
+    // is over count towards the last one). Withdrawals made before this got tracked are not included
+    pub withdrawn: Coin,
+    // the below are all evaluated at the end of the period
+    pub vested: Coin,
+    pub vesting: Coin,
+    pub delegated_free: Coin,
+    pub delegated_vesting: Coin,
+    pub pledged_free: Coin,
+    pub pledged_vesting: Coin,
+    pub locked: Coin,
+    pub spendable: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingScheduleResponse {
+    pub original_vesting: Coin,
+    pub balance: Coin,
+    pub withdrawn: Coin,
+    pub current_period: Period,
+    pub periods: Vec<VestingPeriodSummary>,
+}
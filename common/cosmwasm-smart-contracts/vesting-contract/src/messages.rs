@@ -1,4 +1,4 @@
-use cosmwasm_std::{Coin, Timestamp};
+use cosmwasm_std::{Coin, Timestamp, Uint128};
 use mixnet_contract_common::{Gateway, IdentityKey, MixNode};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -18,6 +18,17 @@ pub struct VestingSpecification {
     start_time: Option<u64>,
     period_seconds: Option<u64>,
     num_periods: Option<u64>,
+    // nothing vests before `start_time + cliff_seconds`, at which point everything
+    // that would have vested so far vests at once
+    #[serde(default)]
+    cliff_seconds: Option<u64>,
+    // amounts vesting in each of the periods, if not set, the coins vest in equal tranches.
+    // they must add up to the total amount of the account
+    #[serde(default)]
+    tranches: Option<Vec<Uint128>>,
+    // whether the admin is allowed to take back the coins that have not vested yet
+    #[serde(default)]
+    revocable: bool,
 }
 
 impl VestingSpecification {
@@ -30,9 +41,27 @@ impl VestingSpecification {
             start_time,
             period_seconds,
             num_periods,
+            cliff_seconds: None,
+            tranches: None,
+            revocable: false,
         }
     }
 
+    pub fn with_cliff(mut self, cliff_seconds: u64) -> Self {
+        self.cliff_seconds = Some(cliff_seconds);
+        self
+    }
+
+    pub fn with_tranches(mut self, tranches: Vec<Uint128>) -> Self {
+        self.tranches = Some(tranches);
+        self
+    }
+
+    pub fn with_revocable(mut self, revocable: bool) -> Self {
+        self.revocable = revocable;
+        self
+    }
+
     pub fn start_time(&self) -> Option<u64> {
         self.start_time
     }
@@ -42,7 +71,26 @@ impl VestingSpecification {
     }
 
     pub fn num_periods(&self) -> u64 {
-        self.num_periods.unwrap_or(8)
+        match &self.tranches {
+            Some(tranches) => tranches.len() as u64,
+            None => self.num_periods.unwrap_or(8),
+        }
+    }
+
+    pub fn explicit_num_periods(&self) -> Option<u64> {
+        self.num_periods
+    }
+
+    pub fn cliff_seconds(&self) -> Option<u64> {
+        self.cliff_seconds
+    }
+
+    pub fn tranches(&self) -> Option<&[Uint128]> {
+        self.tranches.as_deref()
+    }
+
+    pub fn revocable(&self) -> bool {
+        self.revocable
     }
 }
 
@@ -55,6 +103,8 @@ pub enum ExecuteMsg {
     CompoundOperatorReward {},
     UpdateMixnodeConfig {
         profit_margin_percent: u8,
+        #[serde(default)]
+        operating_cost: Option<Uint128>,
     },
     UpdateMixnetAddress {
         address: String,
@@ -66,6 +116,15 @@ pub enum ExecuteMsg {
     UndelegateFromMixnode {
         mix_identity: IdentityKey,
     },
+    PartiallyUndelegateFromMixnode {
+        mix_identity: IdentityKey,
+        amount: Coin,
+    },
+    RedelegateFromMixnode {
+        from: IdentityKey,
+        to: IdentityKey,
+        amount: Coin,
+    },
     CreateAccount {
         owner_address: String,
         staking_address: Option<String>,
@@ -78,6 +137,15 @@ pub enum ExecuteMsg {
         owner: String,
         mix_identity: IdentityKey,
         amount: Coin,
+        // set if only part of the delegation got withdrawn
+        #[serde(default)]
+        partial: bool,
+    },
+    TrackRedelegation {
+        owner: String,
+        from: IdentityKey,
+        to: IdentityKey,
+        amount: Coin,
     },
     BondMixnode {
         mix_node: MixNode,
@@ -85,9 +153,15 @@ pub enum ExecuteMsg {
         amount: Coin,
     },
     UnbondMixnode {},
+    DecreaseMixnodePledge {
+        amount: Coin,
+    },
     TrackUnbondMixnode {
         owner: String,
         amount: Coin,
+        // set if the node is still bonded and only part of its pledge got withdrawn
+        #[serde(default)]
+        partial: bool,
     },
     BondGateway {
         gateway: Gateway,
@@ -105,6 +179,11 @@ pub enum ExecuteMsg {
     UpdateStakingAddress {
         to_address: Option<String>,
     },
+    // only applicable to accounts created as revocable. The unvested coins that are not
+    // currently delegated or pledged are sent back to the admin and the account stops vesting.
+    RevokeUnvestedCoins {
+        owner_address: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -155,4 +234,9 @@ pub enum QueryMsg {
     GetCurrentVestingPeriod {
         address: String,
     },
+    // gets all vesting periods of the account alongside the amounts of vested, delegated,
+    // pledged and spendable coins at the end of each of them
+    GetVestingSchedule {
+        vesting_account_address: String,
+    },
 }
@@ -0,0 +1,30 @@
+/*
+ * Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+CREATE TABLE contract_events
+(
+    id                  INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    height              INTEGER NOT NULL,
+    tx_hash             TEXT    NOT NULL,
+    msg_index           INTEGER NOT NULL,
+    contract_address    TEXT    NOT NULL,
+    event_type          TEXT    NOT NULL,
+    -- columns extracted from the event data purely so that we could efficiently search through the events
+    delegator           TEXT,
+    owner               TEXT,
+    node_identity       TEXT,
+    -- json-serialized `ContractEvent`
+    data                TEXT    NOT NULL
+);
+
+CREATE INDEX contract_events_delegator_index ON contract_events (delegator);
+CREATE INDEX contract_events_owner_index ON contract_events (owner);
+CREATE INDEX contract_events_node_identity_index ON contract_events (node_identity);
+
+CREATE TABLE indexer_status
+(
+    id                      INTEGER NOT NULL PRIMARY KEY CHECK (id = 0),
+    last_processed_height   INTEGER NOT NULL
+);
@@ -0,0 +1,30 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use thiserror::Error;
+use validator_client::nymd::error::NymdError;
+
+#[derive(Error, Debug)]
+pub enum IndexerError {
+    #[error("Database experienced an internal error - {0}")]
+    InternalDatabaseError(#[from] sqlx::Error),
+
+    #[error("Failed to perform database migration - {0}")]
+    MigrationError(#[from] sqlx::migrate::MigrateError),
+
+    #[error("Failed to query the validator - {0}")]
+    NymdError(#[from] NymdError),
+
+    #[error("Failed to (de)serialize the stored event - {0}")]
+    SerializationError(#[from] serde_json::Error),
+
+    #[error("The '{event_type}' event does not contain the required '{key}' attribute")]
+    MissingEventAttribute { event_type: String, key: String },
+
+    #[error("The '{event_type}' event has a malformed '{key}' attribute: '{value}'")]
+    MalformedEventAttribute {
+        event_type: String,
+        key: String,
+        value: String,
+    },
+}
@@ -0,0 +1,762 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::IndexerError;
+use cosmwasm_std::{Coin, Event, Uint128};
+use mixnet_contract_common::events as mixnet_events;
+use mixnet_contract_common::IdentityKey;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use vesting_contract_common::events as vesting_events;
+
+// events emitted by the contracts are prefixed by the wasm module and have the address
+// of the emitting contract attached as an additional attribute
+const WASM_EVENT_PREFIX: &str = "wasm-";
+const CONTRACT_ADDRESS_KEY: &str = "_contract_address";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractEvent {
+    Mixnet(MixnetEvent),
+    Vesting(VestingEvent),
+}
+
+impl ContractEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            ContractEvent::Mixnet(event) => event.event_type(),
+            ContractEvent::Vesting(event) => event.event_type(),
+        }
+    }
+
+    pub fn delegator(&self) -> Option<&str> {
+        match self {
+            ContractEvent::Mixnet(event) => event.delegator(),
+            ContractEvent::Vesting(_) => None,
+        }
+    }
+
+    pub fn owner(&self) -> Option<&str> {
+        match self {
+            ContractEvent::Mixnet(event) => event.owner(),
+            ContractEvent::Vesting(event) => event.owner(),
+        }
+    }
+
+    pub fn node_identity(&self) -> Option<&str> {
+        match self {
+            ContractEvent::Mixnet(event) => event.node_identity(),
+            ContractEvent::Vesting(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RewardingOutcome {
+    Rewarded {
+        pledge: Uint128,
+        delegated: Uint128,
+        total_node_reward: Uint128,
+    },
+    NotRewarded {
+        reason: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MixnetEvent {
+    Delegation {
+        delegator: String,
+        proxy: Option<String>,
+        amount: Coin,
+        mix_identity: IdentityKey,
+    },
+    PendingDelegation {
+        delegator: String,
+        proxy: Option<String>,
+        amount: Coin,
+        mix_identity: IdentityKey,
+    },
+    ReconcileDelegation {
+        delegator: String,
+        proxy: Option<String>,
+        amount: Coin,
+        mix_identity: IdentityKey,
+    },
+    Undelegation {
+        delegator: String,
+        proxy: Option<String>,
+        amount: Uint128,
+        mix_identity: IdentityKey,
+    },
+    PendingUndelegation {
+        delegator: String,
+        proxy: Option<String>,
+        // only known upfront for partial undelegations
+        amount: Option<Uint128>,
+        mix_identity: IdentityKey,
+    },
+    Redelegation {
+        delegator: String,
+        proxy: Option<String>,
+        amount: Coin,
+        from_mix_identity: IdentityKey,
+        to_mix_identity: IdentityKey,
+    },
+    CompoundDelegatorReward {
+        delegator: String,
+        proxy: Option<String>,
+        amount: Uint128,
+        mix_identity: IdentityKey,
+    },
+    CompoundOperatorReward {
+        owner: String,
+        amount: Uint128,
+    },
+    MixnodeBonding {
+        owner: String,
+        proxy: Option<String>,
+        identity: IdentityKey,
+        amount: Coin,
+        assigned_layer: String,
+    },
+    MixnodeUnbonding {
+        owner: String,
+        proxy: Option<String>,
+        identity: IdentityKey,
+        amount: Coin,
+    },
+    MixnodePledgeDecrease {
+        owner: String,
+        proxy: Option<String>,
+        identity: IdentityKey,
+        amount: Coin,
+        remaining_pledge: Coin,
+    },
+    MixnodeCostParamsUpdate {
+        identity: IdentityKey,
+        effective_epoch_id: u32,
+        profit_margin_percent: u8,
+        operating_cost: Option<Uint128>,
+    },
+    MixnodeJailing {
+        identity: IdentityKey,
+        jailed_at_epoch_id: u32,
+        unjail_epoch_id: u32,
+    },
+    MixnodeUnjailing {
+        owner: String,
+        identity: IdentityKey,
+    },
+    GatewayBonding {
+        owner: String,
+        proxy: Option<String>,
+        identity: IdentityKey,
+        amount: Coin,
+    },
+    GatewayUnbonding {
+        owner: String,
+        proxy: Option<String>,
+        identity: IdentityKey,
+        amount: Coin,
+    },
+    OperatorRewarding {
+        interval_id: u32,
+        identity: IdentityKey,
+        outcome: RewardingOutcome,
+    },
+    DelegatorsRewarding {
+        interval_id: u32,
+        identity: IdentityKey,
+        distributed_rewards: Uint128,
+        further_delegations: bool,
+    },
+}
+
+impl MixnetEvent {
+    /// Event types, as emitted by the contract, of all events associated with the history of a mixnode bond.
+    pub const BOND_HISTORY_EVENT_TYPES: [&'static str; 6] = [
+        mixnet_events::MIXNODE_BONDING_EVENT_TYPE,
+        mixnet_events::MIXNODE_UNBONDING_EVENT_TYPE,
+        mixnet_events::MIXNODE_PLEDGE_DECREASE_EVENT_TYPE,
+        mixnet_events::MIXNODE_COST_PARAMS_UPDATE_EVENT_TYPE,
+        mixnet_events::MIXNODE_JAILING_EVENT_TYPE,
+        mixnet_events::MIXNODE_UNJAILING_EVENT_TYPE,
+    ];
+
+    /// Event types, as emitted by the contract, of all events describing rewards received by a delegator.
+    pub const DELEGATOR_REWARD_EVENT_TYPES: [&'static str; 1] =
+        [mixnet_events::COMPOUND_DELEGATOR_REWARD_EVENT_TYPE];
+
+    fn decode(
+        event_type: &str,
+        attributes: EventAttributes<'_>,
+    ) -> Result<Option<Self>, IndexerError> {
+        let event = match event_type {
+            mixnet_events::DELEGATION_EVENT_TYPE => MixnetEvent::Delegation {
+                delegator: attributes.get(mixnet_events::DELEGATOR_KEY)?,
+                proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                amount: attributes.coin(mixnet_events::AMOUNT_KEY)?,
+                mix_identity: attributes.get(mixnet_events::DELEGATION_TARGET_KEY)?,
+            },
+            mixnet_events::PENDING_DELEGATION_EVENT_TYPE => MixnetEvent::PendingDelegation {
+                delegator: attributes.get(mixnet_events::DELEGATOR_KEY)?,
+                proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                amount: attributes.coin(mixnet_events::AMOUNT_KEY)?,
+                mix_identity: attributes.get(mixnet_events::DELEGATION_TARGET_KEY)?,
+            },
+            mixnet_events::RECONCILE_DELEGATION_EVENT_TYPE => MixnetEvent::ReconcileDelegation {
+                delegator: attributes.get(mixnet_events::DELEGATOR_KEY)?,
+                proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                amount: attributes.coin(mixnet_events::AMOUNT_KEY)?,
+                mix_identity: attributes.get(mixnet_events::DELEGATION_TARGET_KEY)?,
+            },
+            mixnet_events::UNDELEGATION_EVENT_TYPE => MixnetEvent::Undelegation {
+                delegator: attributes.get(mixnet_events::DELEGATOR_KEY)?,
+                proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                amount: attributes.parse(mixnet_events::AMOUNT_KEY)?,
+                mix_identity: attributes.get(mixnet_events::DELEGATION_TARGET_KEY)?,
+            },
+            mixnet_events::PENDING_UNDELEGATION_EVENT_TYPE => MixnetEvent::PendingUndelegation {
+                delegator: attributes.get(mixnet_events::DELEGATOR_KEY)?,
+                proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                amount: attributes.may_parse(mixnet_events::AMOUNT_KEY)?,
+                mix_identity: attributes.get(mixnet_events::DELEGATION_TARGET_KEY)?,
+            },
+            mixnet_events::REDELEGATION_EVENT_TYPE => MixnetEvent::Redelegation {
+                delegator: attributes.get(mixnet_events::DELEGATOR_KEY)?,
+                proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                amount: attributes.coin(mixnet_events::AMOUNT_KEY)?,
+                from_mix_identity: attributes.get(mixnet_events::REDELEGATION_SOURCE_KEY)?,
+                to_mix_identity: attributes.get(mixnet_events::DELEGATION_TARGET_KEY)?,
+            },
+            mixnet_events::COMPOUND_DELEGATOR_REWARD_EVENT_TYPE => {
+                MixnetEvent::CompoundDelegatorReward {
+                    delegator: attributes.get(mixnet_events::DELEGATOR_KEY)?,
+                    proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                    amount: attributes.parse(mixnet_events::AMOUNT_KEY)?,
+                    mix_identity: attributes.get(mixnet_events::DELEGATION_TARGET_KEY)?,
+                }
+            }
+            mixnet_events::COMPOUND_OPERATOR_REWARD_EVENT_TYPE => {
+                MixnetEvent::CompoundOperatorReward {
+                    owner: attributes.get(mixnet_events::OWNER_KEY)?,
+                    amount: attributes.parse(mixnet_events::AMOUNT_KEY)?,
+                }
+            }
+            mixnet_events::MIXNODE_BONDING_EVENT_TYPE => MixnetEvent::MixnodeBonding {
+                owner: attributes.get(mixnet_events::OWNER_KEY)?,
+                proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                identity: attributes.get(mixnet_events::NODE_IDENTITY_KEY)?,
+                amount: attributes.coin(mixnet_events::AMOUNT_KEY)?,
+                assigned_layer: attributes.get(mixnet_events::ASSIGNED_LAYER_KEY)?,
+            },
+            mixnet_events::MIXNODE_UNBONDING_EVENT_TYPE => MixnetEvent::MixnodeUnbonding {
+                owner: attributes.get(mixnet_events::OWNER_KEY)?,
+                proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                identity: attributes.get(mixnet_events::NODE_IDENTITY_KEY)?,
+                amount: attributes.coin(mixnet_events::AMOUNT_KEY)?,
+            },
+            mixnet_events::MIXNODE_PLEDGE_DECREASE_EVENT_TYPE => {
+                MixnetEvent::MixnodePledgeDecrease {
+                    owner: attributes.get(mixnet_events::OWNER_KEY)?,
+                    proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                    identity: attributes.get(mixnet_events::NODE_IDENTITY_KEY)?,
+                    amount: attributes.coin(mixnet_events::AMOUNT_KEY)?,
+                    remaining_pledge: attributes.coin(mixnet_events::REMAINING_PLEDGE_KEY)?,
+                }
+            }
+            mixnet_events::MIXNODE_COST_PARAMS_UPDATE_EVENT_TYPE => {
+                MixnetEvent::MixnodeCostParamsUpdate {
+                    identity: attributes.get(mixnet_events::NODE_IDENTITY_KEY)?,
+                    effective_epoch_id: attributes.parse(mixnet_events::EFFECTIVE_EPOCH_ID_KEY)?,
+                    profit_margin_percent: attributes
+                        .parse(mixnet_events::PROFIT_MARGIN_PERCENT_KEY)?,
+                    operating_cost: attributes.may_parse(mixnet_events::OPERATING_COST_KEY)?,
+                }
+            }
+            mixnet_events::MIXNODE_JAILING_EVENT_TYPE => MixnetEvent::MixnodeJailing {
+                identity: attributes.get(mixnet_events::NODE_IDENTITY_KEY)?,
+                jailed_at_epoch_id: attributes.parse(mixnet_events::JAILED_AT_EPOCH_KEY)?,
+                unjail_epoch_id: attributes.parse(mixnet_events::UNJAIL_EPOCH_KEY)?,
+            },
+            mixnet_events::MIXNODE_UNJAILING_EVENT_TYPE => MixnetEvent::MixnodeUnjailing {
+                owner: attributes.get(mixnet_events::OWNER_KEY)?,
+                identity: attributes.get(mixnet_events::NODE_IDENTITY_KEY)?,
+            },
+            mixnet_events::GATEWAY_BONDING_EVENT_TYPE => MixnetEvent::GatewayBonding {
+                owner: attributes.get(mixnet_events::OWNER_KEY)?,
+                proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                identity: attributes.get(mixnet_events::NODE_IDENTITY_KEY)?,
+                amount: attributes.coin(mixnet_events::AMOUNT_KEY)?,
+            },
+            mixnet_events::GATEWAY_UNBONDING_EVENT_TYPE => MixnetEvent::GatewayUnbonding {
+                owner: attributes.get(mixnet_events::OWNER_KEY)?,
+                proxy: attributes.may_get(mixnet_events::PROXY_KEY),
+                identity: attributes.get(mixnet_events::NODE_IDENTITY_KEY)?,
+                amount: attributes.coin(mixnet_events::AMOUNT_KEY)?,
+            },
+            mixnet_events::OPERATOR_REWARDING_EVENT_TYPE => {
+                let outcome = match attributes.may_get(mixnet_events::NO_REWARD_REASON_KEY) {
+                    Some(reason) => RewardingOutcome::NotRewarded { reason },
+                    None => RewardingOutcome::Rewarded {
+                        pledge: attributes.parse(mixnet_events::TOTAL_PLEDGE_KEY)?,
+                        delegated: attributes.parse(mixnet_events::TOTAL_DELEGATIONS_KEY)?,
+                        total_node_reward: attributes
+                            .parse(mixnet_events::TOTAL_MIXNODE_REWARD_KEY)?,
+                    },
+                };
+                MixnetEvent::OperatorRewarding {
+                    interval_id: attributes.parse(mixnet_events::INTERVAL_ID_KEY)?,
+                    identity: attributes.get(mixnet_events::NODE_IDENTITY_KEY)?,
+                    outcome,
+                }
+            }
+            mixnet_events::MIX_DELEGATORS_REWARDING_EVENT_TYPE => {
+                MixnetEvent::DelegatorsRewarding {
+                    interval_id: attributes.parse(mixnet_events::INTERVAL_ID_KEY)?,
+                    identity: attributes.get(mixnet_events::NODE_IDENTITY_KEY)?,
+                    distributed_rewards: attributes
+                        .parse(mixnet_events::DISTRIBUTED_DELEGATION_REWARDS_KEY)?,
+                    further_delegations: attributes
+                        .parse(mixnet_events::FURTHER_DELEGATIONS_TO_REWARD_KEY)?,
+                }
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(event))
+    }
+
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            MixnetEvent::Delegation { .. } => mixnet_events::DELEGATION_EVENT_TYPE,
+            MixnetEvent::PendingDelegation { .. } => mixnet_events::PENDING_DELEGATION_EVENT_TYPE,
+            MixnetEvent::ReconcileDelegation { .. } => {
+                mixnet_events::RECONCILE_DELEGATION_EVENT_TYPE
+            }
+            MixnetEvent::Undelegation { .. } => mixnet_events::UNDELEGATION_EVENT_TYPE,
+            MixnetEvent::PendingUndelegation { .. } => {
+                mixnet_events::PENDING_UNDELEGATION_EVENT_TYPE
+            }
+            MixnetEvent::Redelegation { .. } => mixnet_events::REDELEGATION_EVENT_TYPE,
+            MixnetEvent::CompoundDelegatorReward { .. } => {
+                mixnet_events::COMPOUND_DELEGATOR_REWARD_EVENT_TYPE
+            }
+            MixnetEvent::CompoundOperatorReward { .. } => {
+                mixnet_events::COMPOUND_OPERATOR_REWARD_EVENT_TYPE
+            }
+            MixnetEvent::MixnodeBonding { .. } => mixnet_events::MIXNODE_BONDING_EVENT_TYPE,
+            MixnetEvent::MixnodeUnbonding { .. } => mixnet_events::MIXNODE_UNBONDING_EVENT_TYPE,
+            MixnetEvent::MixnodePledgeDecrease { .. } => {
+                mixnet_events::MIXNODE_PLEDGE_DECREASE_EVENT_TYPE
+            }
+            MixnetEvent::MixnodeCostParamsUpdate { .. } => {
+                mixnet_events::MIXNODE_COST_PARAMS_UPDATE_EVENT_TYPE
+            }
+            MixnetEvent::MixnodeJailing { .. } => mixnet_events::MIXNODE_JAILING_EVENT_TYPE,
+            MixnetEvent::MixnodeUnjailing { .. } => mixnet_events::MIXNODE_UNJAILING_EVENT_TYPE,
+            MixnetEvent::GatewayBonding { .. } => mixnet_events::GATEWAY_BONDING_EVENT_TYPE,
+            MixnetEvent::GatewayUnbonding { .. } => mixnet_events::GATEWAY_UNBONDING_EVENT_TYPE,
+            MixnetEvent::OperatorRewarding { .. } => mixnet_events::OPERATOR_REWARDING_EVENT_TYPE,
+            MixnetEvent::DelegatorsRewarding { .. } => {
+                mixnet_events::MIX_DELEGATORS_REWARDING_EVENT_TYPE
+            }
+        }
+    }
+
+    pub fn delegator(&self) -> Option<&str> {
+        match self {
+            MixnetEvent::Delegation { delegator, .. }
+            | MixnetEvent::PendingDelegation { delegator, .. }
+            | MixnetEvent::ReconcileDelegation { delegator, .. }
+            | MixnetEvent::Undelegation { delegator, .. }
+            | MixnetEvent::PendingUndelegation { delegator, .. }
+            | MixnetEvent::Redelegation { delegator, .. }
+            | MixnetEvent::CompoundDelegatorReward { delegator, .. } => Some(delegator),
+            _ => None,
+        }
+    }
+
+    pub fn owner(&self) -> Option<&str> {
+        match self {
+            MixnetEvent::CompoundOperatorReward { owner, .. }
+            | MixnetEvent::MixnodeBonding { owner, .. }
+            | MixnetEvent::MixnodeUnbonding { owner, .. }
+            | MixnetEvent::MixnodePledgeDecrease { owner, .. }
+            | MixnetEvent::MixnodeUnjailing { owner, .. }
+            | MixnetEvent::GatewayBonding { owner, .. }
+            | MixnetEvent::GatewayUnbonding { owner, .. } => Some(owner),
+            _ => None,
+        }
+    }
+
+    // for the delegation-related events this is the identity of the delegation target
+    pub fn node_identity(&self) -> Option<&str> {
+        match self {
+            MixnetEvent::Delegation { mix_identity, .. }
+            | MixnetEvent::PendingDelegation { mix_identity, .. }
+            | MixnetEvent::ReconcileDelegation { mix_identity, .. }
+            | MixnetEvent::Undelegation { mix_identity, .. }
+            | MixnetEvent::PendingUndelegation { mix_identity, .. }
+            | MixnetEvent::CompoundDelegatorReward { mix_identity, .. } => Some(mix_identity),
+            MixnetEvent::Redelegation {
+                to_mix_identity, ..
+            } => Some(to_mix_identity),
+            MixnetEvent::MixnodeBonding { identity, .. }
+            | MixnetEvent::MixnodeUnbonding { identity, .. }
+            | MixnetEvent::MixnodePledgeDecrease { identity, .. }
+            | MixnetEvent::MixnodeCostParamsUpdate { identity, .. }
+            | MixnetEvent::MixnodeJailing { identity, .. }
+            | MixnetEvent::MixnodeUnjailing { identity, .. }
+            | MixnetEvent::GatewayBonding { identity, .. }
+            | MixnetEvent::GatewayUnbonding { identity, .. }
+            | MixnetEvent::OperatorRewarding { identity, .. }
+            | MixnetEvent::DelegatorsRewarding { identity, .. } => Some(identity),
+            MixnetEvent::CompoundOperatorReward { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VestingEvent {
+    VestedCoinsWithdraw {
+        owner: String,
+        amount: Coin,
+        remaining_spendable: Coin,
+    },
+    // note: staking address updates are (currently) emitted with the same event type
+    OwnershipTransfer {
+        from: String,
+        to: String,
+    },
+    NewPeriodicVestingAccount {
+        owner: String,
+        amount: Coin,
+        staking_address: Option<String>,
+        start_time: String,
+    },
+    UnvestedCoinsRevocation {
+        owner: String,
+        amount: Coin,
+    },
+}
+
+impl VestingEvent {
+    fn decode(
+        event_type: &str,
+        attributes: EventAttributes<'_>,
+    ) -> Result<Option<Self>, IndexerError> {
+        let event = match event_type {
+            vesting_events::WITHDRAW_EVENT_TYPE => VestingEvent::VestedCoinsWithdraw {
+                owner: attributes.get(vesting_events::OWNER_KEY)?,
+                amount: attributes.coin(vesting_events::AMOUNT_KEY)?,
+                remaining_spendable: attributes.coin(vesting_events::REMAINING_SPENDABLE_KEY)?,
+            },
+            vesting_events::OWNERSHIP_TRANSFER_EVENT_TYPE => VestingEvent::OwnershipTransfer {
+                from: attributes.get(vesting_events::FROM_ACCOUNT_KEY)?,
+                to: attributes.get(vesting_events::TO_ACCOUNT_KEY)?,
+            },
+            vesting_events::NEW_PERIODIC_VESTING_ACCOUNT_EVENT_TYPE => {
+                VestingEvent::NewPeriodicVestingAccount {
+                    owner: attributes.get(vesting_events::OWNER_KEY)?,
+                    amount: attributes.coin(vesting_events::AMOUNT_KEY)?,
+                    staking_address: attributes.may_get(vesting_events::STAKING_ADDRESS_KEY),
+                    start_time: attributes.get(vesting_events::START_TIME_KEY)?,
+                }
+            }
+            vesting_events::UNVESTED_COINS_REVOCATION_EVENT_TYPE => {
+                VestingEvent::UnvestedCoinsRevocation {
+                    owner: attributes.get(vesting_events::OWNER_KEY)?,
+                    amount: attributes.coin(vesting_events::AMOUNT_KEY)?,
+                }
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(event))
+    }
+
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            VestingEvent::VestedCoinsWithdraw { .. } => vesting_events::WITHDRAW_EVENT_TYPE,
+            VestingEvent::OwnershipTransfer { .. } => vesting_events::OWNERSHIP_TRANSFER_EVENT_TYPE,
+            VestingEvent::NewPeriodicVestingAccount { .. } => {
+                vesting_events::NEW_PERIODIC_VESTING_ACCOUNT_EVENT_TYPE
+            }
+            VestingEvent::UnvestedCoinsRevocation { .. } => {
+                vesting_events::UNVESTED_COINS_REVOCATION_EVENT_TYPE
+            }
+        }
+    }
+
+    pub fn owner(&self) -> Option<&str> {
+        match self {
+            VestingEvent::VestedCoinsWithdraw { owner, .. }
+            | VestingEvent::NewPeriodicVestingAccount { owner, .. }
+            | VestingEvent::UnvestedCoinsRevocation { owner, .. } => Some(owner),
+            VestingEvent::OwnershipTransfer { from, .. } => Some(from),
+        }
+    }
+}
+
+/// Addresses of the contracts whose events are going to get decoded.
+#[derive(Debug, Clone)]
+pub struct WatchedContracts {
+    pub mixnet_contract: String,
+    pub vesting_contract: String,
+}
+
+impl WatchedContracts {
+    /// Attempts to decode the provided event into one of the known contract events.
+    /// Returns `Ok(None)` if the event was not emitted by any of the watched contracts or if
+    /// its type is not (yet) understood by the indexer.
+    ///
+    /// # Arguments
+    ///
+    /// * `event`: raw event, as included in the transaction logs.
+    pub fn decode_event(&self, event: &Event) -> Result<Option<ContractEvent>, IndexerError> {
+        let event_type = match event.ty.strip_prefix(WASM_EVENT_PREFIX) {
+            Some(event_type) => event_type,
+            None => return Ok(None),
+        };
+        let attributes = EventAttributes { event, event_type };
+
+        match attributes.may_get(CONTRACT_ADDRESS_KEY) {
+            Some(address) if address == self.mixnet_contract => {
+                Ok(MixnetEvent::decode(event_type, attributes)?.map(ContractEvent::Mixnet))
+            }
+            Some(address) if address == self.vesting_contract => {
+                Ok(VestingEvent::decode(event_type, attributes)?.map(ContractEvent::Vesting))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the address of the contract that has emitted the provided event.
+    pub fn event_contract<'a>(&'a self, event: &ContractEvent) -> &'a str {
+        match event {
+            ContractEvent::Mixnet(_) => &self.mixnet_contract,
+            ContractEvent::Vesting(_) => &self.vesting_contract,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct EventAttributes<'a> {
+    event: &'a Event,
+    event_type: &'a str,
+}
+
+impl<'a> EventAttributes<'a> {
+    fn may_get(&self, key: &str) -> Option<String> {
+        mixnet_events::may_find_attribute(self.event, key)
+    }
+
+    fn get(&self, key: &str) -> Result<String, IndexerError> {
+        self.may_get(key)
+            .ok_or_else(|| IndexerError::MissingEventAttribute {
+                event_type: self.event_type.to_string(),
+                key: key.to_string(),
+            })
+    }
+
+    fn may_parse<T: FromStr>(&self, key: &str) -> Result<Option<T>, IndexerError> {
+        self.may_get(key)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| self.malformed_attribute(key, value))
+            })
+            .transpose()
+    }
+
+    fn parse<T: FromStr>(&self, key: &str) -> Result<T, IndexerError> {
+        let value = self.get(key)?;
+        value
+            .parse()
+            .map_err(|_| self.malformed_attribute(key, value))
+    }
+
+    // coins are emitted using their `Display` implementation, i.e. "<amount><denom>"
+    fn coin(&self, key: &str) -> Result<Coin, IndexerError> {
+        let value = self.get(key)?;
+        let denom_start = value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len());
+        let (amount, denom) = value.split_at(denom_start);
+        if amount.is_empty() || denom.is_empty() {
+            return Err(self.malformed_attribute(key, value));
+        }
+        match amount.parse::<u128>() {
+            Ok(amount) => Ok(Coin::new(amount, denom)),
+            Err(_) => Err(self.malformed_attribute(key, value)),
+        }
+    }
+
+    fn malformed_attribute(&self, key: &str, value: String) -> IndexerError {
+        IndexerError::MalformedEventAttribute {
+            event_type: self.event_type.to_string(),
+            key: key.to_string(),
+            value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::Addr;
+    use mixnet_contract_common::JailStatus;
+
+    const MIXNET_CONTRACT: &str = "n14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9sjyvg3g";
+    const VESTING_CONTRACT: &str = "n1nc5tatafv6eyq7llkr2gv50ff9e22mnf70qgjlv737ktmt4eswrq73f2nw";
+
+    fn watched_contracts() -> WatchedContracts {
+        WatchedContracts {
+            mixnet_contract: MIXNET_CONTRACT.to_string(),
+            vesting_contract: VESTING_CONTRACT.to_string(),
+        }
+    }
+
+    // emulates what the wasm module does to the events emitted by the contract
+    fn as_emitted(event: Event, contract: &str) -> Event {
+        let mut emitted = Event::new(format!("{}{}", WASM_EVENT_PREFIX, event.ty))
+            .add_attribute(CONTRACT_ADDRESS_KEY, contract);
+        emitted.attributes.extend(event.attributes);
+        emitted
+    }
+
+    #[test]
+    fn decoding_delegator_reward_compounding() {
+        let delegator = Addr::unchecked("delegator");
+        let event = mixnet_events::new_compound_delegator_reward_event(
+            &delegator,
+            &None,
+            Uint128::new(12345),
+            "mix-identity",
+        );
+
+        let decoded = watched_contracts()
+            .decode_event(&as_emitted(event, MIXNET_CONTRACT))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            decoded,
+            ContractEvent::Mixnet(MixnetEvent::CompoundDelegatorReward {
+                delegator: "delegator".to_string(),
+                proxy: None,
+                amount: Uint128::new(12345),
+                mix_identity: "mix-identity".to_string(),
+            })
+        );
+        assert_eq!(decoded.delegator(), Some("delegator"));
+        assert_eq!(decoded.node_identity(), Some("mix-identity"));
+    }
+
+    #[test]
+    fn decoding_bond_history_events() {
+        let owner = Addr::unchecked("owner");
+        let proxy = Some(Addr::unchecked("proxy"));
+
+        let bonding = mixnet_events::new_mixnode_pledge_decrease_event(
+            &owner,
+            &proxy,
+            &Coin::new(100, "unym"),
+            &Coin::new(900, "unym"),
+            "mix-identity",
+        );
+        assert_eq!(
+            watched_contracts()
+                .decode_event(&as_emitted(bonding, MIXNET_CONTRACT))
+                .unwrap(),
+            Some(ContractEvent::Mixnet(MixnetEvent::MixnodePledgeDecrease {
+                owner: "owner".to_string(),
+                proxy: Some("proxy".to_string()),
+                identity: "mix-identity".to_string(),
+                amount: Coin::new(100, "unym"),
+                remaining_pledge: Coin::new(900, "unym"),
+            }))
+        );
+
+        let jailing = mixnet_events::new_mixnode_jailing_event(
+            "mix-identity",
+            &JailStatus {
+                jailed_at_epoch_id: 42,
+                unjail_epoch_id: 66,
+            },
+        );
+        assert_eq!(
+            watched_contracts()
+                .decode_event(&as_emitted(jailing, MIXNET_CONTRACT))
+                .unwrap(),
+            Some(ContractEvent::Mixnet(MixnetEvent::MixnodeJailing {
+                identity: "mix-identity".to_string(),
+                jailed_at_epoch_id: 42,
+                unjail_epoch_id: 66,
+            }))
+        );
+    }
+
+    #[test]
+    fn decoding_vesting_events() {
+        let event = vesting_events::new_vested_coins_withdraw_event(
+            &Addr::unchecked("owner"),
+            &Coin::new(100, "unym"),
+            &Coin::new(50, "unym"),
+        );
+        assert_eq!(
+            watched_contracts()
+                .decode_event(&as_emitted(event, VESTING_CONTRACT))
+                .unwrap(),
+            Some(ContractEvent::Vesting(VestingEvent::VestedCoinsWithdraw {
+                owner: "owner".to_string(),
+                amount: Coin::new(100, "unym"),
+                remaining_spendable: Coin::new(50, "unym"),
+            }))
+        );
+    }
+
+    #[test]
+    fn ignoring_unknown_events() {
+        let contracts = watched_contracts();
+
+        // not emitted by a contract
+        let event = Event::new("message").add_attribute("action", "send");
+        assert!(contracts.decode_event(&event).unwrap().is_none());
+
+        // emitted by some other contract
+        let event = mixnet_events::new_compound_operator_reward_event(
+            &Addr::unchecked("owner"),
+            Uint128::new(100),
+        );
+        assert!(contracts
+            .decode_event(&as_emitted(event, "some-other-contract"))
+            .unwrap()
+            .is_none());
+
+        // not an event we know how to decode
+        let event = mixnet_events::new_checkpoint_mixnodes_event(123);
+        assert!(contracts
+            .decode_event(&as_emitted(event, MIXNET_CONTRACT))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn rejecting_malformed_events() {
+        let event = Event::new(mixnet_events::MIXNODE_BONDING_EVENT_TYPE)
+            .add_attribute(mixnet_events::OWNER_KEY, "owner")
+            .add_attribute(mixnet_events::NODE_IDENTITY_KEY, "mix-identity")
+            .add_attribute(mixnet_events::ASSIGNED_LAYER_KEY, "1")
+            .add_attribute(mixnet_events::AMOUNT_KEY, "unym");
+
+        assert!(matches!(
+            watched_contracts().decode_event(&as_emitted(event, MIXNET_CONTRACT)),
+            Err(IndexerError::MalformedEventAttribute { .. })
+        ));
+    }
+}
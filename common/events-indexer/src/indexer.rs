@@ -0,0 +1,145 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::IndexerError;
+use crate::events::WatchedContracts;
+use crate::models::IndexedEvent;
+use crate::storage::EventsStorage;
+use log::{debug, error, info, warn};
+use std::time::Duration;
+use validator_client::nymd::cosmwasm_client::logs::parse_raw_logs;
+use validator_client::nymd::{CosmWasmClient, Query, TxResponse};
+
+const DEFAULT_POLLING_RATE: Duration = Duration::from_secs(10);
+
+// the maximum number of blocks whose transactions are going to get searched through in a single query
+const MAX_BLOCKS_PER_QUERY: u64 = 1000;
+
+// every execution of a contract emits the base "wasm" event with the contract address attached
+const CONTRACT_ADDRESS_QUERY_KEY: &str = "wasm._contract_address";
+
+pub struct Indexer<C> {
+    client: C,
+    contracts: WatchedContracts,
+    storage: EventsStorage,
+    polling_rate: Duration,
+    starting_height: u64,
+}
+
+impl<C> Indexer<C>
+where
+    C: CosmWasmClient + Send + Sync,
+{
+    pub fn new(client: C, contracts: WatchedContracts, storage: EventsStorage) -> Self {
+        Indexer {
+            client,
+            contracts,
+            storage,
+            polling_rate: DEFAULT_POLLING_RATE,
+            starting_height: 1,
+        }
+    }
+
+    pub fn with_polling_rate(mut self, polling_rate: Duration) -> Self {
+        self.polling_rate = polling_rate;
+        self
+    }
+
+    /// Sets the height of the first block to index if the storage has not indexed anything yet,
+    /// for example the height at which the contracts were instantiated.
+    pub fn with_starting_height(mut self, starting_height: u64) -> Self {
+        self.starting_height = starting_height;
+        self
+    }
+
+    async fn search_contract_txs(
+        &self,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<TxResponse>, IndexerError> {
+        let mut txs = Vec::new();
+        for contract in [
+            &self.contracts.mixnet_contract,
+            &self.contracts.vesting_contract,
+        ] {
+            let query = Query::eq(CONTRACT_ADDRESS_QUERY_KEY, contract.as_str())
+                .and_gte("tx.height", from)
+                .and_lte("tx.height", to);
+            txs.append(&mut self.client.search_tx(query).await?);
+        }
+
+        // transactions involving both contracts (say, vesting delegations) are going to be returned twice
+        txs.sort_by_key(|tx| (tx.height.value(), tx.index));
+        txs.dedup_by(|a, b| a.hash == b.hash);
+        Ok(txs)
+    }
+
+    fn decode_tx_events(&self, tx: TxResponse) -> Result<Vec<IndexedEvent>, IndexerError> {
+        // failed transactions did not change the state of the contracts
+        if tx.tx_result.code.is_err() {
+            return Ok(Vec::new());
+        }
+
+        let mut events = Vec::new();
+        for log in parse_raw_logs(tx.tx_result.log)? {
+            for raw_event in log.events() {
+                if let Some(event) = self.contracts.decode_event(raw_event)? {
+                    events.push(IndexedEvent {
+                        height: tx.height.value(),
+                        tx_hash: tx.hash.to_string(),
+                        msg_index: log.msg_index() as u32,
+                        contract_address: self.contracts.event_contract(&event).to_owned(),
+                        event,
+                    })
+                }
+            }
+        }
+
+        Ok(events)
+    }
+
+    async fn index_blocks(&self, from: u64, to: u64) -> Result<(), IndexerError> {
+        debug!("Indexing contract events from blocks {} - {}", from, to);
+
+        let mut events = Vec::new();
+        for tx in self.search_contract_txs(from, to).await? {
+            let hash = tx.hash;
+            match self.decode_tx_events(tx) {
+                Ok(mut tx_events) => events.append(&mut tx_events),
+                // we don't want to get permanently stuck on an event we can't understand
+                Err(err) => warn!("Failed to decode events of transaction {} - {}", hash, err),
+            }
+        }
+
+        self.storage.insert_events(&events, to).await
+    }
+
+    /// Indexes all blocks produced since the last processed height.
+    pub async fn index_new_blocks(&self) -> Result<(), IndexerError> {
+        let latest_height = self.client.get_height().await?.value();
+        let mut next_height = match self.storage.last_processed_height().await? {
+            Some(height) => height + 1,
+            None => self.starting_height,
+        };
+
+        while next_height <= latest_height {
+            let last_height = (next_height + MAX_BLOCKS_PER_QUERY - 1).min(latest_height);
+            self.index_blocks(next_height, last_height).await?;
+            next_height = last_height + 1;
+        }
+
+        Ok(())
+    }
+
+    pub async fn run(&self) {
+        info!("Starting the contract events indexer");
+
+        let mut interval = tokio::time::interval(self.polling_rate);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.index_new_blocks().await {
+                error!("Failed to index new blocks - {}", err);
+            }
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tails the blocks produced by the chain and stores the decoded events emitted by the mixnet and vesting
+//! contracts, so that the history of, for example, delegations or mixnode bonds could be easily recovered.
+
+pub mod error;
+pub mod events;
+pub mod indexer;
+mod models;
+pub mod storage;
+
+pub use error::IndexerError;
+pub use events::{ContractEvent, MixnetEvent, RewardingOutcome, VestingEvent, WatchedContracts};
+pub use indexer::Indexer;
+pub use models::IndexedEvent;
+pub use storage::EventsStorage;
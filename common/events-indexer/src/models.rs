@@ -0,0 +1,39 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::IndexerError;
+use crate::events::ContractEvent;
+use std::convert::TryFrom;
+
+// the remaining columns are only used for filtering the events and are not retrieved
+pub(crate) struct StoredContractEvent {
+    pub height: i64,
+    pub tx_hash: String,
+    pub msg_index: i64,
+    pub contract_address: String,
+    pub data: String,
+}
+
+/// Decoded contract event alongside information on where it has been emitted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedEvent {
+    pub height: u64,
+    pub tx_hash: String,
+    pub msg_index: u32,
+    pub contract_address: String,
+    pub event: ContractEvent,
+}
+
+impl TryFrom<StoredContractEvent> for IndexedEvent {
+    type Error = IndexerError;
+
+    fn try_from(stored: StoredContractEvent) -> Result<Self, Self::Error> {
+        Ok(IndexedEvent {
+            height: stored.height as u64,
+            tx_hash: stored.tx_hash,
+            msg_index: stored.msg_index as u32,
+            contract_address: stored.contract_address,
+            event: serde_json::from_str(&stored.data)?,
+        })
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::IndexerError;
+use crate::events::MixnetEvent;
+use crate::models::{IndexedEvent, StoredContractEvent};
+use log::{debug, error};
+use sqlx::ConnectOptions;
+use std::convert::TryInto;
+use std::path::Path;
+
+// note that clone here is fine as upon cloning the same underlying pool will be used
+#[derive(Clone)]
+pub struct EventsStorage {
+    connection_pool: sqlx::SqlitePool,
+}
+
+impl EventsStorage {
+    /// Initialises `EventsStorage` using the provided path.
+    ///
+    /// # Arguments
+    ///
+    /// * `database_path`: path to the database.
+    pub async fn init<P: AsRef<Path> + Send>(database_path: P) -> Result<Self, IndexerError> {
+        debug!(
+            "Attempting to connect to database {:?}",
+            database_path.as_ref().as_os_str()
+        );
+
+        let mut opts = sqlx::sqlite::SqliteConnectOptions::new()
+            .filename(database_path)
+            .create_if_missing(true);
+
+        opts.disable_statement_logging();
+
+        let connection_pool = match sqlx::SqlitePool::connect_with(opts).await {
+            Ok(db) => db,
+            Err(err) => {
+                error!("Failed to connect to SQLx database: {}", err);
+                return Err(err.into());
+            }
+        };
+
+        if let Err(err) = sqlx::migrate!("./migrations").run(&connection_pool).await {
+            error!("Failed to perform migration on the SQLx database: {}", err);
+            return Err(err.into());
+        }
+
+        Ok(EventsStorage { connection_pool })
+    }
+
+    /// Gets the height of the last block whose events got fully indexed.
+    pub async fn last_processed_height(&self) -> Result<Option<u64>, IndexerError> {
+        let height = sqlx::query!("SELECT last_processed_height FROM indexer_status WHERE id = 0")
+            .fetch_optional(&self.connection_pool)
+            .await?
+            .map(|row| row.last_processed_height as u64);
+
+        Ok(height)
+    }
+
+    /// Inserts the provided events into the database and marks all blocks up to `processed_height`
+    /// as indexed. Either all or none of the changes are applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `events`: decoded events emitted in the processed blocks.
+    /// * `processed_height`: height of the last processed block.
+    pub async fn insert_events(
+        &self,
+        events: &[IndexedEvent],
+        processed_height: u64,
+    ) -> Result<(), IndexerError> {
+        let mut tx = self.connection_pool.begin().await?;
+
+        for indexed in events {
+            let height = indexed.height as i64;
+            let msg_index = indexed.msg_index as i64;
+            let event_type = indexed.event.event_type();
+            let delegator = indexed.event.delegator();
+            let owner = indexed.event.owner();
+            let node_identity = indexed.event.node_identity();
+            let data = serde_json::to_string(&indexed.event)?;
+
+            sqlx::query!(
+                r#"
+                    INSERT INTO contract_events(height, tx_hash, msg_index, contract_address, event_type, delegator, owner, node_identity, data)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+                height,
+                indexed.tx_hash,
+                msg_index,
+                indexed.contract_address,
+                event_type,
+                delegator,
+                owner,
+                node_identity,
+                data,
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        let processed_height = processed_height as i64;
+        sqlx::query!(
+            r#"
+                INSERT INTO indexer_status(id, last_processed_height) VALUES (0, ?)
+                ON CONFLICT(id) DO UPDATE SET last_processed_height = excluded.last_processed_height
+            "#,
+            processed_height
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Gets all rewards (in chronological order) that have been received by the particular delegator.
+    ///
+    /// # Arguments
+    ///
+    /// * `delegator`: address of the delegator.
+    pub async fn rewards_for_delegator(
+        &self,
+        delegator: &str,
+    ) -> Result<Vec<IndexedEvent>, IndexerError> {
+        let [reward_type] = MixnetEvent::DELEGATOR_REWARD_EVENT_TYPES;
+        let stored = sqlx::query_as!(
+            StoredContractEvent,
+            r#"
+                SELECT height, tx_hash, msg_index, contract_address, data FROM contract_events
+                WHERE delegator = ? AND event_type = ?
+                ORDER BY id
+            "#,
+            delegator,
+            reward_type
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+
+        stored.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Gets all events (in chronological order) involving the particular delegator.
+    ///
+    /// # Arguments
+    ///
+    /// * `delegator`: address of the delegator.
+    pub async fn delegator_history(
+        &self,
+        delegator: &str,
+    ) -> Result<Vec<IndexedEvent>, IndexerError> {
+        let stored = sqlx::query_as!(
+            StoredContractEvent,
+            r#"
+                SELECT height, tx_hash, msg_index, contract_address, data FROM contract_events
+                WHERE delegator = ?
+                ORDER BY id
+            "#,
+            delegator
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+
+        stored.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Gets all events (in chronological order) that have affected the bond of the particular mixnode,
+    /// such as its bonding, unbonding, pledge changes or jailing.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity key of the mixnode.
+    pub async fn bond_history(&self, identity: &str) -> Result<Vec<IndexedEvent>, IndexerError> {
+        let [bonding, unbonding, pledge_decrease, cost_params_update, jailing, unjailing] =
+            MixnetEvent::BOND_HISTORY_EVENT_TYPES;
+        let stored = sqlx::query_as!(
+            StoredContractEvent,
+            r#"
+                SELECT height, tx_hash, msg_index, contract_address, data FROM contract_events
+                WHERE node_identity = ? AND event_type IN (?, ?, ?, ?, ?, ?)
+                ORDER BY id
+            "#,
+            identity,
+            bonding,
+            unbonding,
+            pledge_decrease,
+            cost_params_update,
+            jailing,
+            unjailing
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+
+        stored.into_iter().map(TryInto::try_into).collect()
+    }
+}
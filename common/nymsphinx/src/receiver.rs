@@ -218,6 +218,7 @@ mod message_receiver {
                 .unwrap(),
                 layer: Layer::One,
                 version: "0.8.0-dev".to_string(),
+                family: None,
             }],
         );
 
@@ -239,6 +240,7 @@ mod message_receiver {
                 .unwrap(),
                 layer: Layer::Two,
                 version: "0.8.0-dev".to_string(),
+                family: None,
             }],
         );
 
@@ -260,6 +262,7 @@ mod message_receiver {
                 .unwrap(),
                 layer: Layer::Three,
                 version: "0.8.0-dev".to_string(),
+                family: None,
             }],
         );
 
@@ -122,6 +122,7 @@ impl Controller {
 
     fn send_to_connection(&mut self, conn_id: ConnectionId, payload: Vec<u8>, is_closed: bool) {
         if let Some(active_connection) = self.active_connections.get_mut(&conn_id) {
+            let is_empty_close = payload.is_empty() && is_closed;
             if !payload.is_empty() {
                 active_connection.write_to_buf(payload);
             } else if !is_closed {
@@ -131,7 +132,15 @@ impl Controller {
             // remote socket getting closed!
             active_connection.is_closed |= is_closed;
 
-            if let Some(payload) = active_connection.read_from_buf() {
+            let payload = match active_connection.read_from_buf() {
+                Some(payload) => Some(payload),
+                // an empty closing message is not ordered, it's sent when the remote
+                // failed to establish the connection, so close it straight away
+                None if is_empty_close => Some(Vec::new()),
+                None => None,
+            };
+
+            if let Some(payload) = payload {
                 if let Err(err) = active_connection
                     .connection_sender
                     .as_mut()
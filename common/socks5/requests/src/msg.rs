@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::request::{Request, RequestError};
-use crate::response::{Response, ResponseError};
+use crate::response::{ConnectionError, Response, ResponseError};
+use crate::ConnectionId;
+use std::convert::TryInto;
 
 #[derive(Debug)]
 pub enum MessageError {
@@ -26,11 +28,16 @@ impl std::fmt::Display for MessageError {
 pub enum Message {
     Request(Request),
     Response(Response),
+    ConnectionError(ConnectionError),
+    /// Sent by the service provider once it has established the requested connection.
+    ConnectionEstablished(ConnectionId),
 }
 
 impl Message {
     const REQUEST_FLAG: u8 = 0;
     const RESPONSE_FLAG: u8 = 1;
+    const CONNECTION_ERROR_FLAG: u8 = 2;
+    const CONNECTION_ESTABLISHED_FLAG: u8 = 3;
 
     pub fn try_from_bytes(b: &[u8]) -> Result<Message, MessageError> {
         if b.is_empty() {
@@ -45,6 +52,14 @@ impl Message {
             Response::try_from_bytes(&b[1..])
                 .map(Message::Response)
                 .map_err(MessageError::Response)
+        } else if b[0] == Self::CONNECTION_ERROR_FLAG {
+            ConnectionError::try_from_bytes(&b[1..])
+                .map(Message::ConnectionError)
+                .map_err(MessageError::Response)
+        } else if b[0] == Self::CONNECTION_ESTABLISHED_FLAG {
+            Self::connection_id_from_bytes(&b[1..])
+                .map(Message::ConnectionEstablished)
+                .map_err(MessageError::Response)
         } else {
             Err(MessageError::UnknownMessageType)
         }
@@ -58,6 +73,53 @@ impl Message {
             Self::Response(r) => std::iter::once(Self::RESPONSE_FLAG)
                 .chain(r.into_bytes().iter().cloned())
                 .collect(),
+            Self::ConnectionError(e) => std::iter::once(Self::CONNECTION_ERROR_FLAG)
+                .chain(e.into_bytes().iter().cloned())
+                .collect(),
+            Self::ConnectionEstablished(conn_id) => {
+                std::iter::once(Self::CONNECTION_ESTABLISHED_FLAG)
+                    .chain(conn_id.to_be_bytes().iter().cloned())
+                    .collect()
+            }
+        }
+    }
+
+    fn connection_id_from_bytes(b: &[u8]) -> Result<ConnectionId, ResponseError> {
+        if b.is_empty() {
+            return Err(ResponseError::NoData);
         }
+
+        let connection_id_bytes = b
+            .get(..8)
+            .ok_or(ResponseError::ConnectionIdTooShort)?
+            .try_into()
+            .unwrap();
+        Ok(u64::from_be_bytes(connection_id_bytes))
+    }
+}
+
+#[cfg(test)]
+mod connection_established {
+    use super::*;
+
+    #[test]
+    fn can_be_serialized_and_deserialized() {
+        let bytes = Message::ConnectionEstablished(42).into_bytes();
+        assert!(matches!(
+            Message::try_from_bytes(&bytes),
+            Ok(Message::ConnectionEstablished(42))
+        ));
+    }
+
+    #[test]
+    fn fails_when_connection_id_bytes_are_too_short() {
+        assert!(matches!(
+            Message::try_from_bytes(&[3, 0, 1, 2, 3, 4, 5, 6]),
+            Err(MessageError::Response(ResponseError::ConnectionIdTooShort))
+        ));
+        assert!(matches!(
+            Message::try_from_bytes(&[3]),
+            Err(MessageError::Response(ResponseError::NoData))
+        ));
     }
 }
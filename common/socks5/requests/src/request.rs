@@ -10,6 +10,9 @@ pub type RemoteAddress = String;
 pub enum RequestFlag {
     Connect = 0,
     Send = 1,
+    /// Same as `Connect`, but the client also wants to be told whether the connection
+    /// got established. Older service providers do not understand it.
+    ConnectWithStatus = 2,
 }
 
 #[derive(Debug)]
@@ -58,6 +61,7 @@ impl TryFrom<u8> for RequestFlag {
         match value {
             _ if value == (RequestFlag::Connect as u8) => Ok(Self::Connect),
             _ if value == (RequestFlag::Send as u8) => Ok(Self::Send),
+            _ if value == (RequestFlag::ConnectWithStatus as u8) => Ok(Self::ConnectWithStatus),
             _ => Err(RequestError::UnknownRequestFlag),
         }
     }
@@ -68,6 +72,9 @@ pub struct ConnectRequest {
     pub conn_id: ConnectionId,
     pub remote_addr: RemoteAddress,
     pub return_address: Recipient,
+    /// Whether the client expects to receive `ConnectionEstablished` or `ConnectionError`
+    /// once the connection attempt is finished.
+    pub wants_status: bool,
 }
 
 /// A request from a SOCKS5 client that a Nym Socks5 service provider should
@@ -94,6 +101,22 @@ impl Request {
             conn_id,
             remote_addr,
             return_address,
+            wants_status: false,
+        }))
+    }
+
+    /// Construct a new Request::Connect instance for a client that wants to be told
+    /// about the result of the connection attempt
+    pub fn new_connect_with_status(
+        conn_id: ConnectionId,
+        remote_addr: RemoteAddress,
+        return_address: Recipient,
+    ) -> Request {
+        Request::Connect(Box::new(ConnectRequest {
+            conn_id,
+            remote_addr,
+            return_address,
+            wants_status: true,
         }))
     }
 
@@ -126,7 +149,7 @@ impl Request {
         }
         let connection_id = u64::from_be_bytes([b[1], b[2], b[3], b[4], b[5], b[6], b[7], b[8]]);
         match RequestFlag::try_from(b[0])? {
-            RequestFlag::Connect => {
+            flag @ (RequestFlag::Connect | RequestFlag::ConnectWithStatus) => {
                 let connect_request_bytes = &b[9..];
 
                 // we need to be able to read at least 2 bytes that specify address length
@@ -159,11 +182,12 @@ impl Request {
                 let return_address = Recipient::try_from_bytes(return_bytes)
                     .map_err(RequestError::MalformedReturnAddress)?;
 
-                Ok(Request::new_connect(
-                    connection_id,
-                    remote_address,
+                Ok(Request::Connect(Box::new(ConnectRequest {
+                    conn_id: connection_id,
+                    remote_addr: remote_address,
                     return_address,
-                ))
+                    wants_status: matches!(flag, RequestFlag::ConnectWithStatus),
+                })))
             }
             RequestFlag::Send => {
                 let local_closed = b[9] != 0;
@@ -184,7 +208,13 @@ impl Request {
                 let remote_address_bytes = req.remote_addr.into_bytes();
                 let remote_address_bytes_len = remote_address_bytes.len() as u16;
 
-                std::iter::once(RequestFlag::Connect as u8)
+                let flag = if req.wants_status {
+                    RequestFlag::ConnectWithStatus
+                } else {
+                    RequestFlag::Connect
+                };
+
+                std::iter::once(flag as u8)
                     .chain(req.conn_id.to_be_bytes().iter().cloned())
                     .chain(remote_address_bytes_len.to_be_bytes().iter().cloned())
                     .chain(remote_address_bytes.into_iter())
@@ -432,6 +462,50 @@ mod request_deserialization_tests {
         }
     }
 
+    #[cfg(test)]
+    mod connection_status {
+        use super::*;
+
+        fn recipient() -> Recipient {
+            Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap()
+        }
+
+        #[test]
+        fn is_not_requested_by_legacy_connect() {
+            let bytes = Request::new_connect(42, "foo.com".to_string(), recipient()).into_bytes();
+            assert_eq!(RequestFlag::Connect as u8, bytes[0]);
+
+            match Request::try_from_bytes(&bytes).unwrap() {
+                Request::Connect(req) => {
+                    assert_eq!(42, req.conn_id);
+                    assert_eq!("foo.com".to_string(), req.remote_addr);
+                    assert!(!req.wants_status)
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        #[test]
+        fn is_requested_by_connect_with_status() {
+            let bytes = Request::new_connect_with_status(42, "foo.com".to_string(), recipient())
+                .into_bytes();
+            assert_eq!(RequestFlag::ConnectWithStatus as u8, bytes[0]);
+
+            match Request::try_from_bytes(&bytes).unwrap() {
+                Request::Connect(req) => {
+                    assert_eq!(42, req.conn_id);
+                    assert_eq!("foo.com".to_string(), req.remote_addr);
+                    assert_eq!(
+                        req.return_address.to_bytes().to_vec(),
+                        recipient().to_bytes().to_vec()
+                    );
+                    assert!(req.wants_status)
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
     #[cfg(test)]
     mod sending_additional_data_over_an_existing_connection {
         use super::*;
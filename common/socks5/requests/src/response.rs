@@ -1,10 +1,139 @@
 use crate::ConnectionId;
+use std::convert::TryFrom;
+use std::fmt::{self};
 
 #[derive(Debug, PartialEq)]
 pub enum ResponseError {
     ConnectionIdTooShort,
     NoData,
+    UnknownErrorKind(u8),
 }
+
+/// Reason for the Socks5 service provider failing to establish or maintain the connection.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConnectionErrorKind {
+    /// The remote address was rejected by the outbound request filter.
+    Filtered = 0,
+    /// The remote address couldn't be resolved.
+    DnsFailure = 1,
+    /// The remote host refused the connection.
+    ConnectionRefused = 2,
+    /// The connection attempt to the remote host has timed out.
+    Timeout = 3,
+    /// The client has exceeded one of the limits imposed by the service provider.
+    RateLimited = 4,
+    /// Any other failure, such as the remote host being unreachable.
+    Other = 255,
+}
+
+impl fmt::Display for ConnectionErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectionErrorKind::Filtered => write!(f, "remote address is not allowed"),
+            ConnectionErrorKind::DnsFailure => write!(f, "failed to resolve remote address"),
+            ConnectionErrorKind::ConnectionRefused => write!(f, "connection refused"),
+            ConnectionErrorKind::Timeout => write!(f, "connection timed out"),
+            ConnectionErrorKind::RateLimited => write!(f, "client rate limited"),
+            ConnectionErrorKind::Other => write!(f, "connection failure"),
+        }
+    }
+}
+
+impl TryFrom<u8> for ConnectionErrorKind {
+    type Error = ResponseError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            _ if value == (ConnectionErrorKind::Filtered as u8) => Ok(Self::Filtered),
+            _ if value == (ConnectionErrorKind::DnsFailure as u8) => Ok(Self::DnsFailure),
+            _ if value == (ConnectionErrorKind::ConnectionRefused as u8) => {
+                Ok(Self::ConnectionRefused)
+            }
+            _ if value == (ConnectionErrorKind::Timeout as u8) => Ok(Self::Timeout),
+            _ if value == (ConnectionErrorKind::RateLimited as u8) => Ok(Self::RateLimited),
+            _ if value == (ConnectionErrorKind::Other as u8) => Ok(Self::Other),
+            _ => Err(ResponseError::UnknownErrorKind(value)),
+        }
+    }
+}
+
+impl From<&std::io::Error> for ConnectionErrorKind {
+    fn from(err: &std::io::Error) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::ConnectionRefused => ConnectionErrorKind::ConnectionRefused,
+            std::io::ErrorKind::TimedOut => ConnectionErrorKind::Timeout,
+            _ => ConnectionErrorKind::Other,
+        }
+    }
+}
+
+/// Error sent back by the Socks5 service provider when it failed to establish the connection
+/// on behalf of the requesting application.
+#[derive(Debug)]
+pub struct ConnectionError {
+    pub connection_id: ConnectionId,
+    pub kind: ConnectionErrorKind,
+    pub message: String,
+}
+
+impl ConnectionError {
+    /// Constructor for connection errors
+    pub fn new(connection_id: ConnectionId, kind: ConnectionErrorKind, message: String) -> Self {
+        ConnectionError {
+            connection_id,
+            kind,
+            message,
+        }
+    }
+
+    /// Deserializes the error from bytes.
+    ///
+    /// Serialized bytes looks like this:
+    ///
+    /// ----------------------------------------------
+    ///  connection_id | error_kind | error_message |
+    ///        8       |     1      |      ...      |
+    /// ----------------------------------------------
+    pub fn try_from_bytes(b: &[u8]) -> Result<ConnectionError, ResponseError> {
+        if b.is_empty() {
+            return Err(ResponseError::NoData);
+        }
+
+        if b.len() < 9 {
+            return Err(ResponseError::ConnectionIdTooShort);
+        }
+
+        let connection_id = u64::from_be_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]);
+        let kind = ConnectionErrorKind::try_from(b[8])?;
+        let message = String::from_utf8_lossy(&b[9..]).to_string();
+
+        Ok(ConnectionError::new(connection_id, kind, message))
+    }
+
+    /// Serializes the error into bytes so that it can be sent back through
+    /// the mixnet to the requesting application.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.connection_id
+            .to_be_bytes()
+            .iter()
+            .cloned()
+            .chain(std::iter::once(self.kind as u8))
+            .chain(self.message.into_bytes().into_iter())
+            .collect()
+    }
+}
+
+impl fmt::Display for ConnectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.message.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{} - {}", self.kind, self.message)
+        }
+    }
+}
+
 /// A remote network response retrieved by the Socks5 service provider. This
 /// can be serialized and sent back through the mixnet to the requesting
 /// application.
@@ -115,3 +244,45 @@ mod constructing_socks5_responses_from_bytes {
         assert_eq!(expected.is_closed, actual.is_closed);
     }
 }
+
+#[cfg(test)]
+mod connection_errors {
+    use super::*;
+
+    #[test]
+    fn fail_to_deserialize_when_connection_id_bytes_are_too_short() {
+        assert_eq!(
+            ResponseError::ConnectionIdTooShort,
+            ConnectionError::try_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn fail_to_deserialize_with_unknown_error_kind() {
+        assert_eq!(
+            ResponseError::UnknownErrorKind(42),
+            ConnectionError::try_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7, 42]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn can_be_serialized_and_deserialized() {
+        let error = ConnectionError::new(
+            42,
+            ConnectionErrorKind::DnsFailure,
+            "failed to lookup nymtech.net".to_string(),
+        );
+        let recovered = ConnectionError::try_from_bytes(&error.into_bytes()).unwrap();
+        assert_eq!(42, recovered.connection_id);
+        assert_eq!(ConnectionErrorKind::DnsFailure, recovered.kind);
+        assert_eq!("failed to lookup nymtech.net", recovered.message);
+    }
+
+    #[test]
+    fn can_be_deserialized_without_message() {
+        let recovered = ConnectionError::try_from_bytes(&[0, 0, 0, 0, 0, 0, 0, 42, 4]).unwrap();
+        assert_eq!(42, recovered.connection_id);
+        assert_eq!(ConnectionErrorKind::RateLimited, recovered.kind);
+        assert!(recovered.message.is_empty());
+    }
+}
@@ -132,6 +132,7 @@ impl NymTopology {
             return Err(NymTopologyError::InvalidNumberOfHopsError);
         }
         let mut route = Vec::with_capacity(num_mix_hops as usize);
+        let mut route_families: Vec<&str> = Vec::new();
 
         // there is no "layer 0"
         for layer in 1..=num_mix_hops {
@@ -141,11 +142,28 @@ impl NymTopology {
                 .get(&layer)
                 .ok_or(NymTopologyError::NoMixesOnLayerAvailable(layer))?;
 
+            // avoid putting multiple nodes operated by the same family on a single route,
+            // unless there's no other choice on this layer
+            let candidates = layer_mixes
+                .iter()
+                .filter(|mix| match &mix.family {
+                    Some(family) => !route_families.contains(&family.as_str()),
+                    None => true,
+                })
+                .collect::<Vec<_>>();
+
             // choose a random mix from the above list
             // this can return a 'None' only if slice is empty
-            let random_mix = layer_mixes
-                .choose(rng)
-                .ok_or(NymTopologyError::NoMixesOnLayerAvailable(layer))?;
+            let random_mix = if candidates.is_empty() {
+                layer_mixes.choose(rng)
+            } else {
+                candidates.choose(rng).copied()
+            }
+            .ok_or(NymTopologyError::NoMixesOnLayerAvailable(layer))?;
+
+            if let Some(family) = &random_mix.family {
+                route_families.push(family)
+            }
             route.push(random_mix.into());
         }
 
@@ -294,6 +312,7 @@ mod converting_mixes_to_vec {
                 .unwrap(),
                 layer: Layer::One,
                 version: "0.x.0".to_string(),
+                family: None,
             };
 
             let node2 = mix::Node {
@@ -316,6 +335,51 @@ mod converting_mixes_to_vec {
         }
     }
 
+    #[cfg(test)]
+    mod route_selection {
+        use super::*;
+        use crypto::asymmetric::{encryption, identity};
+        use mixnet_contract_common::Layer;
+        use rand::thread_rng;
+
+        fn mix(id: u8, layer: Layer, family: Option<&str>) -> mix::Node {
+            mix::Node {
+                owner: "N/A".to_string(),
+                stake: 0,
+                delegation: 0,
+                host: "3.3.3.3".parse().unwrap(),
+                mix_host: "3.3.3.3:1789".parse().unwrap(),
+                identity_key: identity::PublicKey::from_base58_string(
+                    "3ebjp1Fb9hdcS1AR6AZihgeJiMHkB5jjJUsvqNnfQwU7",
+                )
+                .unwrap(),
+                sphinx_key: encryption::PublicKey::from_bytes(&[id; 32]).unwrap(),
+                layer,
+                version: "0.x.0".to_string(),
+                family: family.map(|family| family.to_string()),
+            }
+        }
+
+        #[test]
+        fn avoids_multiple_members_of_the_same_family() {
+            let family_l2 = mix(2, Layer::Two, Some("family"));
+            let other_l2 = mix(3, Layer::Two, None);
+
+            let mut mixes: HashMap<MixLayer, Vec<mix::Node>> = HashMap::new();
+            mixes.insert(1, vec![mix(1, Layer::One, Some("family"))]);
+            mixes.insert(2, vec![family_l2, other_l2.clone()]);
+            mixes.insert(3, vec![mix(4, Layer::Three, Some("family"))]);
+            let topology = NymTopology::new(mixes, vec![]);
+
+            for _ in 0..50 {
+                let route = topology.random_mix_route(&mut thread_rng(), 3).unwrap();
+                // the only non-family node has to be chosen on layer 2, while on layer 3
+                // there's no alternative so the family member is used anyway
+                assert_eq!(other_l2.sphinx_key.to_bytes(), *route[1].pub_key.as_bytes());
+            }
+        }
+    }
+
     #[cfg(test)]
     mod when_no_nodes_exist {
         use super::*;
@@ -81,6 +81,9 @@ pub struct Node {
     pub sphinx_key: encryption::PublicKey, // TODO: or nymsphinx::PublicKey? both are x25519
     pub layer: Layer,
     pub version: String,
+    // identity of the head of the family this node belongs to, nodes of the same family
+    // should never be put on the same route
+    pub family: Option<String>,
 }
 
 impl filter::Versioned for Node {
@@ -125,6 +128,7 @@ impl<'a> TryFrom<&'a MixNodeBond> for Node {
             sphinx_key: encryption::PublicKey::from_base58_string(&bond.mix_node.sphinx_key)?,
             layer: bond.layer,
             version: bond.mix_node.version.clone(),
+            family: bond.family.clone(),
         })
     }
 }
@@ -1,12 +1,14 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
 
 use coconut_bandwidth_contract_common::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 use crate::error::ContractError;
-use crate::state::{Config, ADMIN, CONFIG};
+use crate::state::{Config, ADMIN, CONFIG, DEPOSITS};
 use crate::transactions;
 
 /// Instantiate the contract.
@@ -22,12 +24,16 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     let multisig_addr = deps.api.addr_validate(&msg.multisig_addr)?;
     let pool_addr = deps.api.addr_validate(&msg.pool_addr)?;
+    let signers = transactions::validate_signers(deps.api, &msg.signers, msg.signer_threshold)?;
 
     ADMIN.set(deps.branch(), Some(multisig_addr.clone()))?;
 
     let cfg = Config {
         multisig_addr,
         pool_addr,
+        signers,
+        signer_threshold: msg.signer_threshold,
+        deposit_timeout_secs: msg.deposit_timeout_secs,
     };
     CONFIG.save(deps.storage, &cfg)?;
 
@@ -45,16 +51,51 @@ pub fn execute(
     match msg {
         ExecuteMsg::DepositFunds { data } => transactions::deposit_funds(deps, env, info, data),
         ExecuteMsg::ReleaseFunds { funds } => transactions::release_funds(deps, env, info, funds),
+        ExecuteMsg::MarkIssued { deposit_id } => {
+            transactions::mark_issued(deps, env, info, deposit_id)
+        }
+        ExecuteMsg::RefundDeposit { deposit_id } => {
+            transactions::refund_deposit(deps, env, info, deposit_id)
+        }
+        ExecuteMsg::UpdateSigners {
+            signers,
+            signer_threshold,
+        } => transactions::update_signers(deps, info, signers, signer_threshold),
     }
 }
 
 #[entry_point]
-pub fn query(_deps: Deps, _env: Env, _msg: QueryMsg) -> StdResult<Binary> {
-    unimplemented!();
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetDeposit { deposit_id } => {
+            to_binary(&DEPOSITS.may_load(deps.storage, deposit_id)?)
+        }
+    }
 }
 
 #[entry_point]
-pub fn migrate(_deps: DepsMut<'_>, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut<'_>, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let mut cfg = CONFIG.load(deps.storage)?;
+
+    if let Some(deposit_timeout_secs) = msg.deposit_timeout_secs {
+        cfg.deposit_timeout_secs = deposit_timeout_secs;
+    }
+
+    // contracts deployed before the signer set was introduced have to get it provided here
+    let signers = match msg.signers {
+        Some(signers) => signers,
+        None => cfg
+            .signers
+            .iter()
+            .map(|signer| signer.to_string())
+            .collect(),
+    };
+    let signer_threshold = msg.signer_threshold.unwrap_or(cfg.signer_threshold);
+    cfg.signers = transactions::validate_signers(deps.api, &signers, signer_threshold)?;
+    cfg.signer_threshold = signer_threshold;
+
+    CONFIG.save(deps.storage, &cfg)?;
+
     Ok(Default::default())
 }
 
@@ -62,7 +103,7 @@ pub fn migrate(_deps: DepsMut<'_>, _env: Env, _msg: MigrateMsg) -> Result<Respon
 mod tests {
     use super::*;
     use crate::support::tests::helpers::*;
-    use coconut_bandwidth_contract_common::deposit::DepositData;
+    use coconut_bandwidth_contract_common::deposit::{Deposit, DepositData};
     use config::defaults::DENOM;
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::{coins, Addr};
@@ -76,6 +117,9 @@ mod tests {
         let msg = InstantiateMsg {
             multisig_addr: String::from(MULTISIG_CONTRACT),
             pool_addr: String::from(POOL_CONTRACT),
+            signers: SIGNERS.iter().map(|signer| signer.to_string()).collect(),
+            signer_threshold: SIGNER_THRESHOLD,
+            deposit_timeout_secs: DEPOSIT_TIMEOUT_SECS,
         };
         let info = mock_info("creator", &[]);
 
@@ -106,6 +150,9 @@ mod tests {
         let msg = InstantiateMsg {
             multisig_addr: multisig_addr.clone(),
             pool_addr: pool_addr.clone(),
+            signers: SIGNERS.iter().map(|signer| signer.to_string()).collect(),
+            signer_threshold: SIGNER_THRESHOLD,
+            deposit_timeout_secs: DEPOSIT_TIMEOUT_SECS,
         };
         let contract_addr = app
             .instantiate_contract(
@@ -147,9 +194,30 @@ mod tests {
             .unwrap_err();
         assert_eq!(ContractError::NotEnoughFunds, err.downcast().unwrap());
 
+        // the deposit can't be released before it gets issued
         let msg = ExecuteMsg::ReleaseFunds {
             funds: deposit_funds[0].clone(),
         };
+        let err = app
+            .execute_contract(
+                Addr::unchecked(multisig_addr.clone()),
+                contract_addr.clone(),
+                &msg,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::NotEnoughFunds, err.downcast().unwrap());
+
+        for signer in &SIGNERS[..SIGNER_THRESHOLD as usize] {
+            app.execute_contract(
+                Addr::unchecked(*signer),
+                contract_addr.clone(),
+                &ExecuteMsg::MarkIssued { deposit_id: 1 },
+                &[],
+            )
+            .unwrap();
+        }
+
         app.execute_contract(
             Addr::unchecked(multisig_addr),
             contract_addr.clone(),
@@ -160,4 +228,138 @@ mod tests {
         let pool_bal = app.wrap().query_balance(pool_addr, DENOM).unwrap();
         assert_eq!(pool_bal, deposit_funds[0]);
     }
+
+    #[test]
+    fn invalid_signer_threshold() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        let msg = |signer_threshold| InstantiateMsg {
+            multisig_addr: String::from(MULTISIG_CONTRACT),
+            pool_addr: String::from(POOL_CONTRACT),
+            signers: SIGNERS.iter().map(|signer| signer.to_string()).collect(),
+            signer_threshold,
+            deposit_timeout_secs: DEPOSIT_TIMEOUT_SECS,
+        };
+
+        assert_eq!(
+            instantiate(deps.as_mut(), env.clone(), info.clone(), msg(0)),
+            Err(ContractError::InvalidSignerThreshold {
+                threshold: 0,
+                signers: 3
+            })
+        );
+        assert_eq!(
+            instantiate(deps.as_mut(), env, info, msg(4)),
+            Err(ContractError::InvalidSignerThreshold {
+                threshold: 4,
+                signers: 3
+            })
+        );
+    }
+
+    #[test]
+    fn deposit_and_refund() {
+        let init_funds = coins(10, DENOM);
+        let deposit_funds = coins(4, DENOM);
+        let mut app = mock_app(&init_funds);
+
+        let code_id = app.store_code(contract_bandwidth());
+        let msg = InstantiateMsg {
+            multisig_addr: String::from(MULTISIG_CONTRACT),
+            pool_addr: String::from(POOL_CONTRACT),
+            signers: SIGNERS.iter().map(|signer| signer.to_string()).collect(),
+            signer_threshold: SIGNER_THRESHOLD,
+            deposit_timeout_secs: DEPOSIT_TIMEOUT_SECS,
+        };
+        let contract_addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked(OWNER),
+                &msg,
+                &[],
+                "bandwidth",
+                None,
+            )
+            .unwrap();
+
+        let msg = ExecuteMsg::DepositFunds {
+            data: DepositData::new(
+                String::from("info"),
+                String::from("id"),
+                String::from("enc"),
+            ),
+        };
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            contract_addr.clone(),
+            &msg,
+            &deposit_funds,
+        )
+        .unwrap();
+
+        // a single signer isn't enough to prevent the refund
+        app.execute_contract(
+            Addr::unchecked(SIGNERS[0]),
+            contract_addr.clone(),
+            &ExecuteMsg::MarkIssued { deposit_id: 1 },
+            &[],
+        )
+        .unwrap();
+
+        let refund = ExecuteMsg::RefundDeposit { deposit_id: 1 };
+        let err = app
+            .execute_contract(Addr::unchecked(OWNER), contract_addr.clone(), &refund, &[])
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::DepositNotExpired { deposit_id: 1, .. }
+        ));
+
+        app.update_block(|block| {
+            block.time = block.time.plus_seconds(DEPOSIT_TIMEOUT_SECS);
+        });
+        app.execute_contract(Addr::unchecked(OWNER), contract_addr.clone(), &refund, &[])
+            .unwrap();
+
+        let owner_bal = app.wrap().query_balance(OWNER, DENOM).unwrap();
+        assert_eq!(owner_bal, init_funds[0]);
+
+        let deposit: Option<Deposit> = app
+            .wrap()
+            .query_wasm_smart(contract_addr, &QueryMsg::GetDeposit { deposit_id: 1 })
+            .unwrap();
+        assert!(deposit.is_none());
+    }
+
+    #[test]
+    fn migrating_signers() {
+        let mut deps = init_contract();
+        let env = mock_env();
+
+        let msg = MigrateMsg {
+            signers: Some(vec![String::from("signer4")]),
+            signer_threshold: Some(2),
+            deposit_timeout_secs: None,
+        };
+        assert_eq!(
+            migrate(deps.as_mut(), env.clone(), msg),
+            Err(ContractError::InvalidSignerThreshold {
+                threshold: 2,
+                signers: 1
+            })
+        );
+
+        let msg = MigrateMsg {
+            signers: Some(vec![String::from("signer4")]),
+            signer_threshold: Some(1),
+            deposit_timeout_secs: Some(42),
+        };
+        migrate(deps.as_mut(), env, msg).unwrap();
+
+        let cfg = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(cfg.signers, vec![Addr::unchecked("signer4")]);
+        assert_eq!(cfg.signer_threshold, 1);
+        assert_eq!(cfg.deposit_timeout_secs, 42);
+    }
 }
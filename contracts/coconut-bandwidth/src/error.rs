@@ -30,4 +30,25 @@ pub enum ContractError {
 
     #[error("{0}")]
     Admin(#[from] AdminError),
+
+    #[error("Signer threshold of {threshold} is invalid for {signers} signers")]
+    InvalidSignerThreshold { threshold: u32, signers: usize },
+
+    #[error("Deposit with id {deposit_id} does not exist")]
+    DepositNotFound { deposit_id: u64 },
+
+    #[error("Only credential signers can mark deposits as issued")]
+    NotSigner,
+
+    #[error("Deposit with id {deposit_id} has already been marked as issued by this signer")]
+    AlreadyMarkedIssued { deposit_id: u64 },
+
+    #[error("Only the owner of the deposit can get it refunded")]
+    NotDepositOwner,
+
+    #[error("Credential for deposit with id {deposit_id} has already been issued")]
+    DepositAlreadyIssued { deposit_id: u64 },
+
+    #[error("Deposit with id {deposit_id} can't be refunded before {refundable_at}")]
+    DepositNotExpired { deposit_id: u64, refundable_at: u64 },
 }
@@ -1,18 +1,46 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use cosmwasm_std::Addr;
+use coconut_bandwidth_contract_common::deposit::Deposit;
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
 use cw_controllers::Admin;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub const ADMIN: Admin = Admin::new("admin");
 
+pub const DEFAULT_DEPOSIT_TIMEOUT_SECS: u64 = 7 * 24 * 60 * 60;
+
+fn default_deposit_timeout_secs() -> u64 {
+    DEFAULT_DEPOSIT_TIMEOUT_SECS
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct Config {
     pub multisig_addr: Addr,
     pub pool_addr: Addr,
+    // the signer fields were introduced after the initial deployment, they have to be set
+    // through the migration of existing contracts
+    #[serde(default)]
+    pub signers: Vec<Addr>,
+    #[serde(default)]
+    pub signer_threshold: u32,
+    #[serde(default = "default_deposit_timeout_secs")]
+    pub deposit_timeout_secs: u64,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+pub const DEPOSIT_ID_COUNTER: Item<u64> = Item::new("deposit_id");
+pub const DEPOSITS: Map<u64, Deposit> = Map::new("deposits");
+
+// total value of the deposits that can still be refunded, i.e. haven't been issued yet.
+// Those funds must stay in the contract and can't be released to the pool.
+pub const REFUNDABLE_DEPOSITS: Item<Uint128> = Item::new("refundable");
+
+pub fn next_deposit_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = DEPOSIT_ID_COUNTER.may_load(store)?.unwrap_or_default() + 1;
+    DEPOSIT_ID_COUNTER.save(store, &id)?;
+    Ok(id)
+}
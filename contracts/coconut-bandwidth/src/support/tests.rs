@@ -7,6 +7,9 @@ pub mod helpers {
     pub const SOMEBODY: &str = "somebody";
     pub const MULTISIG_CONTRACT: &str = "multisig contract address";
     pub const POOL_CONTRACT: &str = "mix pool contract address";
+    pub const SIGNERS: [&str; 3] = ["signer1", "signer2", "signer3"];
+    pub const SIGNER_THRESHOLD: u32 = 2;
+    pub const DEPOSIT_TIMEOUT_SECS: u64 = 86400;
 
     use crate::contract::instantiate;
     use coconut_bandwidth_contract_common::msg::InstantiateMsg;
@@ -19,6 +22,9 @@ pub mod helpers {
         let msg = InstantiateMsg {
             multisig_addr: String::from(MULTISIG_CONTRACT),
             pool_addr: String::from(POOL_CONTRACT),
+            signers: SIGNERS.iter().map(|signer| signer.to_string()).collect(),
+            signer_threshold: SIGNER_THRESHOLD,
+            deposit_timeout_secs: DEPOSIT_TIMEOUT_SECS,
         };
         let env = mock_env();
         let info = mock_info("creator", &[]);
@@ -1,21 +1,51 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use cosmwasm_std::{BankMsg, Coin, DepsMut, Env, Event, MessageInfo, Response};
+use cosmwasm_std::{
+    Addr, Api, BankMsg, Coin, DepsMut, Env, Event, MessageInfo, Response, StdResult, Storage,
+    Uint128,
+};
 
 use crate::error::ContractError;
-use crate::state::{ADMIN, CONFIG};
+use crate::state::{next_deposit_id, ADMIN, CONFIG, DEPOSITS, REFUNDABLE_DEPOSITS};
 
-use coconut_bandwidth_contract_common::deposit::DepositData;
+use coconut_bandwidth_contract_common::deposit::{Deposit, DepositData};
 use coconut_bandwidth_contract_common::events::{
-    DEPOSITED_FUNDS_EVENT_TYPE, DEPOSIT_ENCRYPTION_KEY, DEPOSIT_IDENTITY_KEY, DEPOSIT_INFO,
-    DEPOSIT_VALUE,
+    DEPOSITED_FUNDS_EVENT_TYPE, DEPOSIT_ENCRYPTION_KEY, DEPOSIT_ID, DEPOSIT_IDENTITY_KEY,
+    DEPOSIT_INFO, DEPOSIT_ISSUED_EVENT_TYPE, DEPOSIT_OWNER, DEPOSIT_REFUNDED_EVENT_TYPE,
+    DEPOSIT_SIGNER, DEPOSIT_VALUE, SIGNERS, SIGNERS_UPDATED_EVENT_TYPE, SIGNER_THRESHOLD,
 };
 use config::defaults::DENOM;
 
+pub(crate) fn validate_signers(
+    api: &dyn Api,
+    signers: &[String],
+    signer_threshold: u32,
+) -> Result<Vec<Addr>, ContractError> {
+    let signers = signers
+        .iter()
+        .map(|signer| api.addr_validate(signer))
+        .collect::<StdResult<Vec<_>>>()?;
+    if signer_threshold == 0 || signer_threshold as usize > signers.len() {
+        return Err(ContractError::InvalidSignerThreshold {
+            threshold: signer_threshold,
+            signers: signers.len(),
+        });
+    }
+    Ok(signers)
+}
+
+fn update_refundable_deposits(
+    storage: &mut dyn Storage,
+    update: impl FnOnce(Uint128) -> StdResult<Uint128>,
+) -> StdResult<()> {
+    let refundable = REFUNDABLE_DEPOSITS.may_load(storage)?.unwrap_or_default();
+    REFUNDABLE_DEPOSITS.save(storage, &update(refundable)?)
+}
+
 pub(crate) fn deposit_funds(
-    _deps: DepsMut<'_>,
-    _env: Env,
+    deps: DepsMut<'_>,
+    env: Env,
     info: MessageInfo,
     data: DepositData,
 ) -> Result<Response, ContractError> {
@@ -30,7 +60,15 @@ pub(crate) fn deposit_funds(
     }
 
     let voucher_value = info.funds.last().unwrap();
+    let deposit_id = next_deposit_id(deps.storage)?;
+    let deposit = Deposit::new(info.sender.clone(), voucher_value.clone(), env.block.time);
+    DEPOSITS.save(deps.storage, deposit_id, &deposit)?;
+    update_refundable_deposits(deps.storage, |refundable| {
+        Ok(refundable.checked_add(voucher_value.amount)?)
+    })?;
+
     let event = Event::new(DEPOSITED_FUNDS_EVENT_TYPE)
+        .add_attribute(DEPOSIT_ID, deposit_id.to_string())
         .add_attribute(DEPOSIT_VALUE, voucher_value.amount)
         .add_attribute(DEPOSIT_INFO, data.deposit_info())
         .add_attribute(DEPOSIT_IDENTITY_KEY, data.identity_key())
@@ -48,8 +86,13 @@ pub(crate) fn release_funds(
     if funds.denom != DENOM {
         return Err(ContractError::WrongDenom);
     }
+    // only the funds of the issued deposits can be released, the rest might still get refunded
     let current_balance = deps.querier.query_balance(env.contract.address, DENOM)?;
-    if funds.amount > current_balance.amount {
+    let refundable = REFUNDABLE_DEPOSITS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let releasable = current_balance.amount.saturating_sub(refundable);
+    if funds.amount > releasable {
         return Err(ContractError::NotEnoughFunds);
     }
     ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
@@ -65,13 +108,119 @@ pub(crate) fn release_funds(
     Ok(response)
 }
 
+pub(crate) fn mark_issued(
+    deps: DepsMut<'_>,
+    _env: Env,
+    info: MessageInfo,
+    deposit_id: u64,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    if !cfg.signers.contains(&info.sender) {
+        return Err(ContractError::NotSigner);
+    }
+
+    let mut deposit = DEPOSITS
+        .may_load(deps.storage, deposit_id)?
+        .ok_or(ContractError::DepositNotFound { deposit_id })?;
+    if deposit.issued_by.contains(&info.sender) {
+        return Err(ContractError::AlreadyMarkedIssued { deposit_id });
+    }
+    deposit.issued_by.push(info.sender.clone());
+    if !deposit.issued && deposit.has_quorum(cfg.signer_threshold) {
+        // the deposit can no longer be refunded, so its funds can be released to the pool
+        deposit.issued = true;
+        update_refundable_deposits(deps.storage, |refundable| {
+            Ok(refundable.checked_sub(deposit.amount.amount)?)
+        })?;
+    }
+    DEPOSITS.save(deps.storage, deposit_id, &deposit)?;
+
+    let event = Event::new(DEPOSIT_ISSUED_EVENT_TYPE)
+        .add_attribute(DEPOSIT_ID, deposit_id.to_string())
+        .add_attribute(DEPOSIT_SIGNER, info.sender);
+
+    Ok(Response::new().add_event(event))
+}
+
+pub(crate) fn refund_deposit(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    deposit_id: u64,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let deposit = DEPOSITS
+        .may_load(deps.storage, deposit_id)?
+        .ok_or(ContractError::DepositNotFound { deposit_id })?;
+    if deposit.owner != info.sender {
+        return Err(ContractError::NotDepositOwner);
+    }
+    // once a quorum of signers has issued the credential, the deposit has been used up
+    if deposit.issued {
+        return Err(ContractError::DepositAlreadyIssued { deposit_id });
+    }
+    let refundable_at = deposit.created_at.plus_seconds(cfg.deposit_timeout_secs);
+    if env.block.time < refundable_at {
+        return Err(ContractError::DepositNotExpired {
+            deposit_id,
+            refundable_at: refundable_at.seconds(),
+        });
+    }
+
+    DEPOSITS.remove(deps.storage, deposit_id);
+    update_refundable_deposits(deps.storage, |refundable| {
+        Ok(refundable.checked_sub(deposit.amount.amount)?)
+    })?;
+
+    let event = Event::new(DEPOSIT_REFUNDED_EVENT_TYPE)
+        .add_attribute(DEPOSIT_ID, deposit_id.to_string())
+        .add_attribute(DEPOSIT_OWNER, deposit.owner.as_str())
+        .add_attribute(DEPOSIT_VALUE, deposit.amount.amount);
+    let return_tokens = BankMsg::Send {
+        to_address: deposit.owner.into(),
+        amount: vec![deposit.amount],
+    };
+
+    Ok(Response::new().add_message(return_tokens).add_event(event))
+}
+
+pub(crate) fn update_signers(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    signers: Vec<String>,
+    signer_threshold: u32,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    let signers = validate_signers(deps.api, &signers, signer_threshold)?;
+
+    let mut cfg = CONFIG.load(deps.storage)?;
+    let event = Event::new(SIGNERS_UPDATED_EVENT_TYPE)
+        .add_attribute(
+            SIGNERS,
+            signers
+                .iter()
+                .map(Addr::as_str)
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+        .add_attribute(SIGNER_THRESHOLD, signer_threshold.to_string());
+
+    cfg.signers = signers;
+    cfg.signer_threshold = signer_threshold;
+    CONFIG.save(deps.storage, &cfg)?;
+
+    Ok(Response::new().add_event(event))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::support::tests::helpers;
-    use crate::support::tests::helpers::{MULTISIG_CONTRACT, POOL_CONTRACT};
+    use crate::support::tests::helpers::{
+        DEPOSIT_TIMEOUT_SECS, MULTISIG_CONTRACT, POOL_CONTRACT, SIGNERS,
+    };
     use cosmwasm_std::testing::{mock_env, mock_info};
-    use cosmwasm_std::{Coin, CosmosMsg};
+    use cosmwasm_std::{Addr, Coin, CosmosMsg};
     use cw_controllers::AdminError;
 
     #[test]
@@ -133,7 +282,24 @@ mod tests {
         assert_eq!(events.len(), 1);
 
         let event = events[0];
-        assert_eq!(event.attributes.len(), 4);
+        assert_eq!(event.attributes.len(), 5);
+
+        let id_attr = event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == DEPOSIT_ID)
+            .unwrap();
+        assert_eq!(id_attr.value, "1");
+
+        let deposit = DEPOSITS.load(&deps.storage, 1).unwrap();
+        assert_eq!(
+            deposit,
+            Deposit::new(
+                Addr::unchecked("requester"),
+                Coin::new(deposit_value, DENOM),
+                env.block.time
+            )
+        );
 
         let deposit_attr = event
             .attributes
@@ -224,4 +390,247 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn marking_deposit_issued() {
+        let mut deps = helpers::init_contract();
+        let env = mock_env();
+        let data = DepositData::new(
+            String::from("Deposit info"),
+            String::from("Verification key"),
+            String::from("Encryption key"),
+        );
+        let info = mock_info("requester", &[Coin::new(1000, DENOM)]);
+        deposit_funds(deps.as_mut(), env.clone(), info, data).unwrap();
+
+        assert_eq!(
+            mark_issued(deps.as_mut(), env.clone(), mock_info("requester", &[]), 1),
+            Err(ContractError::NotSigner)
+        );
+        assert_eq!(
+            mark_issued(deps.as_mut(), env.clone(), mock_info(SIGNERS[0], &[]), 2),
+            Err(ContractError::DepositNotFound { deposit_id: 2 })
+        );
+
+        let res = mark_issued(deps.as_mut(), env.clone(), mock_info(SIGNERS[0], &[]), 1).unwrap();
+        assert_eq!(res.events[0].ty, DEPOSIT_ISSUED_EVENT_TYPE);
+        assert_eq!(
+            mark_issued(deps.as_mut(), env.clone(), mock_info(SIGNERS[0], &[]), 1),
+            Err(ContractError::AlreadyMarkedIssued { deposit_id: 1 })
+        );
+
+        mark_issued(deps.as_mut(), env, mock_info(SIGNERS[1], &[]), 1).unwrap();
+        let deposit = DEPOSITS.load(&deps.storage, 1).unwrap();
+        assert_eq!(
+            deposit.issued_by,
+            vec![Addr::unchecked(SIGNERS[0]), Addr::unchecked(SIGNERS[1])]
+        );
+    }
+
+    #[test]
+    fn refunding_deposit() {
+        let mut deps = helpers::init_contract();
+        let mut env = mock_env();
+        let coin = Coin::new(1000, DENOM);
+        let data = DepositData::new(
+            String::from("Deposit info"),
+            String::from("Verification key"),
+            String::from("Encryption key"),
+        );
+        for _ in 0..2 {
+            let info = mock_info("requester", &[Coin::new(1000, DENOM)]);
+            deposit_funds(deps.as_mut(), env.clone(), info, data.clone()).unwrap();
+        }
+
+        assert_eq!(
+            refund_deposit(deps.as_mut(), env.clone(), mock_info("requester", &[]), 3),
+            Err(ContractError::DepositNotFound { deposit_id: 3 })
+        );
+        assert_eq!(
+            refund_deposit(deps.as_mut(), env.clone(), mock_info("someone", &[]), 1),
+            Err(ContractError::NotDepositOwner)
+        );
+        assert_eq!(
+            refund_deposit(deps.as_mut(), env.clone(), mock_info("requester", &[]), 1),
+            Err(ContractError::DepositNotExpired {
+                deposit_id: 1,
+                refundable_at: env.block.time.plus_seconds(DEPOSIT_TIMEOUT_SECS).seconds()
+            })
+        );
+
+        // the second deposit gets issued by a quorum of signers
+        for signer in &SIGNERS[..2] {
+            mark_issued(deps.as_mut(), env.clone(), mock_info(signer, &[]), 2).unwrap();
+        }
+
+        env.block.time = env.block.time.plus_seconds(DEPOSIT_TIMEOUT_SECS);
+        assert_eq!(
+            refund_deposit(deps.as_mut(), env.clone(), mock_info("requester", &[]), 2),
+            Err(ContractError::DepositAlreadyIssued { deposit_id: 2 })
+        );
+
+        let res =
+            refund_deposit(deps.as_mut(), env.clone(), mock_info("requester", &[]), 1).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("requester"),
+                amount: vec![coin]
+            })
+        );
+        assert_eq!(res.events[0].ty, DEPOSIT_REFUNDED_EVENT_TYPE);
+
+        // a deposit can only be refunded once
+        assert_eq!(
+            refund_deposit(deps.as_mut(), env, mock_info("requester", &[]), 1),
+            Err(ContractError::DepositNotFound { deposit_id: 1 })
+        );
+        assert_eq!(
+            REFUNDABLE_DEPOSITS.load(&deps.storage).unwrap(),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn releasing_only_issued_deposits() {
+        let mut deps = helpers::init_contract();
+        let env = mock_env();
+        let data = DepositData::new(
+            String::from("Deposit info"),
+            String::from("Verification key"),
+            String::from("Encryption key"),
+        );
+        for _ in 0..2 {
+            let info = mock_info("requester", &[Coin::new(1000, DENOM)]);
+            deposit_funds(deps.as_mut(), env.clone(), info, data.clone()).unwrap();
+        }
+        deps.querier
+            .update_balance(env.contract.address.clone(), vec![Coin::new(2000, DENOM)]);
+        assert_eq!(
+            REFUNDABLE_DEPOSITS.load(&deps.storage).unwrap(),
+            Uint128::new(2000)
+        );
+
+        // nothing has been issued yet
+        assert_eq!(
+            release_funds(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(MULTISIG_CONTRACT, &[]),
+                Coin::new(1, DENOM)
+            ),
+            Err(ContractError::NotEnoughFunds)
+        );
+
+        // a single signer doesn't make the deposit issued
+        mark_issued(deps.as_mut(), env.clone(), mock_info(SIGNERS[0], &[]), 1).unwrap();
+        assert!(!DEPOSITS.load(&deps.storage, 1).unwrap().issued);
+        assert_eq!(
+            REFUNDABLE_DEPOSITS.load(&deps.storage).unwrap(),
+            Uint128::new(2000)
+        );
+
+        mark_issued(deps.as_mut(), env.clone(), mock_info(SIGNERS[1], &[]), 1).unwrap();
+        assert!(DEPOSITS.load(&deps.storage, 1).unwrap().issued);
+        assert_eq!(
+            REFUNDABLE_DEPOSITS.load(&deps.storage).unwrap(),
+            Uint128::new(1000)
+        );
+
+        // additional confirmations don't change the refundable amount
+        mark_issued(deps.as_mut(), env.clone(), mock_info(SIGNERS[2], &[]), 1).unwrap();
+        assert_eq!(
+            REFUNDABLE_DEPOSITS.load(&deps.storage).unwrap(),
+            Uint128::new(1000)
+        );
+
+        assert_eq!(
+            release_funds(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(MULTISIG_CONTRACT, &[]),
+                Coin::new(1001, DENOM)
+            ),
+            Err(ContractError::NotEnoughFunds)
+        );
+        release_funds(
+            deps.as_mut(),
+            env,
+            mock_info(MULTISIG_CONTRACT, &[]),
+            Coin::new(1000, DENOM),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn updating_signers() {
+        let mut deps = helpers::init_contract();
+        let env = mock_env();
+        let new_signers = vec![String::from("signer4"), String::from("signer5")];
+
+        assert_eq!(
+            update_signers(
+                deps.as_mut(),
+                mock_info("requester", &[]),
+                new_signers.clone(),
+                1
+            ),
+            Err(ContractError::Admin(AdminError::NotAdmin {}))
+        );
+        assert_eq!(
+            update_signers(
+                deps.as_mut(),
+                mock_info(MULTISIG_CONTRACT, &[]),
+                new_signers.clone(),
+                3
+            ),
+            Err(ContractError::InvalidSignerThreshold {
+                threshold: 3,
+                signers: 2
+            })
+        );
+        assert_eq!(
+            update_signers(
+                deps.as_mut(),
+                mock_info(MULTISIG_CONTRACT, &[]),
+                new_signers.clone(),
+                0
+            ),
+            Err(ContractError::InvalidSignerThreshold {
+                threshold: 0,
+                signers: 2
+            })
+        );
+
+        let res = update_signers(
+            deps.as_mut(),
+            mock_info(MULTISIG_CONTRACT, &[]),
+            new_signers,
+            1,
+        )
+        .unwrap();
+        assert_eq!(res.events[0].ty, SIGNERS_UPDATED_EVENT_TYPE);
+
+        let cfg = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(
+            cfg.signers,
+            vec![Addr::unchecked("signer4"), Addr::unchecked("signer5")]
+        );
+        assert_eq!(cfg.signer_threshold, 1);
+
+        // the old signers can no longer mark deposits as issued
+        let data = DepositData::new(
+            String::from("Deposit info"),
+            String::from("Verification key"),
+            String::from("Encryption key"),
+        );
+        let info = mock_info("requester", &[Coin::new(1000, DENOM)]);
+        deposit_funds(deps.as_mut(), env.clone(), info, data).unwrap();
+        assert_eq!(
+            mark_issued(deps.as_mut(), env.clone(), mock_info(SIGNERS[0], &[]), 1),
+            Err(ContractError::NotSigner)
+        );
+        mark_issued(deps.as_mut(), env, mock_info("signer4", &[]), 1).unwrap();
+        assert!(DEPOSITS.load(&deps.storage, 1).unwrap().issued);
+    }
 }
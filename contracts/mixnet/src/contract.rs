@@ -9,6 +9,7 @@ use crate::delegations::queries::{
 };
 use crate::delegations::storage::delegations;
 use crate::error::ContractError;
+use crate::families::queries::{query_families_paged, query_family_members};
 use crate::gateways::queries::query_gateways_paged;
 use crate::gateways::queries::query_owns_gateway;
 use crate::interval::queries::query_current_epoch;
@@ -22,10 +23,14 @@ use crate::mixnet_contract_settings::queries::{
     query_contract_settings_params, query_contract_version, query_rewarding_validator_address,
 };
 use crate::mixnet_contract_settings::storage as mixnet_params_storage;
-use crate::mixnet_contract_settings::transactions::try_update_rewarding_validator_address;
+use crate::mixnet_contract_settings::transactions::{
+    try_accept_contract_ownership, try_propose_contract_owner,
+    try_update_rewarding_validator_address,
+};
 use crate::mixnodes::bonding_queries as mixnode_queries;
 use crate::mixnodes::bonding_queries::{
     query_checkpoints_for_mixnode, query_mixnode_at_height, query_mixnodes_paged,
+    query_pending_cost_params_change,
 };
 use crate::mixnodes::layer_queries::query_layer_distribution;
 use crate::rewards::queries::{
@@ -39,6 +44,7 @@ use cosmwasm_std::{
 use mixnet_contract_common::mixnode::DelegationEvent;
 use mixnet_contract_common::{
     ContractStateParams, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    DEFAULT_JAIL_COOLDOWN_EPOCHS, DEFAULT_MAXIMUM_MIXNODE_OPERATING_COST,
 };
 use time::OffsetDateTime;
 
@@ -53,6 +59,9 @@ pub const INITIAL_MIXNODE_ACTIVE_SET_SIZE: u32 = 100;
 
 pub const INITIAL_REWARD_POOL: u128 = 250_000_000_000_000;
 pub const INITIAL_ACTIVE_SET_WORK_FACTOR: u8 = 10;
+pub const INITIAL_COST_PARAMS_CHANGE_NOTICE_EPOCHS: u32 = 24;
+pub const INITIAL_MAXIMUM_MIXNODE_OPERATING_COST: Uint128 = DEFAULT_MAXIMUM_MIXNODE_OPERATING_COST;
+pub const INITIAL_JAIL_COOLDOWN_EPOCHS: u32 = DEFAULT_JAIL_COOLDOWN_EPOCHS;
 
 pub const DEFAULT_FIRST_INTERVAL_START: OffsetDateTime =
     time::macros::datetime!(2022-01-01 12:00 UTC);
@@ -70,6 +79,9 @@ fn default_initial_state(owner: Addr, rewarding_validator_address: Addr) -> Cont
             minimum_gateway_pledge: INITIAL_GATEWAY_PLEDGE,
             mixnode_rewarded_set_size: INITIAL_MIXNODE_REWARDED_SET_SIZE,
             mixnode_active_set_size: INITIAL_MIXNODE_ACTIVE_SET_SIZE,
+            cost_params_change_notice_epochs: INITIAL_COST_PARAMS_CHANGE_NOTICE_EPOCHS,
+            maximum_mixnode_operating_cost: INITIAL_MAXIMUM_MIXNODE_OPERATING_COST,
+            jail_cooldown_epochs: INITIAL_JAIL_COOLDOWN_EPOCHS,
         },
     }
 }
@@ -109,6 +121,10 @@ pub fn execute(
         ExecuteMsg::UpdateRewardingValidatorAddress { address } => {
             try_update_rewarding_validator_address(deps, info, address)
         }
+        ExecuteMsg::ProposeContractOwner { address } => {
+            try_propose_contract_owner(deps, info, address)
+        }
+        ExecuteMsg::AcceptContractOwnership {} => try_accept_contract_ownership(deps, info),
         ExecuteMsg::InitEpoch {} => try_init_epoch(info, deps.storage, env),
         ExecuteMsg::BondMixnode {
             mix_node,
@@ -123,22 +139,29 @@ pub fn execute(
         ExecuteMsg::UnbondMixnode {} => {
             crate::mixnodes::transactions::try_remove_mixnode(env, deps, info)
         }
+        ExecuteMsg::DecreaseMixnodePledge { amount } => {
+            crate::mixnodes::transactions::try_decrease_mixnode_pledge(env, deps, info, amount)
+        }
         ExecuteMsg::UpdateMixnodeConfig {
             profit_margin_percent,
+            operating_cost,
         } => crate::mixnodes::transactions::try_update_mixnode_config(
             deps,
             env,
             info,
             profit_margin_percent,
+            operating_cost,
         ),
         ExecuteMsg::UpdateMixnodeConfigOnBehalf {
             profit_margin_percent,
+            operating_cost,
             owner,
         } => crate::mixnodes::transactions::try_update_mixnode_config_on_behalf(
             deps,
             env,
             info,
             profit_margin_percent,
+            operating_cost,
             owner,
         ),
         ExecuteMsg::BondGateway {
@@ -173,6 +196,21 @@ pub fn execute(
                 mix_identity,
             )
         }
+        ExecuteMsg::PartiallyUndelegateFromMixnode {
+            mix_identity,
+            amount,
+        } => crate::delegations::transactions::try_partially_remove_delegation_from_mixnode(
+            deps,
+            env,
+            info,
+            mix_identity,
+            amount,
+        ),
+        ExecuteMsg::RedelegateFromMixnode { from, to, amount } => {
+            crate::delegations::transactions::try_redelegate_from_mixnode(
+                deps, env, info, from, to, amount,
+            )
+        }
         // ExecuteMsg::RewardNextMixDelegators {
         //     mix_identity,
         //     interval_id,
@@ -202,6 +240,28 @@ pub fn execute(
             mix_identity,
             delegate,
         ),
+        ExecuteMsg::PartiallyUndelegateFromMixnodeOnBehalf {
+            mix_identity,
+            amount,
+            delegate,
+        } => {
+            crate::delegations::transactions::try_partially_remove_delegation_from_mixnode_on_behalf(
+                deps,
+                env,
+                info,
+                mix_identity,
+                amount,
+                delegate,
+            )
+        }
+        ExecuteMsg::RedelegateFromMixnodeOnBehalf {
+            from,
+            to,
+            amount,
+            delegate,
+        } => crate::delegations::transactions::try_redelegate_from_mixnode_on_behalf(
+            deps, env, info, from, to, amount, delegate,
+        ),
         ExecuteMsg::BondMixnodeOnBehalf {
             mix_node,
             owner,
@@ -217,6 +277,11 @@ pub fn execute(
         ExecuteMsg::UnbondMixnodeOnBehalf { owner } => {
             crate::mixnodes::transactions::try_remove_mixnode_on_behalf(env, deps, info, owner)
         }
+        ExecuteMsg::DecreaseMixnodePledgeOnBehalf { amount, owner } => {
+            crate::mixnodes::transactions::try_decrease_mixnode_pledge_on_behalf(
+                env, deps, info, amount, owner,
+            )
+        }
         ExecuteMsg::BondGatewayOnBehalf {
             gateway,
             owner,
@@ -242,6 +307,23 @@ pub fn execute(
             rewarded_set,
             expected_active_set_size,
         ),
+        ExecuteMsg::CreateFamily { label } => {
+            crate::families::transactions::try_create_family(deps, info, label)
+        }
+        ExecuteMsg::JoinFamily {
+            family_head,
+            signature,
+        } => crate::families::transactions::try_join_family(deps, info, family_head, signature),
+        ExecuteMsg::AcceptFamilyMember { member } => {
+            crate::families::transactions::try_accept_family_member(deps, info, member)
+        }
+        ExecuteMsg::LeaveFamily {} => crate::families::transactions::try_leave_family(deps, info),
+        ExecuteMsg::JailMixnode { mix_identity } => {
+            crate::mixnodes::transactions::try_jail_mixnode(deps, info, mix_identity)
+        }
+        ExecuteMsg::UnjailMixnode {} => {
+            crate::mixnodes::transactions::try_unjail_mixnode(deps, info)
+        }
         ExecuteMsg::AdvanceCurrentEpoch {} => crate::interval::transactions::try_advance_epoch(
             env,
             deps.storage,
@@ -401,6 +483,15 @@ pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg) -> Result<QueryResponse, C
             mix_identity,
             height,
         } => to_binary(&query_mixnode_at_height(deps, mix_identity, height)?),
+        QueryMsg::GetPendingCostParamsChange { mix_identity } => {
+            to_binary(&query_pending_cost_params_change(deps, mix_identity)?)
+        }
+        QueryMsg::GetFamiliesPaged { start_after, limit } => {
+            to_binary(&query_families_paged(deps, start_after, limit)?)
+        }
+        QueryMsg::GetFamilyMembers { family_head } => {
+            to_binary(&query_family_members(deps, family_head)?)
+        }
     };
 
     Ok(query_res?)
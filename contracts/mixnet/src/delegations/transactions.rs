@@ -13,7 +13,7 @@ use cosmwasm_std::{
 };
 use mixnet_contract_common::events::{
     new_error_event, new_pending_delegation_event, new_pending_undelegation_event,
-    new_undelegation_event,
+    new_redelegation_event, new_undelegation_event,
 };
 use mixnet_contract_common::mixnode::{DelegationEvent, PendingUndelegate};
 use mixnet_contract_common::{Delegation, IdentityKey};
@@ -248,6 +248,41 @@ pub(crate) fn try_remove_delegation_from_mixnode_on_behalf(
     _try_remove_delegation_from_mixnode(deps, env, mix_identity, &delegate, Some(info.sender))
 }
 
+pub(crate) fn try_partially_remove_delegation_from_mixnode(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    mix_identity: IdentityKey,
+    amount: Coin,
+) -> Result<Response, ContractError> {
+    _try_partially_remove_delegation_from_mixnode(
+        deps,
+        env,
+        mix_identity,
+        info.sender.as_str(),
+        amount,
+        None,
+    )
+}
+
+pub(crate) fn try_partially_remove_delegation_from_mixnode_on_behalf(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    mix_identity: IdentityKey,
+    amount: Coin,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    _try_partially_remove_delegation_from_mixnode(
+        deps,
+        env,
+        mix_identity,
+        &delegate,
+        amount,
+        Some(info.sender),
+    )
+}
+
 pub struct ReconcileUndelegateResponse {
     bank_msg: Option<BankMsg>,
     wasm_msg: Option<WasmMsg>,
@@ -316,6 +351,18 @@ pub(crate) fn try_reconcile_undelegation(
 
     let mut total_delegation = Uint128::zero();
 
+    // if only part of the delegation is withdrawn, the rest of it is going to be recreated and
+    // the rewards are considered claimed. To not count any of them twice, we use the most recent
+    // checkpoint of the node if it happened after the undelegation request.
+    let claim_height = if pending_undelegate.is_partial() {
+        latest_checkpoint_height(storage, &pending_undelegate.mix_identity())
+            .map_or(pending_undelegate.block_height(), |height| {
+                height.max(pending_undelegate.block_height())
+            })
+    } else {
+        pending_undelegate.block_height()
+    };
+
     // debug_with_visibility(api, "Reducing accumulated rewards");
 
     {
@@ -330,7 +377,7 @@ pub(crate) fn try_reconcile_undelegation(
                 storage,
                 &pending_undelegate.mix_identity(),
                 &bond,
-                pending_undelegate.block_height(),
+                claim_height,
             )?;
         }
     }
@@ -379,10 +426,36 @@ pub(crate) fn try_reconcile_undelegation(
         },
     )?;
 
-    let total_funds = total_delegation + reward;
+    let available_funds = total_delegation + reward;
+    let total_funds = pending_undelegate
+        .amount()
+        .map_or(available_funds, |amount| amount.min(available_funds));
+
+    // whatever was not withdrawn stays delegated to the node
+    let remaining = available_funds - total_funds;
+    if !remaining.is_zero() {
+        crate::rewards::storage::DELEGATOR_REWARD_CLAIMED_HEIGHT.save(
+            storage,
+            (
+                pending_undelegate.proxy_storage_key(),
+                pending_undelegate.mix_identity(),
+            ),
+            &claim_height,
+        )?;
+        try_reconcile_delegation(
+            storage,
+            Delegation::new(
+                pending_undelegate.delegate(),
+                pending_undelegate.mix_identity(),
+                Coin::new(remaining.u128(), DENOM),
+                claim_height,
+                pending_undelegate.proxy(),
+            ),
+        )?;
+    }
 
     // don't add a bank message if it would have resulted in attempting to send 0 tokens
-    let bank_msg = if total_delegation != Uint128::zero() {
+    let bank_msg = if total_funds != Uint128::zero() {
         Some(BankMsg::Send {
             to_address: pending_undelegate
                 .proxy()
@@ -402,6 +475,7 @@ pub(crate) fn try_reconcile_undelegation(
             owner: pending_undelegate.delegate().as_str().to_string(),
             mix_identity: pending_undelegate.mix_identity(),
             amount: Coin::new(total_funds.u128(), DENOM),
+            partial: !remaining.is_zero(),
         });
 
         wasm_msg = Some(wasm_execute(proxy, &msg, vec![one_ucoin()])?);
@@ -431,12 +505,76 @@ pub(crate) fn _try_remove_delegation_from_mixnode(
     proxy: Option<Addr>,
 ) -> Result<Response, ContractError> {
     let delegate = deps.api.addr_validate(delegate)?;
+    queue_undelegation(deps, env, mix_identity, delegate, proxy, None)
+}
+
+pub(crate) fn _try_partially_remove_delegation_from_mixnode(
+    deps: DepsMut<'_>,
+    env: Env,
+    mix_identity: IdentityKey,
+    delegate: &str,
+    amount: Coin,
+    proxy: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(delegate)?;
+    let amount = validate_delegation_stake(vec![amount])?;
+
+    let key = mixnet_contract_common::delegation::generate_storage_key(&delegate, proxy.as_ref());
+    let delegated = storage::delegations()
+        .prefix((mix_identity.clone(), key.clone()))
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|record| record.ok())
+        .fold(Uint128::zero(), |acc, (_, delegation)| {
+            acc + delegation.amount.amount
+        });
+
+    if delegated.is_zero() {
+        return Err(ContractError::NoMixnodeDelegationFound {
+            identity: mix_identity,
+            address: delegate.to_string(),
+        });
+    }
+
+    let reward = crate::rewards::transactions::calculate_delegator_reward(
+        deps.storage,
+        deps.api,
+        key,
+        &mix_identity,
+    )?;
+
+    let available = delegated + reward;
+    if amount.amount > available {
+        return Err(ContractError::InsufficientDelegation {
+            identity: mix_identity,
+            requested: amount.amount.u128(),
+            available: available.u128(),
+        });
+    }
+
+    queue_undelegation(
+        deps,
+        env,
+        mix_identity,
+        delegate,
+        proxy,
+        Some(amount.amount),
+    )
+}
 
+fn queue_undelegation(
+    deps: DepsMut<'_>,
+    env: Env,
+    mix_identity: IdentityKey,
+    delegate: Addr,
+    proxy: Option<Addr>,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
     let event = PendingUndelegate::new(
         mix_identity.to_string(),
         delegate.clone(),
         proxy.clone(),
         env.block.height,
+        amount,
     );
 
     if storage::PENDING_DELEGATION_EVENTS
@@ -460,9 +598,232 @@ pub(crate) fn _try_remove_delegation_from_mixnode(
         &delegate,
         &proxy,
         &mix_identity,
+        amount,
     )))
 }
 
+pub(crate) fn try_redelegate_from_mixnode(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    from: IdentityKey,
+    to: IdentityKey,
+    amount: Coin,
+) -> Result<Response, ContractError> {
+    _try_redelegate_from_mixnode(
+        deps,
+        env.block.height,
+        &from,
+        &to,
+        info.sender.as_str(),
+        amount,
+        None,
+    )
+}
+
+pub(crate) fn try_redelegate_from_mixnode_on_behalf(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    from: IdentityKey,
+    to: IdentityKey,
+    amount: Coin,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    _try_redelegate_from_mixnode(
+        deps,
+        env.block.height,
+        &from,
+        &to,
+        &delegate,
+        amount,
+        Some(info.sender),
+    )
+}
+
+// Unlike undelegating and delegating again, redelegation is applied immediately rather than
+// through the pending delegation events, so that the stake keeps earning rewards throughout.
+// Any rewards accrued on the source delegation are claimed first and can be redelegated alongside it.
+// Whatever is not moved stays delegated to the source mixnode.
+pub(crate) fn _try_redelegate_from_mixnode(
+    deps: DepsMut<'_>,
+    block_height: u64,
+    from: &str,
+    to: &str,
+    delegate: &str,
+    amount: Coin,
+    proxy: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(delegate)?;
+    let amount = validate_delegation_stake(vec![amount])?;
+
+    if from == to {
+        return Err(ContractError::RedelegationToSameMixnode {
+            identity: from.to_string(),
+        });
+    }
+
+    // check if the target node actually exists
+    if mixnodes_storage::mixnodes()
+        .may_load(deps.storage, to)?
+        .is_none()
+    {
+        return Err(ContractError::MixNodeBondNotFound {
+            identity: to.to_string(),
+        });
+    }
+
+    let key = mixnet_contract_common::delegation::generate_storage_key(&delegate, proxy.as_ref());
+    let delegation_map = storage::delegations();
+
+    // Might want to introduce paging here
+    let source_delegations = delegation_map
+        .prefix((from.to_string(), key.clone()))
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|record| record.ok())
+        .collect::<Vec<_>>();
+
+    if source_delegations.is_empty() {
+        return Err(ContractError::NoMixnodeDelegationFound {
+            identity: from.to_string(),
+            address: delegate.to_string(),
+        });
+    }
+
+    // a pending undelegation from the same node could otherwise find less than it expects
+    // by the time it's reconciled
+    let pending_undelegation = storage::PENDING_DELEGATION_EVENTS
+        .sub_prefix(key.clone())
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|record| record.ok())
+        .find(|(_, event)| match event {
+            DelegationEvent::Delegate(_) => false,
+            DelegationEvent::Undelegate(undelegate) => undelegate.mix_identity() == from,
+        });
+    if let Some(((pending_height, _), _)) = pending_undelegation {
+        return Err(ContractError::DelegationEventAlreadyPending {
+            block_height: pending_height,
+            identity: from.to_string(),
+            kind: "undelegation".to_string(),
+        });
+    }
+
+    let reward = crate::rewards::transactions::calculate_delegator_reward(
+        deps.storage,
+        deps.api,
+        key.clone(),
+        from,
+    )?;
+
+    let delegated = source_delegations
+        .iter()
+        .fold(Uint128::zero(), |acc, (_, delegation)| {
+            acc + delegation.amount.amount
+        });
+    let available = delegated + reward;
+    if amount.amount > available {
+        return Err(ContractError::InsufficientDelegation {
+            identity: from.to_string(),
+            requested: amount.amount.u128(),
+            available: available.u128(),
+        });
+    }
+
+    // the rewards are claimed up to and including the current height, which, as the claimed
+    // height bound is exclusive, means both the stake left behind and the moved stake must be
+    // recorded at the following height. This way the moved stake is never counted on both nodes
+    // and only starts earning on the target node from its next checkpoint onwards.
+    let restake_height = block_height + 1;
+
+    // the claimed reward is no longer owed by the source node
+    if let Some(mut bond) = mixnodes_storage::mixnodes().may_load(deps.storage, from)? {
+        bond.accumulated_rewards = Some(bond.accumulated_rewards().saturating_sub(reward));
+        mixnodes_storage::mixnodes().save(deps.storage, from, &bond, block_height)?;
+    }
+
+    for (h, delegation) in source_delegations {
+        delegation_map.replace(
+            deps.storage,
+            (from.to_string(), key.clone(), h),
+            None,
+            Some(&delegation),
+        )?;
+    }
+
+    crate::rewards::storage::DELEGATOR_REWARD_CLAIMED_HEIGHT.save(
+        deps.storage,
+        (key, from.to_string()),
+        &block_height,
+    )?;
+
+    mixnodes_storage::TOTAL_DELEGATION.update::<_, ContractError>(
+        deps.storage,
+        from,
+        |total_node_delegation| {
+            // the entry MUST exist as the delegation we've just removed was counted towards it
+            let total_node_delegation = total_node_delegation.unwrap_or_default();
+            total_node_delegation.checked_sub(delegated).map_err(|_| {
+                ContractError::TotalDelegationSubOverflow {
+                    mix_identity: from.to_string(),
+                    total_node_delegation: total_node_delegation.u128(),
+                    to_subtract: delegated.u128(),
+                }
+            })
+        },
+    )?;
+
+    let remaining = available - amount.amount;
+    if !remaining.is_zero() {
+        try_reconcile_delegation(
+            deps.storage,
+            Delegation::new(
+                delegate.clone(),
+                from.to_string(),
+                Coin::new(remaining.u128(), DENOM),
+                restake_height,
+                proxy.clone(),
+            ),
+        )?;
+    }
+
+    try_reconcile_delegation(
+        deps.storage,
+        Delegation::new(
+            delegate.clone(),
+            to.to_string(),
+            amount.clone(),
+            restake_height,
+            proxy.clone(),
+        ),
+    )?;
+
+    let mut response = Response::new();
+
+    if let Some(proxy) = &proxy {
+        // let the vesting contract move the tracked delegation alongside. The one_ucoin the proxy
+        // had to send in order to execute the contract to contract transaction is returned with it
+        let msg = VestingContractExecuteMsg::TrackRedelegation {
+            owner: delegate.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            amount: amount.clone(),
+        };
+        response = response.add_message(wasm_execute(proxy, &msg, vec![one_ucoin()])?);
+    }
+
+    Ok(response.add_event(new_redelegation_event(&delegate, &proxy, &amount, from, to)))
+}
+
+// height of the most recent checkpoint (i.e. rewarding) of the node, if any
+fn latest_checkpoint_height(storage: &dyn Storage, mix_identity: &str) -> Option<u64> {
+    mixnodes_storage::mixnodes()
+        .changelog()
+        .prefix(mix_identity)
+        .keys(storage, None, None, Order::Descending)
+        .filter_map(|v| v.ok())
+        .next()
+}
+
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::coins;
@@ -1294,6 +1655,605 @@ mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod partially_removing_mix_stake_delegation {
+        use cosmwasm_std::coin;
+        use cosmwasm_std::testing::mock_env;
+        use cosmwasm_std::testing::mock_info;
+        use cosmwasm_std::Addr;
+
+        use super::*;
+
+        fn setup_delegation(
+            mut deps: DepsMut<'_>,
+            delegation_owner: &str,
+            amount: u128,
+        ) -> IdentityKey {
+            let identity = test_helpers::add_mixnode(
+                "alice",
+                tests::fixtures::good_mixnode_pledge(),
+                deps.branch(),
+            );
+            try_delegate_to_mixnode(
+                deps.branch(),
+                mock_env(),
+                mock_info(delegation_owner, &coins(amount, DENOM)),
+                identity.clone(),
+            )
+            .unwrap();
+            _try_reconcile_all_delegation_events(deps.storage, deps.api).unwrap();
+            identity
+        }
+
+        fn delegations_of(
+            storage: &dyn Storage,
+            identity: &str,
+            delegation_owner: &Addr,
+        ) -> Vec<Uint128> {
+            storage::delegations()
+                .prefix((identity.to_string(), delegation_owner.as_bytes().to_vec()))
+                .range(storage, None, None, Order::Ascending)
+                .filter_map(|record| record.ok())
+                .map(|(_, delegation)| delegation.amount.amount)
+                .collect()
+        }
+
+        #[test]
+        fn fails_if_delegation_never_existed() {
+            let mut deps = test_helpers::init_contract();
+            let identity = setup_delegation(deps.as_mut(), "sender", 1000);
+
+            assert_eq!(
+                Err(ContractError::NoMixnodeDelegationFound {
+                    identity: identity.clone(),
+                    address: "someone-else".to_string(),
+                }),
+                try_partially_remove_delegation_from_mixnode(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("someone-else", &[]),
+                    identity,
+                    coin(100, DENOM),
+                )
+            );
+        }
+
+        #[test]
+        fn fails_if_amount_exceeds_delegation() {
+            let mut deps = test_helpers::init_contract();
+            let identity = setup_delegation(deps.as_mut(), "sender", 1000);
+
+            assert_eq!(
+                Err(ContractError::InsufficientDelegation {
+                    identity: identity.clone(),
+                    requested: 1001,
+                    available: 1000,
+                }),
+                try_partially_remove_delegation_from_mixnode(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("sender", &[]),
+                    identity,
+                    coin(1001, DENOM),
+                )
+            );
+        }
+
+        #[test]
+        fn withdraws_requested_amount_and_keeps_the_rest() {
+            let mut deps = test_helpers::init_contract();
+            let identity = setup_delegation(deps.as_mut(), "sender", 1000);
+            let delegation_owner = Addr::unchecked("sender");
+
+            let mut env = mock_env();
+            env.block.height += 1;
+
+            let res = try_partially_remove_delegation_from_mixnode(
+                deps.as_mut(),
+                env,
+                mock_info(delegation_owner.as_str(), &[]),
+                identity.clone(),
+                coin(300, DENOM),
+            )
+            .unwrap();
+            assert_eq!(
+                res,
+                Response::new().add_event(new_pending_undelegation_event(
+                    &delegation_owner,
+                    &None,
+                    &identity,
+                    Some(Uint128::new(300)),
+                ))
+            );
+
+            // nothing changes until the events are reconciled
+            assert_eq!(
+                vec![Uint128::new(1000)],
+                delegations_of(&deps.storage, &identity, &delegation_owner)
+            );
+
+            let res = _try_reconcile_all_delegation_events(&mut deps.storage, &deps.api).unwrap();
+            assert_eq!(
+                vec![cosmwasm_std::SubMsg::new(BankMsg::Send {
+                    to_address: delegation_owner.to_string(),
+                    amount: coins(300, DENOM),
+                })],
+                res.messages
+            );
+
+            assert_eq!(
+                vec![Uint128::new(700)],
+                delegations_of(&deps.storage, &identity, &delegation_owner)
+            );
+            assert_eq!(
+                Uint128::new(700),
+                mixnodes_storage::TOTAL_DELEGATION
+                    .load(&deps.storage, &identity)
+                    .unwrap()
+            );
+        }
+
+        #[test]
+        fn withdrawing_everything_removes_the_delegation() {
+            let mut deps = test_helpers::init_contract();
+            let identity = setup_delegation(deps.as_mut(), "sender", 1000);
+            let delegation_owner = Addr::unchecked("sender");
+
+            let mut env = mock_env();
+            env.block.height += 1;
+
+            try_partially_remove_delegation_from_mixnode(
+                deps.as_mut(),
+                env,
+                mock_info(delegation_owner.as_str(), &[]),
+                identity.clone(),
+                coin(1000, DENOM),
+            )
+            .unwrap();
+            _try_reconcile_all_delegation_events(&mut deps.storage, &deps.api).unwrap();
+
+            assert!(delegations_of(&deps.storage, &identity, &delegation_owner).is_empty());
+            assert_eq!(
+                Uint128::zero(),
+                mixnodes_storage::TOTAL_DELEGATION
+                    .load(&deps.storage, &identity)
+                    .unwrap()
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod redelegating_mix_stake {
+        use cosmwasm_std::coin;
+        use cosmwasm_std::testing::mock_env;
+        use cosmwasm_std::testing::mock_info;
+        use cosmwasm_std::Addr;
+        use cosmwasm_std::CosmosMsg;
+
+        use super::*;
+
+        fn setup_delegation(
+            mut deps: DepsMut<'_>,
+            delegation_owner: &str,
+            amount: u128,
+        ) -> (IdentityKey, IdentityKey) {
+            let from = test_helpers::add_mixnode(
+                "alice",
+                tests::fixtures::good_mixnode_pledge(),
+                deps.branch(),
+            );
+            let to = test_helpers::add_mixnode(
+                "bob",
+                tests::fixtures::good_mixnode_pledge(),
+                deps.branch(),
+            );
+            try_delegate_to_mixnode(
+                deps.branch(),
+                mock_env(),
+                mock_info(delegation_owner, &coins(amount, DENOM)),
+                from.clone(),
+            )
+            .unwrap();
+            _try_reconcile_all_delegation_events(deps.storage, deps.api).unwrap();
+            (from, to)
+        }
+
+        #[test]
+        fn fails_if_target_is_the_same_node() {
+            let mut deps = test_helpers::init_contract();
+            let (from, _) = setup_delegation(deps.as_mut(), "sender", 1000);
+
+            assert_eq!(
+                Err(ContractError::RedelegationToSameMixnode {
+                    identity: from.clone()
+                }),
+                try_redelegate_from_mixnode(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("sender", &[]),
+                    from.clone(),
+                    from,
+                    coin(100, DENOM),
+                )
+            );
+        }
+
+        #[test]
+        fn fails_if_target_node_doesnt_exist() {
+            let mut deps = test_helpers::init_contract();
+            let (from, _) = setup_delegation(deps.as_mut(), "sender", 1000);
+
+            assert_eq!(
+                Err(ContractError::MixNodeBondNotFound {
+                    identity: "nonexistent".to_string()
+                }),
+                try_redelegate_from_mixnode(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("sender", &[]),
+                    from,
+                    "nonexistent".to_string(),
+                    coin(100, DENOM),
+                )
+            );
+        }
+
+        #[test]
+        fn fails_if_delegation_never_existed() {
+            let mut deps = test_helpers::init_contract();
+            let (from, to) = setup_delegation(deps.as_mut(), "sender", 1000);
+
+            assert_eq!(
+                Err(ContractError::NoMixnodeDelegationFound {
+                    identity: from.clone(),
+                    address: "someone-else".to_string(),
+                }),
+                try_redelegate_from_mixnode(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("someone-else", &[]),
+                    from,
+                    to,
+                    coin(100, DENOM),
+                )
+            );
+        }
+
+        #[test]
+        fn fails_if_amount_exceeds_delegation() {
+            let mut deps = test_helpers::init_contract();
+            let (from, to) = setup_delegation(deps.as_mut(), "sender", 1000);
+
+            assert_eq!(
+                Err(ContractError::InsufficientDelegation {
+                    identity: from.clone(),
+                    requested: 1001,
+                    available: 1000,
+                }),
+                try_redelegate_from_mixnode(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("sender", &[]),
+                    from,
+                    to,
+                    coin(1001, DENOM),
+                )
+            );
+        }
+
+        #[test]
+        fn partial_redelegation_moves_stake_immediately() {
+            let mut deps = test_helpers::init_contract();
+            let (from, to) = setup_delegation(deps.as_mut(), "sender", 1000);
+            let delegation_owner = Addr::unchecked("sender");
+
+            let mut env = mock_env();
+            env.block.height += 1;
+
+            let res = try_redelegate_from_mixnode(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(delegation_owner.as_str(), &[]),
+                from.clone(),
+                to.clone(),
+                coin(400, DENOM),
+            )
+            .unwrap();
+
+            assert_eq!(
+                res,
+                Response::new().add_event(new_redelegation_event(
+                    &delegation_owner,
+                    &None,
+                    &coin(400, DENOM),
+                    &from,
+                    &to,
+                ))
+            );
+
+            // nothing is left pending, the stake is moved within this transaction
+            assert!(storage::PENDING_DELEGATION_EVENTS
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .next()
+                .is_none());
+
+            assert_eq!(
+                Uint128::new(600),
+                mixnodes_storage::TOTAL_DELEGATION
+                    .load(&deps.storage, &from)
+                    .unwrap()
+            );
+            assert_eq!(
+                Uint128::new(400),
+                mixnodes_storage::TOTAL_DELEGATION
+                    .load(&deps.storage, &to)
+                    .unwrap()
+            );
+
+            // both the remaining and the moved stake only count from the block after the claim
+            assert!(test_helpers::read_delegation(
+                &deps.storage,
+                &from,
+                delegation_owner.as_bytes(),
+                mock_env().block.height
+            )
+            .is_none());
+            assert_eq!(
+                coin(600, DENOM),
+                test_helpers::read_delegation(
+                    &deps.storage,
+                    &from,
+                    delegation_owner.as_bytes(),
+                    env.block.height + 1
+                )
+                .unwrap()
+                .amount
+            );
+            assert_eq!(
+                coin(400, DENOM),
+                test_helpers::read_delegation(
+                    &deps.storage,
+                    &to,
+                    delegation_owner.as_bytes(),
+                    env.block.height + 1
+                )
+                .unwrap()
+                .amount
+            );
+        }
+
+        #[test]
+        fn fails_if_source_delegation_is_already_being_withdrawn() {
+            let mut deps = test_helpers::init_contract();
+            let (from, to) = setup_delegation(deps.as_mut(), "sender", 1000);
+
+            let mut env = mock_env();
+            env.block.height += 1;
+            try_remove_delegation_from_mixnode(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("sender", &[]),
+                from.clone(),
+            )
+            .unwrap();
+
+            env.block.height += 1;
+            assert_eq!(
+                Err(ContractError::DelegationEventAlreadyPending {
+                    block_height: mock_env().block.height + 1,
+                    identity: from.clone(),
+                    kind: "undelegation".to_string(),
+                }),
+                try_redelegate_from_mixnode(
+                    deps.as_mut(),
+                    env,
+                    mock_info("sender", &[]),
+                    from,
+                    to,
+                    coin(100, DENOM),
+                )
+            );
+        }
+
+        #[test]
+        fn redelegation_on_behalf_is_tracked_by_proxy_in_the_same_transaction() {
+            let mut deps = test_helpers::init_contract();
+            let from = test_helpers::add_mixnode(
+                "alice",
+                tests::fixtures::good_mixnode_pledge(),
+                deps.as_mut(),
+            );
+            let to = test_helpers::add_mixnode(
+                "bob",
+                tests::fixtures::good_mixnode_pledge(),
+                deps.as_mut(),
+            );
+            let delegation_owner = Addr::unchecked("sender");
+            let proxy = Addr::unchecked("proxy");
+
+            try_delegate_to_mixnode_on_behalf(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(proxy.as_str(), &coins(1000, DENOM)),
+                from.clone(),
+                delegation_owner.to_string(),
+            )
+            .unwrap();
+            _try_reconcile_all_delegation_events(&mut deps.storage, &deps.api).unwrap();
+
+            let mut env = mock_env();
+            env.block.height += 1;
+            let res = try_redelegate_from_mixnode_on_behalf(
+                deps.as_mut(),
+                env,
+                mock_info(proxy.as_str(), &[one_ucoin()]),
+                from.clone(),
+                to.clone(),
+                coin(400, DENOM),
+                delegation_owner.to_string(),
+            )
+            .unwrap();
+
+            // the one_ucoin is returned alongside the tracking request
+            let expected_msg = wasm_execute(
+                &proxy,
+                &VestingContractExecuteMsg::TrackRedelegation {
+                    owner: delegation_owner.to_string(),
+                    from,
+                    to,
+                    amount: coin(400, DENOM),
+                },
+                vec![one_ucoin()],
+            )
+            .unwrap();
+            assert_eq!(
+                vec![CosmosMsg::Wasm(expected_msg)],
+                res.messages
+                    .into_iter()
+                    .map(|sub_msg| sub_msg.msg)
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        // checkpoints and rewards both nodes and moves onto the next epoch
+        fn reward_epoch(mut deps: DepsMut<'_>, env: &mut Env, nodes: &[&IdentityKey]) {
+            let rewarding_validator =
+                crate::mixnet_contract_settings::storage::rewarding_validator_address(deps.storage)
+                    .unwrap();
+            let info = mock_info(&rewarding_validator, &[]);
+
+            env.block.height += 1000;
+            env.block.time = env.block.time.plus_seconds(3601);
+            crate::mixnodes::transactions::try_checkpoint_mixnodes(
+                deps.storage,
+                env.block.height,
+                info.clone(),
+            )
+            .unwrap();
+            for node in nodes {
+                crate::rewards::transactions::try_reward_mixnode(
+                    deps.branch(),
+                    env.clone(),
+                    info.clone(),
+                    node.to_string(),
+                    tests::fixtures::node_reward_params_fixture(100),
+                )
+                .unwrap();
+            }
+            crate::interval::transactions::try_advance_epoch(
+                env.clone(),
+                deps.storage,
+                rewarding_validator,
+            )
+            .unwrap();
+        }
+
+        #[test]
+        fn redelegated_stake_is_rewarded_exactly_once() {
+            let mut deps = test_helpers::init_contract();
+            let mut env = mock_env();
+            let epoch =
+                crate::interval::transactions::init_epoch(&mut deps.storage, env.clone()).unwrap();
+            crate::interval::storage::save_epoch_reward_params(epoch.id(), &mut deps.storage)
+                .unwrap();
+            let (from, to) = setup_delegation(deps.as_mut(), "sender", 800_000_000_000);
+            let key = b"sender".to_vec();
+            let reward = |deps: cosmwasm_std::Deps<'_>, mix_identity: &str| {
+                crate::rewards::transactions::calculate_delegator_reward(
+                    deps.storage,
+                    deps.api,
+                    key.clone(),
+                    mix_identity,
+                )
+                .unwrap()
+            };
+
+            reward_epoch(deps.as_mut(), &mut env, &[&from, &to]);
+            reward_epoch(deps.as_mut(), &mut env, &[&from, &to]);
+            let accrued = reward(deps.as_ref(), &from);
+            assert!(!accrued.is_zero());
+
+            env.block.height += 1;
+            try_redelegate_from_mixnode(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("sender", &[]),
+                from.clone(),
+                to.clone(),
+                coin(400_000_000_000, DENOM),
+            )
+            .unwrap();
+
+            // the accrued reward got claimed alongside the redelegation and is not owed again
+            assert_eq!(
+                Uint128::new(800_000_000_000) + accrued,
+                mixnodes_storage::TOTAL_DELEGATION
+                    .load(&deps.storage, &from)
+                    .unwrap()
+                    + mixnodes_storage::TOTAL_DELEGATION
+                        .load(&deps.storage, &to)
+                        .unwrap()
+            );
+            assert!(reward(deps.as_ref(), &from).is_zero());
+            assert!(reward(deps.as_ref(), &to).is_zero());
+
+            // but both the remaining and the moved stake keep on earning afterwards
+            reward_epoch(deps.as_mut(), &mut env, &[&from, &to]);
+            reward_epoch(deps.as_mut(), &mut env, &[&from, &to]);
+            assert!(!reward(deps.as_ref(), &from).is_zero());
+            assert!(!reward(deps.as_ref(), &to).is_zero());
+        }
+
+        #[test]
+        fn full_redelegation_removes_source_delegation() {
+            let mut deps = test_helpers::init_contract();
+            let (from, to) = setup_delegation(deps.as_mut(), "sender", 1000);
+            let delegation_owner = Addr::unchecked("sender");
+
+            let mut env = mock_env();
+            env.block.height += 1;
+
+            try_redelegate_from_mixnode(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(delegation_owner.as_str(), &[]),
+                from.clone(),
+                to.clone(),
+                coin(1000, DENOM),
+            )
+            .unwrap();
+
+            assert_eq!(
+                Uint128::zero(),
+                mixnodes_storage::TOTAL_DELEGATION
+                    .load(&deps.storage, &from)
+                    .unwrap()
+            );
+            assert_eq!(
+                Uint128::new(1000),
+                mixnodes_storage::TOTAL_DELEGATION
+                    .load(&deps.storage, &to)
+                    .unwrap()
+            );
+            assert!(storage::delegations()
+                .prefix((from, delegation_owner.as_bytes().to_vec()))
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .next()
+                .is_none());
+            assert_eq!(
+                coin(1000, DENOM),
+                test_helpers::read_delegation(
+                    &deps.storage,
+                    &to,
+                    delegation_owner.as_bytes(),
+                    env.block.height + 1
+                )
+                .unwrap()
+                .amount
+            );
+        }
+    }
+
     // #[cfg(test)]
     // mod multi_delegations {
     //     use super::*;
@@ -21,6 +21,16 @@ pub enum ContractError {
     #[error("MIXNET ({}): Mixnode ({identity}) does not exist", line!())]
     MixNodeBondNotFound { identity: IdentityKey },
 
+    #[error("MIXNET ({}): Decreasing the pledge by {decrease} would put it below the minimum of {minimum} (current pledge: {current})", line!())]
+    PledgeBelowMinimum {
+        current: u128,
+        decrease: u128,
+        minimum: u128,
+    },
+
+    #[error("MIXNET ({}): The pledge decrease can't be zero", line!())]
+    EmptyPledgeDecrease,
+
     #[error("MIXNET ({}): Not enough funds sent for gateway bond. (received {received}, minimum {minimum})", line!())]
     InsufficientGatewayBond { received: u128, minimum: u128 },
 
@@ -72,6 +82,16 @@ pub enum ContractError {
         address: String,
     },
 
+    #[error("MIXNET ({}): Can't redelegate to the same mixnode ({identity}) the delegation is already on", line!())]
+    RedelegationToSameMixnode { identity: IdentityKey },
+
+    #[error("MIXNET ({}): Attempted to move {requested} of the delegation on mixnode {identity}, but only {available} is available", line!())]
+    InsufficientDelegation {
+        identity: IdentityKey,
+        requested: u128,
+        available: u128,
+    },
+
     #[error("MIXNET ({}): We tried to remove more funds then are available in the Reward pool. Wanted to remove {to_remove}, but have only {reward_pool}", line!())]
     OutOfFunds { to_remove: u128, reward_pool: u128 },
 
@@ -102,6 +122,12 @@ pub enum ContractError {
     #[error("MIXNET ({}): Profit margin percent needs to be an integer in range [0, 100], received {0}", line!())]
     InvalidProfitMarginPercent(u8),
 
+    #[error("MIXNET ({}): Operating cost of {cost} exceeds the maximum of {maximum}", line!())]
+    OperatingCostTooHigh { cost: u128, maximum: u128 },
+
+    #[error("MIXNET ({}): Maximum operating cost of {0} is too large to be used for rewarding", line!())]
+    InvalidMaximumOperatingCost(u128),
+
     #[error("MIXNET ({}): Rewarded set height not set, was rewarding set determined?", line!())]
     RewardSetHeightMapEmpty,
 
@@ -179,4 +205,35 @@ pub enum ContractError {
         last_update_time: u64,
         current_block_time: u64,
     },
+
+    #[error("Mixnode {identity} is not jailed")]
+    MixnodeNotJailed { identity: String },
+
+    #[error("Mixnode {identity} can only be unjailed from epoch {unjail_epoch_id}, while the current epoch is {current_epoch_id}")]
+    UnjailTooSoon {
+        identity: String,
+        current_epoch_id: u32,
+        unjail_epoch_id: u32,
+    },
+
+    #[error("Mixnode {identity} is jailed and can't be put in the rewarded set")]
+    JailedMixnodeInRewardedSet { identity: String },
+
+    #[error("Mixnode {identity} already belongs to (or has requested to join) the family headed by {family_head}")]
+    AlreadyInFamily {
+        identity: String,
+        family_head: String,
+    },
+
+    #[error("Family headed by {family_head} does not exist")]
+    FamilyNotFound { family_head: String },
+
+    #[error("Mixnode {member} has not requested to join the family headed by {family_head}")]
+    NoPendingFamilyJoinRequest { member: String, family_head: String },
+
+    #[error("Mixnode {identity} does not belong to any family")]
+    NotInFamily { identity: String },
+
+    #[error("There is no pending transfer of the contract ownership")]
+    NoPendingOwnershipTransfer,
 }
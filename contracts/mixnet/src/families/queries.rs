@@ -0,0 +1,100 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::storage;
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+use mixnet_contract_common::{Family, FamilyMembersResponse, IdentityKey, PagedFamiliesResponse};
+
+pub fn query_families_paged(
+    deps: Deps<'_>,
+    start_after: Option<IdentityKey>,
+    limit: Option<u32>,
+) -> StdResult<PagedFamiliesResponse> {
+    let limit = limit
+        .unwrap_or(storage::FAMILIES_PAGE_DEFAULT_LIMIT)
+        .min(storage::FAMILIES_PAGE_MAX_LIMIT) as usize;
+
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let families = storage::FAMILIES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<Family>>>()?;
+
+    let start_next_after = families.last().map(|family| family.head.clone());
+
+    Ok(PagedFamiliesResponse::new(
+        families,
+        limit,
+        start_next_after,
+    ))
+}
+
+pub fn query_family_members(
+    deps: Deps<'_>,
+    family_head: IdentityKey,
+) -> StdResult<FamilyMembersResponse> {
+    let family = storage::FAMILIES.may_load(deps.storage, &family_head)?;
+
+    let mut members = Vec::new();
+    let mut pending_members = Vec::new();
+    for record in storage::family_members()
+        .idx
+        .head
+        .prefix(family_head)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (member, membership) = record?;
+        if membership.pending {
+            pending_members.push(member)
+        } else {
+            members.push(member)
+        }
+    }
+
+    Ok(FamilyMembersResponse {
+        family,
+        members,
+        pending_members,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::support::tests::test_helpers;
+
+    #[test]
+    fn families_paged_retrieval_obeys_limits() {
+        let mut deps = test_helpers::init_contract();
+        for i in 0..100 {
+            let head = format!("head{:03}", i);
+            storage::FAMILIES
+                .save(
+                    deps.as_mut().storage,
+                    &head,
+                    &Family::new(head.clone(), "label".to_string()),
+                )
+                .unwrap();
+        }
+
+        let page = query_families_paged(deps.as_ref(), None, Some(10)).unwrap();
+        assert_eq!(10, page.families.len());
+        assert_eq!(Some("head009".to_string()), page.start_next_after);
+
+        let page = query_families_paged(deps.as_ref(), page.start_next_after, None).unwrap();
+        assert_eq!(
+            storage::FAMILIES_PAGE_DEFAULT_LIMIT as usize,
+            page.families.len()
+        );
+        assert_eq!("head010", page.families[0].head);
+
+        let page = query_families_paged(deps.as_ref(), None, Some(1000)).unwrap();
+        assert_eq!(
+            storage::FAMILIES_PAGE_MAX_LIMIT as usize,
+            page.families.len()
+        );
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use cosmwasm_std::{StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Map, MultiIndex};
+use mixnet_contract_common::{Family, IdentityKey, IdentityKeyRef};
+use serde::{Deserialize, Serialize};
+
+// storage prefixes
+const FAMILIES_NAMESPACE: &str = "fam";
+const FAMILY_MEMBERS_PK_NAMESPACE: &str = "famm";
+const FAMILY_MEMBERS_HEAD_IDX_NAMESPACE: &str = "fammh";
+
+// paged retrieval limits for all queries and transactions
+pub(crate) const FAMILIES_PAGE_MAX_LIMIT: u32 = 75;
+pub(crate) const FAMILIES_PAGE_DEFAULT_LIMIT: u32 = 50;
+
+// families keyed by the identity of their head
+pub(crate) const FAMILIES: Map<'_, IdentityKeyRef<'_>, Family> = Map::new(FAMILIES_NAMESPACE);
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub(crate) struct FamilyMembership {
+    pub(crate) head: IdentityKey,
+    // set until the family head accepts the join request
+    pub(crate) pending: bool,
+}
+
+pub(crate) struct FamilyMembershipIndex<'a> {
+    pub(crate) head: MultiIndex<'a, IdentityKey, FamilyMembership, IdentityKey>,
+}
+
+impl<'a> IndexList<FamilyMembership> for FamilyMembershipIndex<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<FamilyMembership>> + '_> {
+        let v: Vec<&dyn Index<FamilyMembership>> = vec![&self.head];
+        Box::new(v.into_iter())
+    }
+}
+
+// memberships (and pending join requests) keyed by the identity of the member node.
+// note that the head of the family is also stored here as an accepted member of its own family
+pub(crate) fn family_members<'a>(
+) -> IndexedMap<'a, IdentityKeyRef<'a>, FamilyMembership, FamilyMembershipIndex<'a>> {
+    let indexes = FamilyMembershipIndex {
+        head: MultiIndex::new(
+            |d| d.head.clone(),
+            FAMILY_MEMBERS_PK_NAMESPACE,
+            FAMILY_MEMBERS_HEAD_IDX_NAMESPACE,
+        ),
+    };
+
+    IndexedMap::new(FAMILY_MEMBERS_PK_NAMESPACE, indexes)
+}
+
+// returns the head of the family the node is an accepted member of
+pub(crate) fn family_of(
+    storage: &dyn Storage,
+    identity: IdentityKeyRef<'_>,
+) -> StdResult<Option<IdentityKey>> {
+    Ok(family_members()
+        .may_load(storage, identity)?
+        .filter(|membership| !membership.pending)
+        .map(|membership| membership.head))
+}
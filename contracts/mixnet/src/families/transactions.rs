@@ -0,0 +1,407 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::storage::{self, FamilyMembership};
+use crate::error::ContractError;
+use crate::mixnodes::storage::{mixnodes, StoredMixnodeBond};
+use crate::support::helpers::verify_identity_signature;
+use cosmwasm_std::{Addr, DepsMut, MessageInfo, Order, Response, StdResult, Storage};
+use mixnet_contract_common::events::{
+    new_family_creation_event, new_family_join_event, new_family_join_request_event,
+    new_family_leave_event,
+};
+use mixnet_contract_common::{Family, IdentityKey, IdentityKeyRef};
+
+fn owned_mixnode(storage: &dyn Storage, owner: &Addr) -> Result<StoredMixnodeBond, ContractError> {
+    match mixnodes().idx.owner.item(storage, owner.clone())? {
+        Some(record) => Ok(record.1),
+        None => Err(ContractError::NoAssociatedMixNodeBond {
+            owner: owner.clone(),
+        }),
+    }
+}
+
+fn ensure_not_in_family(
+    storage: &dyn Storage,
+    identity: IdentityKeyRef<'_>,
+) -> Result<(), ContractError> {
+    if let Some(membership) = storage::family_members().may_load(storage, identity)? {
+        return Err(ContractError::AlreadyInFamily {
+            identity: identity.to_string(),
+            family_head: membership.head,
+        });
+    }
+    Ok(())
+}
+
+pub fn try_create_family(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    label: String,
+) -> Result<Response, ContractError> {
+    let mixnode_bond = owned_mixnode(deps.storage, &info.sender)?;
+    let identity = mixnode_bond.identity();
+    ensure_not_in_family(deps.storage, identity)?;
+
+    let family = Family::new(identity.clone(), label);
+    storage::FAMILIES.save(deps.storage, identity, &family)?;
+    storage::family_members().save(
+        deps.storage,
+        identity,
+        &FamilyMembership {
+            head: identity.clone(),
+            pending: false,
+        },
+    )?;
+
+    Ok(Response::new().add_event(new_family_creation_event(
+        &info.sender,
+        identity,
+        &family.label,
+    )))
+}
+
+pub fn try_join_family(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    family_head: IdentityKey,
+    signature: String,
+) -> Result<Response, ContractError> {
+    let mixnode_bond = owned_mixnode(deps.storage, &info.sender)?;
+    let identity = mixnode_bond.identity();
+    ensure_not_in_family(deps.storage, identity)?;
+
+    if !storage::FAMILIES.has(deps.storage, &family_head) {
+        return Err(ContractError::FamilyNotFound { family_head });
+    }
+
+    // the node proves it wants to join this particular family by signing the identity of its head
+    verify_identity_signature(deps.as_ref(), family_head.as_bytes(), signature, identity)?;
+
+    storage::family_members().save(
+        deps.storage,
+        identity,
+        &FamilyMembership {
+            head: family_head.clone(),
+            pending: true,
+        },
+    )?;
+
+    Ok(Response::new().add_event(new_family_join_request_event(
+        &info.sender,
+        &family_head,
+        identity,
+    )))
+}
+
+pub fn try_accept_family_member(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    member: IdentityKey,
+) -> Result<Response, ContractError> {
+    let mixnode_bond = owned_mixnode(deps.storage, &info.sender)?;
+    let family_head = mixnode_bond.identity();
+
+    if !storage::FAMILIES.has(deps.storage, family_head) {
+        return Err(ContractError::FamilyNotFound {
+            family_head: family_head.clone(),
+        });
+    }
+
+    match storage::family_members().may_load(deps.storage, &member)? {
+        Some(membership) if membership.pending && &membership.head == family_head => {
+            storage::family_members().replace(
+                deps.storage,
+                &member,
+                Some(&FamilyMembership {
+                    head: membership.head.clone(),
+                    pending: false,
+                }),
+                Some(&membership),
+            )?;
+        }
+        _ => {
+            return Err(ContractError::NoPendingFamilyJoinRequest {
+                member,
+                family_head: family_head.clone(),
+            })
+        }
+    }
+
+    Ok(Response::new().add_event(new_family_join_event(&info.sender, family_head, &member)))
+}
+
+// also withdraws a pending join request. If the head leaves, the whole family is disbanded.
+pub fn try_leave_family(deps: DepsMut<'_>, info: MessageInfo) -> Result<Response, ContractError> {
+    let mixnode_bond = owned_mixnode(deps.storage, &info.sender)?;
+    let identity = mixnode_bond.identity();
+
+    match remove_family_membership(deps.storage, identity)? {
+        Some(family_head) => Ok(Response::new().add_event(new_family_leave_event(
+            &info.sender,
+            &family_head,
+            identity,
+        ))),
+        None => Err(ContractError::NotInFamily {
+            identity: identity.clone(),
+        }),
+    }
+}
+
+// removes the node from its family (or its pending join request) and returns the head of that family.
+// If the node is the head itself, the family gets disbanded.
+pub(crate) fn remove_family_membership(
+    storage: &mut dyn Storage,
+    identity: IdentityKeyRef<'_>,
+) -> Result<Option<IdentityKey>, ContractError> {
+    let membership = match storage::family_members().may_load(storage, identity)? {
+        Some(membership) => membership,
+        None => return Ok(None),
+    };
+
+    if membership.head == identity {
+        let members = storage::family_members()
+            .idx
+            .head
+            .prefix(membership.head.clone())
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for member in members {
+            storage::family_members().remove(storage, &member)?;
+        }
+        storage::FAMILIES.remove(storage, identity);
+    } else {
+        storage::family_members().remove(storage, identity)?;
+    }
+
+    Ok(Some(membership.head))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::execute;
+    use crate::families::queries::query_family_members;
+    use crate::support::tests;
+    use crate::support::tests::test_helpers;
+    use cosmwasm_std::testing::{mock_env, mock_info};
+    use cosmwasm_std::DepsMut;
+    use crypto::asymmetric::identity;
+    use mixnet_contract_common::{ExecuteMsg, MixNode};
+    use rand::thread_rng;
+
+    fn add_mixnode_with_keys(sender: &str, deps: DepsMut<'_>) -> identity::KeyPair {
+        let keypair = identity::KeyPair::new(&mut thread_rng());
+        let legit_sphinx_key = crypto::asymmetric::encryption::KeyPair::new(&mut thread_rng());
+        let owner_signature = keypair
+            .private_key()
+            .sign(sender.as_bytes())
+            .to_base58_string();
+
+        let msg = ExecuteMsg::BondMixnode {
+            mix_node: MixNode {
+                identity_key: keypair.public_key().to_base58_string(),
+                sphinx_key: legit_sphinx_key.public_key().to_base58_string(),
+                ..tests::fixtures::mix_node_fixture()
+            },
+            owner_signature,
+        };
+        let info = mock_info(sender, &tests::fixtures::good_mixnode_pledge());
+        execute(deps, mock_env(), info, msg).unwrap();
+        keypair
+    }
+
+    fn join_msg(member: &identity::KeyPair, family_head: &str) -> ExecuteMsg {
+        ExecuteMsg::JoinFamily {
+            family_head: family_head.to_string(),
+            signature: member
+                .private_key()
+                .sign(family_head.as_bytes())
+                .to_base58_string(),
+        }
+    }
+
+    #[test]
+    fn joining_family_requires_acceptance_of_the_head() {
+        let mut deps = test_helpers::init_contract();
+        let head_keys = add_mixnode_with_keys("alice", deps.as_mut());
+        let member_keys = add_mixnode_with_keys("bob", deps.as_mut());
+        let head = head_keys.public_key().to_base58_string();
+        let member = member_keys.public_key().to_base58_string();
+
+        // family must exist first
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            join_msg(&member_keys, &head),
+        );
+        assert_eq!(
+            Err(ContractError::FamilyNotFound {
+                family_head: head.clone()
+            }),
+            res
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::CreateFamily {
+                label: "alice's nodes".to_string(),
+            },
+        )
+        .unwrap();
+
+        // signature must be made with the identity key of the joining node
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            join_msg(&head_keys, &head),
+        );
+        assert_eq!(Err(ContractError::InvalidEd25519Signature), res);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            join_msg(&member_keys, &head),
+        )
+        .unwrap();
+
+        // until accepted, the node is not considered part of the family
+        assert_eq!(None, storage::family_of(&deps.storage, &member).unwrap());
+        let members = query_family_members(deps.as_ref(), head.clone()).unwrap();
+        assert_eq!(vec![head.clone()], members.members);
+        assert_eq!(vec![member.clone()], members.pending_members);
+
+        // only the head can accept new members
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            ExecuteMsg::AcceptFamilyMember {
+                member: member.clone(),
+            },
+        );
+        assert_eq!(
+            Err(ContractError::FamilyNotFound {
+                family_head: member.clone()
+            }),
+            res
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::AcceptFamilyMember {
+                member: member.clone(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(head.clone()),
+            storage::family_of(&deps.storage, &member).unwrap()
+        );
+        let members = query_family_members(deps.as_ref(), head.clone()).unwrap();
+        assert_eq!(2, members.members.len());
+        assert!(members.pending_members.is_empty());
+
+        // and the node can't join another family while being in one
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            ExecuteMsg::CreateFamily {
+                label: "bob's nodes".to_string(),
+            },
+        );
+        assert_eq!(
+            Err(ContractError::AlreadyInFamily {
+                identity: member,
+                family_head: head
+            }),
+            res
+        );
+    }
+
+    #[test]
+    fn family_is_disbanded_when_head_leaves_or_unbonds() {
+        let mut deps = test_helpers::init_contract();
+        let head_keys = add_mixnode_with_keys("alice", deps.as_mut());
+        let member_keys = add_mixnode_with_keys("bob", deps.as_mut());
+        let head = head_keys.public_key().to_base58_string();
+        let member = member_keys.public_key().to_base58_string();
+
+        for (sender, msg) in [
+            (
+                "alice",
+                ExecuteMsg::CreateFamily {
+                    label: "alice's nodes".to_string(),
+                },
+            ),
+            ("bob", join_msg(&member_keys, &head)),
+            (
+                "alice",
+                ExecuteMsg::AcceptFamilyMember {
+                    member: member.clone(),
+                },
+            ),
+        ] {
+            execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), msg).unwrap();
+        }
+
+        // regular member leaving doesn't affect the family
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            ExecuteMsg::LeaveFamily {},
+        )
+        .unwrap();
+        assert_eq!(None, storage::family_of(&deps.storage, &member).unwrap());
+        assert!(storage::FAMILIES.has(&deps.storage, &head));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            ExecuteMsg::LeaveFamily {},
+        );
+        assert_eq!(
+            Err(ContractError::NotInFamily {
+                identity: member.clone()
+            }),
+            res
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            join_msg(&member_keys, &head),
+        )
+        .unwrap();
+
+        // unbonding the head removes the family alongside any requests to join it
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::UnbondMixnode {},
+        )
+        .unwrap();
+        assert!(!storage::FAMILIES.has(&deps.storage, &head));
+        assert!(storage::family_members()
+            .may_load(&deps.storage, &member)
+            .unwrap()
+            .is_none());
+        assert!(storage::family_members()
+            .may_load(&deps.storage, &head)
+            .unwrap()
+            .is_none());
+    }
+}
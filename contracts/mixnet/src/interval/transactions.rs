@@ -5,6 +5,8 @@ use super::storage;
 use crate::error::ContractError;
 use crate::error::ContractError::EpochInProgress;
 use crate::mixnet_contract_settings::storage as mixnet_params_storage;
+use crate::mixnodes::storage::JAILED_MIXNODES;
+use crate::mixnodes::transactions::apply_pending_cost_params_changes;
 use crate::support::helpers::is_authorized;
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Storage};
 use mixnet_contract_common::events::{new_advance_interval_event, new_change_rewarded_set_event};
@@ -42,6 +44,15 @@ pub fn try_write_rewarded_set(
         });
     }
 
+    if let Some(jailed) = rewarded_set
+        .iter()
+        .find(|identity| JAILED_MIXNODES.has(deps.storage, identity))
+    {
+        return Err(ContractError::JailedMixnodeInRewardedSet {
+            identity: jailed.clone(),
+        });
+    }
+
     let block_height = env.block.height;
     let num_nodes = rewarded_set.len();
 
@@ -88,12 +99,17 @@ pub fn try_advance_epoch(
 
     let current_epoch = storage::current_epoch(storage)?;
     if current_epoch.is_over(env.clone()) {
-        let next_epoch = current_epoch.next_on_chain(env);
+        let next_epoch = current_epoch.next_on_chain(env.clone());
 
         storage::save_epoch(storage, &next_epoch)?;
         storage::save_epoch_reward_params(next_epoch.id(), storage)?;
 
-        return Ok(Response::new().add_event(new_advance_interval_event(next_epoch)));
+        let cost_params_events =
+            apply_pending_cost_params_changes(storage, env.block.height, &next_epoch)?;
+
+        return Ok(Response::new()
+            .add_event(new_advance_interval_event(next_epoch))
+            .add_events(cost_params_events));
     }
     Err(EpochInProgress {
         current_block_time: env.block.time.seconds(),
@@ -108,7 +124,7 @@ mod tests {
     use crate::support::tests::test_helpers;
     use cosmwasm_std::testing::{mock_env, mock_info};
     use cosmwasm_std::Timestamp;
-    use mixnet_contract_common::RewardedSetNodeStatus;
+    use mixnet_contract_common::{JailStatus, RewardedSetNodeStatus};
     use mixnet_params_storage::rewarding_validator_address;
 
     #[test]
@@ -172,6 +188,31 @@ mod tests {
             )
         );
 
+        // jailed nodes can't be put in the rewarded set
+        JAILED_MIXNODES
+            .save(
+                deps.as_mut().storage,
+                &full_rewarded_set[0],
+                &JailStatus {
+                    jailed_at_epoch_id: 1,
+                    unjail_epoch_id: 25,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            Err(ContractError::JailedMixnodeInRewardedSet {
+                identity: full_rewarded_set[0].clone()
+            }),
+            try_write_rewarded_set(
+                deps.as_mut(),
+                env.clone(),
+                authorised_sender.clone(),
+                full_rewarded_set.clone(),
+                current_state.params.mixnode_active_set_size
+            )
+        );
+        JAILED_MIXNODES.remove(deps.as_mut().storage, &full_rewarded_set[0]);
+
         // cannot be performed too soon after a previous update
         env.block.height = last_update + 1;
         // after successful rewarded set write, all internal storage structures are updated appropriately
@@ -5,6 +5,7 @@ mod constants;
 pub mod contract;
 mod delegations;
 mod error;
+mod families;
 mod gateways;
 mod interval;
 mod mixnet_contract_settings;
@@ -51,6 +51,9 @@ pub(crate) mod tests {
                 minimum_gateway_pledge: 456u128.into(),
                 mixnode_rewarded_set_size: 1000,
                 mixnode_active_set_size: 500,
+                cost_params_change_notice_epochs: 12,
+                maximum_mixnode_operating_cost: 1_000_000u128.into(),
+                jail_cooldown_epochs: 48,
             },
         };
 
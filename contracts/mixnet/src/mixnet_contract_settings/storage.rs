@@ -3,6 +3,7 @@
 
 use crate::error::ContractError;
 use crate::mixnet_contract_settings::models::ContractState;
+use cosmwasm_std::Addr;
 use cosmwasm_std::StdResult;
 use cosmwasm_std::Storage;
 use cw_storage_plus::Item;
@@ -10,6 +11,8 @@ use mixnet_contract_common::{Layer, LayerDistribution};
 
 pub(crate) const CONTRACT_STATE: Item<'_, ContractState> = Item::new("config");
 pub(crate) const LAYERS: Item<'_, LayerDistribution> = Item::new("layers");
+// owner proposed by the current one, who has yet to accept the ownership of the contract
+pub(crate) const PENDING_OWNER: Item<'_, Addr> = Item::new("pending_owner");
 
 pub fn rewarding_validator_address(storage: &dyn Storage) -> Result<String, ContractError> {
     Ok(CONTRACT_STATE
@@ -7,8 +7,10 @@ use cosmwasm_std::Addr;
 use cosmwasm_std::DepsMut;
 use cosmwasm_std::MessageInfo;
 use cosmwasm_std::Response;
-use mixnet_contract_common::events::new_settings_update_event;
-use mixnet_contract_common::ContractStateParams;
+use mixnet_contract_common::events::{
+    new_contract_owner_proposal_event, new_contract_owner_update_event, new_settings_update_event,
+};
+use mixnet_contract_common::{ContractStateParams, U128};
 
 pub fn try_update_rewarding_validator_address(
     deps: DepsMut<'_>,
@@ -27,6 +29,52 @@ pub fn try_update_rewarding_validator_address(
     Ok(Response::default())
 }
 
+// note: proposing a new owner replaces any previous proposal
+pub(crate) fn try_propose_contract_owner(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let state = storage::CONTRACT_STATE.load(deps.storage)?;
+
+    if info.sender != state.owner {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let proposed_owner = deps.api.addr_validate(&address)?;
+    storage::PENDING_OWNER.save(deps.storage, &proposed_owner)?;
+
+    Ok(Response::new().add_event(new_contract_owner_proposal_event(
+        &state.owner,
+        &proposed_owner,
+    )))
+}
+
+pub(crate) fn try_accept_contract_ownership(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending_owner = storage::PENDING_OWNER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingOwnershipTransfer)?;
+
+    if info.sender != pending_owner {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let mut state = storage::CONTRACT_STATE.load(deps.storage)?;
+    let response = Response::new().add_event(new_contract_owner_update_event(
+        &state.owner,
+        &pending_owner,
+    ));
+
+    state.owner = pending_owner;
+    storage::CONTRACT_STATE.save(deps.storage, &state)?;
+    storage::PENDING_OWNER.remove(deps.storage);
+
+    Ok(response)
+}
+
 pub(crate) fn try_update_contract_settings(
     deps: DepsMut<'_>,
     info: MessageInfo,
@@ -53,6 +101,13 @@ pub(crate) fn try_update_contract_settings(
         return Err(ContractError::InvalidActiveSetSize);
     }
 
+    // the maximum cost must remain representable once scaled for rewarding
+    if U128::checked_from_num(params.maximum_mixnode_operating_cost.u128()).is_none() {
+        return Err(ContractError::InvalidMaximumOperatingCost(
+            params.maximum_mixnode_operating_cost.u128(),
+        ));
+    }
+
     let response = Response::new().add_event(new_settings_update_event(&state.params, &params));
 
     state.params = params;
@@ -70,7 +125,7 @@ pub mod tests {
     use crate::mixnet_contract_settings::transactions::try_update_contract_settings;
     use crate::support::tests::test_helpers;
     use cosmwasm_std::testing::mock_info;
-    use cosmwasm_std::Response;
+    use cosmwasm_std::{Response, Uint128};
     use mixnet_contract_common::ContractStateParams;
 
     #[test]
@@ -114,6 +169,9 @@ pub mod tests {
             minimum_gateway_pledge: INITIAL_GATEWAY_PLEDGE,
             mixnode_rewarded_set_size: 100,
             mixnode_active_set_size: 50,
+            cost_params_change_notice_epochs: 48,
+            maximum_mixnode_operating_cost: Uint128::new(500_000_000),
+            jail_cooldown_epochs: 12,
         };
 
         let initial_params = storage::CONTRACT_STATE
@@ -157,9 +215,77 @@ pub mod tests {
 
         // error is thrown for 0 size active set
         let info = mock_info("creator", &[]);
-        let mut new_params = current_state.params;
+        let mut new_params = current_state.params.clone();
         new_params.mixnode_active_set_size = 0;
         let res = try_update_contract_settings(deps.as_mut(), info, new_params);
         assert_eq!(Err(ContractError::ZeroActiveSet), res);
+
+        // error is thrown for maximum operating cost that can't be used for rewarding
+        let info = mock_info("creator", &[]);
+        let mut new_params = current_state.params;
+        new_params.maximum_mixnode_operating_cost = Uint128::new(u128::MAX);
+        let res = try_update_contract_settings(deps.as_mut(), info, new_params);
+        assert_eq!(
+            Err(ContractError::InvalidMaximumOperatingCost(u128::MAX)),
+            res
+        );
+    }
+
+    #[test]
+    fn transferring_contract_ownership() {
+        let mut deps = test_helpers::init_contract();
+
+        // nothing to accept before anything got proposed
+        let info = mock_info("multisig", &[]);
+        let res = try_accept_contract_ownership(deps.as_mut(), info);
+        assert_eq!(res, Err(ContractError::NoPendingOwnershipTransfer));
+
+        let info = mock_info("not-the-creator", &[]);
+        let res = try_propose_contract_owner(deps.as_mut(), info, "not-the-creator".to_string());
+        assert_eq!(res, Err(ContractError::Unauthorized));
+
+        let info = mock_info("creator", &[]);
+        let res = try_propose_contract_owner(deps.as_mut(), info, "multisig".to_string());
+        assert_eq!(
+            res,
+            Ok(Response::new().add_event(new_contract_owner_proposal_event(
+                &Addr::unchecked("creator"),
+                &Addr::unchecked("multisig")
+            )))
+        );
+
+        // the proposal alone doesn't change the owner
+        let params = storage::CONTRACT_STATE.load(&deps.storage).unwrap().params;
+        let info = mock_info("multisig", &[]);
+        let res = try_update_contract_settings(deps.as_mut(), info, params.clone());
+        assert_eq!(res, Err(ContractError::Unauthorized));
+
+        // and only the proposed owner can accept it
+        let info = mock_info("creator", &[]);
+        let res = try_accept_contract_ownership(deps.as_mut(), info);
+        assert_eq!(res, Err(ContractError::Unauthorized));
+
+        let info = mock_info("multisig", &[]);
+        let res = try_accept_contract_ownership(deps.as_mut(), info);
+        assert_eq!(
+            res,
+            Ok(Response::new().add_event(new_contract_owner_update_event(
+                &Addr::unchecked("creator"),
+                &Addr::unchecked("multisig")
+            )))
+        );
+        assert!(storage::PENDING_OWNER
+            .may_load(&deps.storage)
+            .unwrap()
+            .is_none());
+
+        // the previous owner can no longer change the settings
+        let info = mock_info("creator", &[]);
+        let res = try_update_contract_settings(deps.as_mut(), info, params.clone());
+        assert_eq!(res, Err(ContractError::Unauthorized));
+
+        // but the new one can
+        let info = mock_info("multisig", &[]);
+        assert!(try_update_contract_settings(deps.as_mut(), info, params).is_ok());
     }
 }
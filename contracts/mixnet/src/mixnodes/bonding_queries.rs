@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::storage::{self, StoredMixnodeBond};
+use crate::families::storage::family_of;
 use cosmwasm_std::{Deps, Order, StdResult};
 use cw_storage_plus::Bound;
 use mixnet_contract_common::{
-    IdentityKey, MixNodeBond, MixOwnershipResponse, PagedMixnodeResponse,
+    IdentityKey, MixNodeBond, MixOwnershipResponse, PagedMixnodeResponse, PendingCostParamsChange,
 };
 
 pub fn query_mixnode_at_height(
@@ -16,6 +17,13 @@ pub fn query_mixnode_at_height(
     storage::mixnodes().may_load_at_height(deps.storage, &mix_identity, height)
 }
 
+pub fn query_pending_cost_params_change(
+    deps: Deps<'_>,
+    mix_identity: IdentityKey,
+) -> StdResult<Option<PendingCostParamsChange>> {
+    storage::pending_cost_params_changes().may_load(deps.storage, &mix_identity)
+}
+
 pub fn query_checkpoints_for_mixnode(
     deps: Deps<'_>,
     mix_identity: IdentityKey,
@@ -44,15 +52,15 @@ pub fn query_mixnodes_paged(
         .take(limit)
         .map(|res| res.map(|item| item.1))
         .map(|stored_bond| {
-            // I really don't like this additional read per entry, but I don't see an obvious way to remove it
-            stored_bond.map(|stored_bond| {
-                let total_delegation =
-                    storage::TOTAL_DELEGATION.load(deps.storage, stored_bond.identity());
-                total_delegation
-                    .map(|total_delegation| stored_bond.attach_delegation(total_delegation))
-            })
+            // I really don't like those additional reads per entry, but I don't see an obvious way to remove them
+            let stored_bond = stored_bond?;
+            let total_delegation =
+                storage::TOTAL_DELEGATION.load(deps.storage, stored_bond.identity())?;
+            let family = family_of(deps.storage, stored_bond.identity())?;
+            let jailed = storage::JAILED_MIXNODES.may_load(deps.storage, stored_bond.identity())?;
+            Ok(stored_bond.attach_delegation(total_delegation, family, jailed))
         })
-        .collect::<StdResult<StdResult<Vec<MixNodeBond>>>>()??;
+        .collect::<StdResult<Vec<MixNodeBond>>>()?;
 
     let start_next_after = nodes.last().map(|node| node.identity().clone());
 
@@ -72,7 +80,9 @@ pub fn query_owns_mixnode(deps: Deps<'_>, address: String) -> StdResult<MixOwner
         Some(bond) => {
             let total_delegation =
                 storage::TOTAL_DELEGATION.may_load(deps.storage, bond.identity())?;
-            Some(bond.attach_delegation(total_delegation.unwrap_or_default()))
+            let family = family_of(deps.storage, bond.identity())?;
+            let jailed = storage::JAILED_MIXNODES.may_load(deps.storage, bond.identity())?;
+            Some(bond.attach_delegation(total_delegation.unwrap_or_default(), family, jailed))
         }
     };
 
@@ -1,11 +1,15 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::families::storage::family_of;
 use config::defaults::DENOM;
 use cosmwasm_std::{StdResult, Storage, Uint128};
-use cw_storage_plus::{Index, IndexList, IndexedSnapshotMap, Map, Strategy, UniqueIndex};
+use cw_storage_plus::{
+    Index, IndexList, IndexedMap, IndexedSnapshotMap, Map, MultiIndex, Strategy, UniqueIndex,
+};
 use mixnet_contract_common::{
-    reward_params::NodeEpochRewards, Addr, Coin, IdentityKeyRef, Layer, MixNode, MixNodeBond,
+    reward_params::NodeEpochRewards, Addr, Coin, IdentityKey, IdentityKeyRef, JailStatus, Layer,
+    MixNode, MixNodeBond, PendingCostParamsChange,
 };
 use mixnet_contract_common::{SphinxKey, U128};
 use serde::{Deserialize, Serialize};
@@ -20,6 +24,9 @@ const MIXNODES_OWNER_IDX_NAMESPACE: &str = "mno";
 const MIXNODES_SPHINX_IDX_NAMESPACE: &str = "mns";
 
 const LAST_PM_UPDATE_NAMESPACE: &str = "lpm";
+const PENDING_COST_PARAMS_CHANGES_NAMESPACE: &str = "pcp";
+const PENDING_COST_PARAMS_CHANGES_EPOCH_IDX_NAMESPACE: &str = "pcpe";
+const JAILED_MIXNODES_NAMESPACE: &str = "jmn";
 
 // paged retrieval limits for all queries and transactions
 pub(crate) const BOND_PAGE_MAX_LIMIT: u32 = 75;
@@ -31,6 +38,10 @@ pub(crate) const TOTAL_DELEGATION: Map<'_, IdentityKeyRef<'_>, Uint128> =
 pub(crate) const LAST_PM_UPDATE_TIME: Map<'_, IdentityKeyRef<'_>, u64> =
     Map::new(LAST_PM_UPDATE_NAMESPACE);
 
+// note that the jail status is kept even if the node unbonds, so that it couldn't escape it by rebonding
+pub(crate) const JAILED_MIXNODES: Map<'_, IdentityKeyRef<'_>, JailStatus> =
+    Map::new(JAILED_MIXNODES_NAMESPACE);
+
 pub(crate) struct MixnodeBondIndex<'a> {
     pub(crate) owner: UniqueIndex<'a, Addr, StoredMixnodeBond>,
 
@@ -65,6 +76,33 @@ pub(crate) fn mixnodes<'a>(
     )
 }
 
+pub(crate) struct PendingCostParamsChangeIndex<'a> {
+    pub(crate) effective_epoch: MultiIndex<'a, u32, PendingCostParamsChange, IdentityKey>,
+}
+
+impl<'a> IndexList<PendingCostParamsChange> for PendingCostParamsChangeIndex<'a> {
+    fn get_indexes(
+        &'_ self,
+    ) -> Box<dyn Iterator<Item = &'_ dyn Index<PendingCostParamsChange>> + '_> {
+        let v: Vec<&dyn Index<PendingCostParamsChange>> = vec![&self.effective_epoch];
+        Box::new(v.into_iter())
+    }
+}
+
+// profit margin and operating cost changes requested by the operators that are yet to take effect,
+// indexed by the epoch they're going to take effect in, so that only the due ones are ever loaded
+pub(crate) fn pending_cost_params_changes<'a>(
+) -> IndexedMap<'a, IdentityKeyRef<'a>, PendingCostParamsChange, PendingCostParamsChangeIndex<'a>> {
+    let indexes = PendingCostParamsChangeIndex {
+        effective_epoch: MultiIndex::new(
+            |change| change.effective_epoch_id,
+            PENDING_COST_PARAMS_CHANGES_NAMESPACE,
+            PENDING_COST_PARAMS_CHANGES_EPOCH_IDX_NAMESPACE,
+        ),
+    };
+    IndexedMap::new(PENDING_COST_PARAMS_CHANGES_NAMESPACE, indexes)
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct StoredMixnodeBond {
     pub pledge_amount: Coin,
@@ -120,7 +158,12 @@ impl StoredMixnodeBond {
         self.accumulated_rewards.unwrap_or_else(Uint128::zero)
     }
 
-    pub(crate) fn attach_delegation(self, total_delegation: Uint128) -> MixNodeBond {
+    pub(crate) fn attach_delegation(
+        self,
+        total_delegation: Uint128,
+        family: Option<IdentityKey>,
+        jailed: Option<JailStatus>,
+    ) -> MixNodeBond {
         MixNodeBond {
             total_delegation: Coin {
                 denom: self.pledge_amount.denom.clone(),
@@ -133,6 +176,8 @@ impl StoredMixnodeBond {
             mix_node: self.mix_node,
             proxy: self.proxy,
             accumulated_rewards: self.accumulated_rewards,
+            family,
+            jailed,
         }
     }
 
@@ -147,6 +192,15 @@ impl StoredMixnodeBond {
     pub fn profit_margin(&self) -> U128 {
         U128::from_num(self.mix_node.profit_margin_percent) / U128::from_num(100)
     }
+
+    // the profit margin that was in effect when the last epoch rewards were distributed,
+    // falls back to the current one if it was not recorded
+    pub fn epoch_profit_margin(&self) -> U128 {
+        self.epoch_rewards
+            .as_ref()
+            .and_then(|epoch_rewards| epoch_rewards.profit_margin())
+            .unwrap_or_else(|| self.profit_margin())
+    }
 }
 
 impl Display for StoredMixnodeBond {
@@ -168,6 +222,8 @@ pub(crate) fn read_full_mixnode_bond(
         None => Ok(None),
         Some(stored_bond) => {
             let total_delegation = TOTAL_DELEGATION.may_load(storage, mix_identity)?;
+            let family = family_of(storage, mix_identity)?;
+            let jailed = JAILED_MIXNODES.may_load(storage, mix_identity)?;
             Ok(Some(MixNodeBond {
                 pledge_amount: stored_bond.pledge_amount,
                 total_delegation: Coin {
@@ -180,6 +236,8 @@ pub(crate) fn read_full_mixnode_bond(
                 mix_node: stored_bond.mix_node,
                 proxy: stored_bond.proxy,
                 accumulated_rewards: stored_bond.accumulated_rewards,
+                family,
+                jailed,
             }))
         }
     }
@@ -3,23 +3,35 @@
 
 use super::storage::{self, LAST_PM_UPDATE_TIME};
 use crate::error::ContractError;
+use crate::families::transactions::remove_family_membership;
+use crate::interval::storage as interval_storage;
 use crate::mixnet_contract_settings::storage as mixnet_params_storage;
 use crate::mixnodes::layer_queries::query_layer_distribution;
 use crate::mixnodes::storage::StoredMixnodeBond;
-use crate::support::helpers::{ensure_no_existing_bond, validate_node_identity_signature};
+use crate::support::helpers::{
+    ensure_no_existing_bond, is_authorized, validate_node_identity_signature,
+};
 use config::defaults::DENOM;
 use cosmwasm_std::{
-    wasm_execute, Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Response, Storage, Uint128,
+    wasm_execute, Addr, BankMsg, Coin, DepsMut, Env, Event, MessageInfo, Order, Response,
+    StdResult, Storage, Uint128,
 };
+use cw_storage_plus::{KeyDeserialize, PrefixBound};
 use mixnet_contract_common::events::{
-    new_checkpoint_mixnodes_event, new_mixnode_bonding_event, new_mixnode_unbonding_event,
+    new_already_jailed_mixnode_jailing_event, new_checkpoint_mixnodes_event,
+    new_mixnode_bonding_event, new_mixnode_cost_params_update_event, new_mixnode_jailing_event,
+    new_mixnode_pledge_decrease_event, new_mixnode_unbonding_event, new_mixnode_unjailing_event,
+    new_not_found_mixnode_jailing_event, new_pending_mixnode_cost_params_update_event,
 };
-use mixnet_contract_common::MixNode;
+use mixnet_contract_common::{IdentityKey, Interval, JailStatus, MixNode, PendingCostParamsChange};
 use vesting_contract_common::messages::ExecuteMsg as VestingContractExecuteMsg;
 use vesting_contract_common::one_ucoin;
 
 const MIN_PM_UPDATE_INTERVAL: u64 = 60 * 60 * 24 * 30; // one month roughly
 
+// upper bound on the cost parameter changes applied when advancing a single epoch
+pub(crate) const MAX_COST_PARAMS_CHANGES_PER_EPOCH: usize = 100;
+
 pub fn try_checkpoint_mixnodes(
     storage: &mut dyn Storage,
     block_height: u64,
@@ -107,6 +119,7 @@ fn _try_add_mixnode(
             mix_node.profit_margin_percent,
         ));
     }
+    validate_operating_cost(deps.storage, mix_node.operating_cost)?;
 
     // check if somebody else has already bonded a mixnode with this identity
     if let Some(existing_bond) =
@@ -225,8 +238,10 @@ pub(crate) fn _try_remove_mixnode(
         amount: vec![mixnode_bond.pledge_amount()],
     };
 
-    // remove the bond
+    // remove the bond alongside any cost changes that were yet to be applied
     storage::mixnodes().remove(deps.storage, mixnode_bond.identity(), env.block.height)?;
+    storage::pending_cost_params_changes().remove(deps.storage, mixnode_bond.identity())?;
+    remove_family_membership(deps.storage, mixnode_bond.identity())?;
 
     // decrement layer count
     mixnet_params_storage::decrement_layer_count(deps.storage, mixnode_bond.layer)?;
@@ -237,6 +252,7 @@ pub(crate) fn _try_remove_mixnode(
         let msg = VestingContractExecuteMsg::TrackUnbondMixnode {
             owner: owner.as_str().to_string(),
             amount: mixnode_bond.pledge_amount(),
+            partial: false,
         };
 
         let track_unbond_message = wasm_execute(proxy, &msg, vec![one_ucoin()])?;
@@ -253,14 +269,139 @@ pub(crate) fn _try_remove_mixnode(
     )))
 }
 
+pub fn try_decrease_mixnode_pledge_on_behalf(
+    env: Env,
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    amount: Coin,
+    owner: String,
+) -> Result<Response, ContractError> {
+    let proxy = info.sender;
+    _try_decrease_mixnode_pledge(env, deps, amount, &owner, Some(proxy))
+}
+
+pub fn try_decrease_mixnode_pledge(
+    env: Env,
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    amount: Coin,
+) -> Result<Response, ContractError> {
+    _try_decrease_mixnode_pledge(env, deps, amount, info.sender.as_ref(), None)
+}
+
+pub(crate) fn _try_decrease_mixnode_pledge(
+    env: Env,
+    deps: DepsMut<'_>,
+    amount: Coin,
+    owner: &str,
+    proxy: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_validate(owner)?;
+
+    if amount.denom != DENOM {
+        return Err(ContractError::WrongDenom {});
+    }
+    if amount.amount.is_zero() {
+        return Err(ContractError::EmptyPledgeDecrease);
+    }
+
+    // same as with unbonding, make sure any pending operator rewards are included in the pledge
+    // before we change it
+    crate::rewards::transactions::_try_compound_operator_reward(
+        deps.storage,
+        deps.api,
+        env.block.height,
+        &owner,
+        proxy.clone(),
+    )?;
+
+    let mut mixnode_bond = match storage::mixnodes()
+        .idx
+        .owner
+        .item(deps.storage, owner.clone())?
+    {
+        Some(record) => record.1,
+        None => return Err(ContractError::NoAssociatedMixNodeBond { owner }),
+    };
+
+    if proxy != mixnode_bond.proxy {
+        return Err(ContractError::ProxyMismatch {
+            existing: mixnode_bond
+                .proxy
+                .map_or_else(|| "None".to_string(), |a| a.as_str().to_string()),
+            incoming: proxy.map_or_else(|| "None".to_string(), |a| a.as_str().to_string()),
+        });
+    }
+
+    let minimum_pledge = mixnet_params_storage::CONTRACT_STATE
+        .load(deps.storage)?
+        .params
+        .minimum_mixnode_pledge;
+
+    let current_pledge = mixnode_bond.pledge_amount.amount;
+    let remaining_pledge = current_pledge
+        .checked_sub(amount.amount)
+        .ok()
+        .filter(|remaining| *remaining >= minimum_pledge)
+        .ok_or(ContractError::PledgeBelowMinimum {
+            current: current_pledge.u128(),
+            decrease: amount.amount.u128(),
+            minimum: minimum_pledge.u128(),
+        })?;
+
+    mixnode_bond.pledge_amount.amount = remaining_pledge;
+    storage::mixnodes().save(
+        deps.storage,
+        mixnode_bond.identity(),
+        &mixnode_bond,
+        env.block.height,
+    )?;
+
+    let mut response = Response::new();
+
+    if let Some(proxy) = &proxy {
+        let msg = VestingContractExecuteMsg::TrackUnbondMixnode {
+            owner: owner.as_str().to_string(),
+            amount: amount.clone(),
+            partial: true,
+        };
+
+        let track_unbond_message = wasm_execute(proxy, &msg, vec![one_ucoin()])?;
+        response = response.add_message(track_unbond_message);
+    }
+
+    let return_tokens = BankMsg::Send {
+        to_address: proxy.as_ref().unwrap_or(&owner).to_string(),
+        amount: vec![amount.clone()],
+    };
+
+    Ok(response
+        .add_message(return_tokens)
+        .add_event(new_mixnode_pledge_decrease_event(
+            &owner,
+            &proxy,
+            &amount,
+            &mixnode_bond.pledge_amount,
+            mixnode_bond.identity(),
+        )))
+}
+
 pub(crate) fn try_update_mixnode_config(
     deps: DepsMut<'_>,
     env: Env,
     info: MessageInfo,
     profit_margin_percent: u8,
+    operating_cost: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     let owner = deps.api.addr_validate(info.sender.as_ref())?;
-    _try_update_mixnode_config(deps, env, profit_margin_percent, owner, None)
+    _try_update_mixnode_config(
+        deps,
+        env,
+        profit_margin_percent,
+        operating_cost,
+        owner,
+        None,
+    )
 }
 
 pub(crate) fn try_update_mixnode_config_on_behalf(
@@ -268,17 +409,28 @@ pub(crate) fn try_update_mixnode_config_on_behalf(
     env: Env,
     info: MessageInfo,
     profit_margin_percent: u8,
+    operating_cost: Option<Uint128>,
     owner: String,
 ) -> Result<Response, ContractError> {
     let owner = deps.api.addr_validate(&owner)?;
     let proxy = deps.api.addr_validate(info.sender.as_ref())?;
-    _try_update_mixnode_config(deps, env, profit_margin_percent, owner, Some(proxy))
+    _try_update_mixnode_config(
+        deps,
+        env,
+        profit_margin_percent,
+        operating_cost,
+        owner,
+        Some(proxy),
+    )
 }
 
+// The new cost parameters are not applied immediately. Instead, they're queued and only take effect
+// once the notice period has passed, so that the delegators have a chance to react to them.
 pub(crate) fn _try_update_mixnode_config(
     deps: DepsMut,
     env: Env,
     profit_margin_percent: u8,
+    operating_cost: Option<Uint128>,
     owner: Addr,
     proxy: Option<Addr>,
 ) -> Result<Response, ContractError> {
@@ -286,7 +438,9 @@ pub(crate) fn _try_update_mixnode_config(
         .idx
         .owner
         .item(deps.storage, owner.clone())?
-        .ok_or(ContractError::NoAssociatedMixNodeBond { owner })?
+        .ok_or_else(|| ContractError::NoAssociatedMixNodeBond {
+            owner: owner.clone(),
+        })?
         .1;
 
     if proxy != mixnode_bond.proxy {
@@ -317,25 +471,34 @@ pub(crate) fn _try_update_mixnode_config(
             profit_margin_percent,
         ));
     }
+    validate_operating_cost(deps.storage, operating_cost)?;
+
+    let notice_epochs = mixnet_params_storage::CONTRACT_STATE
+        .load(deps.storage)?
+        .params
+        .cost_params_change_notice_epochs;
+    let current_epoch = interval_storage::current_epoch(deps.storage)?;
+
+    // the earliest a change could take effect is the start of the following epoch
+    let pending_change = PendingCostParamsChange {
+        profit_margin_percent,
+        operating_cost: operating_cost.or(mixnode_bond.mix_node.operating_cost),
+        effective_epoch_id: current_epoch.id() + 1 + notice_epochs,
+    };
 
-    storage::mixnodes().update(
+    storage::pending_cost_params_changes().save(
         deps.storage,
         mixnode_bond.identity(),
-        env.block.height,
-        |mixnode_bond_opt| {
-            mixnode_bond_opt
-                .map(|mut mixnode_bond| {
-                    mixnode_bond.mix_node.profit_margin_percent = profit_margin_percent;
-                    mixnode_bond.block_height = env.block.height;
-                    mixnode_bond
-                })
-                .ok_or(ContractError::NoBondFound)
-        },
+        &pending_change,
     )?;
-
     LAST_PM_UPDATE_TIME.save(deps.storage, mixnode_bond.identity(), &current_block_time)?;
 
-    let mut response = Response::new();
+    let mut response = Response::new().add_event(new_pending_mixnode_cost_params_update_event(
+        &owner,
+        &proxy,
+        mixnode_bond.identity(),
+        &pending_change,
+    ));
 
     if let Some(proxy) = proxy {
         // Returns one_ucoin proxy had to send in order to execute the contract to contract transaction, this is potentially leaky as anyone can say that they're a proxy,
@@ -350,6 +513,148 @@ pub(crate) fn _try_update_mixnode_config(
     Ok(response)
 }
 
+pub(crate) fn try_jail_mixnode(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    mix_identity: IdentityKey,
+) -> Result<Response, ContractError> {
+    // only the rewarding validator, which keeps track of the node performance, can jail nodes
+    is_authorized(info.sender.into_string(), deps.storage)?;
+
+    // the node might have unbonded or been jailed in the meantime; that's not an error
+    // as the rewarding validator would otherwise get its whole transaction rejected
+    if storage::mixnodes()
+        .may_load(deps.storage, &mix_identity)?
+        .is_none()
+    {
+        return Ok(Response::new().add_event(new_not_found_mixnode_jailing_event(&mix_identity)));
+    }
+
+    if storage::JAILED_MIXNODES.has(deps.storage, &mix_identity) {
+        return Ok(
+            Response::new().add_event(new_already_jailed_mixnode_jailing_event(&mix_identity))
+        );
+    }
+
+    let jail_cooldown_epochs = mixnet_params_storage::CONTRACT_STATE
+        .load(deps.storage)?
+        .params
+        .jail_cooldown_epochs;
+    let epoch_id = interval_storage::current_epoch(deps.storage)?.id();
+    let jail_status = JailStatus {
+        jailed_at_epoch_id: epoch_id,
+        unjail_epoch_id: epoch_id + jail_cooldown_epochs,
+    };
+    storage::JAILED_MIXNODES.save(deps.storage, &mix_identity, &jail_status)?;
+
+    Ok(Response::new().add_event(new_mixnode_jailing_event(&mix_identity, &jail_status)))
+}
+
+pub(crate) fn try_unjail_mixnode(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // try to find the node of the sender
+    let mixnode_bond = match storage::mixnodes()
+        .idx
+        .owner
+        .item(deps.storage, info.sender.clone())?
+    {
+        Some(record) => record.1,
+        None => return Err(ContractError::NoAssociatedMixNodeBond { owner: info.sender }),
+    };
+    let identity = mixnode_bond.identity();
+
+    let jail_status = match storage::JAILED_MIXNODES.may_load(deps.storage, identity)? {
+        Some(jail_status) => jail_status,
+        None => {
+            return Err(ContractError::MixnodeNotJailed {
+                identity: identity.clone(),
+            })
+        }
+    };
+
+    let current_epoch_id = interval_storage::current_epoch(deps.storage)?.id();
+    if current_epoch_id < jail_status.unjail_epoch_id {
+        return Err(ContractError::UnjailTooSoon {
+            identity: identity.clone(),
+            current_epoch_id,
+            unjail_epoch_id: jail_status.unjail_epoch_id,
+        });
+    }
+
+    storage::JAILED_MIXNODES.remove(deps.storage, identity);
+
+    Ok(Response::new().add_event(new_mixnode_unjailing_event(&info.sender, identity)))
+}
+
+// Applies the queued cost parameter changes that are due by the start of the provided epoch.
+// At most `MAX_COST_PARAMS_CHANGES_PER_EPOCH` are applied at once, any remaining ones are still
+// going to be due at the following epoch.
+pub(crate) fn apply_pending_cost_params_changes(
+    storage: &mut dyn Storage,
+    block_height: u64,
+    epoch: &Interval,
+) -> Result<Vec<Event>, ContractError> {
+    // note: `prefix_range` of `MultiIndex` fails to deserialize the values in this version
+    // of cw-storage-plus, hence the raw variant with the primary keys decoded manually
+    let due_changes = storage::pending_cost_params_changes()
+        .idx
+        .effective_epoch
+        .prefix_range_raw(
+            storage,
+            None,
+            Some(PrefixBound::inclusive(epoch.id())),
+            Order::Ascending,
+        )
+        .take(MAX_COST_PARAMS_CHANGES_PER_EPOCH)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut events = Vec::with_capacity(due_changes.len());
+    for (raw_identity, change) in due_changes {
+        let identity = IdentityKey::from_vec(raw_identity)?;
+        storage::pending_cost_params_changes().remove(storage, &identity)?;
+
+        // the node might have unbonded in the meantime
+        if storage::mixnodes().may_load(storage, &identity)?.is_none() {
+            continue;
+        }
+
+        storage::mixnodes().update(storage, &identity, block_height, |mixnode_bond_opt| {
+            mixnode_bond_opt
+                .map(|mut mixnode_bond| {
+                    mixnode_bond.mix_node.profit_margin_percent = change.profit_margin_percent;
+                    mixnode_bond.mix_node.operating_cost = change.operating_cost;
+                    mixnode_bond
+                })
+                .ok_or(ContractError::NoBondFound)
+        })?;
+
+        events.push(new_mixnode_cost_params_update_event(&identity, &change));
+    }
+
+    Ok(events)
+}
+
+fn validate_operating_cost(
+    storage: &dyn Storage,
+    operating_cost: Option<Uint128>,
+) -> Result<(), ContractError> {
+    if let Some(cost) = operating_cost {
+        let maximum = mixnet_params_storage::CONTRACT_STATE
+            .load(storage)?
+            .params
+            .maximum_mixnode_operating_cost;
+        if cost > maximum {
+            return Err(ContractError::OperatingCostTooHigh {
+                cost: cost.u128(),
+                maximum: maximum.u128(),
+            });
+        }
+    }
+    Ok(())
+}
+
 fn validate_mixnode_pledge(
     mut pledge: Vec<Coin>,
     minimum_pledge: Uint128,
@@ -384,14 +689,18 @@ pub mod tests {
     use std::f64::MIN;
 
     use super::*;
-    use crate::contract::{execute, query, INITIAL_MIXNODE_PLEDGE};
+    use crate::contract::{
+        execute, query, INITIAL_COST_PARAMS_CHANGE_NOTICE_EPOCHS,
+        INITIAL_MAXIMUM_MIXNODE_OPERATING_COST, INITIAL_MIXNODE_PLEDGE,
+    };
     use crate::error::ContractError;
+    use crate::mixnodes::bonding_queries::query_pending_cost_params_change;
     use crate::mixnodes::transactions::validate_mixnode_pledge;
     use crate::support::tests;
     use crate::support::tests::test_helpers;
     use config::defaults::DENOM;
     use cosmwasm_std::testing::{mock_env, mock_info};
-    use cosmwasm_std::{coins, BankMsg, Response};
+    use cosmwasm_std::{coin, coins, BankMsg, Response};
     use cosmwasm_std::{from_binary, Addr, Uint128};
     use mixnet_contract_common::{
         ExecuteMsg, Layer, LayerDistribution, MixNode, PagedMixnodeResponse, QueryMsg,
@@ -499,6 +808,49 @@ pub mod tests {
         // if we attempt to register a second node from the same address, should we get an error? It would probably be polite.
     }
 
+    #[test]
+    fn adding_mixnode_with_operating_cost_above_maximum_fails() {
+        let mut deps = test_helpers::init_contract();
+
+        let keypair = crypto::asymmetric::identity::KeyPair::new(&mut thread_rng());
+        let owner_signature = keypair
+            .private_key()
+            .sign("mix-owner".as_bytes())
+            .to_base58_string();
+        let sphinx_keypair = crypto::asymmetric::encryption::KeyPair::new(&mut thread_rng());
+
+        let mix_node = MixNode {
+            identity_key: keypair.public_key().to_base58_string(),
+            sphinx_key: sphinx_keypair.public_key().to_base58_string(),
+            operating_cost: Some(INITIAL_MAXIMUM_MIXNODE_OPERATING_COST + Uint128::new(1)),
+            ..tests::fixtures::mix_node_fixture()
+        };
+        let info = mock_info("mix-owner", &tests::fixtures::good_mixnode_pledge());
+        let msg = ExecuteMsg::BondMixnode {
+            mix_node: mix_node.clone(),
+            owner_signature: owner_signature.clone(),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert_eq!(
+            res,
+            Err(ContractError::OperatingCostTooHigh {
+                cost: INITIAL_MAXIMUM_MIXNODE_OPERATING_COST.u128() + 1,
+                maximum: INITIAL_MAXIMUM_MIXNODE_OPERATING_COST.u128(),
+            })
+        );
+
+        // declaring exactly the maximum is fine
+        let msg = ExecuteMsg::BondMixnode {
+            mix_node: MixNode {
+                operating_cost: Some(INITIAL_MAXIMUM_MIXNODE_OPERATING_COST),
+                ..mix_node
+            },
+            owner_signature,
+        };
+        assert!(execute(deps.as_mut(), mock_env(), info, msg).is_ok());
+    }
+
     #[test]
     fn adding_mixnode_without_existing_owner_succeeds() {
         let mut deps = test_helpers::init_contract();
@@ -682,6 +1034,72 @@ pub mod tests {
         assert_eq!(&Addr::unchecked("bob"), mix_node_bonds[0].owner());
     }
 
+    #[test]
+    fn decreasing_mixnode_pledge() {
+        let mut deps = test_helpers::init_contract();
+        let pledge = Uint128::new(150_000_000);
+        let identity = test_helpers::add_mixnode("bob", coins(pledge.u128(), DENOM), deps.as_mut());
+
+        // only the owner can decrease the pledge
+        let info = mock_info("fred", &[]);
+        let msg = ExecuteMsg::DecreaseMixnodePledge {
+            amount: coin(10_000_000, DENOM),
+        };
+        assert_eq!(
+            Err(ContractError::NoAssociatedMixNodeBond {
+                owner: Addr::unchecked("fred")
+            }),
+            execute(deps.as_mut(), mock_env(), info, msg)
+        );
+
+        // can't go below the minimum pledge
+        let info = mock_info("bob", &[]);
+        let msg = ExecuteMsg::DecreaseMixnodePledge {
+            amount: coin(50_000_001, DENOM),
+        };
+        assert_eq!(
+            Err(ContractError::PledgeBelowMinimum {
+                current: pledge.u128(),
+                decrease: 50_000_001,
+                minimum: INITIAL_MIXNODE_PLEDGE.u128(),
+            }),
+            execute(deps.as_mut(), mock_env(), info, msg)
+        );
+
+        let info = mock_info("bob", &[]);
+        let msg = ExecuteMsg::DecreaseMixnodePledge {
+            amount: coin(0, DENOM),
+        };
+        assert_eq!(
+            Err(ContractError::EmptyPledgeDecrease),
+            execute(deps.as_mut(), mock_env(), info, msg)
+        );
+
+        let info = mock_info("bob", &[]);
+        let msg = ExecuteMsg::DecreaseMixnodePledge {
+            amount: coin(50_000_000, DENOM),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let expected_response = Response::new()
+            .add_message(BankMsg::Send {
+                to_address: "bob".to_string(),
+                amount: coins(50_000_000, DENOM),
+            })
+            .add_event(new_mixnode_pledge_decrease_event(
+                &Addr::unchecked("bob"),
+                &None,
+                &coin(50_000_000, DENOM),
+                &coin(INITIAL_MIXNODE_PLEDGE.u128(), DENOM),
+                &identity,
+            ));
+        assert_eq!(expected_response, res);
+
+        // the node is still bonded, just with a smaller pledge
+        let bond = storage::mixnodes().load(&deps.storage, &identity).unwrap();
+        assert_eq!(INITIAL_MIXNODE_PLEDGE, bond.pledge_amount.amount);
+    }
+
     #[test]
     fn removing_mixnode_clears_ownership() {
         let mut deps = test_helpers::init_contract();
@@ -743,6 +1161,7 @@ pub mod tests {
         // try updating a non existing mixnode bond
         let msg = ExecuteMsg::UpdateMixnodeConfig {
             profit_margin_percent: 10,
+            operating_cost: None,
         };
         let ret = execute(deps.as_mut(), env.clone(), info.clone(), msg);
         assert_eq!(
@@ -752,7 +1171,7 @@ pub mod tests {
             })
         );
 
-        test_helpers::add_mixnode(
+        let identity = test_helpers::add_mixnode(
             sender,
             tests::fixtures::good_mixnode_pledge(),
             deps.as_mut(),
@@ -779,6 +1198,7 @@ pub mod tests {
         let profit_margin_percent = 101;
         let msg = ExecuteMsg::UpdateMixnodeConfig {
             profit_margin_percent,
+            operating_cost: None,
         };
         let ret = execute(deps.as_mut(), env.clone(), info.clone(), msg);
         assert_eq!(
@@ -788,23 +1208,179 @@ pub mod tests {
             ))
         );
 
+        // or with an operating cost above the allowed maximum
+        let msg = ExecuteMsg::UpdateMixnodeConfig {
+            profit_margin_percent: fixture_profit_margin,
+            operating_cost: Some(INITIAL_MAXIMUM_MIXNODE_OPERATING_COST + Uint128::new(1)),
+        };
+        let ret = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert_eq!(
+            ret,
+            Err(ContractError::OperatingCostTooHigh {
+                cost: INITIAL_MAXIMUM_MIXNODE_OPERATING_COST.u128() + 1,
+                maximum: INITIAL_MAXIMUM_MIXNODE_OPERATING_COST.u128(),
+            })
+        );
+
         let profit_margin_percent = fixture_profit_margin + 10;
+        let operating_cost = Uint128::new(123_000_000);
         let msg = ExecuteMsg::UpdateMixnodeConfig {
             profit_margin_percent,
+            operating_cost: Some(operating_cost),
         };
-        execute(deps.as_mut(), env, info, msg).unwrap();
-        assert_eq!(
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // the change is only queued and doesn't affect the bond until the notice period is over
+        let current_epoch = interval_storage::current_epoch(deps.as_ref().storage).unwrap();
+        let expected_change = PendingCostParamsChange {
             profit_margin_percent,
-            storage::mixnodes()
-                .idx
-                .owner
-                .item(deps.as_ref().storage, Addr::unchecked("bob"))
+            operating_cost: Some(operating_cost),
+            effective_epoch_id: current_epoch.id() + 1 + INITIAL_COST_PARAMS_CHANGE_NOTICE_EPOCHS,
+        };
+        let pending_change = query_pending_cost_params_change(deps.as_ref(), identity.clone())
+            .unwrap()
+            .unwrap();
+        assert_eq!(expected_change, pending_change);
+
+        for _ in 0..INITIAL_COST_PARAMS_CHANGE_NOTICE_EPOCHS {
+            test_helpers::update_env_and_progress_interval(&mut env, deps.as_mut().storage);
+            let bond = storage::mixnodes()
+                .load(deps.as_ref().storage, &identity)
+                .unwrap();
+            assert_eq!(fixture_profit_margin, bond.mix_node.profit_margin_percent);
+            assert_eq!(None, bond.mix_node.operating_cost);
+        }
+
+        // and is applied at the start of the effective epoch
+        test_helpers::update_env_and_progress_interval(&mut env, deps.as_mut().storage);
+        let bond = storage::mixnodes()
+            .load(deps.as_ref().storage, &identity)
+            .unwrap();
+        assert_eq!(
+            expected_change.effective_epoch_id,
+            interval_storage::current_epoch(deps.as_ref().storage)
                 .unwrap()
+                .id()
+        );
+        assert_eq!(profit_margin_percent, bond.mix_node.profit_margin_percent);
+        assert_eq!(Some(operating_cost), bond.mix_node.operating_cost);
+        assert!(query_pending_cost_params_change(deps.as_ref(), identity)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn updating_mixnode_config_keeps_current_operating_cost_if_not_provided() {
+        let sender = "bob";
+        let mut env = mock_env();
+        let mut deps = test_helpers::init_contract();
+        let info = mock_info(sender, &[]);
+        let identity = test_helpers::add_mixnode(
+            sender,
+            tests::fixtures::good_mixnode_pledge(),
+            deps.as_mut(),
+        );
+
+        let operating_cost = Uint128::new(42_000_000);
+        storage::mixnodes()
+            .update::<_, ContractError>(
+                deps.as_mut().storage,
+                &identity,
+                env.block.height,
+                |bond| {
+                    let mut bond = bond.unwrap();
+                    bond.mix_node.operating_cost = Some(operating_cost);
+                    Ok(bond)
+                },
+            )
+            .unwrap();
+
+        env.block.time = env.block.time.plus_seconds(MIN_PM_UPDATE_INTERVAL + 1);
+        let msg = ExecuteMsg::UpdateMixnodeConfig {
+            profit_margin_percent: 42,
+            operating_cost: None,
+        };
+        execute(deps.as_mut(), env, info, msg).unwrap();
+
+        let pending_change = query_pending_cost_params_change(deps.as_ref(), identity)
+            .unwrap()
+            .unwrap();
+        assert_eq!(42, pending_change.profit_margin_percent);
+        assert_eq!(Some(operating_cost), pending_change.operating_cost);
+    }
+
+    #[test]
+    fn pending_cost_params_change_is_dropped_on_unbonding() {
+        let sender = "bob";
+        let mut env = mock_env();
+        let mut deps = test_helpers::init_contract();
+        let info = mock_info(sender, &[]);
+        let identity = test_helpers::add_mixnode(
+            sender,
+            tests::fixtures::good_mixnode_pledge(),
+            deps.as_mut(),
+        );
+
+        env.block.time = env.block.time.plus_seconds(MIN_PM_UPDATE_INTERVAL + 1);
+        let msg = ExecuteMsg::UpdateMixnodeConfig {
+            profit_margin_percent: 42,
+            operating_cost: None,
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert!(
+            query_pending_cost_params_change(deps.as_ref(), identity.clone())
                 .unwrap()
-                .1
-                .mix_node
-                .profit_margin_percent
+                .is_some()
         );
+
+        execute(deps.as_mut(), env, info, ExecuteMsg::UnbondMixnode {}).unwrap();
+        assert!(query_pending_cost_params_change(deps.as_ref(), identity)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn only_bounded_number_of_due_cost_params_changes_is_applied_at_once() {
+        let env = mock_env();
+        let mut deps = test_helpers::init_contract();
+        let current_epoch = interval_storage::current_epoch(deps.as_ref().storage).unwrap();
+
+        // the nodes don't exist anymore, so the changes are just going to be dropped
+        for i in 0..=MAX_COST_PARAMS_CHANGES_PER_EPOCH {
+            let change = PendingCostParamsChange {
+                profit_margin_percent: 42,
+                operating_cost: None,
+                effective_epoch_id: current_epoch.id(),
+            };
+            storage::pending_cost_params_changes()
+                .save(deps.as_mut().storage, &format!("due{}", i), &change)
+                .unwrap();
+        }
+        let future_change = PendingCostParamsChange {
+            profit_margin_percent: 42,
+            operating_cost: None,
+            effective_epoch_id: current_epoch.id() + 1,
+        };
+        storage::pending_cost_params_changes()
+            .save(deps.as_mut().storage, "future", &future_change)
+            .unwrap();
+
+        let remaining = |deps: &cosmwasm_std::OwnedDeps<_, _, _>| {
+            storage::pending_cost_params_changes()
+                .range(deps.as_ref().storage, None, None, Order::Ascending)
+                .map(|res| res.unwrap().0)
+                .collect::<Vec<_>>()
+        };
+
+        apply_pending_cost_params_changes(deps.as_mut().storage, env.block.height, &current_epoch)
+            .unwrap();
+        let left = remaining(&deps);
+        assert_eq!(2, left.len());
+        assert!(left.contains(&"future".to_string()));
+
+        apply_pending_cost_params_changes(deps.as_mut().storage, env.block.height, &current_epoch)
+            .unwrap();
+        assert_eq!(vec!["future".to_string()], remaining(&deps));
     }
 
     #[test]
@@ -895,6 +1471,7 @@ pub mod tests {
             identity_key: keypair1.public_key().to_base58_string(),
             version: "v0.1.2.3".to_string(),
             profit_margin_percent: 10,
+            operating_cost: None,
         };
 
         assert!(
@@ -930,6 +1507,7 @@ pub mod tests {
             identity_key: keypair1.public_key().to_base58_string(),
             version: "v0.1.2.3".to_string(),
             profit_margin_percent: 10,
+            operating_cost: None,
         };
 
         assert!(try_add_mixnode(
@@ -944,13 +1522,110 @@ pub mod tests {
         env.block.time = env.block.time.plus_seconds(MIN_PM_UPDATE_INTERVAL - 1);
 
         // fails if too soon after bonding
-        assert!(
-            try_update_mixnode_config(deps.as_mut(), env.clone(), info_alice.clone(), 20).is_err()
-        );
+        assert!(try_update_mixnode_config(
+            deps.as_mut(),
+            env.clone(),
+            info_alice.clone(),
+            20,
+            None
+        )
+        .is_err());
 
         env.block.time = env.block.time.plus_seconds(2);
 
         // succeds after some time
-        assert!(try_update_mixnode_config(deps.as_mut(), env, info_alice, 20).is_ok());
+        assert!(try_update_mixnode_config(deps.as_mut(), env, info_alice, 20, None).is_ok());
+    }
+
+    #[test]
+    fn jailing_and_unjailing_mixnode() {
+        let mut deps = test_helpers::init_contract();
+        let mut env = mock_env();
+        let rewarding_validator =
+            crate::mixnet_contract_settings::storage::rewarding_validator_address(&deps.storage)
+                .unwrap();
+        let identity = test_helpers::add_mixnode(
+            "alice",
+            tests::fixtures::good_mixnode_pledge(),
+            deps.as_mut(),
+        );
+
+        // only the rewarding validator can jail nodes
+        let res = try_jail_mixnode(deps.as_mut(), mock_info("alice", &[]), identity.clone());
+        assert_eq!(Err(ContractError::Unauthorized), res);
+
+        let res = try_jail_mixnode(
+            deps.as_mut(),
+            mock_info(rewarding_validator.as_str(), &[]),
+            "non-existent".to_string(),
+        );
+        assert_eq!(
+            Ok(Response::new().add_event(new_not_found_mixnode_jailing_event("non-existent"))),
+            res
+        );
+
+        // the cooldown is controlled by the contract settings
+        let jail_cooldown_epochs = 3;
+        mixnet_params_storage::CONTRACT_STATE
+            .update::<_, ContractError>(deps.as_mut().storage, |mut state| {
+                state.params.jail_cooldown_epochs = jail_cooldown_epochs;
+                Ok(state)
+            })
+            .unwrap();
+
+        let res = try_unjail_mixnode(deps.as_mut(), mock_info("alice", &[]));
+        assert_eq!(
+            Err(ContractError::MixnodeNotJailed {
+                identity: identity.clone()
+            }),
+            res
+        );
+
+        let epoch_id = interval_storage::current_epoch(&deps.storage).unwrap().id();
+        try_jail_mixnode(
+            deps.as_mut(),
+            mock_info(rewarding_validator.as_str(), &[]),
+            identity.clone(),
+        )
+        .unwrap();
+
+        let bond = storage::read_full_mixnode_bond(&deps.storage, &identity)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            Some(JailStatus {
+                jailed_at_epoch_id: epoch_id,
+                unjail_epoch_id: epoch_id + jail_cooldown_epochs,
+            }),
+            bond.jailed
+        );
+
+        let res = try_jail_mixnode(
+            deps.as_mut(),
+            mock_info(rewarding_validator.as_str(), &[]),
+            identity.clone(),
+        );
+        assert_eq!(
+            Ok(Response::new().add_event(new_already_jailed_mixnode_jailing_event(&identity))),
+            res
+        );
+
+        // the operator has to wait for the cooldown to pass
+        for _ in 0..jail_cooldown_epochs - 1 {
+            test_helpers::update_env_and_progress_interval(&mut env, deps.as_mut().storage);
+        }
+        let res = try_unjail_mixnode(deps.as_mut(), mock_info("alice", &[]));
+        assert_eq!(
+            Err(ContractError::UnjailTooSoon {
+                identity: identity.clone(),
+                current_epoch_id: epoch_id + jail_cooldown_epochs - 1,
+                unjail_epoch_id: epoch_id + jail_cooldown_epochs,
+            }),
+            res
+        );
+
+        test_helpers::update_env_and_progress_interval(&mut env, deps.as_mut().storage);
+        try_unjail_mixnode(deps.as_mut(), mock_info("alice", &[])).unwrap();
+        assert!(!storage::JAILED_MIXNODES.has(&deps.storage, &identity));
     }
 }
@@ -19,8 +19,9 @@ use cosmwasm_std::{Addr, Api, Coin, DepsMut, Env, MessageInfo, Order, Response,
 use cw_storage_plus::Bound;
 use mixnet_contract_common::events::{
     new_compound_delegator_reward_event, new_compound_operator_reward_event,
-    new_mix_operator_rewarding_event, new_not_found_mix_operator_rewarding_event,
-    new_too_fresh_bond_mix_operator_rewarding_event, new_zero_uptime_mix_operator_rewarding_event,
+    new_invalid_operating_cost_mix_operator_rewarding_event, new_mix_operator_rewarding_event,
+    new_not_found_mix_operator_rewarding_event, new_too_fresh_bond_mix_operator_rewarding_event,
+    new_zero_uptime_mix_operator_rewarding_event,
 };
 use mixnet_contract_common::mixnode::StoredNodeRewardResult;
 use mixnet_contract_common::reward_params::{NodeEpochRewards, NodeRewardParams, RewardParams};
@@ -133,7 +134,7 @@ pub fn calculate_operator_reward(
                 {
                     if let Some(ref epoch_rewards) = bond.epoch_rewards {
                         // Compound rewards from previous heights
-                        match epoch_rewards.operator_reward(bond.profit_margin()) {
+                        match epoch_rewards.operator_reward(bond.epoch_profit_margin()) {
                             Ok(reward) => return Ok(accumulated_reward + reward),
                             Err(err) => {
                                 debug_with_visibility(
@@ -349,7 +350,7 @@ pub fn calculate_delegator_reward(
                                 Ok(params) => {
                                     let reward_at_height = match epoch_rewards.delegation_reward(
                                         delegation_at_height + accumulated_reward,
-                                        bond.profit_margin(),
+                                        bond.epoch_profit_margin(),
                                         params,
                                     ) {
                                         Ok(reward) => {
@@ -464,6 +465,22 @@ pub(crate) fn try_reward_mixnode(
         );
     }
 
+    // check if its operating cost can be used for rewarding at all
+    if params
+        .operator_cost_for(current_bond.operating_cost())
+        .is_none()
+    {
+        storage::REWARDING_STATUS.save(
+            deps.storage,
+            (epoch.id(), mix_identity.clone()),
+            &RewardingStatus::Complete(Default::default()),
+        )?;
+
+        return Ok(Response::new().add_event(
+            new_invalid_operating_cost_mix_operator_rewarding_event(epoch.id(), &mix_identity),
+        ));
+    }
+
     let mut node_reward_params = params;
     node_reward_params.set_reward_blockstamp(env.block.height);
 
@@ -474,6 +491,8 @@ pub(crate) fn try_reward_mixnode(
 
     current_bond.accumulated_rewards =
         Some(current_bond.accumulated_rewards() + stored_node_result.reward());
+    let profit_margin_percent = current_bond.mix_node.profit_margin_percent;
+    let operating_cost = Uint128::new(current_bond.operating_cost());
     let mut stored_bond: StoredMixnodeBond = current_bond.into();
     // technically we don't have to set the total_delegation bucket, but it makes things easier
     // in different places that we can guarantee that if node exists, so does the data behind the total delegation
@@ -482,6 +501,8 @@ pub(crate) fn try_reward_mixnode(
         node_reward_params,
         stored_node_result,
         epoch.id(),
+        profit_margin_percent,
+        operating_cost,
     ));
 
     let identity = stored_bond.identity();
@@ -541,8 +562,8 @@ pub mod tests {
     use cosmwasm_std::testing::{mock_env, mock_info};
     use cosmwasm_std::{coin, coins, Addr, StdError, Timestamp, Uint128};
     use mixnet_contract_common::events::{
-        must_find_attribute, BOND_TOO_FRESH_VALUE, NO_REWARD_REASON_KEY,
-        OPERATOR_REWARDING_EVENT_TYPE,
+        must_find_attribute, BOND_TOO_FRESH_VALUE, INVALID_OPERATING_COST_VALUE,
+        NO_REWARD_REASON_KEY, OPERATOR_REWARDING_EVENT_TYPE,
     };
     use mixnet_contract_common::reward_params::{NodeRewardParams, RewardParams};
     use mixnet_contract_common::{Delegation, IdentityKey, Interval, Layer, MixNode};
@@ -678,6 +699,57 @@ pub mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn rewarding_mixnode_with_unrepresentable_operating_cost_is_skipped() {
+        let mut deps = test_helpers::init_contract();
+        let mut env = mock_env();
+        let sender = rewarding_validator_address(&deps.storage).unwrap();
+        let info = mock_info(&sender, &[]);
+
+        let node_identity = test_helpers::add_mixnode(
+            "node-owner",
+            tests::fixtures::good_mixnode_pledge(),
+            deps.as_mut(),
+        );
+        env.block.height += constants::MINIMUM_BLOCK_AGE_FOR_REWARDING;
+
+        // this can't be declared via bonding or config updates, but make sure rewarding doesn't
+        // blow up if such a cost ever ends up in the storage
+        mixnodes_storage::mixnodes()
+            .update::<_, ContractError>(
+                deps.as_mut().storage,
+                &node_identity,
+                env.block.height,
+                |bond| {
+                    let mut bond = bond.unwrap();
+                    bond.mix_node.operating_cost = Some(Uint128::new(u128::MAX));
+                    Ok(bond)
+                },
+            )
+            .unwrap();
+
+        let res = try_reward_mixnode(
+            deps.as_mut(),
+            env,
+            info,
+            node_identity.clone(),
+            tests::fixtures::node_reward_params_fixture(100),
+        )
+        .unwrap();
+        assert_eq!(
+            INVALID_OPERATING_COST_VALUE,
+            must_find_attribute(&res.events[0], NO_REWARD_REASON_KEY)
+        );
+
+        let epoch = crate::interval::storage::current_epoch(&deps.storage).unwrap();
+        assert!(matches!(
+            storage::REWARDING_STATUS
+                .may_load(&deps.storage, (epoch.id(), node_identity))
+                .unwrap(),
+            Some(RewardingStatus::Complete(_))
+        ));
+    }
+
     #[test]
     fn rewarding_mixnode_blockstamp_based() {
         let mut deps = test_helpers::init_contract();
@@ -16,6 +16,7 @@ pub fn mix_node_fixture() -> MixNode {
         identity_key: "identity".to_string(),
         version: "0.10.0".to_string(),
         profit_margin_percent: 10,
+        operating_cost: None,
     }
 }
 
@@ -3,22 +3,25 @@ use crate::storage::{account_from_address, ADMIN, MIXNET_CONTRACT_ADDRESS};
 use crate::traits::{
     DelegatingAccount, GatewayBondingAccount, MixnodeBondingAccount, VestingAccount,
 };
-use crate::vesting::{populate_vesting_periods, Account};
+use crate::vesting::{populate_vesting_periods, Account, ScheduleOptions};
 use config::defaults::DENOM;
 use cosmwasm_std::{
     coin, entry_point, to_binary, BankMsg, Coin, Deps, DepsMut, Env, MessageInfo, QueryResponse,
-    Response, Timestamp,
+    Response, Timestamp, Uint128,
 };
 use mixnet_contract_common::{Gateway, IdentityKey, MixNode};
 use vesting_contract_common::events::{
     new_ownership_transfer_event, new_periodic_vesting_account_event,
     new_staking_address_update_event, new_track_gateway_unbond_event,
-    new_track_mixnode_unbond_event, new_track_undelegation_event, new_vested_coins_withdraw_event,
+    new_track_mixnode_unbond_event, new_track_redelegation_event, new_track_undelegation_event,
+    new_unvested_coins_revocation_event, new_vested_coins_withdraw_event,
 };
 use vesting_contract_common::messages::{
     ExecuteMsg, InitMsg, MigrateMsg, QueryMsg, VestingSpecification,
 };
-use vesting_contract_common::{OriginalVestingResponse, Period, PledgeData};
+use vesting_contract_common::{
+    OriginalVestingResponse, Period, PledgeData, VestingScheduleResponse,
+};
 
 #[entry_point]
 pub fn instantiate(
@@ -52,7 +55,8 @@ pub fn execute(
         ExecuteMsg::CompoundOperatorReward {} => try_compound_operator_reward(info, deps),
         ExecuteMsg::UpdateMixnodeConfig {
             profit_margin_percent,
-        } => try_update_mixnode_config(profit_margin_percent, info, deps),
+            operating_cost,
+        } => try_update_mixnode_config(profit_margin_percent, operating_cost, info, deps),
         ExecuteMsg::UpdateMixnetAddress { address } => {
             try_update_mixnet_address(address, info, deps)
         }
@@ -63,6 +67,13 @@ pub fn execute(
         ExecuteMsg::UndelegateFromMixnode { mix_identity } => {
             try_undelegate_from_mixnode(mix_identity, info, deps)
         }
+        ExecuteMsg::PartiallyUndelegateFromMixnode {
+            mix_identity,
+            amount,
+        } => try_partially_undelegate_from_mixnode(mix_identity, amount, info, deps),
+        ExecuteMsg::RedelegateFromMixnode { from, to, amount } => {
+            try_redelegate_from_mixnode(from, to, amount, info, env, deps)
+        }
         ExecuteMsg::CreateAccount {
             owner_address,
             staking_address,
@@ -82,16 +93,28 @@ pub fn execute(
             owner,
             mix_identity,
             amount,
-        } => try_track_undelegation(&owner, mix_identity, amount, info, deps),
+            partial,
+        } => try_track_undelegation(&owner, mix_identity, amount, partial, info, deps),
+        ExecuteMsg::TrackRedelegation {
+            owner,
+            from,
+            to,
+            amount,
+        } => try_track_redelegation(&owner, from, to, amount, info, env, deps),
         ExecuteMsg::BondMixnode {
             mix_node,
             owner_signature,
             amount,
         } => try_bond_mixnode(mix_node, owner_signature, amount, info, env, deps),
         ExecuteMsg::UnbondMixnode {} => try_unbond_mixnode(info, deps),
-        ExecuteMsg::TrackUnbondMixnode { owner, amount } => {
-            try_track_unbond_mixnode(&owner, amount, info, deps)
+        ExecuteMsg::DecreaseMixnodePledge { amount } => {
+            try_decrease_mixnode_pledge(amount, info, deps)
         }
+        ExecuteMsg::TrackUnbondMixnode {
+            owner,
+            amount,
+            partial,
+        } => try_track_unbond_mixnode(&owner, amount, partial, info, deps),
         ExecuteMsg::BondGateway {
             gateway,
             owner_signature,
@@ -107,16 +130,20 @@ pub fn execute(
         ExecuteMsg::UpdateStakingAddress { to_address } => {
             try_update_staking_address(to_address, info, deps)
         }
+        ExecuteMsg::RevokeUnvestedCoins { owner_address } => {
+            try_revoke_unvested_coins(&owner_address, info, env, deps)
+        }
     }
 }
 
 pub fn try_update_mixnode_config(
     profit_margin_percent: u8,
+    operating_cost: Option<Uint128>,
     info: MessageInfo,
     deps: DepsMut,
 ) -> Result<Response, ContractError> {
     let account = account_from_address(info.sender.as_str(), deps.storage, deps.api)?;
-    account.try_update_mixnode_config(profit_margin_percent, deps.storage)
+    account.try_update_mixnode_config(profit_margin_percent, operating_cost, deps.storage)
 }
 
 // Only contract admin, set at init
@@ -150,7 +177,7 @@ pub fn try_withdraw_vested_coins(
     }
     let spendable_coins = account.spendable_coins(None, &env, deps.storage)?;
     if amount.amount <= spendable_coins.amount {
-        let new_balance = account.withdraw(&amount, deps.storage)?;
+        let new_balance = account.withdraw(&amount, env.block.time, deps.storage)?;
 
         let send_tokens = BankMsg::Send {
             to_address: account.owner_address().as_str().to_string(),
@@ -172,6 +199,33 @@ pub fn try_withdraw_vested_coins(
     }
 }
 
+// Only contract admin, set at init
+fn try_revoke_unvested_coins(
+    owner_address: &str,
+    info: MessageInfo,
+    env: Env,
+    deps: DepsMut<'_>,
+) -> Result<Response, ContractError> {
+    if info.sender != ADMIN.load(deps.storage)? {
+        return Err(ContractError::NotAdmin(info.sender.as_str().to_string()));
+    }
+
+    let mut account = account_from_address(owner_address, deps.storage, deps.api)?;
+    let revoked = coin(account.revoke_unvested(&env, deps.storage)?.u128(), DENOM);
+
+    let return_tokens = BankMsg::Send {
+        to_address: info.sender.as_str().to_string(),
+        amount: vec![revoked.clone()],
+    };
+
+    Ok(Response::new()
+        .add_message(return_tokens)
+        .add_event(new_unvested_coins_revocation_event(
+            &account.owner_address(),
+            &revoked,
+        )))
+}
+
 fn try_transfer_ownership(
     to_address: String,
     info: MessageInfo,
@@ -264,9 +318,20 @@ pub fn try_unbond_mixnode(info: MessageInfo, deps: DepsMut<'_>) -> Result<Respon
     account.try_unbond_mixnode(deps.storage)
 }
 
+pub fn try_decrease_mixnode_pledge(
+    amount: Coin,
+    info: MessageInfo,
+    deps: DepsMut<'_>,
+) -> Result<Response, ContractError> {
+    let amount = validate_funds(&[amount])?;
+    let account = account_from_address(info.sender.as_str(), deps.storage, deps.api)?;
+    account.try_decrease_mixnode_pledge(amount, deps.storage)
+}
+
 pub fn try_track_unbond_mixnode(
     owner: &str,
     amount: Coin,
+    partial: bool,
     info: MessageInfo,
     deps: DepsMut<'_>,
 ) -> Result<Response, ContractError> {
@@ -274,7 +339,11 @@ pub fn try_track_unbond_mixnode(
         return Err(ContractError::NotMixnetContract(info.sender));
     }
     let account = account_from_address(owner, deps.storage, deps.api)?;
-    account.try_track_unbond_mixnode(amount, deps.storage)?;
+    if partial {
+        account.try_track_decrease_mixnode_pledge(amount, deps.storage)?;
+    } else {
+        account.try_track_unbond_mixnode(amount, deps.storage)?;
+    }
     Ok(Response::new().add_event(new_track_mixnode_unbond_event()))
 }
 
@@ -282,6 +351,7 @@ fn try_track_undelegation(
     address: &str,
     mix_identity: IdentityKey,
     amount: Coin,
+    partial: bool,
     info: MessageInfo,
     deps: DepsMut<'_>,
 ) -> Result<Response, ContractError> {
@@ -289,10 +359,31 @@ fn try_track_undelegation(
         return Err(ContractError::NotMixnetContract(info.sender));
     }
     let account = account_from_address(address, deps.storage, deps.api)?;
-    account.track_undelegation(mix_identity, amount, deps.storage)?;
+    if partial {
+        account.track_partial_undelegation(mix_identity, amount, deps.storage)?;
+    } else {
+        account.track_undelegation(mix_identity, amount, deps.storage)?;
+    }
     Ok(Response::new().add_event(new_track_undelegation_event()))
 }
 
+fn try_track_redelegation(
+    address: &str,
+    from: IdentityKey,
+    to: IdentityKey,
+    amount: Coin,
+    info: MessageInfo,
+    env: Env,
+    deps: DepsMut<'_>,
+) -> Result<Response, ContractError> {
+    if info.sender != MIXNET_CONTRACT_ADDRESS.load(deps.storage)? {
+        return Err(ContractError::NotMixnetContract(info.sender));
+    }
+    let account = account_from_address(address, deps.storage, deps.api)?;
+    account.track_redelegation(env.block.height, from, to, amount, deps.storage)?;
+    Ok(Response::new().add_event(new_track_redelegation_event()))
+}
+
 fn try_delegate_to_mixnode(
     mix_identity: IdentityKey,
     amount: Coin,
@@ -323,6 +414,30 @@ fn try_undelegate_from_mixnode(
     account.try_undelegate_from_mixnode(mix_identity, deps.storage)
 }
 
+fn try_partially_undelegate_from_mixnode(
+    mix_identity: IdentityKey,
+    amount: Coin,
+    info: MessageInfo,
+    deps: DepsMut<'_>,
+) -> Result<Response, ContractError> {
+    let amount = validate_funds(&[amount])?;
+    let account = account_from_address(info.sender.as_str(), deps.storage, deps.api)?;
+    account.try_partially_undelegate_from_mixnode(mix_identity, amount, deps.storage)
+}
+
+fn try_redelegate_from_mixnode(
+    from: IdentityKey,
+    to: IdentityKey,
+    amount: Coin,
+    info: MessageInfo,
+    env: Env,
+    deps: DepsMut<'_>,
+) -> Result<Response, ContractError> {
+    let amount = validate_funds(&[amount])?;
+    let account = account_from_address(info.sender.as_str(), deps.storage, deps.api)?;
+    account.try_redelegate_from_mixnode(from, to, amount, &env, deps.storage)
+}
+
 fn try_create_periodic_vesting_account(
     owner_address: &str,
     staking_address: Option<String>,
@@ -356,6 +471,39 @@ fn try_create_periodic_vesting_account(
         .start_time()
         .unwrap_or_else(|| env.block.time.seconds());
 
+    if let Some(tranches) = vesting_spec.tranches() {
+        if let Some(num_periods) = vesting_spec.explicit_num_periods() {
+            if num_periods != tranches.len() as u64 {
+                return Err(ContractError::TrancheCountMismatch {
+                    tranches: tranches.len(),
+                    periods: num_periods,
+                });
+            }
+        }
+        let sum: u128 = tranches.iter().map(|tranche| tranche.u128()).sum();
+        if sum != coin.amount.u128() {
+            return Err(ContractError::TrancheSumMismatch {
+                sum,
+                funds: coin.amount.u128(),
+            });
+        }
+    }
+
+    let cliff_time = match vesting_spec.cliff_seconds() {
+        Some(cliff_seconds) => {
+            if cliff_seconds > vesting_spec.num_periods() * vesting_spec.period_seconds() {
+                return Err(ContractError::CliffAfterVestingEnd { cliff_seconds });
+            }
+            Some(Timestamp::from_seconds(start_time + cliff_seconds))
+        }
+        None => None,
+    };
+    let schedule = ScheduleOptions {
+        cliff_time,
+        tranches: vesting_spec.tranches().map(|tranches| tranches.to_vec()),
+        revocable: vesting_spec.revocable(),
+    };
+
     let periods = populate_vesting_periods(start_time, vesting_spec);
 
     let start_time = Timestamp::from_seconds(start_time);
@@ -368,6 +516,7 @@ fn try_create_periodic_vesting_account(
         coin.clone(),
         start_time,
         periods,
+        schedule,
         deps.storage,
     )?;
 
@@ -451,6 +600,13 @@ pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg) -> Result<QueryResponse, C
         QueryMsg::GetCurrentVestingPeriod { address } => {
             to_binary(&try_get_current_vesting_period(&address, deps, env)?)
         }
+        QueryMsg::GetVestingSchedule {
+            vesting_account_address,
+        } => to_binary(&try_get_vesting_schedule(
+            &vesting_account_address,
+            env,
+            deps,
+        )?),
     };
 
     Ok(query_res?)
@@ -465,6 +621,15 @@ pub fn try_get_current_vesting_period(
     Ok(account.get_current_vesting_period(env.block.time))
 }
 
+pub fn try_get_vesting_schedule(
+    vesting_account_address: &str,
+    env: Env,
+    deps: Deps<'_>,
+) -> Result<VestingScheduleResponse, ContractError> {
+    let account = account_from_address(vesting_account_address, deps.storage, deps.api)?;
+    account.vesting_schedule(&env, deps.storage)
+}
+
 pub fn try_get_mixnode(address: &str, deps: Deps<'_>) -> Result<Option<PledgeData>, ContractError> {
     let account = account_from_address(address, deps.storage, deps.api)?;
     account.load_mixnode_pledge(deps.storage)
@@ -46,4 +46,14 @@ pub enum ContractError {
     AccountAlreadyExists(String),
     #[error("VESTING ({}): Too few coins sent for vesting account creation, sent {sent}, need at least {need}", line!())]
     MinVestingFunds { sent: u128, need: u128 },
+    #[error("VESTING ({}): Vesting tranches add up to {sum}, while the account is created with {funds}", line!())]
+    TrancheSumMismatch { sum: u128, funds: u128 },
+    #[error("VESTING ({}): Received {tranches} vesting tranches for {periods} vesting periods", line!())]
+    TrancheCountMismatch { tranches: usize, periods: u64 },
+    #[error("VESTING ({}): Vesting cliff of {cliff_seconds}s ends after the vesting itself is over", line!())]
+    CliffAfterVestingEnd { cliff_seconds: u64 },
+    #[error("VESTING ({}): Unvested coins of account {0} can't be revoked", line!())]
+    NotRevocable(String),
+    #[error("VESTING ({}): Account {0} has no unvested coins that could be revoked", line!())]
+    NothingToRevoke(String),
 }
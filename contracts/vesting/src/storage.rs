@@ -12,6 +12,8 @@ const ACCOUNTS: Map<'_, String, Account> = Map::new("acc");
 // Holds data related to individual accounts
 const BALANCES: Map<'_, u32, Uint128> = Map::new("blc");
 const WITHDRAWNS: Map<'_, u32, Uint128> = Map::new("wthd");
+// Withdrawals made within each of the vesting periods, keyed by the account and the period index
+const PERIOD_WITHDRAWNS: Map<'_, (u32, u32), Uint128> = Map::new("pwthd");
 const BOND_PLEDGES: Map<'_, u32, PledgeData> = Map::new("bnd");
 const GATEWAY_PLEDGES: Map<'_, u32, PledgeData> = Map::new("gtw");
 pub const DELEGATIONS: Map<'_, (u32, IdentityKey, BlockHeight), Uint128> = Map::new("dlg");
@@ -47,6 +49,26 @@ pub fn load_withdrawn(key: u32, storage: &dyn Storage) -> Result<Uint128, Contra
         .unwrap_or_else(Uint128::zero))
 }
 
+pub fn load_period_withdrawn(
+    key: u32,
+    period: u32,
+    storage: &dyn Storage,
+) -> Result<Uint128, ContractError> {
+    Ok(PERIOD_WITHDRAWNS
+        .may_load(storage, (key, period))?
+        .unwrap_or_default())
+}
+
+pub fn save_period_withdrawn(
+    key: u32,
+    period: u32,
+    value: Uint128,
+    storage: &mut dyn Storage,
+) -> Result<(), ContractError> {
+    PERIOD_WITHDRAWNS.save(storage, (key, period), &value)?;
+    Ok(())
+}
+
 pub fn load_balance(key: u32, storage: &dyn Storage) -> Result<Uint128, ContractError> {
     Ok(BALANCES
         .may_load(storage, key)
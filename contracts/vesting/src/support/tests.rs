@@ -1,7 +1,7 @@
 #[cfg(test)]
 pub mod helpers {
     use crate::contract::instantiate;
-    use crate::vesting::{populate_vesting_periods, Account};
+    use crate::vesting::{populate_vesting_periods, Account, ScheduleOptions};
     use config::defaults::DENOM;
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier};
     use cosmwasm_std::{Addr, Coin, Empty, Env, MemoryStorage, OwnedDeps, Storage, Uint128};
@@ -35,6 +35,7 @@ pub mod helpers {
             },
             start_time_ts,
             periods,
+            ScheduleOptions::default(),
             storage,
         )
         .unwrap()
@@ -54,6 +55,7 @@ pub mod helpers {
             },
             start_time,
             periods,
+            ScheduleOptions::default(),
             storage,
         )
         .unwrap()
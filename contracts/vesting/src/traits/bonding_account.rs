@@ -1,5 +1,5 @@
 use crate::errors::ContractError;
-use cosmwasm_std::{Coin, Env, Response, Storage};
+use cosmwasm_std::{Coin, Env, Response, Storage, Uint128};
 use mixnet_contract_common::{Gateway, MixNode};
 
 pub trait MixnodeBondingAccount {
@@ -25,9 +25,22 @@ pub trait MixnodeBondingAccount {
         storage: &mut dyn Storage,
     ) -> Result<(), ContractError>;
 
+    fn try_decrease_mixnode_pledge(
+        &self,
+        amount: Coin,
+        storage: &dyn Storage,
+    ) -> Result<Response, ContractError>;
+
+    fn try_track_decrease_mixnode_pledge(
+        &self,
+        amount: Coin,
+        storage: &mut dyn Storage,
+    ) -> Result<(), ContractError>;
+
     fn try_update_mixnode_config(
         &self,
         profit_margin_percent: u8,
+        operating_cost: Option<Uint128>,
         storage: &mut dyn Storage,
     ) -> Result<Response, ContractError>;
 }
@@ -23,6 +23,22 @@ pub trait DelegatingAccount {
         storage: &dyn Storage,
     ) -> Result<Response, ContractError>;
 
+    fn try_partially_undelegate_from_mixnode(
+        &self,
+        mix_identity: IdentityKey,
+        amount: Coin,
+        storage: &dyn Storage,
+    ) -> Result<Response, ContractError>;
+
+    fn try_redelegate_from_mixnode(
+        &self,
+        from: IdentityKey,
+        to: IdentityKey,
+        amount: Coin,
+        env: &Env,
+        storage: &mut dyn Storage,
+    ) -> Result<Response, ContractError>;
+
     // track_delegation performs internal vesting accounting necessary when
     // delegating from a vesting account. It accepts the current block height, the
     // delegation amount and balance of all coins whose denomination exists in
@@ -44,4 +60,23 @@ pub trait DelegatingAccount {
         amount: Coin,
         storage: &mut dyn Storage,
     ) -> Result<(), ContractError>;
+    // track_partial_undelegation performs internal vesting accounting necessary when
+    // only part of the delegation got withdrawn.
+    fn track_partial_undelegation(
+        &self,
+        mix_identity: IdentityKey,
+        amount: Coin,
+        storage: &mut dyn Storage,
+    ) -> Result<(), ContractError>;
+
+    // track_redelegation moves the tracked delegation of up to `amount` from one mixnode
+    // to another. The account balance is unaffected as no tokens leave the mixnet contract.
+    fn track_redelegation(
+        &self,
+        block_height: u64,
+        from: IdentityKey,
+        to: IdentityKey,
+        amount: Coin,
+        storage: &mut dyn Storage,
+    ) -> Result<(), ContractError>;
 }
@@ -1,12 +1,13 @@
 use crate::errors::ContractError;
 use crate::storage::save_delegation;
+use crate::storage::DELEGATIONS;
 use crate::storage::MIXNET_CONTRACT_ADDRESS;
 use crate::traits::DelegatingAccount;
-use cosmwasm_std::{wasm_execute, Coin, Env, Response, Storage, Uint128};
+use cosmwasm_std::{wasm_execute, Coin, Env, Order, Response, Storage, Uint128};
 use mixnet_contract_common::ExecuteMsg as MixnetExecuteMsg;
 use mixnet_contract_common::IdentityKey;
 use vesting_contract_common::events::{
-    new_vesting_delegation_event, new_vesting_undelegation_event,
+    new_vesting_delegation_event, new_vesting_redelegation_event, new_vesting_undelegation_event,
 };
 use vesting_contract_common::one_ucoin;
 
@@ -96,6 +97,66 @@ impl DelegatingAccount for Account {
             .add_event(new_vesting_undelegation_event()))
     }
 
+    fn try_partially_undelegate_from_mixnode(
+        &self,
+        mix_identity: IdentityKey,
+        amount: Coin,
+        storage: &dyn Storage,
+    ) -> Result<Response, ContractError> {
+        if !self.any_delegation_for_mix(&mix_identity, storage) {
+            return Err(ContractError::NoSuchDelegation(
+                self.owner_address(),
+                mix_identity,
+            ));
+        }
+
+        let msg = MixnetExecuteMsg::PartiallyUndelegateFromMixnodeOnBehalf {
+            mix_identity,
+            amount,
+            delegate: self.owner_address().into_string(),
+        };
+        let undelegate_from_mixnode = wasm_execute(
+            MIXNET_CONTRACT_ADDRESS.load(storage)?,
+            &msg,
+            vec![one_ucoin()],
+        )?;
+
+        Ok(Response::new()
+            .add_message(undelegate_from_mixnode)
+            .add_event(new_vesting_undelegation_event()))
+    }
+
+    fn try_redelegate_from_mixnode(
+        &self,
+        from: IdentityKey,
+        to: IdentityKey,
+        amount: Coin,
+        _env: &Env,
+        storage: &mut dyn Storage,
+    ) -> Result<Response, ContractError> {
+        if !self.any_delegation_for_mix(&from, storage) {
+            return Err(ContractError::NoSuchDelegation(self.owner_address(), from));
+        }
+
+        let msg = MixnetExecuteMsg::RedelegateFromMixnodeOnBehalf {
+            from,
+            to,
+            amount,
+            delegate: self.owner_address().into_string(),
+        };
+        let redelegate_from_mixnode = wasm_execute(
+            MIXNET_CONTRACT_ADDRESS.load(storage)?,
+            &msg,
+            vec![one_ucoin()],
+        )?;
+        // the mixnet contract calls back with `TrackRedelegation` within the same transaction,
+        // so that the bookkeeping reflects whatever part of the stake it actually moved
+
+        Ok(Response::new()
+            .add_message(redelegate_from_mixnode)
+            .add_event(new_vesting_redelegation_event()))
+    }
+
     fn track_delegation(
         &self,
         block_height: u64,
@@ -125,4 +186,62 @@ impl DelegatingAccount for Account {
         self.save_balance(new_balance, storage)?;
         Ok(())
     }
+
+    fn track_partial_undelegation(
+        &self,
+        mix_identity: IdentityKey,
+        amount: Coin,
+        storage: &mut dyn Storage,
+    ) -> Result<(), ContractError> {
+        let tracked = self.total_delegations_for_mix(mix_identity.clone(), storage)?;
+        let latest_height = DELEGATIONS
+            .prefix((self.storage_key(), mix_identity.clone()))
+            .keys(storage, None, None, Order::Descending)
+            .filter_map(|height| height.ok())
+            .next();
+
+        // similarly to redelegation, the withdrawn amount might include rewards that we never tracked.
+        // the remaining entry is kept even if it's zero as the delegation still exists in the mixnet contract
+        self.remove_delegations_for_mix(&mix_identity, storage)?;
+        if let Some(height) = latest_height {
+            save_delegation(
+                (self.storage_key(), mix_identity, height),
+                tracked.saturating_sub(amount.amount),
+                storage,
+            )?;
+        }
+
+        let new_balance = Uint128::new(self.load_balance(storage)?.u128() + amount.amount.u128());
+        self.save_balance(new_balance, storage)?;
+        Ok(())
+    }
+
+    fn track_redelegation(
+        &self,
+        block_height: u64,
+        from: IdentityKey,
+        to: IdentityKey,
+        amount: Coin,
+        storage: &mut dyn Storage,
+    ) -> Result<(), ContractError> {
+        // rewards are not tracked by the vesting contract, so if the redelegated amount includes
+        // any of them, only the tracked part is moved
+        let tracked_from = self.total_delegations_for_mix(from.clone(), storage)?;
+        let moved = amount.amount.min(tracked_from);
+        let remaining = tracked_from - moved;
+
+        self.remove_delegations_for_mix(&from, storage)?;
+        if !remaining.is_zero() {
+            save_delegation((self.storage_key(), from, block_height), remaining, storage)?;
+        }
+
+        if !moved.is_zero() {
+            let key = (self.storage_key(), to, block_height);
+            let existing = DELEGATIONS
+                .may_load(storage, key.clone())?
+                .unwrap_or_default();
+            save_delegation(key, existing + moved, storage)?;
+        }
+        Ok(())
+    }
 }
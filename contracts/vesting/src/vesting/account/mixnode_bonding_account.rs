@@ -4,8 +4,8 @@ use crate::traits::MixnodeBondingAccount;
 use cosmwasm_std::{wasm_execute, Coin, Env, Response, Storage, Uint128};
 use mixnet_contract_common::{ExecuteMsg as MixnetExecuteMsg, MixNode};
 use vesting_contract_common::events::{
-    new_vesting_mixnode_bonding_event, new_vesting_mixnode_unbonding_event,
-    new_vesting_update_mixnode_config_event,
+    new_vesting_mixnode_bonding_event, new_vesting_mixnode_pledge_decrease_event,
+    new_vesting_mixnode_unbonding_event, new_vesting_update_mixnode_config_event,
 };
 
 use vesting_contract_common::one_ucoin;
@@ -34,10 +34,12 @@ impl MixnodeBondingAccount for Account {
     fn try_update_mixnode_config(
         &self,
         profit_margin_percent: u8,
+        operating_cost: Option<Uint128>,
         storage: &mut dyn Storage,
     ) -> Result<Response, ContractError> {
         let msg = MixnetExecuteMsg::UpdateMixnodeConfigOnBehalf {
             profit_margin_percent,
+            operating_cost,
             owner: self.owner_address().into_string(),
         };
 
@@ -129,4 +131,50 @@ impl MixnodeBondingAccount for Account {
         self.remove_mixnode_pledge(storage)?;
         Ok(())
     }
+
+    fn try_decrease_mixnode_pledge(
+        &self,
+        amount: Coin,
+        storage: &dyn Storage,
+    ) -> Result<Response, ContractError> {
+        if self.load_mixnode_pledge(storage)?.is_none() {
+            return Err(ContractError::NoBondFound(
+                self.owner_address().as_str().to_string(),
+            ));
+        }
+
+        let msg = MixnetExecuteMsg::DecreaseMixnodePledgeOnBehalf {
+            amount,
+            owner: self.owner_address().into_string(),
+        };
+        let decrease_pledge_msg = wasm_execute(
+            MIXNET_CONTRACT_ADDRESS.load(storage)?,
+            &msg,
+            vec![one_ucoin()],
+        )?;
+
+        Ok(Response::new()
+            .add_message(decrease_pledge_msg)
+            .add_event(new_vesting_mixnode_pledge_decrease_event()))
+    }
+
+    fn try_track_decrease_mixnode_pledge(
+        &self,
+        amount: Coin,
+        storage: &mut dyn Storage,
+    ) -> Result<(), ContractError> {
+        let pledge = self
+            .load_mixnode_pledge(storage)?
+            .ok_or_else(|| ContractError::NoBondFound(self.owner_address().as_str().to_string()))?;
+
+        let new_balance = Uint128::new(self.load_balance(storage)?.u128() + amount.amount.u128());
+        self.save_balance(new_balance, storage)?;
+
+        // the withdrawn amount might include compounded rewards that were never tracked here.
+        // note that the pledge data is kept even if it drops to zero, as the node is still bonded
+        let mut remaining = pledge.amount();
+        remaining.amount = remaining.amount.saturating_sub(amount.amount);
+        self.save_mixnode_pledge(PledgeData::new(remaining, pledge.block_time()), storage)?;
+        Ok(())
+    }
 }
@@ -1,16 +1,17 @@
-use super::VestingPeriod;
+use super::{Revocation, ScheduleOptions, VestingPeriod};
 use crate::errors::ContractError;
 use crate::storage::{
-    load_balance, load_bond_pledge, load_gateway_pledge, load_withdrawn, remove_bond_pledge,
-    remove_delegation, remove_gateway_pledge, save_account, save_balance, save_bond_pledge,
-    save_gateway_pledge, save_withdrawn, DELEGATIONS, KEY,
+    load_balance, load_bond_pledge, load_gateway_pledge, load_period_withdrawn, load_withdrawn,
+    remove_bond_pledge, remove_delegation, remove_gateway_pledge, save_account, save_balance,
+    save_bond_pledge, save_gateway_pledge, save_period_withdrawn, save_withdrawn, DELEGATIONS, KEY,
 };
-use cosmwasm_std::{Addr, Coin, Order, Storage, Timestamp, Uint128};
+use crate::traits::VestingAccount;
+use cosmwasm_std::{Addr, Coin, Env, Order, Storage, Timestamp, Uint128};
 use cw_storage_plus::Bound;
 use mixnet_contract_common::IdentityKey;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use vesting_contract_common::{Period, PledgeData};
+use vesting_contract_common::{Period, PledgeData, VestingPeriodSummary, VestingScheduleResponse};
 
 mod delegating_account;
 mod gateway_bonding_account;
@@ -31,6 +32,10 @@ pub struct Account {
     periods: Vec<VestingPeriod>,
     coin: Coin,
     storage_key: u32,
+    #[serde(default)]
+    schedule: ScheduleOptions,
+    #[serde(default)]
+    revocation: Option<Revocation>,
 }
 
 impl Account {
@@ -40,6 +45,7 @@ impl Account {
         coin: Coin,
         start_time: Timestamp,
         periods: Vec<VestingPeriod>,
+        schedule: ScheduleOptions,
         storage: &mut dyn Storage,
     ) -> Result<Self, ContractError> {
         let storage_key = generate_storage_key(storage)?;
@@ -51,6 +57,8 @@ impl Account {
             periods,
             coin,
             storage_key,
+            schedule,
+            revocation: None,
         };
         save_account(&account, storage)?;
         account.save_balance(amount, storage)?;
@@ -91,6 +99,10 @@ impl Account {
         self.start_time
     }
 
+    pub fn revocation(&self) -> Option<&Revocation> {
+        self.revocation.as_ref()
+    }
+
     pub fn tokens_per_period(&self) -> Result<u128, ContractError> {
         let amount = self.coin.amount.u128();
         if amount < self.num_vesting_periods() as u128 {
@@ -101,6 +113,106 @@ impl Account {
         }
     }
 
+    // amount of coins that have vested by the end of the first `num_periods` periods
+    pub fn vested_after_periods(&self, num_periods: usize) -> Result<u128, ContractError> {
+        match &self.schedule.tranches {
+            Some(tranches) => Ok(tranches
+                .iter()
+                .take(num_periods)
+                .map(|tranche| tranche.u128())
+                .sum()),
+            None => Ok(self.tokens_per_period()? * num_periods as u128),
+        }
+    }
+
+    /// Summarises all vesting periods of the account. The amounts for each period are evaluated at its end,
+    /// assuming the current delegations, pledges and withdrawals remain unchanged.
+    pub fn vesting_schedule(
+        &self,
+        env: &Env,
+        storage: &dyn Storage,
+    ) -> Result<VestingScheduleResponse, ContractError> {
+        let denom = self.coin.denom.clone();
+        let num_periods = self.num_vesting_periods();
+
+        let mut periods = Vec::with_capacity(num_periods);
+        let mut previously_vested = Uint128::zero();
+        for (i, period) in self.periods.iter().enumerate() {
+            // any remainder is lumped into the last period, which is only over once we're past its end
+            let evaluation_time = if i + 1 == num_periods {
+                period.end_time().plus_seconds(1)
+            } else {
+                period.end_time()
+            };
+            let at = Some(evaluation_time);
+
+            // going by the actually vested coins accounts for both the cliff and the revocation
+            let vested = self.get_vested_coins(at, env)?;
+            let tranche = vested.amount.saturating_sub(previously_vested);
+            previously_vested = vested.amount;
+
+            periods.push(VestingPeriodSummary {
+                start_time: Timestamp::from_seconds(period.start_time),
+                end_time: period.end_time(),
+                tranche: Coin::new(tranche.u128(), &denom),
+                withdrawn: Coin::new(self.load_period_withdrawn(i, storage)?.u128(), &denom),
+                vested,
+                vesting: self.get_vesting_coins(at, env)?,
+                delegated_free: self.get_delegated_free(at, env, storage)?,
+                delegated_vesting: self.get_delegated_vesting(at, env, storage)?,
+                pledged_free: self.get_pledged_free(at, env, storage)?,
+                pledged_vesting: self.get_pledged_vesting(at, env, storage)?,
+                locked: self.locked_coins(at, env, storage)?,
+                spendable: self.spendable_coins(at, env, storage)?,
+            })
+        }
+
+        Ok(VestingScheduleResponse {
+            original_vesting: self.coin(),
+            balance: Coin::new(self.load_balance(storage)?.u128(), &denom),
+            withdrawn: Coin::new(self.load_withdrawn(storage)?.u128(), &denom),
+            current_period: self.get_current_vesting_period(env.block.time),
+            periods,
+        })
+    }
+
+    /// Takes back all unvested coins that are currently held by the account, i.e. that are not
+    /// delegated or pledged, and stops any further vesting. Returns the amount of revoked coins.
+    pub fn revoke_unvested(
+        &mut self,
+        env: &Env,
+        storage: &mut dyn Storage,
+    ) -> Result<Uint128, ContractError> {
+        if !self.schedule.revocable {
+            return Err(ContractError::NotRevocable(self.owner_address.to_string()));
+        }
+
+        let revoked = self.locked_coins(None, env, storage)?.amount;
+        if revoked.is_zero() {
+            return Err(ContractError::NothingToRevoke(
+                self.owner_address.to_string(),
+            ));
+        }
+
+        // the delegated or pledged unvested coins remain locked and could be revoked once they return to the account
+        self.revocation = Some(match self.revocation.take() {
+            Some(revocation) => Revocation {
+                revoked_at: revocation.revoked_at,
+                amount: revocation.amount + revoked,
+            },
+            None => Revocation {
+                revoked_at: env.block.time,
+                amount: revoked,
+            },
+        });
+        save_account(self, storage)?;
+
+        let balance = self.load_balance(storage)?;
+        self.save_balance(balance - revoked, storage)?;
+
+        Ok(revoked)
+    }
+
     pub fn get_current_vesting_period(&self, block_time: Timestamp) -> Period {
         // Returns the index of the next vesting period. Unless the current time is somehow in the past or vesting has not started yet.
         // In case vesting is over it will always return NUM_VESTING_PERIODS.
@@ -121,9 +233,20 @@ impl Account {
         }
     }
 
+    // index of the vesting period the withdrawals made at the given time are attributed to.
+    // Anything withdrawn after the vesting is over counts towards the last period.
+    fn withdrawal_period(&self, block_time: Timestamp) -> usize {
+        match self.get_current_vesting_period(block_time) {
+            Period::Before => 0,
+            Period::In(idx) => idx,
+            Period::After => self.num_vesting_periods().saturating_sub(1),
+        }
+    }
+
     pub fn withdraw(
         &self,
         amount: &Coin,
+        block_time: Timestamp,
         storage: &mut dyn Storage,
     ) -> Result<u128, ContractError> {
         let new_balance = self
@@ -133,6 +256,10 @@ impl Account {
         self.save_balance(Uint128::new(new_balance), storage)?;
         let withdrawn = self.load_withdrawn(storage)?;
         self.save_withdrawn(withdrawn + amount.amount, storage)?;
+
+        let period = self.withdrawal_period(block_time);
+        let period_withdrawn = self.load_period_withdrawn(period, storage)?;
+        self.save_period_withdrawn(period, period_withdrawn + amount.amount, storage)?;
         Ok(new_balance)
     }
 
@@ -148,6 +275,23 @@ impl Account {
         save_withdrawn(self.storage_key, withdrawn, storage)
     }
 
+    pub fn load_period_withdrawn(
+        &self,
+        period: usize,
+        storage: &dyn Storage,
+    ) -> Result<Uint128, ContractError> {
+        load_period_withdrawn(self.storage_key, period as u32, storage)
+    }
+
+    pub fn save_period_withdrawn(
+        &self,
+        period: usize,
+        withdrawn: Uint128,
+        storage: &mut dyn Storage,
+    ) -> Result<(), ContractError> {
+        save_period_withdrawn(self.storage_key, period as u32, withdrawn, storage)
+    }
+
     pub fn load_balance(&self, storage: &dyn Storage) -> Result<Uint128, ContractError> {
         load_balance(self.storage_key(), storage)
     }
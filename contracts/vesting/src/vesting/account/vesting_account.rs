@@ -56,7 +56,23 @@ impl VestingAccount for Account {
         block_time: Option<Timestamp>,
         env: &Env,
     ) -> Result<Coin, ContractError> {
-        let block_time = block_time.unwrap_or(env.block.time);
+        let mut block_time = block_time.unwrap_or(env.block.time);
+        // nothing vests after the unvested coins got revoked
+        if let Some(revocation) = &self.revocation {
+            if revocation.revoked_at < block_time {
+                block_time = revocation.revoked_at
+            }
+        }
+
+        if let Some(cliff_time) = self.schedule.cliff_time {
+            if block_time < cliff_time {
+                return Ok(Coin {
+                    amount: Uint128::zero(),
+                    denom: DENOM.to_string(),
+                });
+            }
+        }
+
         let period = self.get_current_vesting_period(block_time);
 
         let amount = match period {
@@ -65,7 +81,7 @@ impl VestingAccount for Account {
                 denom: DENOM.to_string(),
             },
             Period::In(idx) => Coin {
-                amount: Uint128::new(self.tokens_per_period()? * idx as u128),
+                amount: Uint128::new(self.vested_after_periods(idx)?),
                 denom: DENOM.to_string(),
             },
             Period::After => Coin {
@@ -81,8 +97,14 @@ impl VestingAccount for Account {
         block_time: Option<Timestamp>,
         env: &Env,
     ) -> Result<Coin, ContractError> {
+        let revoked = self
+            .revocation
+            .as_ref()
+            .map(|revocation| revocation.amount)
+            .unwrap_or_default();
         Ok(Coin {
             amount: self.get_original_vesting().amount().amount
+                - revoked
                 - self.get_vested_coins(block_time, env)?.amount,
             denom: DENOM.to_string(),
         })
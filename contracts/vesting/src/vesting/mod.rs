@@ -1,4 +1,4 @@
-use cosmwasm_std::Timestamp;
+use cosmwasm_std::{Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -19,6 +19,22 @@ impl VestingPeriod {
     }
 }
 
+// customizations of the schedule on top of the vesting periods themselves
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ScheduleOptions {
+    pub cliff_time: Option<Timestamp>,
+    // amounts vesting in each period, if not set, the coins are split equally between all periods
+    pub tranches: Option<Vec<Uint128>>,
+    pub revocable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Revocation {
+    // no further coins vest after this point
+    pub revoked_at: Timestamp,
+    pub amount: Uint128,
+}
+
 pub fn populate_vesting_periods(
     start_time: u64,
     vesting_spec: VestingSpecification,
@@ -37,6 +53,7 @@ pub fn populate_vesting_periods(
 #[cfg(test)]
 mod tests {
     use crate::contract::execute;
+    use crate::errors::ContractError;
     use crate::storage::load_account;
     use crate::support::tests::helpers::{
         init_contract, vesting_account_mid_fixture, vesting_account_new_fixture,
@@ -46,9 +63,9 @@ mod tests {
     use crate::traits::{GatewayBondingAccount, MixnodeBondingAccount};
     use config::defaults::DENOM;
     use cosmwasm_std::testing::{mock_env, mock_info};
-    use cosmwasm_std::{coins, Addr, Coin, Timestamp, Uint128};
+    use cosmwasm_std::{coins, Addr, BankMsg, Coin, CosmosMsg, Timestamp, Uint128};
     use mixnet_contract_common::{Gateway, MixNode};
-    use vesting_contract_common::messages::ExecuteMsg;
+    use vesting_contract_common::messages::{ExecuteMsg, VestingSpecification};
     use vesting_contract_common::Period;
 
     #[test]
@@ -321,6 +338,7 @@ mod tests {
         account
             .withdraw(
                 &account.spendable_coins(None, &env, &deps.storage).unwrap(),
+                env.block.time,
                 &mut deps.storage,
             )
             .unwrap();
@@ -487,6 +505,217 @@ mod tests {
         assert_eq!(Uint128::zero(), delegated_free.amount);
     }
 
+    #[test]
+    fn test_redelegations() {
+        let mut deps = init_contract();
+        let mut env = mock_env();
+
+        let account = vesting_account_new_fixture(&mut deps.storage, &env);
+
+        account
+            .try_delegate_to_mixnode(
+                "alice".to_string(),
+                Coin {
+                    amount: Uint128::new(500_000_000_000),
+                    denom: DENOM.to_string(),
+                },
+                &env,
+                &mut deps.storage,
+            )
+            .unwrap();
+
+        // there's nothing to redelegate from "bob"
+        let err = account.try_redelegate_from_mixnode(
+            "bob".to_string(),
+            "alice".to_string(),
+            Coin::new(100_000_000_000, DENOM),
+            &env,
+            &mut deps.storage,
+        );
+        assert!(err.is_err());
+
+        env.block.height += 1;
+        account
+            .try_redelegate_from_mixnode(
+                "alice".to_string(),
+                "bob".to_string(),
+                Coin::new(200_000_000_000, DENOM),
+                &env,
+                &mut deps.storage,
+            )
+            .unwrap();
+
+        // nothing is moved until the mixnet contract calls back with `TrackRedelegation`
+        assert_eq!(
+            Uint128::new(500_000_000_000),
+            account
+                .total_delegations_for_mix("alice".to_string(), &deps.storage)
+                .unwrap()
+        );
+        assert!(!account.any_delegation_for_mix("bob", &deps.storage));
+
+        account
+            .track_redelegation(
+                env.block.height,
+                "alice".to_string(),
+                "bob".to_string(),
+                Coin::new(200_000_000_000, DENOM),
+                &mut deps.storage,
+            )
+            .unwrap();
+
+        assert_eq!(
+            Uint128::new(300_000_000_000),
+            account
+                .total_delegations_for_mix("alice".to_string(), &deps.storage)
+                .unwrap()
+        );
+        assert_eq!(
+            Uint128::new(200_000_000_000),
+            account
+                .total_delegations_for_mix("bob".to_string(), &deps.storage)
+                .unwrap()
+        );
+        // redelegation doesn't touch the account balance
+        assert_eq!(
+            Uint128::new(500_000_000_000),
+            account.load_balance(&deps.storage).unwrap()
+        );
+
+        // redelegating more than what's tracked (i.e. including rewards) only moves the tracked part
+        account
+            .track_redelegation(
+                env.block.height,
+                "alice".to_string(),
+                "bob".to_string(),
+                Coin::new(350_000_000_000, DENOM),
+                &mut deps.storage,
+            )
+            .unwrap();
+
+        assert!(!account.any_delegation_for_mix("alice", &deps.storage));
+        assert_eq!(
+            Uint128::new(500_000_000_000),
+            account
+                .total_delegations_for_mix("bob".to_string(), &deps.storage)
+                .unwrap()
+        );
+        assert_eq!(
+            Uint128::new(500_000_000_000),
+            account.total_delegations(&deps.storage).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_partial_undelegation_tracking() {
+        let mut deps = init_contract();
+        let env = mock_env();
+
+        let account = vesting_account_new_fixture(&mut deps.storage, &env);
+
+        account
+            .try_delegate_to_mixnode(
+                "alice".to_string(),
+                Coin::new(500_000_000_000, DENOM),
+                &env,
+                &mut deps.storage,
+            )
+            .unwrap();
+
+        let err = account.try_partially_undelegate_from_mixnode(
+            "bob".to_string(),
+            Coin::new(100_000_000_000, DENOM),
+            &deps.storage,
+        );
+        assert!(err.is_err());
+
+        account
+            .track_partial_undelegation(
+                "alice".to_string(),
+                Coin::new(200_000_000_000, DENOM),
+                &mut deps.storage,
+            )
+            .unwrap();
+        assert_eq!(
+            Uint128::new(300_000_000_000),
+            account
+                .total_delegations_for_mix("alice".to_string(), &deps.storage)
+                .unwrap()
+        );
+        assert_eq!(
+            Uint128::new(700_000_000_000),
+            account.load_balance(&deps.storage).unwrap()
+        );
+
+        // withdrawing more than tracked (i.e. including rewards) keeps the delegation around
+        account
+            .track_partial_undelegation(
+                "alice".to_string(),
+                Coin::new(350_000_000_000, DENOM),
+                &mut deps.storage,
+            )
+            .unwrap();
+        assert!(account.any_delegation_for_mix("alice", &deps.storage));
+        assert_eq!(
+            Uint128::zero(),
+            account
+                .total_delegations_for_mix("alice".to_string(), &deps.storage)
+                .unwrap()
+        );
+        assert_eq!(
+            Uint128::new(1_050_000_000_000),
+            account.load_balance(&deps.storage).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_mixnode_pledge_decrease_tracking() {
+        let mut deps = init_contract();
+        let env = mock_env();
+
+        let account = vesting_account_new_fixture(&mut deps.storage, &env);
+
+        let err =
+            account.try_decrease_mixnode_pledge(Coin::new(100_000_000_000, DENOM), &deps.storage);
+        assert!(err.is_err());
+
+        let mix_node = MixNode {
+            host: "mix.node.org".to_string(),
+            mix_port: 1789,
+            verloc_port: 1790,
+            http_api_port: 8000,
+            sphinx_key: "sphinx".to_string(),
+            identity_key: "identity".to_string(),
+            version: "0.10.0".to_string(),
+            profit_margin_percent: 10,
+            operating_cost: None,
+        };
+        account
+            .try_bond_mixnode(
+                mix_node,
+                "alice".to_string(),
+                Coin::new(500_000_000_000, DENOM),
+                &env,
+                &mut deps.storage,
+            )
+            .unwrap();
+
+        assert!(account
+            .try_decrease_mixnode_pledge(Coin::new(100_000_000_000, DENOM), &deps.storage)
+            .is_ok());
+
+        account
+            .try_track_decrease_mixnode_pledge(Coin::new(100_000_000_000, DENOM), &mut deps.storage)
+            .unwrap();
+
+        let pledge = account.load_mixnode_pledge(&deps.storage).unwrap().unwrap();
+        assert_eq!(Uint128::new(400_000_000_000), pledge.amount().amount);
+        assert_eq!(
+            Uint128::new(600_000_000_000),
+            account.load_balance(&deps.storage).unwrap()
+        );
+    }
+
     #[test]
     fn test_mixnode_bonds() {
         let mut deps = init_contract();
@@ -503,6 +732,7 @@ mod tests {
             identity_key: "identity".to_string(),
             version: "0.10.0".to_string(),
             profit_margin_percent: 10,
+            operating_cost: None,
         };
         // Try delegating too much
         let err = account.try_bond_mixnode(
@@ -726,4 +956,352 @@ mod tests {
             .unwrap();
         assert_eq!(Uint128::zero(), bonded_vesting.amount);
     }
+
+    #[test]
+    fn test_custom_vesting_schedule() {
+        let mut deps = init_contract();
+        let env = mock_env();
+        let start_time = env.block.time.seconds();
+        let info = mock_info("admin", &coins(1_000_000, DENOM));
+        let tranches = vec![
+            Uint128::new(100_000),
+            Uint128::new(200_000),
+            Uint128::new(300_000),
+            Uint128::new(400_000),
+        ];
+        let create_account = |vesting_spec: VestingSpecification| ExecuteMsg::CreateAccount {
+            owner_address: "owner".to_string(),
+            staking_address: None,
+            vesting_spec: Some(vesting_spec),
+        };
+
+        // tranches have to add up to the total amount
+        let msg = create_account(
+            VestingSpecification::new(None, Some(3600), None)
+                .with_tranches(vec![Uint128::new(1000)]),
+        );
+        assert_eq!(
+            execute(deps.as_mut(), env.clone(), info.clone(), msg),
+            Err(ContractError::TrancheSumMismatch {
+                sum: 1000,
+                funds: 1_000_000
+            })
+        );
+
+        // and match the number of periods, if it was set explicitly
+        let msg = create_account(
+            VestingSpecification::new(None, Some(3600), Some(3)).with_tranches(tranches.clone()),
+        );
+        assert_eq!(
+            execute(deps.as_mut(), env.clone(), info.clone(), msg),
+            Err(ContractError::TrancheCountMismatch {
+                tranches: 4,
+                periods: 3
+            })
+        );
+
+        // the cliff can't outlast the vesting itself
+        let msg = create_account(
+            VestingSpecification::new(None, Some(3600), None)
+                .with_tranches(tranches.clone())
+                .with_cliff(5 * 3600),
+        );
+        assert_eq!(
+            execute(deps.as_mut(), env.clone(), info.clone(), msg),
+            Err(ContractError::CliffAfterVestingEnd {
+                cliff_seconds: 5 * 3600
+            })
+        );
+
+        let msg = create_account(
+            VestingSpecification::new(None, Some(3600), None)
+                .with_tranches(tranches)
+                .with_cliff(2 * 3600 + 1800),
+        );
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let account = load_account(&Addr::unchecked("owner"), &deps.storage)
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.num_vesting_periods(), 4);
+
+        let vested_at = |seconds: u64| {
+            account
+                .get_vested_coins(Some(Timestamp::from_seconds(start_time + seconds)), &env)
+                .unwrap()
+                .amount
+        };
+
+        // nothing vests before the cliff, even though two periods are already over
+        assert_eq!(vested_at(2 * 3600 + 1), Uint128::zero());
+        // and then everything that would have vested so far vests at once
+        assert_eq!(vested_at(2 * 3600 + 1800), Uint128::new(300_000));
+        assert_eq!(vested_at(3 * 3600 + 1), Uint128::new(600_000));
+        assert_eq!(vested_at(4 * 3600 + 1), Uint128::new(1_000_000));
+        assert_eq!(
+            account
+                .get_vesting_coins(
+                    Some(Timestamp::from_seconds(start_time + 3 * 3600 + 1)),
+                    &env
+                )
+                .unwrap()
+                .amount,
+            Uint128::new(400_000)
+        );
+    }
+
+    #[test]
+    fn test_revoking_unvested_coins() {
+        let mut deps = init_contract();
+        let mut env = mock_env();
+        let start_time = env.block.time.seconds();
+        let admin = mock_info("admin", &coins(1_000_000, DENOM));
+        let create_account = |owner: &str, revocable: bool| ExecuteMsg::CreateAccount {
+            owner_address: owner.to_string(),
+            staking_address: None,
+            vesting_spec: Some(
+                VestingSpecification::new(None, Some(3600), Some(4)).with_revocable(revocable),
+            ),
+        };
+        let revoke = |owner: &str| ExecuteMsg::RevokeUnvestedCoins {
+            owner_address: owner.to_string(),
+        };
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin.clone(),
+            create_account("owner1", false),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin.clone(),
+            create_account("owner2", true),
+        )
+        .unwrap();
+
+        // accounts have to be explicitly created as revocable
+        assert_eq!(
+            execute(deps.as_mut(), env.clone(), admin.clone(), revoke("owner1")),
+            Err(ContractError::NotRevocable("owner1".to_string()))
+        );
+
+        // and only the admin can revoke the coins
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("owner2", &[]),
+                revoke("owner2")
+            ),
+            Err(ContractError::NotAdmin("owner2".to_string()))
+        );
+
+        let account = load_account(&Addr::unchecked("owner2"), &deps.storage)
+            .unwrap()
+            .unwrap();
+        account
+            .try_delegate_to_mixnode(
+                "alice".to_string(),
+                Coin::new(400_000, DENOM),
+                &env,
+                &mut deps.storage,
+            )
+            .unwrap();
+
+        // a single period has passed, so 750_000 coins are still vesting, out of which
+        // 150_000 are delegated and can't be revoked
+        env.block.time = Timestamp::from_seconds(start_time + 3600 + 1);
+        let res = execute(deps.as_mut(), env.clone(), admin.clone(), revoke("owner2")).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "admin".to_string(),
+                amount: coins(600_000, DENOM),
+            })
+        );
+
+        let account = load_account(&Addr::unchecked("owner2"), &deps.storage)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            account.load_balance(&deps.storage).unwrap(),
+            Uint128::zero()
+        );
+
+        // the account no longer vests
+        let after_vesting = Timestamp::from_seconds(start_time + 5 * 3600);
+        assert_eq!(
+            account
+                .get_vested_coins(Some(after_vesting), &env)
+                .unwrap()
+                .amount,
+            Uint128::new(250_000)
+        );
+        assert_eq!(
+            account
+                .get_vesting_coins(Some(after_vesting), &env)
+                .unwrap()
+                .amount,
+            Uint128::new(150_000)
+        );
+
+        // and there's nothing else to revoke until the delegated coins come back
+        assert_eq!(
+            execute(deps.as_mut(), env, admin, revoke("owner2")),
+            Err(ContractError::NothingToRevoke("owner2".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_vesting_schedule() {
+        let mut deps = init_contract();
+        let env = mock_env();
+        let start_time = env.block.time.seconds();
+        let msg = ExecuteMsg::CreateAccount {
+            owner_address: "owner".to_string(),
+            staking_address: None,
+            vesting_spec: Some(VestingSpecification::new(None, Some(3600), Some(3))),
+        };
+        let info = mock_info("admin", &coins(1_000_000, DENOM));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let account = load_account(&Addr::unchecked("owner"), &deps.storage)
+            .unwrap()
+            .unwrap();
+
+        let schedule = account.vesting_schedule(&env, &deps.storage).unwrap();
+        assert_eq!(schedule.original_vesting, Coin::new(1_000_000, DENOM));
+        assert_eq!(schedule.balance, Coin::new(1_000_000, DENOM));
+        assert_eq!(schedule.withdrawn, Coin::new(0, DENOM));
+        assert_eq!(schedule.current_period, Period::In(0));
+        assert_eq!(schedule.periods.len(), 3);
+
+        // the remainder of the uniform split vests in the last period
+        let tranches: Vec<u128> = schedule
+            .periods
+            .iter()
+            .map(|period| period.tranche.amount.u128())
+            .collect();
+        assert_eq!(tranches, vec![333_333, 333_333, 333_334]);
+
+        let expected_vested = [333_333u128, 666_666, 1_000_000];
+        for (i, period) in schedule.periods.iter().enumerate() {
+            assert_eq!(
+                period.start_time,
+                Timestamp::from_seconds(start_time + i as u64 * 3600)
+            );
+            assert_eq!(
+                period.end_time,
+                Timestamp::from_seconds(start_time + (i as u64 + 1) * 3600)
+            );
+            assert_eq!(period.vested.amount.u128(), expected_vested[i]);
+            assert_eq!(period.vesting.amount.u128(), 1_000_000 - expected_vested[i]);
+            assert_eq!(period.locked, period.vesting);
+            assert_eq!(period.spendable, period.vested);
+            assert_eq!(period.delegated_free.amount, Uint128::zero());
+            assert_eq!(period.pledged_vesting.amount, Uint128::zero());
+        }
+    }
+
+    #[test]
+    fn test_vesting_schedule_with_cliff() {
+        let mut deps = init_contract();
+        let env = mock_env();
+        let start_time = env.block.time.seconds();
+        let msg = ExecuteMsg::CreateAccount {
+            owner_address: "owner".to_string(),
+            staking_address: None,
+            vesting_spec: Some(
+                VestingSpecification::new(None, Some(3600), Some(4)).with_cliff(2 * 3600 + 1800),
+            ),
+        };
+        let info = mock_info("admin", &coins(1_000_000, DENOM));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let account = load_account(&Addr::unchecked("owner"), &deps.storage)
+            .unwrap()
+            .unwrap();
+
+        // withdraw some of the coins that vested at the cliff
+        account
+            .withdraw(
+                &Coin::new(100_000, DENOM),
+                Timestamp::from_seconds(start_time + 2 * 3600 + 1800),
+                &mut deps.storage,
+            )
+            .unwrap();
+
+        let schedule = account.vesting_schedule(&env, &deps.storage).unwrap();
+        let tranches: Vec<u128> = schedule
+            .periods
+            .iter()
+            .map(|period| period.tranche.amount.u128())
+            .collect();
+        // nothing vests until the cliff, at which point everything vested so far is released at once
+        assert_eq!(tranches, vec![0, 0, 750_000, 250_000]);
+
+        let withdrawn: Vec<u128> = schedule
+            .periods
+            .iter()
+            .map(|period| period.withdrawn.amount.u128())
+            .collect();
+        assert_eq!(withdrawn, vec![0, 0, 100_000, 0]);
+        assert_eq!(schedule.withdrawn, Coin::new(100_000, DENOM));
+    }
+
+    #[test]
+    fn test_vesting_schedule_of_revoked_account() {
+        let mut deps = init_contract();
+        let mut env = mock_env();
+        let start_time = env.block.time.seconds();
+        let admin = mock_info("admin", &coins(1_000_000, DENOM));
+        let msg = ExecuteMsg::CreateAccount {
+            owner_address: "owner".to_string(),
+            staking_address: None,
+            vesting_spec: Some(
+                VestingSpecification::new(None, Some(3600), Some(4)).with_revocable(true),
+            ),
+        };
+        execute(deps.as_mut(), env.clone(), admin.clone(), msg).unwrap();
+
+        // revoke everything after the first period is over
+        env.block.time = Timestamp::from_seconds(start_time + 3600 + 1);
+        let msg = ExecuteMsg::RevokeUnvestedCoins {
+            owner_address: "owner".to_string(),
+        };
+        execute(deps.as_mut(), env.clone(), admin, msg).unwrap();
+        let account = load_account(&Addr::unchecked("owner"), &deps.storage)
+            .unwrap()
+            .unwrap();
+
+        // and withdraw whatever has vested after the vesting would have been over
+        env.block.time = Timestamp::from_seconds(start_time + 5 * 3600);
+        account
+            .withdraw(
+                &Coin::new(250_000, DENOM),
+                env.block.time,
+                &mut deps.storage,
+            )
+            .unwrap();
+
+        let schedule = account.vesting_schedule(&env, &deps.storage).unwrap();
+        let tranches: Vec<u128> = schedule
+            .periods
+            .iter()
+            .map(|period| period.tranche.amount.u128())
+            .collect();
+        // nothing vests after the revocation
+        assert_eq!(tranches, vec![250_000, 0, 0, 0]);
+        for period in &schedule.periods {
+            assert_eq!(period.vested, Coin::new(250_000, DENOM));
+        }
+
+        // withdrawals made after the vesting is over count towards the last period
+        let withdrawn: Vec<u128> = schedule
+            .periods
+            .iter()
+            .map(|period| period.withdrawn.amount.u128())
+            .collect();
+        assert_eq!(withdrawn, vec![0, 0, 0, 250_000]);
+    }
 }
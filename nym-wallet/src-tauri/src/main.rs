@@ -65,6 +65,7 @@ fn main() {
       mixnet::delegate::get_delegator_rewards,
       mixnet::delegate::get_pending_delegation_events,
       mixnet::delegate::get_reverse_mix_delegations_paged,
+      mixnet::delegate::redelegate_from_mixnode,
       mixnet::delegate::undelegate_from_mixnode,
       mixnet::epoch::get_current_epoch,
       mixnet::send::send,
@@ -97,11 +98,13 @@ fn main() {
       vesting::bond::withdraw_vested_coins,
       vesting::delegate::get_pending_vesting_delegation_events,
       vesting::delegate::vesting_delegate_to_mixnode,
+      vesting::delegate::vesting_redelegate_from_mixnode,
       vesting::delegate::vesting_undelegate_from_mixnode,
       vesting::queries::delegated_free,
       vesting::queries::delegated_vesting,
       vesting::queries::get_account_info,
       vesting::queries::get_current_vesting_period,
+      vesting::queries::get_vesting_schedule,
       vesting::queries::locked_coins,
       vesting::queries::original_vesting,
       vesting::queries::spendable_coins,
@@ -16,6 +16,9 @@ pub struct TauriContractStateParams {
   minimum_gateway_pledge: String,
   mixnode_rewarded_set_size: u32,
   mixnode_active_set_size: u32,
+  cost_params_change_notice_epochs: u32,
+  maximum_mixnode_operating_cost: String,
+  jail_cooldown_epochs: u32,
 }
 
 impl From<ContractStateParams> for TauriContractStateParams {
@@ -25,6 +28,9 @@ impl From<ContractStateParams> for TauriContractStateParams {
       minimum_gateway_pledge: p.minimum_gateway_pledge.to_string(),
       mixnode_rewarded_set_size: p.mixnode_rewarded_set_size,
       mixnode_active_set_size: p.mixnode_active_set_size,
+      cost_params_change_notice_epochs: p.cost_params_change_notice_epochs,
+      maximum_mixnode_operating_cost: p.maximum_mixnode_operating_cost.to_string(),
+      jail_cooldown_epochs: p.jail_cooldown_epochs,
     }
   }
 }
@@ -38,6 +44,9 @@ impl TryFrom<TauriContractStateParams> for ContractStateParams {
       minimum_gateway_pledge: Uint128::try_from(p.minimum_gateway_pledge.as_str())?,
       mixnode_rewarded_set_size: p.mixnode_rewarded_set_size,
       mixnode_active_set_size: p.mixnode_active_set_size,
+      cost_params_change_notice_epochs: p.cost_params_change_notice_epochs,
+      maximum_mixnode_operating_cost: Uint128::try_from(p.maximum_mixnode_operating_cost.as_str())?,
+      jail_cooldown_epochs: p.jail_cooldown_epochs,
     })
   }
 }
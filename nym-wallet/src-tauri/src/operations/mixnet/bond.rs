@@ -60,7 +60,7 @@ pub async fn update_mixnode(
   state: tauri::State<'_, Arc<RwLock<State>>>,
 ) -> Result<(), BackendError> {
   nymd_client!(state)
-    .update_mixnode_config(profit_margin_percent)
+    .update_mixnode_config(profit_margin_percent, None)
     .await?;
   Ok(())
 }
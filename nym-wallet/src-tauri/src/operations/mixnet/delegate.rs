@@ -56,6 +56,25 @@ pub async fn undelegate_from_mixnode(
   ))
 }
 
+#[tauri::command]
+pub async fn redelegate_from_mixnode(
+  from: &str,
+  to: &str,
+  amount: Coin,
+  state: tauri::State<'_, Arc<RwLock<State>>>,
+) -> Result<DelegationResult, BackendError> {
+  let denom = state.read().await.current_network().denom();
+  let redelegation: CosmWasmCoin = amount.into_cosmwasm_coin(&denom)?;
+  nymd_client!(state)
+    .redelegate_from_mixnode(from, to, &redelegation)
+    .await?;
+  Ok(DelegationResult::new(
+    nymd_client!(state).address().as_ref(),
+    to,
+    Some(redelegation.into()),
+  ))
+}
+
 #[tauri::command]
 pub async fn get_reverse_mix_delegations_paged(
   state: tauri::State<'_, Arc<RwLock<State>>>,
@@ -71,7 +71,7 @@ pub async fn vesting_update_mixnode(
   state: tauri::State<'_, Arc<RwLock<State>>>,
 ) -> Result<(), BackendError> {
   nymd_client!(state)
-    .vesting_update_mixnode_config(profit_margin_percent)
+    .vesting_update_mixnode_config(profit_margin_percent, None)
     .await?;
   Ok(())
 }
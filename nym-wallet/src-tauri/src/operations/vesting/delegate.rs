@@ -61,3 +61,22 @@ pub async fn vesting_undelegate_from_mixnode(
     None,
   ))
 }
+
+#[tauri::command]
+pub async fn vesting_redelegate_from_mixnode(
+  from: &str,
+  to: &str,
+  amount: Coin,
+  state: tauri::State<'_, Arc<RwLock<State>>>,
+) -> Result<DelegationResult, BackendError> {
+  let denom = state.read().await.current_network().denom();
+  let redelegation = amount.into_cosmwasm_coin(&denom)?;
+  nymd_client!(state)
+    .vesting_redelegate_from_mixnode(from, to, redelegation.clone())
+    .await?;
+  Ok(DelegationResult::new(
+    nymd_client!(state).address().as_ref(),
+    to,
+    Some(redelegation.into()),
+  ))
+}
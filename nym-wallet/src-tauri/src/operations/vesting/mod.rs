@@ -3,7 +3,10 @@ use serde::{Deserialize, Serialize};
 use vesting_contract::vesting::Account as VestingAccount;
 use vesting_contract::vesting::VestingPeriod as VestingVestingPeriod;
 use vesting_contract_common::OriginalVestingResponse as VestingOriginalVestingResponse;
+use vesting_contract_common::Period;
 use vesting_contract_common::PledgeData as VestingPledgeData;
+use vesting_contract_common::VestingPeriodSummary as VestingVestingPeriodSummary;
+use vesting_contract_common::VestingScheduleResponse as VestingVestingScheduleResponse;
 
 pub mod bond;
 pub mod delegate;
@@ -100,3 +103,67 @@ impl From<VestingVestingPeriod> for VestingPeriod {
     }
   }
 }
+
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[cfg_attr(
+  test,
+  ts(export, export_to = "../src/types/rust/vestingperiodsummary.ts")
+)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VestingPeriodSummary {
+  start_time: u64,
+  end_time: u64,
+  tranche: Coin,
+  withdrawn: Coin,
+  vested: Coin,
+  vesting: Coin,
+  delegated_free: Coin,
+  delegated_vesting: Coin,
+  pledged_free: Coin,
+  pledged_vesting: Coin,
+  locked: Coin,
+  spendable: Coin,
+}
+
+impl From<VestingVestingPeriodSummary> for VestingPeriodSummary {
+  fn from(summary: VestingVestingPeriodSummary) -> Self {
+    Self {
+      start_time: summary.start_time.seconds(),
+      end_time: summary.end_time.seconds(),
+      tranche: summary.tranche.into(),
+      withdrawn: summary.withdrawn.into(),
+      vested: summary.vested.into(),
+      vesting: summary.vesting.into(),
+      delegated_free: summary.delegated_free.into(),
+      delegated_vesting: summary.delegated_vesting.into(),
+      pledged_free: summary.pledged_free.into(),
+      pledged_vesting: summary.pledged_vesting.into(),
+      locked: summary.locked.into(),
+      spendable: summary.spendable.into(),
+    }
+  }
+}
+
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[cfg_attr(test, ts(export, export_to = "../src/types/rust/vestingschedule.ts"))]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VestingSchedule {
+  original_vesting: Coin,
+  balance: Coin,
+  withdrawn: Coin,
+  #[cfg_attr(test, ts(type = "Period"))]
+  current_period: Period,
+  periods: Vec<VestingPeriodSummary>,
+}
+
+impl From<VestingVestingScheduleResponse> for VestingSchedule {
+  fn from(schedule: VestingVestingScheduleResponse) -> Self {
+    Self {
+      original_vesting: schedule.original_vesting.into(),
+      balance: schedule.balance.into(),
+      withdrawn: schedule.withdrawn.into(),
+      current_period: schedule.current_period,
+      periods: schedule.periods.into_iter().map(Into::into).collect(),
+    }
+  }
+}
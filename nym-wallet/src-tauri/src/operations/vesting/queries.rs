@@ -1,4 +1,4 @@
-use super::VestingAccountInfo;
+use super::{VestingAccountInfo, VestingSchedule};
 use crate::coin::Coin;
 use crate::error::BackendError;
 use crate::nymd_client;
@@ -188,6 +188,19 @@ pub async fn get_current_vesting_period(
   )
 }
 
+#[tauri::command]
+pub async fn get_vesting_schedule(
+  address: &str,
+  state: tauri::State<'_, Arc<RwLock<State>>>,
+) -> Result<VestingSchedule, BackendError> {
+  Ok(
+    nymd_client!(state)
+      .get_vesting_schedule(address)
+      .await?
+      .into(),
+  )
+}
+
 #[tauri::command]
 pub async fn get_account_info(
   address: &str,
@@ -65,6 +65,45 @@ const AdminForm: React.FC<{
               helperText={errors?.mixnode_active_set_size?.message}
             />
           </Grid>
+          <Grid item xs={12}>
+            <TextField
+              {...register('cost_params_change_notice_epochs', { valueAsNumber: true })}
+              required
+              variant="outlined"
+              id="cost_params_change_notice_epochs"
+              name="cost_params_change_notice_epochs"
+              label="Profit margin and operating cost change notice (epochs)"
+              fullWidth
+              error={!!errors.cost_params_change_notice_epochs}
+              helperText={errors?.cost_params_change_notice_epochs?.message}
+            />
+          </Grid>
+          <Grid item xs={12}>
+            <TextField
+              {...register('maximum_mixnode_operating_cost')}
+              required
+              variant="outlined"
+              id="maximum_mixnode_operating_cost"
+              name="maximum_mixnode_operating_cost"
+              label="Maximum mixnode operating cost (per epoch)"
+              fullWidth
+              error={!!errors.maximum_mixnode_operating_cost}
+              helperText={errors?.maximum_mixnode_operating_cost?.message}
+            />
+          </Grid>
+          <Grid item xs={12}>
+            <TextField
+              {...register('jail_cooldown_epochs', { valueAsNumber: true })}
+              required
+              variant="outlined"
+              id="jail_cooldown_epochs"
+              name="jail_cooldown_epochs"
+              label="Jail cooldown (epochs)"
+              fullWidth
+              error={!!errors.jail_cooldown_epochs}
+              helperText={errors?.jail_cooldown_epochs?.message}
+            />
+          </Grid>
         </Grid>
       </Box>
       <Grid
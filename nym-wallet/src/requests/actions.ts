@@ -39,6 +39,19 @@ export const undelegate = async ({
   }
 };
 
+export const redelegate = async ({
+  from,
+  to,
+  amount,
+}: {
+  from: string;
+  to: string;
+  amount: Coin;
+}): Promise<DelegationResult> => {
+  const res: DelegationResult = await invoke('redelegate_from_mixnode', { from, to, amount });
+  return res;
+};
+
 export const send = async (args: { amount: Coin; address: string; memo: string }): Promise<TauriTxResult> => {
   const res: TauriTxResult = await invoke('send', args);
   return res;
@@ -10,6 +10,7 @@ import {
   OriginalVestingResponse,
   Period,
   PledgeData,
+  VestingSchedule,
 } from '../types';
 
 export const getLockedCoins = async (): Promise<Coin> => {
@@ -52,6 +53,11 @@ export const getCurrentVestingPeriod = async (address: string): Promise<Period>
   return res;
 };
 
+export const getVestingSchedule = async (address: string): Promise<VestingSchedule> => {
+  const res: VestingSchedule = await invoke('get_vesting_schedule', { address });
+  return res;
+};
+
 export const vestingBond = async ({
   type,
   data,
@@ -86,6 +92,19 @@ export const vestingUnelegateFromMixnode = async (identity: string): Promise<Del
   return res;
 };
 
+export const vestingRedelegateFromMixnode = async ({
+  from,
+  to,
+  amount,
+}: {
+  from: string;
+  to: string;
+  amount: Coin;
+}): Promise<DelegationResult> => {
+  const res: DelegationResult = await invoke('vesting_redelegate_from_mixnode', { from, to, amount });
+  return res;
+};
+
 export const getVestingAccountInfo = async (address: string): Promise<VestingAccountInfo> => {
   const res: VestingAccountInfo = await invoke('get_account_info', { address });
   return res;
@@ -19,6 +19,8 @@ export * from './transactiondetails';
 export * from './vestingaccountinfo';
 export * from './pledgedata';
 export * from './vestingperiod';
+export * from './vestingperiodsummary';
+export * from './vestingschedule';
 export * from './pendingundelegate';
 export * from './delegationevent';
 export * from './epoch';
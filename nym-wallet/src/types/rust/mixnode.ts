@@ -7,4 +7,5 @@ export interface MixNode {
   identity_key: string;
   version: string;
   profit_margin_percent: number;
+  operating_cost: string | null;
 }
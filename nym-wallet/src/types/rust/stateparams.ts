@@ -3,4 +3,7 @@ export interface TauriContractStateParams {
   minimum_gateway_pledge: string;
   mixnode_rewarded_set_size: number;
   mixnode_active_set_size: number;
+  cost_params_change_notice_epochs: number;
+  maximum_mixnode_operating_cost: string;
+  jail_cooldown_epochs: number;
 }
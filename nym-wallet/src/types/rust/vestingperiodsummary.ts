@@ -0,0 +1,16 @@
+import type { Coin } from './coin';
+
+export interface VestingPeriodSummary {
+  start_time: bigint;
+  end_time: bigint;
+  tranche: Coin;
+  withdrawn: Coin;
+  vested: Coin;
+  vesting: Coin;
+  delegated_free: Coin;
+  delegated_vesting: Coin;
+  pledged_free: Coin;
+  pledged_vesting: Coin;
+  locked: Coin;
+  spendable: Coin;
+}
@@ -0,0 +1,11 @@
+import type { Coin } from './coin';
+import type { Period } from './period';
+import type { VestingPeriodSummary } from './vestingperiodsummary';
+
+export interface VestingSchedule {
+  original_vesting: Coin;
+  balance: Coin;
+  withdrawn: Coin;
+  current_period: Period;
+  periods: Array<VestingPeriodSummary>;
+}
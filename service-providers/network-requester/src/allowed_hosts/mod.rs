@@ -5,6 +5,7 @@ use fs::OpenOptions;
 use io::BufReader;
 use ipnetwork::IpNetwork;
 use publicsuffix::{errors, List};
+use rules::{HostRule, RequestedHost, RuleAction, RuleSet};
 use std::collections::HashSet;
 use std::fs;
 use std::fs::File;
@@ -13,8 +14,15 @@ use std::io::BufRead;
 use std::net::{IpAddr, SocketAddr};
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
-/// Filters outbound requests based on what's in an `allowed_hosts` list.
+mod rules;
+
+/// Minimum amount of time between subsequent checks of whether the rules file was modified on disk.
+const RULES_RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Filters outbound requests based on the rules in the `allowed_hosts` list.
 ///
 /// Requests to unknown hosts are automatically written to an `unknown_hosts`
 /// list so that they can be copy/pasted into the `allowed_hosts` list if desired.
@@ -22,19 +30,21 @@ use std::path::PathBuf;
 /// `unknown_hosts` file and allow new hosts (e.g. if a wallet has added a new outbound request
 /// which needs to be allowed).
 ///
-/// We rely on the list of domains at https://publicsuffix.org/ to figure out what the root
-/// domain is for a given request. This allows us to distinguish all the rules for e.g.
-/// .com, .co.uk, .co.jp, uk.com, etc, so that we can distinguish correct root-ish
-/// domains as allowed. That list is loaded once at startup from the network.
+/// The `allowed_hosts` list can also contain explicit deny rules, which take precedence over
+/// any allow rules, as well as port restrictions. Refer to the `rules` module for the exact syntax.
+/// The list is reloaded whenever it changes on disk.
+///
+/// We rely on the list of domains at https://publicsuffix.org/ to figure out whether a given request
+/// is made for a valid domain. That list is loaded once at startup from the network.
 pub(crate) struct OutboundRequestFilter {
-    allowed_hosts: HostsStore,
+    allowed_hosts: HostRulesStore,
     domain_list: publicsuffix::List,
     unknown_hosts: HostsStore,
 }
 
 impl OutboundRequestFilter {
     pub(crate) fn new(
-        allowed_hosts: HostsStore,
+        allowed_hosts: HostRulesStore,
         unknown_hosts: HostsStore,
     ) -> OutboundRequestFilter {
         let domain_list = match Self::fetch_domain_list() {
@@ -52,50 +62,94 @@ impl OutboundRequestFilter {
         publicsuffix::List::fetch()
     }
 
-    /// Returns `true` if a host's root domain is in the `allowed_hosts` list.
+    /// Returns a filter for the addresses the requested hosts resolved to,
+    /// based on the currently loaded rules.
+    pub(crate) fn resolved_address_filter(&self) -> ResolvedAddressFilter {
+        ResolvedAddressFilter {
+            rules: Arc::clone(&self.allowed_hosts.rules),
+        }
+    }
+
+    /// Returns `true` if a host is allowed by the rules in the `allowed_hosts` list and
+    /// is not explicitly denied by any of them.
     ///
-    /// If it's not in the list, return `false` and write it to the `unknown_hosts` storefile.
+    /// If no rule matches the host, return `false` and write it to the `unknown_hosts` storefile.
     pub(crate) fn check(&mut self, host: &str) -> bool {
+        self.allowed_hosts.maybe_reload();
+
         // first check if it's a socket address (ip:port)
         // (this check is performed to not incorrectly strip what we think might be a port
         // from ipv6 address, as for example ::1 contains colons but has no port
-        let allowed = if let Ok(socketaddr) = host.parse::<SocketAddr>() {
-            if !self.allowed_hosts.contains_ip_address(socketaddr.ip()) {
-                self.unknown_hosts.maybe_add_ip(socketaddr.ip());
-                return false;
-            }
-            true
+        if let Ok(socketaddr) = host.parse::<SocketAddr>() {
+            self.check_ip_address(host, socketaddr.ip(), Some(socketaddr.port()))
         } else if let Ok(ipaddr) = host.parse::<IpAddr>() {
             // then check if it was an ip address
-            if !self.allowed_hosts.contains_ip_address(ipaddr) {
-                self.unknown_hosts.maybe_add_ip(ipaddr);
-                return false;
-            }
-            true
+            self.check_ip_address(host, ipaddr, None)
         } else {
             // finally, then assume it might be a domain
             let trimmed = Self::trim_port(host);
-            if let Some(domain_root) = self.get_domain_root(&trimmed) {
+            if self.get_domain_root(&trimmed).is_some() {
                 // it's a domain
-                if !self.allowed_hosts.contains_domain(&domain_root) {
-                    self.unknown_hosts.maybe_add_domain(&trimmed);
-                    return false;
+                let domain = trimmed.to_lowercase();
+                let requested = RequestedHost::Domain(&domain);
+                match self
+                    .allowed_hosts
+                    .find_matching(&requested, Self::extract_port(host))
+                {
+                    Some(rule) => Self::is_allowed_by(host, rule),
+                    None => {
+                        self.unknown_hosts.maybe_add_domain(&trimmed);
+                        Self::log_unknown(host);
+                        false
+                    }
                 }
-                true
             } else {
                 // it's something else, no idea what, probably some nonsense
+                log::warn!(
+                    "Blocked outbound connection to {:?} as it's neither a valid domain nor an ip address",
+                    &host
+                );
                 false
             }
-        };
+        }
+    }
 
-        if !allowed {
-            log::warn!(
-                "Blocked outbound connection to {:?}, add it to allowed.list if needed",
-                &host
-            );
+    fn check_ip_address(&mut self, host: &str, address: IpAddr, port: Option<u16>) -> bool {
+        match self
+            .allowed_hosts
+            .find_matching(&RequestedHost::Ip(address), port)
+        {
+            Some(rule) => Self::is_allowed_by(host, rule),
+            None => {
+                self.unknown_hosts.maybe_add_ip(address);
+                Self::log_unknown(host);
+                false
+            }
         }
+    }
 
-        allowed
+    fn is_allowed_by(host: &str, rule: &HostRule) -> bool {
+        match rule.action {
+            RuleAction::Allow => {
+                log::trace!("Outbound connection to {:?} allowed by '{}'", host, rule);
+                true
+            }
+            RuleAction::Deny => {
+                log::warn!(
+                    "Blocked outbound connection to {:?} as it matched deny rule '{}'",
+                    host,
+                    rule
+                );
+                false
+            }
+        }
+    }
+
+    fn log_unknown(host: &str) {
+        log::warn!(
+            "Blocked outbound connection to {:?}, add it to allowed.list if needed",
+            host
+        );
     }
 
     fn trim_port(host: &str) -> String {
@@ -108,6 +162,11 @@ impl OutboundRequestFilter {
         }
     }
 
+    fn extract_port(host: &str) -> Option<u16> {
+        host.rsplit_once(':')
+            .and_then(|(_, port)| port.parse().ok())
+    }
+
     /// Attempts to get the root domain, shorn of subdomains, using publicsuffix.
     fn get_domain_root(&self, host: &str) -> Option<String> {
         match self.domain_list.parse_domain(host) {
@@ -120,6 +179,122 @@ impl OutboundRequestFilter {
     }
 }
 
+/// Filters the addresses the requested hosts resolved to using the deny rules of the `allowed_hosts` list,
+/// so that for example a permitted domain resolving to a private address could still be blocked.
+#[derive(Debug, Clone)]
+pub(crate) struct ResolvedAddressFilter {
+    rules: Arc<RuleSet>,
+}
+
+impl ResolvedAddressFilter {
+    /// Returns the first resolved address that is denied alongside the rule denying it.
+    pub(crate) fn find_denied(&self, addresses: &[SocketAddr]) -> Option<(SocketAddr, String)> {
+        addresses.iter().find_map(|address| {
+            self.rules
+                .find_denying(*address)
+                .map(|rule| (*address, rule.to_string()))
+        })
+    }
+}
+
+/// A file-based store of the filtering rules, which is reloaded whenever the file changes on disk.
+#[derive(Debug)]
+pub(crate) struct HostRulesStore {
+    storefile: PathBuf,
+    rules: Arc<RuleSet>,
+
+    // (modification time, length) of the storefile at the time of the last load
+    loaded_fingerprint: Option<(SystemTime, u64)>,
+    last_reload_check: Instant,
+    reload_check_interval: Duration,
+}
+
+impl HostRulesStore {
+    /// Constructs a new HostRulesStore
+    pub(crate) fn new(base_dir: PathBuf, filename: PathBuf) -> HostRulesStore {
+        let storefile = HostsStore::setup_storefile(base_dir, filename);
+        let loaded_fingerprint = Self::storefile_fingerprint(&storefile);
+        let rules = HostRulesStore::load_from_storefile(&storefile)
+            .unwrap_or_else(|_| panic!("Could not load rules from storefile at {:?}", storefile));
+
+        HostRulesStore {
+            storefile,
+            rules: Arc::new(rules),
+            loaded_fingerprint,
+            last_reload_check: Instant::now(),
+            reload_check_interval: RULES_RELOAD_CHECK_INTERVAL,
+        }
+    }
+
+    fn storefile_fingerprint(storefile: &Path) -> Option<(SystemTime, u64)> {
+        let metadata = fs::metadata(storefile).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+
+    /// Reloads the rules if the storefile has changed since it was last loaded.
+    /// If the new content can't be read, the previously loaded rules are kept.
+    fn maybe_reload(&mut self) {
+        if self.last_reload_check.elapsed() < self.reload_check_interval {
+            return;
+        }
+        self.last_reload_check = Instant::now();
+
+        let fingerprint = Self::storefile_fingerprint(&self.storefile);
+        if fingerprint == self.loaded_fingerprint {
+            return;
+        }
+
+        match Self::load_from_storefile(&self.storefile) {
+            Ok(rules) => {
+                log::info!(
+                    "Reloaded {} filtering rules from {:?}",
+                    rules.len(),
+                    self.storefile
+                );
+                self.rules = Arc::new(rules);
+                self.loaded_fingerprint = fingerprint;
+            }
+            Err(err) => log::error!(
+                "Could not reload filtering rules from {:?} - {}. The previous rules are going to be used",
+                self.storefile,
+                err
+            ),
+        }
+    }
+
+    fn find_matching(&self, host: &RequestedHost<'_>, port: Option<u16>) -> Option<&HostRule> {
+        self.rules.find_matching(host, port)
+    }
+
+    /// Loads the storefile rules into memory. Any malformed rules are skipped.
+    fn load_from_storefile<P>(filename: P) -> io::Result<RuleSet>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(filename)?;
+        let reader = BufReader::new(&file);
+
+        let mut rules = Vec::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if HostRule::is_ignored_line(&line) {
+                continue;
+            }
+            match line.parse() {
+                Ok(rule) => rules.push(rule),
+                Err(err) => log::warn!(
+                    "Ignoring malformed filtering rule {:?} on line {} - {}",
+                    line,
+                    i + 1,
+                    err
+                ),
+            }
+        }
+
+        Ok(RuleSet::new(rules))
+    }
+}
+
 // used for parsing file content
 enum Host {
     Domain(String),
@@ -159,10 +334,8 @@ impl Host {
     }
 }
 
-/// A simple file-based store for information about allowed / unknown hosts.
-/// Currently it completely ignores any port information.
-// TODO: in the future allow filtering by port, so for example 1.1.1.1:80 would be a valid filter,
-// which would allow connections to the port :80 while any requests to say 1.1.1.1:1234 would be denied.
+/// A simple file-based store for information about unknown hosts.
+/// It completely ignores any port information.
 #[derive(Debug)]
 pub(crate) struct HostsStore {
     storefile: PathBuf,
@@ -300,7 +473,7 @@ mod tests {
             let base_dir = test_base_dir();
             let allowed_filename = PathBuf::from(format!("allowed-{}.list", random_string()));
             let unknown_filename = PathBuf::from(&format!("unknown-{}.list", random_string()));
-            let allowed = HostsStore::new(base_dir.clone(), allowed_filename);
+            let allowed = HostRulesStore::new(base_dir.clone(), allowed_filename);
             let unknown = HostsStore::new(base_dir, unknown_filename);
             OutboundRequestFilter::new(allowed, unknown)
         }
@@ -362,7 +535,7 @@ mod tests {
             let base_dir = test_base_dir();
             let allowed_filename = PathBuf::from(format!("allowed-{}.list", random_string()));
             let unknown_filename = PathBuf::from(&format!("unknown-{}.list", random_string()));
-            let allowed = HostsStore::new(base_dir.clone(), allowed_filename);
+            let allowed = HostRulesStore::new(base_dir.clone(), allowed_filename);
             let unknown = HostsStore::new(base_dir, unknown_filename);
             OutboundRequestFilter::new(allowed, unknown)
         }
@@ -399,7 +572,7 @@ mod tests {
                 HostsStore::append(&allowed_storefile, allowed_host)
             }
 
-            let allowed = HostsStore::new(base_dir1, allowed_filename);
+            let allowed = HostRulesStore::new(base_dir1, allowed_filename);
             let unknown = HostsStore::new(base_dir2, unknown_filename);
             OutboundRequestFilter::new(allowed, unknown)
         }
@@ -510,6 +683,97 @@ mod tests {
             assert!(filter.check(top));
             assert!(filter.check(mid));
         }
+
+        #[test]
+        fn ignore_comments_in_the_list() {
+            let mut filter = setup(&[
+                "// Copyright 2020 - Nym Technologies SA <contact@nymtech.net>",
+                "# electrum servers",
+                "electrum.org # inline comment",
+            ]);
+            assert_eq!(1, filter.allowed_hosts.rules.len());
+            assert!(filter.check("electrum.org:50002"));
+        }
+
+        #[test]
+        fn respect_port_restrictions() {
+            let mut filter = setup(&["nymtech.net port=443", "1.1.1.1 port=53,853"]);
+            assert!(filter.check("nymtech.net:443"));
+            assert!(!filter.check("nymtech.net:80"));
+            assert!(!filter.check("nymtech.net"));
+            assert!(filter.check("1.1.1.1:853"));
+            assert!(!filter.check("1.1.1.1:22"));
+        }
+
+        #[test]
+        fn respect_wildcard_and_exact_rules() {
+            let mut filter = setup(&["*.nymtech.net", "=api.nym.com"]);
+            assert!(filter.check("foomp.nymtech.net:443"));
+            assert!(!filter.check("nymtech.net:443"));
+            assert!(filter.check("api.nym.com:443"));
+            assert!(!filter.check("foomp.api.nym.com:443"));
+        }
+    }
+
+    #[cfg(test)]
+    mod requests_to_denied_hosts {
+        use super::*;
+
+        fn setup(rules: &[&str]) -> OutboundRequestFilter {
+            let (allowed_storefile, base_dir1, allowed_filename) = create_test_storefile();
+            let (_, base_dir2, unknown_filename) = create_test_storefile();
+
+            for rule in rules {
+                HostsStore::append(&allowed_storefile, rule)
+            }
+
+            let allowed = HostRulesStore::new(base_dir1, allowed_filename);
+            let unknown = HostsStore::new(base_dir2, unknown_filename);
+            OutboundRequestFilter::new(allowed, unknown)
+        }
+
+        #[test]
+        fn are_not_allowed_even_if_allow_rule_exists() {
+            let mut filter = setup(&["nymtech.net", "!=evil.nymtech.net"]);
+            assert!(filter.check("foomp.nymtech.net:443"));
+            assert!(!filter.check("evil.nymtech.net:443"));
+
+            // ordering doesn't matter
+            let mut filter = setup(&["!10.0.0.0/8", "10.0.0.1"]);
+            assert!(!filter.check("10.0.0.1:80"));
+        }
+
+        #[test]
+        fn are_not_appended_to_the_unknown_hosts_list() {
+            let mut filter = setup(&["!evil.com"]);
+            assert!(!filter.check("evil.com:443"));
+            assert!(filter.unknown_hosts.domains.is_empty());
+        }
+
+        #[test]
+        fn apply_to_resolved_addresses() {
+            let filter = setup(&["nymtech.net", "!10.0.0.0/8"]);
+            let resolved_filter = filter.resolved_address_filter();
+
+            let public: SocketAddr = "1.2.3.4:443".parse().unwrap();
+            let private: SocketAddr = "10.0.0.1:443".parse().unwrap();
+            assert!(resolved_filter.find_denied(&[public]).is_none());
+
+            let (denied, rule) = resolved_filter.find_denied(&[public, private]).unwrap();
+            assert_eq!(private, denied);
+            assert_eq!("!10.0.0.0/8", rule);
+        }
+
+        #[test]
+        fn are_picked_up_after_the_list_changes_on_disk() {
+            let mut filter = setup(&["nymtech.net"]);
+            filter.allowed_hosts.reload_check_interval = Duration::from_secs(0);
+            assert!(filter.check("evil.nymtech.net:443"));
+
+            HostsStore::append(&filter.allowed_hosts.storefile, "!=evil.nymtech.net");
+            assert!(!filter.check("evil.nymtech.net:443"));
+            assert!(filter.check("foomp.nymtech.net:443"));
+        }
     }
 
     fn random_string() -> String {
@@ -0,0 +1,549 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rule language used by the `allowed.list` file.
+//!
+//! Every non-empty line that does not start with `#` or `//` is a single rule:
+//!
+//! ```text
+//! [!]<host pattern> [port=<ports>] [tag=<tags>] [# comment]
+//! ```
+//!
+//! * a leading `!` turns the rule into a deny rule. Deny rules always take precedence over allow rules,
+//! * `example.com` matches `example.com` and all of its subdomains (this is the legacy format),
+//! * `*.example.com` matches any subdomain of `example.com`, but not `example.com` itself,
+//! * `=api.example.com` matches exactly `api.example.com` and nothing else,
+//! * `1.2.3.4` or `1.2.3.0/24` (or the ipv6 equivalents) match the address or the whole network,
+//! * `port=443` or `port=80,443,8000-8100` restricts the rule to the specified ports,
+//! * `tag=wallet,electrum` attaches arbitrary labels to the rule, which are included in the logs,
+//! * anything after `#` is treated as the rule comment.
+
+use ipnetwork::IpNetwork;
+use std::fmt::{self, Display, Formatter};
+use std::net::{IpAddr, SocketAddr};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+const DENY_PREFIX: char = '!';
+const EXACT_PREFIX: char = '=';
+const WILDCARD_PREFIX: &str = "*.";
+const PORT_OPTION: &str = "port";
+const TAG_OPTION: &str = "tag";
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum RuleParseError {
+    EmptyRule,
+    InvalidHost(String),
+    InvalidPort(String),
+    UnknownOption(String),
+}
+
+impl Display for RuleParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleParseError::EmptyRule => write!(f, "the rule does not specify any host"),
+            RuleParseError::InvalidHost(host) => write!(f, "'{}' is not a valid host", host),
+            RuleParseError::InvalidPort(port) => write!(f, "'{}' is not a valid port", port),
+            RuleParseError::UnknownOption(option) => {
+                write!(f, "'{}' is not a known rule option", option)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum RuleAction {
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum HostPattern {
+    /// The domain itself alongside all of its subdomains.
+    Domain(String),
+
+    /// Any subdomain of the specified domain, but not the domain itself.
+    Wildcard(String),
+
+    /// Exactly the specified domain.
+    Exact(String),
+
+    /// Any address within the specified network.
+    IpNetwork(IpNetwork),
+}
+
+impl HostPattern {
+    fn matches(&self, host: &RequestedHost<'_>) -> bool {
+        match (self, host) {
+            (HostPattern::Domain(domain), RequestedHost::Domain(requested)) => {
+                requested == domain || is_subdomain_of(requested, domain)
+            }
+            (HostPattern::Wildcard(domain), RequestedHost::Domain(requested)) => {
+                is_subdomain_of(requested, domain)
+            }
+            (HostPattern::Exact(domain), RequestedHost::Domain(requested)) => requested == domain,
+            (HostPattern::IpNetwork(network), RequestedHost::Ip(address)) => {
+                network.contains(*address)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Display for HostPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            HostPattern::Domain(domain) => write!(f, "{}", domain),
+            HostPattern::Wildcard(domain) => write!(f, "{}{}", WILDCARD_PREFIX, domain),
+            HostPattern::Exact(domain) => write!(f, "{}{}", EXACT_PREFIX, domain),
+            HostPattern::IpNetwork(network) => write!(f, "{}", network),
+        }
+    }
+}
+
+fn is_subdomain_of(requested: &str, domain: &str) -> bool {
+    requested.len() > domain.len()
+        && requested.ends_with(domain)
+        && requested.as_bytes()[requested.len() - domain.len() - 1] == b'.'
+}
+
+fn normalize_domain(raw: &str) -> String {
+    raw.trim_end_matches('.').to_lowercase()
+}
+
+fn parse_domain(raw: &str) -> Result<String, RuleParseError> {
+    let domain = normalize_domain(raw);
+    let is_valid = !domain.is_empty()
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        });
+
+    if is_valid {
+        Ok(domain)
+    } else {
+        Err(RuleParseError::InvalidHost(raw.to_string()))
+    }
+}
+
+impl FromStr for HostPattern {
+    type Err = RuleParseError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        if let Ok(network) = raw.parse() {
+            Ok(HostPattern::IpNetwork(network))
+        } else if let Some(domain) = raw.strip_prefix(WILDCARD_PREFIX) {
+            parse_domain(domain).map(HostPattern::Wildcard)
+        } else if let Some(domain) = raw.strip_prefix(EXACT_PREFIX) {
+            parse_domain(domain).map(HostPattern::Exact)
+        } else {
+            parse_domain(raw).map(HostPattern::Domain)
+        }
+    }
+}
+
+/// Host extracted from an outbound request that is matched against the rules.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum RequestedHost<'a> {
+    Ip(IpAddr),
+    Domain(&'a str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HostRule {
+    pub(crate) action: RuleAction,
+    pub(crate) pattern: HostPattern,
+    /// Ports the rule applies to. An empty list means the rule applies to all of them.
+    pub(crate) ports: Vec<RangeInclusive<u16>>,
+    pub(crate) tags: Vec<String>,
+    pub(crate) comment: Option<String>,
+}
+
+impl HostRule {
+    /// Checks whether the line should be ignored by the parser, i.e. it's either empty or it's a comment.
+    pub(crate) fn is_ignored_line(line: &str) -> bool {
+        let trimmed = line.trim();
+        trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("//")
+    }
+
+    pub(crate) fn matches(&self, host: &RequestedHost<'_>, port: Option<u16>) -> bool {
+        if !self.pattern.matches(host) {
+            return false;
+        }
+
+        if self.ports.is_empty() {
+            return true;
+        }
+
+        // if the rule is restricted to particular ports, but we don't know the requested one,
+        // we can't say it matches
+        match port {
+            Some(port) => self.ports.iter().any(|range| range.contains(&port)),
+            None => false,
+        }
+    }
+
+    fn parse_ports(raw: &str) -> Result<Vec<RangeInclusive<u16>>, RuleParseError> {
+        let parse_port = |port: &str| {
+            port.parse::<u16>()
+                .map_err(|_| RuleParseError::InvalidPort(port.to_string()))
+        };
+
+        raw.split(',')
+            .map(|entry| match entry.split_once('-') {
+                Some((start, end)) => {
+                    let start = parse_port(start)?;
+                    let end = parse_port(end)?;
+                    if start > end {
+                        return Err(RuleParseError::InvalidPort(entry.to_string()));
+                    }
+                    Ok(start..=end)
+                }
+                None => parse_port(entry).map(|port| port..=port),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for HostRule {
+    type Err = RuleParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (rule, comment) = match line.split_once('#') {
+            Some((rule, comment)) => (rule, Some(comment.trim().to_string())),
+            None => (line, None),
+        };
+        let comment = comment.filter(|comment| !comment.is_empty());
+
+        let mut tokens = rule.split_whitespace();
+        let raw_host = tokens.next().ok_or(RuleParseError::EmptyRule)?;
+
+        let (action, raw_pattern) = match raw_host.strip_prefix(DENY_PREFIX) {
+            Some(pattern) => (RuleAction::Deny, pattern),
+            None => (RuleAction::Allow, raw_host),
+        };
+        let pattern = raw_pattern.parse()?;
+
+        let mut ports = Vec::new();
+        let mut tags = Vec::new();
+        for option in tokens {
+            match option.split_once('=') {
+                Some((key, value)) if key == PORT_OPTION || key == "ports" => {
+                    ports.extend(Self::parse_ports(value)?)
+                }
+                Some((key, value)) if key == TAG_OPTION || key == "tags" => tags.extend(
+                    value
+                        .split(',')
+                        .filter(|tag| !tag.is_empty())
+                        .map(ToString::to_string),
+                ),
+                _ => return Err(RuleParseError::UnknownOption(option.to_string())),
+            }
+        }
+
+        Ok(HostRule {
+            action,
+            pattern,
+            ports,
+            tags,
+            comment,
+        })
+    }
+}
+
+impl Display for HostRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.action == RuleAction::Deny {
+            write!(f, "{}", DENY_PREFIX)?;
+        }
+        write!(f, "{}", self.pattern)?;
+        if !self.ports.is_empty() {
+            let ports = self
+                .ports
+                .iter()
+                .map(|range| {
+                    if range.start() == range.end() {
+                        range.start().to_string()
+                    } else {
+                        format!("{}-{}", range.start(), range.end())
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            write!(f, " {}={}", PORT_OPTION, ports)?;
+        }
+        if !self.tags.is_empty() {
+            write!(f, " {}={}", TAG_OPTION, self.tags.join(","))?;
+        }
+        if let Some(comment) = &self.comment {
+            write!(f, " # {}", comment)?;
+        }
+        Ok(())
+    }
+}
+
+/// Ordered collection of rules loaded from a single file.
+#[derive(Debug, Default)]
+pub(crate) struct RuleSet {
+    rules: Vec<HostRule>,
+}
+
+impl RuleSet {
+    pub(crate) fn new(rules: Vec<HostRule>) -> Self {
+        RuleSet { rules }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns the rule deciding the fate of the request, if any.
+    /// Any matching deny rule takes precedence over all allow rules.
+    pub(crate) fn find_matching(
+        &self,
+        host: &RequestedHost<'_>,
+        port: Option<u16>,
+    ) -> Option<&HostRule> {
+        let mut matching = self.rules.iter().filter(|rule| rule.matches(host, port));
+        let first = matching.next()?;
+        if first.action == RuleAction::Deny {
+            return Some(first);
+        }
+        matching
+            .find(|rule| rule.action == RuleAction::Deny)
+            .or(Some(first))
+    }
+
+    /// Returns the deny rule matching the resolved socket address, if any.
+    pub(crate) fn find_denying(&self, address: SocketAddr) -> Option<&HostRule> {
+        let host = RequestedHost::Ip(address.ip());
+        self.rules
+            .iter()
+            .filter(|rule| rule.action == RuleAction::Deny)
+            .find(|rule| rule.matches(&host, Some(address.port())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(raw: &str) -> HostRule {
+        raw.parse().unwrap()
+    }
+
+    fn allow(pattern: HostPattern) -> HostRule {
+        HostRule {
+            action: RuleAction::Allow,
+            pattern,
+            ports: Vec::new(),
+            tags: Vec::new(),
+            comment: None,
+        }
+    }
+
+    #[cfg(test)]
+    mod parsing_rules {
+        use super::*;
+
+        #[test]
+        fn works_for_legacy_entries() {
+            assert_eq!(
+                allow(HostPattern::Domain("nymtech.net".to_string())),
+                rule("nymtech.net")
+            );
+            assert_eq!(
+                allow(HostPattern::IpNetwork("1.2.3.4/24".parse().unwrap())),
+                rule("1.2.3.4/24")
+            );
+            assert_eq!(
+                allow(HostPattern::IpNetwork("::1".parse().unwrap())),
+                rule("::1")
+            );
+        }
+
+        #[test]
+        fn normalizes_domains() {
+            assert_eq!(
+                HostPattern::Domain("nymtech.net".to_string()),
+                rule("NymTech.NET.").pattern
+            );
+        }
+
+        #[test]
+        fn recognises_pattern_types() {
+            assert_eq!(
+                HostPattern::Wildcard("nymtech.net".to_string()),
+                rule("*.nymtech.net").pattern
+            );
+            assert_eq!(
+                HostPattern::Exact("api.nymtech.net".to_string()),
+                rule("=api.nymtech.net").pattern
+            );
+        }
+
+        #[test]
+        fn recognises_deny_rules() {
+            assert_eq!(RuleAction::Deny, rule("!nymtech.net").action);
+            assert_eq!(RuleAction::Deny, rule("!10.0.0.0/8").action);
+            assert_eq!(RuleAction::Allow, rule("nymtech.net").action);
+        }
+
+        #[test]
+        fn parses_ports_tags_and_comments() {
+            let parsed = rule("*.nymtech.net port=443,8000-8100 tag=nym,website # our website");
+            assert_eq!(vec![443..=443, 8000..=8100], parsed.ports);
+            assert_eq!(vec!["nym".to_string(), "website".to_string()], parsed.tags);
+            assert_eq!(Some("our website".to_string()), parsed.comment);
+        }
+
+        #[test]
+        fn can_be_displayed_and_parsed_back() {
+            let parsed = rule("!*.nymtech.net port=443,8000-8100 tag=nym # foomp");
+            assert_eq!(parsed, rule(&parsed.to_string()));
+        }
+
+        #[test]
+        fn fails_for_invalid_ports() {
+            assert_eq!(
+                RuleParseError::InvalidPort("foo".to_string()),
+                "nymtech.net port=foo".parse::<HostRule>().unwrap_err()
+            );
+            assert_eq!(
+                RuleParseError::InvalidPort("100-10".to_string()),
+                "nymtech.net port=100-10".parse::<HostRule>().unwrap_err()
+            );
+        }
+
+        #[test]
+        fn fails_for_unknown_options() {
+            assert_eq!(
+                RuleParseError::UnknownOption("foo=bar".to_string()),
+                "nymtech.net foo=bar".parse::<HostRule>().unwrap_err()
+            );
+        }
+
+        #[test]
+        fn fails_for_garbage_hosts() {
+            assert!("::/&&%@".parse::<HostRule>().is_err());
+            assert!("*.".parse::<HostRule>().is_err());
+            assert!("nym..net".parse::<HostRule>().is_err());
+        }
+
+        #[test]
+        fn ignores_comments_and_empty_lines() {
+            assert!(HostRule::is_ignored_line(""));
+            assert!(HostRule::is_ignored_line("   "));
+            assert!(HostRule::is_ignored_line("# some comment"));
+            assert!(HostRule::is_ignored_line(
+                "// Copyright 2020 - Nym Technologies SA <contact@nymtech.net>"
+            ));
+            assert!(!HostRule::is_ignored_line("nymtech.net # comment"));
+        }
+    }
+
+    #[cfg(test)]
+    mod matching_rules {
+        use super::*;
+
+        fn domain(host: &str) -> RequestedHost<'_> {
+            RequestedHost::Domain(host)
+        }
+
+        fn ip(address: &str) -> RequestedHost<'static> {
+            RequestedHost::Ip(address.parse().unwrap())
+        }
+
+        #[test]
+        fn domain_rule_matches_domain_and_subdomains() {
+            let rule = rule("nymtech.net");
+            assert!(rule.matches(&domain("nymtech.net"), None));
+            assert!(rule.matches(&domain("foomp.nymtech.net"), None));
+            assert!(!rule.matches(&domain("foompnymtech.net"), None));
+            assert!(!rule.matches(&domain("nymtech.com"), None));
+        }
+
+        #[test]
+        fn wildcard_rule_matches_only_subdomains() {
+            let rule = rule("*.nymtech.net");
+            assert!(!rule.matches(&domain("nymtech.net"), None));
+            assert!(rule.matches(&domain("foomp.nymtech.net"), None));
+            assert!(rule.matches(&domain("a.b.nymtech.net"), None));
+        }
+
+        #[test]
+        fn exact_rule_matches_only_the_domain() {
+            let rule = rule("=api.nymtech.net");
+            assert!(rule.matches(&domain("api.nymtech.net"), None));
+            assert!(!rule.matches(&domain("foo.api.nymtech.net"), None));
+            assert!(!rule.matches(&domain("nymtech.net"), None));
+        }
+
+        #[test]
+        fn ip_rules_dont_match_domains() {
+            let rule = rule("1.2.3.0/24");
+            assert!(rule.matches(&ip("1.2.3.42"), None));
+            assert!(!rule.matches(&ip("1.2.4.42"), None));
+            assert!(!rule.matches(&domain("1.2.3.nymtech.net"), None));
+        }
+
+        #[test]
+        fn port_restricted_rules_require_matching_port() {
+            let rule = rule("nymtech.net port=443,8000-8100");
+            assert!(rule.matches(&domain("nymtech.net"), Some(443)));
+            assert!(rule.matches(&domain("nymtech.net"), Some(8050)));
+            assert!(!rule.matches(&domain("nymtech.net"), Some(80)));
+            assert!(!rule.matches(&domain("nymtech.net"), None));
+        }
+
+        #[test]
+        fn deny_rules_take_precedence() {
+            let rules = RuleSet::new(vec![
+                rule("nymtech.net"),
+                rule("!=evil.nymtech.net"),
+                rule("!nymtech.net port=22"),
+            ]);
+
+            let allowed = rules
+                .find_matching(&domain("foomp.nymtech.net"), Some(443))
+                .unwrap();
+            assert_eq!(RuleAction::Allow, allowed.action);
+
+            let denied = rules
+                .find_matching(&domain("evil.nymtech.net"), Some(443))
+                .unwrap();
+            assert_eq!(RuleAction::Deny, denied.action);
+
+            let denied = rules
+                .find_matching(&domain("nymtech.net"), Some(22))
+                .unwrap();
+            assert_eq!(RuleAction::Deny, denied.action);
+
+            assert!(rules.find_matching(&domain("nym.com"), Some(443)).is_none());
+        }
+
+        #[test]
+        fn resolved_addresses_are_only_matched_against_deny_rules() {
+            let rules = RuleSet::new(vec![
+                rule("1.2.3.4"),
+                rule("!10.0.0.0/8"),
+                rule("!127.0.0.1 port=22"),
+            ]);
+
+            assert!(rules.find_denying("1.2.3.4:443".parse().unwrap()).is_none());
+            assert!(rules
+                .find_denying("10.1.2.3:443".parse().unwrap())
+                .is_some());
+            assert!(rules
+                .find_denying("127.0.0.1:22".parse().unwrap())
+                .is_some());
+            assert!(rules
+                .find_denying("127.0.0.1:443".parse().unwrap())
+                .is_none());
+        }
+    }
+}
@@ -0,0 +1,306 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nymsphinx::addressing::clients::Recipient;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+pub const DEFAULT_CLIENT_LIMITS_WINDOW: Duration = Duration::from_secs(60);
+
+// clients are identified by their full return address
+type ClientKey = [u8; Recipient::LEN];
+
+/// Limits imposed on each client, identified by its return address.
+/// Any limit set to `None` is not enforced.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientLimitsConfig {
+    /// Maximum number of connections a single client can have open at the same time.
+    pub max_open_connections: Option<usize>,
+
+    /// Maximum number of bytes (sent and received) a single client can proxy within a window.
+    /// Once exceeded, any connection proxying more data is closed and the client is not going
+    /// to be able to open any new connections until the window resets.
+    pub max_bytes_per_window: Option<u64>,
+
+    /// Maximum number of new connections a single client can open within a window.
+    pub max_new_connections_per_window: Option<u32>,
+
+    /// Duration of the window used for the bandwidth and connection rate limits.
+    pub window: Duration,
+}
+
+impl Default for ClientLimitsConfig {
+    fn default() -> Self {
+        ClientLimitsConfig {
+            max_open_connections: None,
+            max_bytes_per_window: None,
+            max_new_connections_per_window: None,
+            window: DEFAULT_CLIENT_LIMITS_WINDOW,
+        }
+    }
+}
+
+impl ClientLimitsConfig {
+    fn is_unlimited(&self) -> bool {
+        self.max_open_connections.is_none()
+            && self.max_bytes_per_window.is_none()
+            && self.max_new_connections_per_window.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum LimitExceeded {
+    OpenConnections(usize),
+    Bandwidth(u64),
+    ConnectionRate(u32),
+}
+
+impl Display for LimitExceeded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::OpenConnections(limit) => {
+                write!(f, "reached the limit of {} open connections", limit)
+            }
+            LimitExceeded::Bandwidth(limit) => {
+                write!(f, "used up the bandwidth quota of {} bytes", limit)
+            }
+            LimitExceeded::ConnectionRate(limit) => {
+                write!(f, "opened more than {} new connections", limit)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ClientUsage {
+    open_connections: usize,
+    window_start: Instant,
+    window_bytes: u64,
+    window_new_connections: u32,
+}
+
+impl ClientUsage {
+    fn new(now: Instant) -> Self {
+        ClientUsage {
+            open_connections: 0,
+            window_start: now,
+            window_bytes: 0,
+            window_new_connections: 0,
+        }
+    }
+
+    fn is_window_expired(&self, now: Instant, window: Duration) -> bool {
+        now.saturating_duration_since(self.window_start) >= window
+    }
+
+    fn maybe_reset_window(&mut self, now: Instant, window: Duration) {
+        if self.is_window_expired(now, window) {
+            self.window_start = now;
+            self.window_bytes = 0;
+            self.window_new_connections = 0;
+        }
+    }
+
+    fn is_idle(&self, now: Instant, window: Duration) -> bool {
+        self.open_connections == 0 && self.is_window_expired(now, window)
+    }
+}
+
+#[derive(Debug)]
+struct ClientLimiterInner {
+    clients: HashMap<ClientKey, ClientUsage>,
+    last_pruned: Instant,
+}
+
+/// Keeps track of resources used by each client and refuses new connections
+/// of clients that went over any of the configured limits.
+#[derive(Debug, Clone)]
+pub(crate) struct ClientLimiter {
+    config: ClientLimitsConfig,
+    inner: Arc<Mutex<ClientLimiterInner>>,
+}
+
+impl ClientLimiter {
+    pub(crate) fn new(config: ClientLimitsConfig) -> Self {
+        ClientLimiter {
+            config,
+            inner: Arc::new(Mutex::new(ClientLimiterInner {
+                clients: HashMap::new(),
+                last_pruned: Instant::now(),
+            })),
+        }
+    }
+
+    /// Checks whether the client is allowed to open a new connection and if so, registers it.
+    pub(crate) async fn try_open_connection(
+        &self,
+        client: &Recipient,
+    ) -> Result<(), LimitExceeded> {
+        if self.config.is_unlimited() {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let mut guard = self.inner.lock().await;
+        let inner = &mut *guard;
+
+        // get rid of clients that don't have any state worth keeping around
+        if now.saturating_duration_since(inner.last_pruned) >= self.config.window {
+            let window = self.config.window;
+            inner.clients.retain(|_, usage| !usage.is_idle(now, window));
+            inner.last_pruned = now;
+        }
+
+        let usage = inner
+            .clients
+            .entry(client.to_bytes())
+            .or_insert_with(|| ClientUsage::new(now));
+        usage.maybe_reset_window(now, self.config.window);
+
+        if let Some(limit) = self.config.max_open_connections {
+            if usage.open_connections >= limit {
+                return Err(LimitExceeded::OpenConnections(limit));
+            }
+        }
+        if let Some(limit) = self.config.max_new_connections_per_window {
+            if usage.window_new_connections >= limit {
+                return Err(LimitExceeded::ConnectionRate(limit));
+            }
+        }
+        if let Some(limit) = self.config.max_bytes_per_window {
+            if usage.window_bytes >= limit {
+                return Err(LimitExceeded::Bandwidth(limit));
+            }
+        }
+
+        usage.open_connections += 1;
+        usage.window_new_connections += 1;
+        Ok(())
+    }
+
+    /// Releases the connection slot previously acquired with `try_open_connection`.
+    pub(crate) async fn connection_closed(&self, client: &Recipient) {
+        if self.config.is_unlimited() {
+            return;
+        }
+
+        if let Some(usage) = self.inner.lock().await.clients.get_mut(&client.to_bytes()) {
+            usage.open_connections = usage.open_connections.saturating_sub(1);
+        }
+    }
+
+    /// Accounts for the data proxied on behalf of the client and checks whether
+    /// it's still within its bandwidth quota.
+    pub(crate) async fn processed(
+        &self,
+        client: &Recipient,
+        bytes: usize,
+    ) -> Result<(), LimitExceeded> {
+        let limit = match self.config.max_bytes_per_window {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+
+        let now = Instant::now();
+        if let Some(usage) = self.inner.lock().await.clients.get_mut(&client.to_bytes()) {
+            usage.maybe_reset_window(now, self.config.window);
+            usage.window_bytes = usage.window_bytes.saturating_add(bytes as u64);
+            if usage.window_bytes > limit {
+                return Err(LimitExceeded::Bandwidth(limit));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> Recipient {
+        Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap()
+    }
+
+    fn other_client() -> Recipient {
+        Recipient::try_from_base58_string("D1rrpsysCGCYXy9saP8y3kmNpGtJZUXN9SvFoUcqAsM9.9Ssso1ea5NfkbMASdiseDSjTN1fSWda5SgEVjdSN4CvV@GJqd3ZxpXWSNxTfx7B1pPtswpetH4LnJdFeLeuY5KUuN").unwrap()
+    }
+
+    #[tokio::test]
+    async fn unlimited_config_never_refuses_connections() {
+        let limiter = ClientLimiter::new(ClientLimitsConfig::default());
+        for _ in 0..100 {
+            assert!(limiter.try_open_connection(&client()).await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn open_connections_are_limited_per_client() {
+        let limiter = ClientLimiter::new(ClientLimitsConfig {
+            max_open_connections: Some(2),
+            ..Default::default()
+        });
+
+        assert!(limiter.try_open_connection(&client()).await.is_ok());
+        assert!(limiter.try_open_connection(&client()).await.is_ok());
+        assert_eq!(
+            Err(LimitExceeded::OpenConnections(2)),
+            limiter.try_open_connection(&client()).await
+        );
+        assert!(limiter.try_open_connection(&other_client()).await.is_ok());
+
+        limiter.connection_closed(&client()).await;
+        assert!(limiter.try_open_connection(&client()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn connection_rate_is_limited_within_window() {
+        let limiter = ClientLimiter::new(ClientLimitsConfig {
+            max_new_connections_per_window: Some(1),
+            ..Default::default()
+        });
+
+        assert!(limiter.try_open_connection(&client()).await.is_ok());
+        limiter.connection_closed(&client()).await;
+        assert_eq!(
+            Err(LimitExceeded::ConnectionRate(1)),
+            limiter.try_open_connection(&client()).await
+        );
+    }
+
+    #[tokio::test]
+    async fn bandwidth_is_limited_within_window() {
+        let limiter = ClientLimiter::new(ClientLimitsConfig {
+            max_bytes_per_window: Some(1000),
+            ..Default::default()
+        });
+
+        assert!(limiter.try_open_connection(&client()).await.is_ok());
+        assert!(limiter.processed(&client(), 600).await.is_ok());
+        assert!(limiter.try_open_connection(&client()).await.is_ok());
+        assert_eq!(
+            Err(LimitExceeded::Bandwidth(1000)),
+            limiter.processed(&client(), 600).await
+        );
+        assert_eq!(
+            Err(LimitExceeded::Bandwidth(1000)),
+            limiter.try_open_connection(&client()).await
+        );
+    }
+
+    #[tokio::test]
+    async fn limits_reset_after_window_expires() {
+        let limiter = ClientLimiter::new(ClientLimitsConfig {
+            max_new_connections_per_window: Some(1),
+            window: Duration::from_millis(50),
+            ..Default::default()
+        });
+
+        assert!(limiter.try_open_connection(&client()).await.is_ok());
+        assert!(limiter.try_open_connection(&client()).await.is_err());
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(limiter.try_open_connection(&client()).await.is_ok());
+    }
+}
@@ -1,14 +1,198 @@
 // Copyright 2020 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::allowed_hosts::ResolvedAddressFilter;
+use crate::resolver::{DnsResolver, ResolverError};
 use futures::channel::mpsc;
+use ipnetwork::IpNetwork;
 use nymsphinx::addressing::clients::Recipient;
 use proxy_helpers::connection_controller::ConnectionReceiver;
 use proxy_helpers::proxy_runner::ProxyRunner;
-use socks5_requests::{ConnectionId, RemoteAddress, Response};
+use socks5_requests::{
+    ConnectionErrorKind, ConnectionId, Message as Socks5Message, RemoteAddress, Response,
+};
 use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use thiserror::Error;
 use tokio::net::TcpStream;
 
+// ranges of addresses internal to the host running the network requester or its local network,
+// such as loopback, private networks or link-local cloud metadata endpoints
+const INTERNAL_RANGES: &[&str] = &[
+    "0.0.0.0/8",
+    "10.0.0.0/8",
+    "100.64.0.0/10",
+    "127.0.0.0/8",
+    "169.254.0.0/16",
+    "172.16.0.0/12",
+    "192.0.0.0/24",
+    "192.168.0.0/16",
+    "198.18.0.0/15",
+    "224.0.0.0/4",
+    "240.0.0.0/4",
+    "::/128",
+    "::1/128",
+    "fc00::/7",
+    "fe80::/10",
+    "ff00::/8",
+];
+
+#[derive(Debug, Error)]
+pub(crate) enum ConnectionFailure {
+    #[error(transparent)]
+    ResolutionFailure(#[from] ResolverError),
+
+    #[error("{address} resolved to {resolved}, which is denied by '{rule}'")]
+    DeniedAddress {
+        address: RemoteAddress,
+        resolved: SocketAddr,
+        rule: String,
+    },
+
+    #[error("{address} resolved to {resolved}, which is within the internal range {range}")]
+    InternalAddress {
+        address: RemoteAddress,
+        resolved: SocketAddr,
+        range: IpNetwork,
+    },
+
+    #[error("failed to connect to {address} - {source}")]
+    ConnectFailure {
+        address: RemoteAddress,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl ConnectionFailure {
+    pub(crate) fn kind(&self) -> ConnectionErrorKind {
+        match self {
+            ConnectionFailure::ResolutionFailure(..) => ConnectionErrorKind::DnsFailure,
+            ConnectionFailure::DeniedAddress { .. } | ConnectionFailure::InternalAddress { .. } => {
+                ConnectionErrorKind::Filtered
+            }
+            ConnectionFailure::ConnectFailure { source, .. } => source.into(),
+        }
+    }
+}
+
+/// Refuses connections to addresses internal to the host or its local network,
+/// apart from the ranges explicitly allowed by the operator.
+/// It's only used in the open proxy mode, otherwise the allow list decides what can be reached.
+#[derive(Debug, Clone)]
+pub(crate) struct InternalAddressFilter {
+    internal_ranges: Vec<IpNetwork>,
+    allowed_ranges: Vec<IpNetwork>,
+}
+
+impl InternalAddressFilter {
+    pub(crate) fn new(allowed_ranges: Vec<IpNetwork>) -> Self {
+        InternalAddressFilter {
+            internal_ranges: INTERNAL_RANGES
+                .iter()
+                .map(|range| range.parse().unwrap())
+                .collect(),
+            allowed_ranges,
+        }
+    }
+
+    // treat ipv6 addresses embedding an ipv4 one as the ipv4 addresses they represent, i.e.
+    // ipv4-mapped `::ffff:a.b.c.d`, NAT64 `64:ff9b::a.b.c.d` and 6to4 `2002:aabb:ccdd::/48`
+    fn canonical(ip: IpAddr) -> IpAddr {
+        let embedded = |high: u16, low: u16| {
+            let [a, b] = high.to_be_bytes();
+            let [c, d] = low.to_be_bytes();
+            IpAddr::V4(Ipv4Addr::new(a, b, c, d))
+        };
+
+        match ip {
+            IpAddr::V6(ipv6) => match ipv6.segments() {
+                [0, 0, 0, 0, 0, 0xffff, high, low] => embedded(high, low),
+                [0x64, 0xff9b, 0, 0, 0, 0, high, low] => embedded(high, low),
+                [0x2002, high, low, ..] => embedded(high, low),
+                _ => ip,
+            },
+            ip => ip,
+        }
+    }
+
+    fn internal_range(&self, ip: IpAddr) -> Option<IpNetwork> {
+        let ip = Self::canonical(ip);
+        if self.allowed_ranges.iter().any(|range| range.contains(ip)) {
+            return None;
+        }
+        self.internal_ranges
+            .iter()
+            .find(|range| range.contains(ip))
+            .copied()
+    }
+
+    /// Returns the first of the addresses that is internal and not explicitly allowed,
+    /// alongside the range it belongs to.
+    pub(crate) fn find_denied(&self, addresses: &[SocketAddr]) -> Option<(SocketAddr, IpNetwork)> {
+        addresses.iter().find_map(|address| {
+            self.internal_range(address.ip())
+                .map(|range| (*address, range))
+        })
+    }
+}
+
+/// Resolves the requested addresses and makes sure connections are only established
+/// to the permitted ones.
+#[derive(Clone)]
+pub(crate) struct Connector {
+    resolver: DnsResolver,
+    internal_filter: Option<InternalAddressFilter>,
+    address_filter: Option<ResolvedAddressFilter>,
+}
+
+impl Connector {
+    pub(crate) fn new(
+        resolver: DnsResolver,
+        internal_filter: Option<InternalAddressFilter>,
+        address_filter: Option<ResolvedAddressFilter>,
+    ) -> Self {
+        Connector {
+            resolver,
+            internal_filter,
+            address_filter,
+        }
+    }
+
+    async fn connect(&self, address: &RemoteAddress) -> Result<TcpStream, ConnectionFailure> {
+        let resolved = self.resolver.resolve(address).await?;
+
+        // don't let anyone reach into the host's own network, unless the operator explicitly said otherwise
+        if let Some(filter) = &self.internal_filter {
+            if let Some((resolved, range)) = filter.find_denied(&resolved) {
+                return Err(ConnectionFailure::InternalAddress {
+                    address: address.clone(),
+                    resolved,
+                    range,
+                });
+            }
+        }
+
+        // make sure the host didn't resolve to anything we explicitly disallow
+        if let Some(filter) = &self.address_filter {
+            if let Some((resolved, rule)) = filter.find_denied(&resolved) {
+                return Err(ConnectionFailure::DeniedAddress {
+                    address: address.clone(),
+                    resolved,
+                    rule,
+                });
+            }
+        }
+
+        TcpStream::connect(resolved.as_slice())
+            .await
+            .map_err(|source| ConnectionFailure::ConnectFailure {
+                address: address.clone(),
+                source,
+            })
+    }
+}
+
 /// A TCP connection between the Socks5 service provider, which makes
 /// outbound requests on behalf of users and returns the responses through
 /// the mixnet.
@@ -25,8 +209,9 @@ impl Connection {
         id: ConnectionId,
         address: RemoteAddress,
         return_address: Recipient,
-    ) -> io::Result<Self> {
-        let conn = TcpStream::connect(&address).await?;
+        connector: &Connector,
+    ) -> Result<Self, ConnectionFailure> {
+        let conn = connector.connect(&address).await?;
 
         Ok(Connection {
             id,
@@ -39,7 +224,7 @@ impl Connection {
     pub(crate) async fn run_proxy(
         &mut self,
         mix_receiver: ConnectionReceiver,
-        mix_sender: mpsc::UnboundedSender<(Response, Recipient)>,
+        mix_sender: mpsc::UnboundedSender<(Socks5Message, Recipient)>,
     ) {
         let stream = self.conn.take().unwrap();
         let remote_source_address = "???".to_string(); // we don't know ip address of requester
@@ -54,10 +239,105 @@ impl Connection {
             connection_id,
         )
         .run(move |conn_id, read_data, socket_closed| {
-            (Response::new(conn_id, read_data, socket_closed), recipient)
+            (
+                Socks5Message::Response(Response::new(conn_id, read_data, socket_closed)),
+                recipient,
+            )
         })
         .await
         .into_inner();
         self.conn = Some(stream);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(address: &str) -> SocketAddr {
+        address.parse().unwrap()
+    }
+
+    #[test]
+    fn internal_ranges_are_valid() {
+        assert_eq!(
+            INTERNAL_RANGES.len(),
+            InternalAddressFilter::new(Vec::new()).internal_ranges.len()
+        );
+    }
+
+    #[test]
+    fn internal_addresses_are_denied_by_default() {
+        let filter = InternalAddressFilter::new(Vec::new());
+        for internal in [
+            "127.0.0.1:80",
+            "10.1.2.3:80",
+            "172.16.0.1:80",
+            "192.168.1.1:80",
+            "169.254.169.254:80",
+            "0.0.0.0:80",
+            "[::1]:80",
+            "[fd00::1]:80",
+            "[fe80::1]:80",
+            "[::ffff:127.0.0.1]:80",
+            "[64:ff9b::127.0.0.1]:80",
+            "[64:ff9b::192.168.1.1]:80",
+            "[2002:7f00:1::1]:80",
+            "[2002:a9fe:a9fe::1]:80",
+        ] {
+            assert!(
+                filter.find_denied(&[address(internal)]).is_some(),
+                "{} should have been denied",
+                internal
+            );
+        }
+    }
+
+    #[test]
+    fn public_addresses_are_not_denied() {
+        let filter = InternalAddressFilter::new(Vec::new());
+        for public in ["1.1.1.1:443", "8.8.8.8:53", "[2606:4700::1111]:443"] {
+            assert!(filter.find_denied(&[address(public)]).is_none());
+        }
+    }
+
+    #[test]
+    fn embedded_ipv4_addresses_are_checked_as_ipv4() {
+        let filter = InternalAddressFilter::new(Vec::new());
+
+        let (_, range) = filter
+            .find_denied(&[address("[64:ff9b::10.0.0.1]:443")])
+            .unwrap();
+        assert_eq!("10.0.0.0/8".parse::<IpNetwork>().unwrap(), range);
+        let (_, range) = filter
+            .find_denied(&[address("[2002:c0a8:101::1]:443")])
+            .unwrap();
+        assert_eq!("192.168.0.0/16".parse::<IpNetwork>().unwrap(), range);
+
+        // public hosts reached through NAT64 or 6to4 are fine
+        assert!(filter
+            .find_denied(&[address("[64:ff9b::1.1.1.1]:443")])
+            .is_none());
+        assert!(filter
+            .find_denied(&[address("[2002:808:808::1]:443")])
+            .is_none());
+    }
+
+    #[test]
+    fn any_internal_resolved_address_is_denied() {
+        let filter = InternalAddressFilter::new(Vec::new());
+        let (denied, range) = filter
+            .find_denied(&[address("1.1.1.1:443"), address("10.0.0.1:443")])
+            .unwrap();
+        assert_eq!(address("10.0.0.1:443"), denied);
+        assert_eq!("10.0.0.0/8".parse::<IpNetwork>().unwrap(), range);
+    }
+
+    #[test]
+    fn explicitly_allowed_ranges_are_not_denied() {
+        let filter = InternalAddressFilter::new(vec!["192.168.1.0/24".parse().unwrap()]);
+        assert!(filter.find_denied(&[address("192.168.1.10:80")]).is_none());
+        assert!(filter.find_denied(&[address("192.168.2.10:80")]).is_some());
+        assert!(filter.find_denied(&[address("127.0.0.1:80")]).is_some());
+    }
+}
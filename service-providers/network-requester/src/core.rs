@@ -1,20 +1,25 @@
 // Copyright 2020 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::allowed_hosts::{HostsStore, OutboundRequestFilter};
-use crate::connection::Connection;
+use crate::allowed_hosts::{HostRulesStore, HostsStore, OutboundRequestFilter};
+use crate::client_limits::{ClientLimiter, ClientLimitsConfig};
+use crate::connection::{Connection, Connector, InternalAddressFilter};
+use crate::resolver::DnsResolver;
 use crate::statistics::{Statistics, StatsData, Timer};
 use crate::websocket;
 use crate::websocket::TSWebsocketStream;
 use futures::channel::mpsc;
 use futures::stream::{SplitSink, SplitStream};
 use futures::{SinkExt, StreamExt};
+use ipnetwork::IpNetwork;
 use log::*;
-use nymsphinx::addressing::clients::{ClientIdentity, Recipient};
+use nymsphinx::addressing::clients::Recipient;
 use nymsphinx::receiver::ReconstructedMessage;
 use proxy_helpers::connection_controller::{Controller, ControllerCommand, ControllerSender};
-use socks5_requests::{ConnectionId, Message as Socks5Message, Request, Response};
-use std::collections::HashMap;
+use socks5_requests::{
+    ConnectionError, ConnectionErrorKind, ConnectionId, Message as Socks5Message, Request, Response,
+};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -33,6 +38,9 @@ pub struct ServiceProvider {
     db_path: PathBuf,
     outbound_request_filter: OutboundRequestFilter,
     open_proxy: bool,
+    client_limiter: ClientLimiter,
+    resolver: DnsResolver,
+    internal_address_filter: InternalAddressFilter,
 }
 
 impl ServiceProvider {
@@ -40,8 +48,11 @@ impl ServiceProvider {
         listening_address: String,
         description: String,
         open_proxy: bool,
+        client_limits: ClientLimitsConfig,
+        resolver: DnsResolver,
+        allowed_internal_ranges: Vec<IpNetwork>,
     ) -> ServiceProvider {
-        let allowed_hosts = HostsStore::new(
+        let allowed_hosts = HostRulesStore::new(
             HostsStore::default_base_dir(),
             PathBuf::from("allowed.list"),
         );
@@ -65,6 +76,9 @@ impl ServiceProvider {
             db_path,
             outbound_request_filter,
             open_proxy,
+            client_limiter: ClientLimiter::new(client_limits),
+            resolver,
+            internal_address_filter: InternalAddressFilter::new(allowed_internal_ranges),
         }
     }
 
@@ -72,19 +86,50 @@ impl ServiceProvider {
     /// via the `websocket_writer`.
     async fn mixnet_response_listener(
         mut websocket_writer: SplitSink<TSWebsocketStream, Message>,
-        mut mix_reader: mpsc::UnboundedReceiver<(Response, Recipient)>,
+        mut mix_reader: mpsc::UnboundedReceiver<(Socks5Message, Recipient)>,
         response_stats_data: &Arc<RwLock<StatsData>>,
+        client_limiter: &ClientLimiter,
+        controller_sender: &ControllerSender,
     ) {
+        // connections closed because their clients went over the bandwidth quota,
+        // whatever is still read from them is not going to be sent back anymore
+        let mut over_quota_connections = HashSet::new();
+
         // TODO: wire SURBs in here once they're available
-        while let Some((response, return_address)) = mix_reader.next().await {
-            response_stats_data
-                .write()
-                .await
-                .processed(return_address.identity(), response.data.len() as u32);
+        while let Some((mut message, return_address)) = mix_reader.next().await {
+            if let Socks5Message::Response(response) = &mut message {
+                if over_quota_connections.contains(&response.connection_id) {
+                    if response.is_closed {
+                        over_quota_connections.remove(&response.connection_id);
+                    }
+                    continue;
+                }
+
+                response_stats_data
+                    .write()
+                    .await
+                    .processed(return_address.identity(), response.data.len() as u32);
+                if let Err(err) = client_limiter
+                    .processed(&return_address, response.data.len())
+                    .await
+                {
+                    log::info!(
+                        "Closing connection {} as the client {}",
+                        response.connection_id,
+                        err
+                    );
+                    Self::close_remote_connection(controller_sender, response.connection_id);
+                    if !response.is_closed {
+                        // let the client know this is the last bit of data it's going to get
+                        response.is_closed = true;
+                        over_quota_connections.insert(response.connection_id);
+                    }
+                }
+            }
             // make 'request' to native-websocket client
             let response_message = ClientRequest::Send {
                 recipient: return_address,
-                message: Socks5Message::Response(response).into_bytes(),
+                message: message.into_bytes(),
                 with_reply_surb: false,
             };
 
@@ -129,26 +174,35 @@ impl ServiceProvider {
         conn_id: ConnectionId,
         remote_addr: String,
         return_address: Recipient,
+        wants_status: bool,
         controller_sender: ControllerSender,
-        mix_input_sender: mpsc::UnboundedSender<(Response, Recipient)>,
+        mix_input_sender: mpsc::UnboundedSender<(Socks5Message, Recipient)>,
+        client_limiter: ClientLimiter,
+        connector: Connector,
     ) {
-        let mut conn = match Connection::new(conn_id, remote_addr.clone(), return_address).await {
-            Ok(conn) => conn,
-            Err(err) => {
-                error!(
-                    "error while connecting to {:?} ! - {:?}",
-                    remote_addr.clone(),
-                    err
-                );
-
-                // inform the remote that the connection is closed before it even was established
-                mix_input_sender
-                    .unbounded_send((Response::new(conn_id, Vec::new(), true), return_address))
-                    .unwrap();
+        let mut conn =
+            match Connection::new(conn_id, remote_addr.clone(), return_address, &connector).await {
+                Ok(conn) => conn,
+                Err(err) => {
+                    error!(
+                        "error while connecting to {:?} ! - {}",
+                        remote_addr.clone(),
+                        err
+                    );
+                    client_limiter.connection_closed(&return_address).await;
+
+                    Self::reject_connection(
+                        &mix_input_sender,
+                        conn_id,
+                        wants_status,
+                        err.kind(),
+                        err.to_string(),
+                        return_address,
+                    );
 
-                return;
-            }
-        };
+                    return;
+                }
+            };
 
         // Connect implies it's a fresh connection - register it with our controller
         let (mix_sender, mix_receiver) = mpsc::unbounded();
@@ -156,6 +210,17 @@ impl ServiceProvider {
             .unbounded_send(ControllerCommand::Insert(conn_id, mix_sender))
             .unwrap();
 
+        // let the remote know it can start using the connection. Older clients just assume
+        // it's there and wouldn't understand the message anyway
+        if wants_status {
+            mix_input_sender
+                .unbounded_send((
+                    Socks5Message::ConnectionEstablished(conn_id),
+                    return_address,
+                ))
+                .unwrap();
+        }
+
         let old_count = ACTIVE_PROXIES.fetch_add(1, Ordering::SeqCst);
         info!(
             "Starting proxy for {} (currently there are {} proxies being handled)",
@@ -170,6 +235,7 @@ impl ServiceProvider {
         controller_sender
             .unbounded_send(ControllerCommand::Remove(conn_id))
             .unwrap();
+        client_limiter.connection_closed(&return_address).await;
 
         let old_count = ACTIVE_PROXIES.fetch_sub(1, Ordering::SeqCst);
         info!(
@@ -179,21 +245,65 @@ impl ServiceProvider {
         );
     }
 
-    fn handle_proxy_connect(
+    async fn handle_proxy_connect(
         &mut self,
         controller_sender: &mut ControllerSender,
-        mix_input_sender: &mpsc::UnboundedSender<(Response, Recipient)>,
+        mix_input_sender: &mpsc::UnboundedSender<(Socks5Message, Recipient)>,
         conn_id: ConnectionId,
         remote_addr: String,
         return_address: Recipient,
+        wants_status: bool,
     ) {
         if !self.open_proxy && !self.outbound_request_filter.check(&remote_addr) {
             log::info!("Domain {:?} failed filter check", remote_addr);
+            Self::reject_connection(
+                mix_input_sender,
+                conn_id,
+                wants_status,
+                ConnectionErrorKind::Filtered,
+                format!("{} is not allowed by the network requester", remote_addr),
+                return_address,
+            );
+            return;
+        }
+
+        if let Err(err) = self
+            .client_limiter
+            .try_open_connection(&return_address)
+            .await
+        {
+            log::info!(
+                "Refused connection to {:?} as the client {}",
+                remote_addr,
+                err
+            );
+
+            Self::reject_connection(
+                mix_input_sender,
+                conn_id,
+                wants_status,
+                ConnectionErrorKind::RateLimited,
+                format!("client {}", err),
+                return_address,
+            );
             return;
         }
 
         let controller_sender_clone = controller_sender.clone();
         let mix_input_sender_clone = mix_input_sender.clone();
+        let client_limiter = self.client_limiter.clone();
+
+        // in open proxy mode we don't care about the rules, but we still keep the traffic away
+        // from our own network. Otherwise it's up to the operator what can be reached
+        let (internal_filter, address_filter) = if self.open_proxy {
+            (Some(self.internal_address_filter.clone()), None)
+        } else {
+            (
+                None,
+                Some(self.outbound_request_filter.resolved_address_filter()),
+            )
+        };
+        let connector = Connector::new(self.resolver.clone(), internal_filter, address_filter);
 
         // and start the proxy for this connection
         tokio::spawn(async move {
@@ -201,13 +311,37 @@ impl ServiceProvider {
                 conn_id,
                 remote_addr,
                 return_address,
+                wants_status,
                 controller_sender_clone,
                 mix_input_sender_clone,
+                client_limiter,
+                connector,
             )
             .await
         });
     }
 
+    /// Informs the remote that its connection is closed before it even was established.
+    /// Clients that did not ask for the connection status only understand an empty closing
+    /// response, so they don't get to learn why.
+    fn reject_connection(
+        mix_input_sender: &mpsc::UnboundedSender<(Socks5Message, Recipient)>,
+        conn_id: ConnectionId,
+        wants_status: bool,
+        kind: ConnectionErrorKind,
+        message: String,
+        return_address: Recipient,
+    ) {
+        let rejection = if wants_status {
+            Socks5Message::ConnectionError(ConnectionError::new(conn_id, kind, message))
+        } else {
+            Socks5Message::Response(Response::new(conn_id, Vec::new(), true))
+        };
+        mix_input_sender
+            .unbounded_send((rejection, return_address))
+            .unwrap();
+    }
+
     fn handle_proxy_send(
         &self,
         controller_sender: &mut ControllerSender,
@@ -220,14 +354,21 @@ impl ServiceProvider {
             .unwrap()
     }
 
+    /// Closes the socket to the remote host, which in turn is going to finish the proxy.
+    fn close_remote_connection(controller_sender: &ControllerSender, conn_id: ConnectionId) {
+        controller_sender
+            .unbounded_send(ControllerCommand::Send(conn_id, Vec::new(), true))
+            .unwrap()
+    }
+
     async fn handle_proxy_message(
         &mut self,
         #[cfg(feature = "stats-service")] storage: &crate::storage::NetworkRequesterStorage,
         raw_request: &[u8],
         controller_sender: &mut ControllerSender,
-        mix_input_sender: &mpsc::UnboundedSender<(Response, Recipient)>,
+        mix_input_sender: &mpsc::UnboundedSender<(Socks5Message, Recipient)>,
         request_stats_data: &Arc<RwLock<StatsData>>,
-        connected_clients: &mut HashMap<ConnectionId, ClientIdentity>,
+        connected_clients: &mut HashMap<ConnectionId, Recipient>,
     ) {
         let deserialized_msg = match Socks5Message::try_from_bytes(raw_request) {
             Ok(msg) => msg,
@@ -239,22 +380,33 @@ impl ServiceProvider {
         match deserialized_msg {
             Socks5Message::Request(deserialized_request) => match deserialized_request {
                 Request::Connect(req) => {
-                    connected_clients.insert(req.conn_id, *req.return_address.identity());
+                    connected_clients.insert(req.conn_id, req.return_address);
                     self.handle_proxy_connect(
                         controller_sender,
                         mix_input_sender,
                         req.conn_id,
                         req.remote_addr,
                         req.return_address,
+                        req.wants_status,
                     )
+                    .await
                 }
 
                 Request::Send(conn_id, data, closed) => {
-                    if let Some(client_identity) = connected_clients.get(&conn_id) {
+                    if let Some(return_address) = connected_clients.get(&conn_id) {
                         request_stats_data
                             .write()
                             .await
-                            .processed(client_identity, data.len() as u32);
+                            .processed(return_address.identity(), data.len() as u32);
+                        if let Err(err) = self
+                            .client_limiter
+                            .processed(return_address, data.len())
+                            .await
+                        {
+                            log::info!("Closing connection {} as the client {}", conn_id, err);
+                            Self::close_remote_connection(controller_sender, conn_id);
+                            return;
+                        }
                     }
                     self.handle_proxy_send(controller_sender, conn_id, data, closed)
                 }
@@ -271,6 +423,18 @@ impl ServiceProvider {
                     Err(e) => error!("Malformed statistics received: {}", e),
                 }
             }
+            Socks5Message::ConnectionError(error) => {
+                warn!(
+                    "Received an unexpected connection error for {} - {}",
+                    error.connection_id, error
+                )
+            }
+            Socks5Message::ConnectionEstablished(conn_id) => {
+                warn!(
+                    "Received an unexpected connection confirmation for {}",
+                    conn_id
+                )
+            }
         }
     }
 
@@ -283,7 +447,8 @@ impl ServiceProvider {
 
         // channels responsible for managing messages that are to be sent to the mix network. The receiver is
         // going to be used by `mixnet_response_listener`
-        let (mix_input_sender, mix_input_receiver) = mpsc::unbounded::<(Response, Recipient)>();
+        let (mix_input_sender, mix_input_receiver) =
+            mpsc::unbounded::<(Socks5Message, Recipient)>();
 
         let (mut timer_sender, timer_receiver) = Timer::new();
         let interval = timer_sender.interval();
@@ -327,11 +492,15 @@ impl ServiceProvider {
         );
 
         // start the listener for mix messages
+        let client_limiter = self.client_limiter.clone();
+        let controller_sender_clone = controller_sender.clone();
         tokio::spawn(async move {
             Self::mixnet_response_listener(
                 websocket_writer,
                 mix_input_receiver,
                 &response_stats_data,
+                &client_limiter,
+                &controller_sender_clone,
             )
             .await;
         });
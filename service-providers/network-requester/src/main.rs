@@ -2,12 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::{App, Arg, ArgMatches};
+use client_limits::ClientLimitsConfig;
+use ipnetwork::IpNetwork;
+use resolver::{DnsResolver, ResolverConfig, DEFAULT_DNS_PORT};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
 
 use network_defaults::DEFAULT_WEBSOCKET_LISTENING_PORT;
 
 mod allowed_hosts;
+mod client_limits;
 mod connection;
 mod core;
+mod resolver;
 mod statistics;
 #[cfg(feature = "stats-service")]
 mod storage;
@@ -16,6 +23,15 @@ mod websocket;
 const OPEN_PROXY_ARG: &str = "open-proxy";
 const WS_PORT: &str = "websocket-port";
 const DESCRIPTION: &str = "description";
+const MAX_CLIENT_CONNECTIONS: &str = "max-client-connections";
+const MAX_CLIENT_BANDWIDTH: &str = "max-client-bandwidth";
+const MAX_CLIENT_CONNECTION_RATE: &str = "max-client-connection-rate";
+const CLIENT_LIMITS_WINDOW: &str = "client-limits-window";
+const DNS_SERVER: &str = "dns-server";
+const DNS_IP_PREFERENCE: &str = "dns-ip-preference";
+const DNS_CACHE_MIN_TTL: &str = "dns-cache-min-ttl";
+const DNS_CACHE_MAX_TTL: &str = "dns-cache-max-ttl";
+const ALLOW_INTERNAL_RANGE: &str = "allow-internal-range";
 
 fn parse_args<'a>() -> ArgMatches<'a> {
     App::new("Nym Network Requester")
@@ -42,6 +58,65 @@ fn parse_args<'a>() -> ArgMatches<'a> {
                 .takes_value(true)
                 .default_value("undefined"),
         )
+        .arg(
+            Arg::with_name(MAX_CLIENT_CONNECTIONS)
+                .help("maximum number of connections a single client can have open at the same time")
+                .long(MAX_CLIENT_CONNECTIONS)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(MAX_CLIENT_BANDWIDTH)
+                .help("maximum number of bytes a single client can proxy within the limits window")
+                .long(MAX_CLIENT_BANDWIDTH)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(MAX_CLIENT_CONNECTION_RATE)
+                .help("maximum number of new connections a single client can open within the limits window")
+                .long(MAX_CLIENT_CONNECTION_RATE)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(CLIENT_LIMITS_WINDOW)
+                .help("duration of the window (in seconds) used for the client bandwidth and connection rate limits")
+                .long(CLIENT_LIMITS_WINDOW)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(DNS_SERVER)
+                .help("upstream dns server to use for resolving the requested hosts, either as 'ip' or 'ip:port'. Can be specified multiple times. If not provided, the system configuration is used")
+                .long(DNS_SERVER)
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name(DNS_IP_PREFERENCE)
+                .help("preferred ip version of the resolved addresses")
+                .long(DNS_IP_PREFERENCE)
+                .takes_value(true)
+                .possible_values(&["ipv4", "ipv6", "ipv4-only", "ipv6-only"]),
+        )
+        .arg(
+            Arg::with_name(DNS_CACHE_MIN_TTL)
+                .help("minimum amount of time (in seconds) the resolved addresses are cached for")
+                .long(DNS_CACHE_MIN_TTL)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(DNS_CACHE_MAX_TTL)
+                .help("maximum amount of time (in seconds) the resolved addresses are cached for")
+                .long(DNS_CACHE_MAX_TTL)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ALLOW_INTERNAL_RANGE)
+                .help("internal address range, such as a private network, that the network requester is allowed to connect to in the open proxy mode. Can be specified multiple times")
+                .long(ALLOW_INTERNAL_RANGE)
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
         .get_matches()
 }
 
@@ -63,11 +138,86 @@ async fn main() {
     );
 
     let description = matches.value_of(DESCRIPTION).unwrap().to_string();
+    let client_limits = parse_client_limits(&matches);
+    let resolver_config = parse_resolver_config(&matches);
+    let resolver = DnsResolver::new(&resolver_config).expect("Failed to setup the dns resolver");
+    let allowed_internal_ranges = parse_allowed_internal_ranges(&matches);
     println!("Starting socks5 service provider:");
-    let mut server = core::ServiceProvider::new(uri, description, open_proxy);
+    let mut server = core::ServiceProvider::new(
+        uri,
+        description,
+        open_proxy,
+        client_limits,
+        resolver,
+        allowed_internal_ranges,
+    );
     server.run().await;
 }
 
+fn parse_client_limits(matches: &ArgMatches<'_>) -> ClientLimitsConfig {
+    let mut client_limits = ClientLimitsConfig {
+        max_open_connections: matches.value_of(MAX_CLIENT_CONNECTIONS).map(|limit| {
+            limit
+                .parse()
+                .expect("Invalid maximum number of client connections")
+        }),
+        max_bytes_per_window: matches
+            .value_of(MAX_CLIENT_BANDWIDTH)
+            .map(|limit| limit.parse().expect("Invalid maximum client bandwidth")),
+        max_new_connections_per_window: matches.value_of(MAX_CLIENT_CONNECTION_RATE).map(|limit| {
+            limit
+                .parse()
+                .expect("Invalid maximum client connection rate")
+        }),
+        ..Default::default()
+    };
+    if let Some(window) = matches.value_of(CLIENT_LIMITS_WINDOW) {
+        let secs = window
+            .parse()
+            .expect("Invalid duration of the client limits window");
+        client_limits.window = Duration::from_secs(secs);
+    }
+    client_limits
+}
+
+fn parse_allowed_internal_ranges(matches: &ArgMatches<'_>) -> Vec<IpNetwork> {
+    matches
+        .values_of(ALLOW_INTERNAL_RANGE)
+        .map(|ranges| {
+            ranges
+                .map(|range| range.parse().expect("Invalid internal address range"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_dns_server(server: &str) -> SocketAddr {
+    if let Ok(address) = server.parse() {
+        return address;
+    }
+    let ip: IpAddr = server.parse().expect("Invalid dns server address");
+    SocketAddr::new(ip, DEFAULT_DNS_PORT)
+}
+
+fn parse_resolver_config(matches: &ArgMatches<'_>) -> ResolverConfig {
+    let mut resolver_config = ResolverConfig::default();
+    if let Some(servers) = matches.values_of(DNS_SERVER) {
+        resolver_config.upstream_servers = servers.map(parse_dns_server).collect();
+    }
+    if let Some(preference) = matches.value_of(DNS_IP_PREFERENCE) {
+        resolver_config.ip_preference = preference.parse().expect("Invalid dns ip preference");
+    }
+    if let Some(ttl) = matches.value_of(DNS_CACHE_MIN_TTL) {
+        let secs = ttl.parse().expect("Invalid minimum dns cache ttl");
+        resolver_config.min_cache_ttl = Duration::from_secs(secs);
+    }
+    if let Some(ttl) = matches.value_of(DNS_CACHE_MAX_TTL) {
+        let secs = ttl.parse().expect("Invalid maximum dns cache ttl");
+        resolver_config.max_cache_ttl = Duration::from_secs(secs);
+    }
+    resolver_config
+}
+
 fn setup_logging() {
     let mut log_builder = pretty_env_logger::formatted_timed_builder();
     if let Ok(s) = ::std::env::var("RUST_LOG") {
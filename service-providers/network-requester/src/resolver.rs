@@ -0,0 +1,345 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use log::*;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use trust_dns_resolver::config::{
+    LookupIpStrategy, NameServerConfigGroup, ResolverConfig as TrustDnsConfig, ResolverOpts,
+};
+use trust_dns_resolver::TokioAsyncResolver;
+
+pub const DEFAULT_DNS_PORT: u16 = 53;
+pub const DEFAULT_MIN_CACHE_TTL: Duration = Duration::from_secs(30);
+pub const DEFAULT_MAX_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+// upper bound on the number of cached entries to not let clients exhaust our memory
+const MAX_CACHE_ENTRIES: usize = 10_000;
+
+#[derive(Debug, Error)]
+pub enum ResolverError {
+    #[error("{0} is not a valid remote address")]
+    InvalidAddress(String),
+
+    #[error("failed to lookup {host} - {source}")]
+    LookupFailure {
+        host: String,
+        #[source]
+        source: trust_dns_resolver::error::ResolveError,
+    },
+
+    #[error("{0} did not resolve to any address")]
+    NoAddresses(String),
+
+    #[error("failed to setup the dns resolver - {0}")]
+    SetupFailure(String),
+}
+
+/// Preference of the ip version of the resolved addresses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IpPreference {
+    Ipv4Only,
+    Ipv6Only,
+    Ipv4ThenIpv6,
+    Ipv6ThenIpv4,
+}
+
+impl Default for IpPreference {
+    fn default() -> Self {
+        IpPreference::Ipv4ThenIpv6
+    }
+}
+
+impl FromStr for IpPreference {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ipv4" => Ok(IpPreference::Ipv4ThenIpv6),
+            "ipv6" => Ok(IpPreference::Ipv6ThenIpv4),
+            "ipv4-only" => Ok(IpPreference::Ipv4Only),
+            "ipv6-only" => Ok(IpPreference::Ipv6Only),
+            other => Err(format!(
+                "'{}' is not a valid ip preference. Use one of 'ipv4', 'ipv6', 'ipv4-only' or 'ipv6-only'",
+                other
+            )),
+        }
+    }
+}
+
+impl From<IpPreference> for LookupIpStrategy {
+    fn from(preference: IpPreference) -> Self {
+        match preference {
+            IpPreference::Ipv4Only => LookupIpStrategy::Ipv4Only,
+            IpPreference::Ipv6Only => LookupIpStrategy::Ipv6Only,
+            IpPreference::Ipv4ThenIpv6 => LookupIpStrategy::Ipv4thenIpv6,
+            IpPreference::Ipv6ThenIpv4 => LookupIpStrategy::Ipv6thenIpv4,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolverConfig {
+    /// Upstream dns servers to use. If empty, the system configuration is used instead.
+    pub upstream_servers: Vec<SocketAddr>,
+
+    pub ip_preference: IpPreference,
+
+    /// Lower bound on the amount of time the resolved addresses are cached for.
+    pub min_cache_ttl: Duration,
+
+    /// Upper bound on the amount of time the resolved addresses are cached for.
+    pub max_cache_ttl: Duration,
+}
+
+impl Default for ResolverConfig {
+    fn default() -> Self {
+        ResolverConfig {
+            upstream_servers: Vec::new(),
+            ip_preference: Default::default(),
+            min_cache_ttl: DEFAULT_MIN_CACHE_TTL,
+            max_cache_ttl: DEFAULT_MAX_CACHE_TTL,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedLookup {
+    addresses: Vec<IpAddr>,
+    expires_at: Instant,
+}
+
+/// In-memory cache of resolved addresses, where each entry lives for the TTL of the dns response
+/// clamped to the configured bounds.
+#[derive(Debug)]
+struct LookupCache {
+    entries: HashMap<String, CachedLookup>,
+    min_ttl: Duration,
+    max_ttl: Duration,
+}
+
+impl LookupCache {
+    fn new(min_ttl: Duration, max_ttl: Duration) -> Self {
+        LookupCache {
+            entries: HashMap::new(),
+            min_ttl,
+            max_ttl,
+        }
+    }
+
+    fn get(&self, host: &str, now: Instant) -> Option<&[IpAddr]> {
+        self.entries
+            .get(host)
+            .filter(|entry| entry.expires_at > now)
+            .map(|entry| entry.addresses.as_ref())
+    }
+
+    fn insert(&mut self, host: String, addresses: Vec<IpAddr>, valid_until: Instant, now: Instant) {
+        if self.entries.len() >= MAX_CACHE_ENTRIES {
+            self.entries.retain(|_, entry| entry.expires_at > now);
+            if self.entries.len() >= MAX_CACHE_ENTRIES {
+                debug!("the dns cache is full - {} is not going to be cached", host);
+                return;
+            }
+        }
+
+        let ttl = valid_until
+            .saturating_duration_since(now)
+            .max(self.min_ttl)
+            .min(self.max_ttl);
+        self.entries.insert(
+            host,
+            CachedLookup {
+                addresses,
+                expires_at: now + ttl,
+            },
+        );
+    }
+}
+
+/// Resolves remote addresses requested by the clients, caching the results.
+#[derive(Clone)]
+pub(crate) struct DnsResolver {
+    resolver: TokioAsyncResolver,
+    cache: Arc<RwLock<LookupCache>>,
+}
+
+impl DnsResolver {
+    pub(crate) fn new(config: &ResolverConfig) -> Result<Self, ResolverError> {
+        let (trust_dns_config, mut options) = if config.upstream_servers.is_empty() {
+            trust_dns_resolver::system_conf::read_system_conf()
+                .map_err(|err| ResolverError::SetupFailure(err.to_string()))?
+        } else {
+            let mut name_servers = NameServerConfigGroup::new();
+            for server in &config.upstream_servers {
+                name_servers.merge(NameServerConfigGroup::from_ips_clear(
+                    &[server.ip()],
+                    server.port(),
+                    true,
+                ));
+            }
+            (
+                TrustDnsConfig::from_parts(None, Vec::new(), name_servers),
+                ResolverOpts::default(),
+            )
+        };
+
+        // we're doing the caching ourselves
+        options.cache_size = 0;
+        options.ip_strategy = config.ip_preference.into();
+
+        let resolver = TokioAsyncResolver::tokio(trust_dns_config, options)
+            .map_err(|err| ResolverError::SetupFailure(err.to_string()))?;
+
+        Ok(DnsResolver {
+            resolver,
+            cache: Arc::new(RwLock::new(LookupCache::new(
+                config.min_cache_ttl,
+                config.max_cache_ttl,
+            ))),
+        })
+    }
+
+    fn split_host_port(address: &str) -> Result<(&str, u16), ResolverError> {
+        let (host, port) = address
+            .rsplit_once(':')
+            .ok_or_else(|| ResolverError::InvalidAddress(address.to_string()))?;
+        let port = port
+            .parse()
+            .map_err(|_| ResolverError::InvalidAddress(address.to_string()))?;
+
+        // ipv6 addresses might be surrounded by brackets
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if host.is_empty() {
+            return Err(ResolverError::InvalidAddress(address.to_string()));
+        }
+        Ok((host, port))
+    }
+
+    /// Resolves `host:port` address into the socket addresses it points to.
+    pub(crate) async fn resolve(&self, address: &str) -> Result<Vec<SocketAddr>, ResolverError> {
+        if let Ok(socket_address) = address.parse::<SocketAddr>() {
+            return Ok(vec![socket_address]);
+        }
+
+        let (host, port) = Self::split_host_port(address)?;
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(vec![SocketAddr::new(ip, port)]);
+        }
+
+        let host = host.to_lowercase();
+        let now = Instant::now();
+        if let Some(addresses) = self.cache.read().await.get(&host, now) {
+            trace!("using cached addresses for {}", host);
+            return Ok(Self::with_port(addresses, port));
+        }
+
+        let lookup = self
+            .resolver
+            .lookup_ip(host.as_str())
+            .await
+            .map_err(|source| ResolverError::LookupFailure {
+                host: host.clone(),
+                source,
+            })?;
+
+        let addresses: Vec<IpAddr> = lookup.iter().collect();
+        if addresses.is_empty() {
+            return Err(ResolverError::NoAddresses(host));
+        }
+
+        let socket_addresses = Self::with_port(&addresses, port);
+        self.cache
+            .write()
+            .await
+            .insert(host, addresses, lookup.valid_until(), Instant::now());
+
+        Ok(socket_addresses)
+    }
+
+    fn with_port(addresses: &[IpAddr], port: u16) -> Vec<SocketAddr> {
+        addresses
+            .iter()
+            .map(|ip| SocketAddr::new(*ip, port))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splitting_host_and_port() {
+        assert_eq!(
+            ("nymtech.net", 443),
+            DnsResolver::split_host_port("nymtech.net:443").unwrap()
+        );
+        assert_eq!(
+            ("::1", 443),
+            DnsResolver::split_host_port("[::1]:443").unwrap()
+        );
+        assert!(DnsResolver::split_host_port("nymtech.net").is_err());
+        assert!(DnsResolver::split_host_port("nymtech.net:foomp").is_err());
+        assert!(DnsResolver::split_host_port(":443").is_err());
+    }
+
+    #[test]
+    fn parsing_ip_preference() {
+        assert_eq!(IpPreference::Ipv4ThenIpv6, "ipv4".parse().unwrap());
+        assert_eq!(IpPreference::Ipv6ThenIpv4, "ipv6".parse().unwrap());
+        assert_eq!(IpPreference::Ipv4Only, "ipv4-only".parse().unwrap());
+        assert_eq!(IpPreference::Ipv6Only, "ipv6-only".parse().unwrap());
+        assert!("foomp".parse::<IpPreference>().is_err());
+    }
+
+    #[test]
+    fn cached_entries_expire() {
+        let mut cache = LookupCache::new(Duration::from_secs(10), Duration::from_secs(100));
+        let now = Instant::now();
+        let addresses = vec!["1.2.3.4".parse().unwrap()];
+
+        cache.insert(
+            "nymtech.net".to_string(),
+            addresses.clone(),
+            now + Duration::from_secs(50),
+            now,
+        );
+        assert_eq!(
+            Some(addresses.as_ref()),
+            cache.get("nymtech.net", now + Duration::from_secs(49))
+        );
+        assert!(cache
+            .get("nymtech.net", now + Duration::from_secs(50))
+            .is_none());
+    }
+
+    #[test]
+    fn cache_ttl_is_clamped() {
+        let mut cache = LookupCache::new(Duration::from_secs(10), Duration::from_secs(100));
+        let now = Instant::now();
+        let addresses = vec!["1.2.3.4".parse().unwrap()];
+
+        // response ttl below the minimum
+        cache.insert("short.com".to_string(), addresses.clone(), now, now);
+        assert!(cache
+            .get("short.com", now + Duration::from_secs(9))
+            .is_some());
+
+        // response ttl above the maximum
+        cache.insert(
+            "long.com".to_string(),
+            addresses,
+            now + Duration::from_secs(1000),
+            now,
+        );
+        assert!(cache
+            .get("long.com", now + Duration::from_secs(100))
+            .is_none());
+    }
+}
@@ -13,7 +13,7 @@ use tokio::sync::RwLock;
 
 use network_defaults::DEFAULT_NETWORK;
 use nymsphinx::addressing::clients::{ClientIdentity, Recipient};
-use socks5_requests::Response;
+use socks5_requests::{Message as Socks5Message, Response};
 
 use super::error::StatsError;
 
@@ -154,7 +154,10 @@ impl Statistics {
         &self.response_data
     }
 
-    pub async fn run(&mut self, mix_input_sender: &mpsc::UnboundedSender<(Response, Recipient)>) {
+    pub async fn run(
+        &mut self,
+        mix_input_sender: &mpsc::UnboundedSender<(Socks5Message, Recipient)>,
+    ) {
         loop {
             if self.timer_receiver.next().await == None {
                 error!("Timer thread has died. No more statistics will be sent");
@@ -197,7 +200,7 @@ impl Statistics {
                         trace!("Sending data to statistics service");
                         mix_input_sender
                             .unbounded_send((
-                                Response::new(0, data, false),
+                                Socks5Message::Response(Response::new(0, data, false)),
                                 self.stats_provider_addr,
                             ))
                             .unwrap();
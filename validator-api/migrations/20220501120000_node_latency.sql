@@ -0,0 +1,9 @@
+/*
+ * Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+-- estimated latency (in milliseconds) of the node measured during the particular monitor run,
+-- it's null if none of the test packets got back or if the status predates latency measurements
+ALTER TABLE mixnode_status ADD COLUMN latency INTEGER;
+ALTER TABLE gateway_status ADD COLUMN latency INTEGER;
@@ -0,0 +1,11 @@
+/*
+ * Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+-- unix timestamp of when the report got created so that the reports could be queried by time ranges.
+-- it's null for all the reports created before this column got introduced
+ALTER TABLE rewarding_report ADD COLUMN timestamp INTEGER;
+
+CREATE INDEX rewarding_report_timestamp ON rewarding_report(`timestamp`);
+CREATE INDEX monitor_run_timestamp ON monitor_run(`timestamp`);
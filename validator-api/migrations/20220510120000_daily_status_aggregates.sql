@@ -0,0 +1,69 @@
+/*
+ * Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+-- days (in UTC) for which the raw statuses have already been rolled up into the daily aggregates
+CREATE TABLE compacted_day
+(
+    -- 'YYYY-MM-DD'
+    date         VARCHAR NOT NULL PRIMARY KEY,
+
+    -- unix timestamp of the start of the day
+    day_start    INTEGER NOT NULL,
+
+    -- number of network monitor runs that happened during the day,
+    -- it's null for the days migrated from the old historical uptime tables
+    monitor_runs INTEGER
+);
+
+CREATE TABLE mixnode_daily_status
+(
+    mixnode_details_id INTEGER NOT NULL,
+
+    -- 'YYYY-MM-DD'
+    date               VARCHAR NOT NULL,
+
+    -- uptime of the node during the day, i.e. its reliability averaged over all monitor runs of that day
+    uptime             INTEGER NOT NULL,
+
+    -- average latency (in milliseconds) of the node during the day, if it was measured
+    average_latency    INTEGER,
+
+    -- number of raw statuses of the node the entry was created from,
+    -- it's null for the days migrated from the old historical uptime tables
+    status_count       INTEGER,
+
+    UNIQUE (mixnode_details_id, date),
+    FOREIGN KEY (mixnode_details_id) REFERENCES mixnode_details (id)
+);
+
+CREATE TABLE gateway_daily_status
+(
+    gateway_details_id INTEGER NOT NULL,
+    date               VARCHAR NOT NULL,
+    uptime             INTEGER NOT NULL,
+    average_latency    INTEGER,
+    status_count       INTEGER,
+
+    UNIQUE (gateway_details_id, date),
+    FOREIGN KEY (gateway_details_id) REFERENCES gateway_details (id)
+);
+
+-- the old daily uptimes become the initial aggregates
+INSERT OR IGNORE INTO mixnode_daily_status (mixnode_details_id, date, uptime)
+SELECT mixnode_details_id, date, uptime
+FROM mixnode_historical_uptime;
+
+INSERT OR IGNORE INTO gateway_daily_status (gateway_details_id, date, uptime)
+SELECT gateway_details_id, date, uptime
+FROM gateway_historical_uptime;
+
+INSERT OR IGNORE INTO compacted_day (date, day_start)
+SELECT date, CAST(strftime('%s', date) AS INTEGER)
+FROM (SELECT date FROM mixnode_historical_uptime UNION SELECT date FROM gateway_historical_uptime);
+
+DROP TABLE mixnode_historical_uptime;
+DROP TABLE gateway_historical_uptime;
+
+CREATE INDEX testing_route_monitor_run_id ON testing_route(`monitor_run_id`);
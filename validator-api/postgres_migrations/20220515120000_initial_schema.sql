@@ -0,0 +1,105 @@
+/*
+ * Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+-- equivalent of the current state of the sqlite schema (see `migrations`) with all the tables
+-- that are still in use. Note that foreign key constraints are not enforced as with sqlite.
+
+CREATE TABLE mixnode_details
+(
+    id       BIGSERIAL PRIMARY KEY,
+    owner    VARCHAR NOT NULL,
+    identity VARCHAR NOT NULL UNIQUE
+);
+
+CREATE TABLE gateway_details
+(
+    id       BIGSERIAL PRIMARY KEY,
+    owner    VARCHAR NOT NULL,
+    identity VARCHAR NOT NULL UNIQUE
+);
+
+CREATE TABLE mixnode_status
+(
+    mixnode_details_id BIGINT   NOT NULL,
+    reliability        SMALLINT NOT NULL,
+    timestamp          BIGINT   NOT NULL,
+    -- estimated latency (in milliseconds) of the node measured during the particular monitor run
+    latency            INTEGER
+);
+
+CREATE TABLE gateway_status
+(
+    gateway_details_id BIGINT   NOT NULL,
+    reliability        SMALLINT NOT NULL,
+    timestamp          BIGINT   NOT NULL,
+    latency            INTEGER
+);
+
+CREATE TABLE monitor_run
+(
+    id        BIGSERIAL PRIMARY KEY,
+    timestamp BIGINT NOT NULL
+);
+
+CREATE TABLE testing_route
+(
+    gateway_id     BIGINT NOT NULL,
+    layer1_mix_id  BIGINT NOT NULL,
+    layer2_mix_id  BIGINT NOT NULL,
+    layer3_mix_id  BIGINT NOT NULL,
+    monitor_run_id BIGINT NOT NULL
+);
+
+CREATE TABLE rewarding_report
+(
+    interval_rewarding_id        BIGINT NOT NULL,
+    eligible_mixnodes            BIGINT NOT NULL,
+    possibly_unrewarded_mixnodes BIGINT NOT NULL,
+    timestamp                    BIGINT
+);
+
+CREATE TABLE compacted_day
+(
+    date         VARCHAR PRIMARY KEY,
+    day_start    BIGINT NOT NULL,
+    monitor_runs BIGINT
+);
+
+CREATE TABLE mixnode_daily_status
+(
+    mixnode_details_id BIGINT   NOT NULL,
+    date               VARCHAR  NOT NULL,
+    uptime             SMALLINT NOT NULL,
+    average_latency    INTEGER,
+    status_count       BIGINT,
+
+    UNIQUE (mixnode_details_id, date)
+);
+
+CREATE TABLE gateway_daily_status
+(
+    gateway_details_id BIGINT   NOT NULL,
+    date               VARCHAR  NOT NULL,
+    uptime             SMALLINT NOT NULL,
+    average_latency    INTEGER,
+    status_count       BIGINT,
+
+    UNIQUE (gateway_details_id, date)
+);
+
+CREATE TABLE signed_deposit
+(
+    id                         BIGSERIAL PRIMARY KEY,
+    tx_hash                    VARCHAR NOT NULL UNIQUE,
+    blinded_signature_response VARCHAR NOT NULL
+);
+
+CREATE INDEX mixnode_status_index ON mixnode_status (mixnode_details_id, timestamp DESC);
+CREATE INDEX gateway_status_index ON gateway_status (gateway_details_id, timestamp DESC);
+CREATE INDEX mixnode_status_timestamp ON mixnode_status (timestamp);
+CREATE INDEX gateway_status_timestamp ON gateway_status (timestamp);
+CREATE INDEX monitor_run_timestamp ON monitor_run (timestamp);
+CREATE INDEX testing_route_monitor_run_id ON testing_route (monitor_run_id);
+CREATE INDEX rewarding_report_timestamp ON rewarding_report (timestamp);
@@ -0,0 +1,16 @@
+/*
+ * Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+-- results of the network monitor checking whether gateways store messages for offline clients
+-- and deliver them once the client reconnects
+CREATE TABLE gateway_inbox_status
+(
+    gateway_details_id BIGINT   NOT NULL,
+    reliability        SMALLINT NOT NULL,
+    timestamp          BIGINT   NOT NULL
+);
+
+CREATE INDEX gateway_inbox_status_index ON gateway_inbox_status (gateway_details_id, timestamp DESC);
+CREATE INDEX gateway_inbox_status_timestamp ON gateway_inbox_status (timestamp);
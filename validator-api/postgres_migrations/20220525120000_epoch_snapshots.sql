@@ -0,0 +1,39 @@
+/*
+ * Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+-- state of the rewarded set at the time of the epoch getting rewarded, so that the rewarding
+-- could later be replayed with different parameters
+CREATE TABLE epoch_snapshot
+(
+    id                  BIGSERIAL PRIMARY KEY,
+    epoch_id            BIGINT NOT NULL,
+    timestamp           BIGINT NOT NULL,
+    -- json encoded `EpochRewardParams`
+    epoch_reward_params TEXT   NOT NULL
+);
+
+CREATE TABLE epoch_snapshot_mixnode
+(
+    epoch_snapshot_id BIGINT   NOT NULL,
+    identity          VARCHAR  NOT NULL,
+    -- json encoded `MixNodeBond`
+    bond              TEXT     NOT NULL,
+    uptime            SMALLINT NOT NULL,
+    in_active_set     BOOLEAN  NOT NULL
+);
+
+CREATE TABLE epoch_snapshot_delegation
+(
+    epoch_snapshot_id BIGINT  NOT NULL,
+    mix_identity      VARCHAR NOT NULL,
+    delegator         VARCHAR NOT NULL,
+    -- the amounts might not fit in 64 bits
+    amount            VARCHAR NOT NULL
+);
+
+CREATE INDEX epoch_snapshot_epoch_id ON epoch_snapshot (epoch_id);
+CREATE INDEX epoch_snapshot_timestamp ON epoch_snapshot (timestamp);
+CREATE INDEX epoch_snapshot_mixnode_index ON epoch_snapshot_mixnode (epoch_snapshot_id);
+CREATE INDEX epoch_snapshot_delegation_index ON epoch_snapshot_delegation (epoch_snapshot_id, delegator);
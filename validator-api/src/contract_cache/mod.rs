@@ -13,20 +13,25 @@ use rocket::Route;
 use rocket_okapi::openapi_get_routes_spec;
 use rocket_okapi::settings::OpenApiSettings;
 
+use crypto::blake3;
 use rocket::fairing::AdHoc;
 use serde::Serialize;
 use std::collections::HashMap;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tokio::time;
-use validator_api_requests::models::MixnodeStatus;
+use validator_api_requests::models::{MixnodeStatus, ValidatorCacheEvent};
 use validator_client::nymd::CosmWasmClient;
 
+pub(crate) mod models;
 pub(crate) mod routes;
 
+// subscribers lagging behind by more than that many events will be told to resync
+const CACHE_EVENTS_CAPACITY: usize = 64;
+
 pub struct ValidatorCacheRefresher<C> {
     nymd_client: Client<C>,
     cache: ValidatorCache,
@@ -37,14 +42,16 @@ pub struct ValidatorCacheRefresher<C> {
 pub struct ValidatorCache {
     initialised: Arc<AtomicBool>,
     inner: Arc<RwLock<ValidatorCacheInner>>,
+    events: broadcast::Sender<ValidatorCacheEvent>,
 }
 
 struct ValidatorCacheInner {
     mixnodes: Cache<Vec<MixNodeBond>>,
     gateways: Cache<Vec<GatewayBond>>,
 
-    mixnodes_blacklist: Cache<HashSet<IdentityKey>>,
-    gateways_blacklist: Cache<HashSet<IdentityKey>>,
+    // kept ordered, so that their versions would only depend on the contents
+    mixnodes_blacklist: Cache<BTreeSet<IdentityKey>>,
+    gateways_blacklist: Cache<BTreeSet<IdentityKey>>,
 
     rewarded_set: Cache<Vec<MixNodeBond>>,
     active_set: Cache<Vec<MixNodeBond>>,
@@ -58,34 +65,108 @@ fn current_unix_timestamp() -> i64 {
     now.unix_timestamp()
 }
 
-#[derive(Default, Serialize, Clone)]
+// hash of the serialized value, used for determining whether it has changed
+fn compute_version<T: Serialize>(value: &T) -> String {
+    match serde_json::to_vec(value) {
+        Ok(bytes) => blake3::hash(&bytes).to_hex().to_string(),
+        Err(err) => {
+            // this should never happen as all the cached types are trivially serializable,
+            // but if it did, it'd just result in the value being always considered as changed
+            error!("Failed to serialize cached value - {}", err);
+            String::new()
+        }
+    }
+}
+
+// version of a value derived from multiple cached values, e.g. mixnodes with blacklist applied
+fn combined_version(versions: &[&str]) -> String {
+    let mut hasher = blake3::Hasher::new();
+    for version in versions {
+        hasher.update(version.as_bytes());
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+#[derive(Serialize, Clone)]
 pub struct Cache<T> {
     value: T,
     as_at: i64,
+    #[serde(skip)]
+    version: String,
 }
 
-impl<T: Clone> Cache<T> {
+impl<T: Clone + Serialize> Cache<T> {
     fn new(value: T) -> Self {
         Cache {
+            version: compute_version(&value),
             value,
             as_at: current_unix_timestamp(),
         }
     }
 
     fn update(&mut self, value: T) {
+        self.version = compute_version(&value);
         self.value = value;
         self.as_at = current_unix_timestamp()
     }
+}
 
+impl<T> Cache<T> {
     pub fn timestamp(&self) -> i64 {
         self.as_at
     }
 
+    /// Hash of the cached value, changing whenever the value itself changes.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
     pub fn into_inner(self) -> T {
         self.value
     }
 }
 
+// returns the newly bonded nodes alongside identities of the ones that got unbonded
+fn bonding_changes<T: Clone>(
+    old: &[T],
+    new: &[T],
+    identity: impl Fn(&T) -> &IdentityKey,
+) -> (Vec<T>, Vec<IdentityKey>) {
+    let old_identities = old.iter().map(&identity).collect::<HashSet<_>>();
+    let new_identities = new.iter().map(&identity).collect::<HashSet<_>>();
+
+    let bonded = new
+        .iter()
+        .filter(|node| !old_identities.contains(identity(node)))
+        .cloned()
+        .collect();
+    let unbonded = old
+        .iter()
+        .map(&identity)
+        .filter(|node_identity| !new_identities.contains(node_identity))
+        .cloned()
+        .collect();
+
+    (bonded, unbonded)
+}
+
+fn identities(bonds: &[MixNodeBond]) -> HashSet<&IdentityKey> {
+    bonds.iter().map(|bond| bond.identity()).collect()
+}
+
+fn blacklist_changes(
+    old: &BTreeSet<IdentityKey>,
+    new: &BTreeSet<IdentityKey>,
+) -> Option<(Vec<IdentityKey>, Vec<IdentityKey>)> {
+    let added = new.difference(old).cloned().collect::<Vec<_>>();
+    let removed = old.difference(new).cloned().collect::<Vec<_>>();
+    if added.is_empty() && removed.is_empty() {
+        None
+    } else {
+        Some((added, removed))
+    }
+}
+
 impl<C> ValidatorCacheRefresher<C> {
     pub(crate) fn new(
         nymd_client: Client<C>,
@@ -190,7 +271,8 @@ pub(crate) fn validator_cache_routes(settings: &OpenApiSettings) -> (Vec<Route>,
         routes::get_blacklisted_mixnodes,
         routes::get_blacklisted_gateways,
         routes::get_epoch_reward_params,
-        routes::get_current_epoch
+        routes::get_current_epoch,
+        routes::get_cache_events,
     ]
 }
 
@@ -199,6 +281,25 @@ impl ValidatorCache {
         ValidatorCache {
             initialised: Arc::new(AtomicBool::new(false)),
             inner: Arc::new(RwLock::new(ValidatorCacheInner::new())),
+            events: broadcast::channel(CACHE_EVENTS_CAPACITY).0,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn with_mixnodes(mixnodes: Vec<MixNodeBond>) -> Self {
+        let cache = Self::new();
+        cache.inner.write().await.mixnodes.update(mixnodes);
+        cache
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<ValidatorCacheEvent> {
+        self.events.subscribe()
+    }
+
+    fn publish(&self, events: Vec<ValidatorCacheEvent>) {
+        for event in events {
+            // this only fails if there are no subscribers at the moment, which is fine
+            let _ = self.events.send(event);
         }
     }
 
@@ -219,12 +320,24 @@ impl ValidatorCache {
     ) {
         match time::timeout(Duration::from_millis(100), self.inner.write()).await {
             Ok(mut cache) => {
+                let events = cache.changes(
+                    &mixnodes,
+                    &gateways,
+                    &rewarded_set,
+                    &active_set,
+                    epoch_rewarding_params,
+                    current_epoch,
+                );
+
                 cache.mixnodes.update(mixnodes);
                 cache.gateways.update(gateways);
                 cache.rewarded_set.update(rewarded_set);
                 cache.active_set.update(active_set);
                 cache.current_reward_params.update(epoch_rewarding_params);
                 cache.current_epoch.update(Some(current_epoch));
+
+                drop(cache);
+                self.publish(events);
             }
             Err(e) => {
                 error!("{}", e);
@@ -232,7 +345,7 @@ impl ValidatorCache {
         }
     }
 
-    pub async fn mixnodes_blacklist(&self) -> Option<Cache<HashSet<IdentityKey>>> {
+    pub async fn mixnodes_blacklist(&self) -> Option<Cache<BTreeSet<IdentityKey>>> {
         match time::timeout(Duration::from_millis(100), self.inner.read()).await {
             Ok(cache) => Some(cache.mixnodes_blacklist.clone()),
             Err(e) => {
@@ -242,7 +355,7 @@ impl ValidatorCache {
         }
     }
 
-    pub async fn gateways_blacklist(&self) -> Option<Cache<HashSet<IdentityKey>>> {
+    pub async fn gateways_blacklist(&self) -> Option<Cache<BTreeSet<IdentityKey>>> {
         match time::timeout(Duration::from_millis(100), self.inner.read()).await {
             Ok(cache) => Some(cache.gateways_blacklist.clone()),
             Err(e) => {
@@ -259,21 +372,21 @@ impl ValidatorCache {
     ) {
         let blacklist = self.mixnodes_blacklist().await;
         if let Some(blacklist) = blacklist {
-            let mut blacklist = blacklist
-                .value
-                .union(&add)
-                .cloned()
-                .collect::<HashSet<IdentityKey>>();
-            let to_remove = blacklist
-                .intersection(&remove)
-                .cloned()
-                .collect::<HashSet<IdentityKey>>();
-            for key in to_remove {
-                blacklist.remove(&key);
-            }
+            let old_blacklist = blacklist.value;
+            let mut blacklist = old_blacklist.clone();
+            blacklist.extend(add);
+            blacklist.retain(|key| !remove.contains(key));
+            let changes = blacklist_changes(&old_blacklist, &blacklist);
             match time::timeout(Duration::from_millis(100), self.inner.write()).await {
                 Ok(mut cache) => {
                     cache.mixnodes_blacklist.update(blacklist);
+                    drop(cache);
+                    if let Some((added, removed)) = changes {
+                        self.publish(vec![ValidatorCacheEvent::MixnodesBlacklistChanged {
+                            added,
+                            removed,
+                        }]);
+                    }
                     return;
                 }
                 Err(e) => error!("{}", e),
@@ -289,21 +402,21 @@ impl ValidatorCache {
     ) {
         let blacklist = self.gateways_blacklist().await;
         if let Some(blacklist) = blacklist {
-            let mut blacklist = blacklist
-                .value
-                .union(&add)
-                .cloned()
-                .collect::<HashSet<IdentityKey>>();
-            let to_remove = blacklist
-                .intersection(&remove)
-                .cloned()
-                .collect::<HashSet<IdentityKey>>();
-            for key in to_remove {
-                blacklist.remove(&key);
-            }
+            let old_blacklist = blacklist.value;
+            let mut blacklist = old_blacklist.clone();
+            blacklist.extend(add);
+            blacklist.retain(|key| !remove.contains(key));
+            let changes = blacklist_changes(&old_blacklist, &blacklist);
             match time::timeout(Duration::from_millis(100), self.inner.write()).await {
                 Ok(mut cache) => {
                     cache.gateways_blacklist.update(blacklist);
+                    drop(cache);
+                    if let Some((added, removed)) = changes {
+                        self.publish(vec![ValidatorCacheEvent::GatewaysBlacklistChanged {
+                            added,
+                            removed,
+                        }]);
+                    }
                     return;
                 }
                 Err(e) => error!("{}", e),
@@ -313,24 +426,33 @@ impl ValidatorCache {
     }
 
     pub async fn mixnodes(&self) -> Vec<MixNodeBond> {
+        self.versioned_mixnodes().await.into_inner()
+    }
+
+    /// Bonded mixnodes, excluding the blacklisted ones, with the version of the returned set.
+    pub(crate) async fn versioned_mixnodes(&self) -> Cache<Vec<MixNodeBond>> {
         let blacklist = self.mixnodes_blacklist().await;
         let mixnodes = match time::timeout(Duration::from_millis(100), self.inner.read()).await {
             Ok(cache) => cache.mixnodes.clone(),
             Err(e) => {
                 error!("{}", e);
-                return Vec::new();
+                return Cache::new(Vec::new());
             }
         };
 
         if let Some(blacklist) = blacklist {
-            mixnodes
-                .value
-                .iter()
-                .filter(|mix| !blacklist.value.contains(mix.identity()))
-                .cloned()
-                .collect()
+            Cache {
+                version: combined_version(&[mixnodes.version(), blacklist.version()]),
+                value: mixnodes
+                    .value
+                    .iter()
+                    .filter(|mix| !blacklist.value.contains(mix.identity()))
+                    .cloned()
+                    .collect(),
+                as_at: mixnodes.as_at,
+            }
         } else {
-            mixnodes.value
+            mixnodes
         }
     }
 
@@ -345,24 +467,33 @@ impl ValidatorCache {
     }
 
     pub async fn gateways(&self) -> Vec<GatewayBond> {
+        self.versioned_gateways().await.into_inner()
+    }
+
+    /// Bonded gateways, excluding the blacklisted ones, with the version of the returned set.
+    pub(crate) async fn versioned_gateways(&self) -> Cache<Vec<GatewayBond>> {
         let blacklist = self.gateways_blacklist().await;
         let gateways = match time::timeout(Duration::from_millis(100), self.inner.read()).await {
             Ok(cache) => cache.gateways.clone(),
             Err(e) => {
                 error!("{}", e);
-                return Vec::new();
+                return Cache::new(Vec::new());
             }
         };
 
         if let Some(blacklist) = blacklist {
-            gateways
-                .value
-                .iter()
-                .filter(|mix| !blacklist.value.contains(mix.identity()))
-                .cloned()
-                .collect()
+            Cache {
+                version: combined_version(&[gateways.version(), blacklist.version()]),
+                value: gateways
+                    .value
+                    .iter()
+                    .filter(|mix| !blacklist.value.contains(mix.identity()))
+                    .cloned()
+                    .collect(),
+                as_at: gateways.as_at,
+            }
         } else {
-            gateways.value
+            gateways
         }
     }
 
@@ -472,18 +603,128 @@ impl ValidatorCache {
 }
 
 impl ValidatorCacheInner {
+    fn changes(
+        &self,
+        mixnodes: &[MixNodeBond],
+        gateways: &[GatewayBond],
+        rewarded_set: &[MixNodeBond],
+        active_set: &[MixNodeBond],
+        epoch_reward_params: EpochRewardParams,
+        current_epoch: Interval,
+    ) -> Vec<ValidatorCacheEvent> {
+        let mut events = Vec::new();
+
+        let (bonded, unbonded) =
+            bonding_changes(&self.mixnodes.value, mixnodes, |bond| bond.identity());
+        if !bonded.is_empty() {
+            events.push(ValidatorCacheEvent::MixnodesBonded { mixnodes: bonded })
+        }
+        if !unbonded.is_empty() {
+            events.push(ValidatorCacheEvent::MixnodesUnbonded {
+                identities: unbonded,
+            })
+        }
+
+        let (bonded, unbonded) =
+            bonding_changes(&self.gateways.value, gateways, |bond| bond.identity());
+        if !bonded.is_empty() {
+            events.push(ValidatorCacheEvent::GatewaysBonded { gateways: bonded })
+        }
+        if !unbonded.is_empty() {
+            events.push(ValidatorCacheEvent::GatewaysUnbonded {
+                identities: unbonded,
+            })
+        }
+
+        if identities(&self.rewarded_set.value) != identities(rewarded_set)
+            || identities(&self.active_set.value) != identities(active_set)
+        {
+            events.push(ValidatorCacheEvent::RewardedSetChanged {
+                rewarded_set: rewarded_set
+                    .iter()
+                    .map(|bond| bond.identity().clone())
+                    .collect(),
+                active_set: active_set
+                    .iter()
+                    .map(|bond| bond.identity().clone())
+                    .collect(),
+            })
+        }
+
+        if self.current_epoch.value.map(|epoch| epoch.id()) != Some(current_epoch.id()) {
+            events.push(ValidatorCacheEvent::EpochAdvanced {
+                epoch: current_epoch,
+                epoch_reward_params,
+            })
+        }
+
+        events
+    }
+
     fn new() -> Self {
         ValidatorCacheInner {
-            mixnodes: Cache::default(),
-            gateways: Cache::default(),
-            rewarded_set: Cache::default(),
-            active_set: Cache::default(),
+            mixnodes: Cache::new(Vec::new()),
+            gateways: Cache::new(Vec::new()),
+            rewarded_set: Cache::new(Vec::new()),
+            active_set: Cache::new(Vec::new()),
             current_reward_params: Cache::new(EpochRewardParams::new_empty()),
-            mixnodes_blacklist: Cache::default(),
-            gateways_blacklist: Cache::default(),
+            mixnodes_blacklist: Cache::new(BTreeSet::new()),
+            gateways_blacklist: Cache::new(BTreeSet::new()),
             // setting it to a dummy value on creation is fine, as nothing will be able to ready from it
             // since 'initialised' flag won't be set
             current_epoch: Cache::new(None),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bonding_changes_are_detected() {
+        let old = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let new = vec!["b".to_string(), "d".to_string(), "c".to_string()];
+
+        let (bonded, unbonded) = bonding_changes(&old, &new, |identity| identity);
+        assert_eq!(bonded, vec!["d".to_string()]);
+        assert_eq!(unbonded, vec!["a".to_string()]);
+
+        let (bonded, unbonded) = bonding_changes(&new, &new, |identity| identity);
+        assert!(bonded.is_empty());
+        assert!(unbonded.is_empty());
+    }
+
+    #[test]
+    fn blacklist_changes_are_only_reported_when_present() {
+        let old = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        let new = ["b", "c"].iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(
+            blacklist_changes(&old, &new),
+            Some((vec!["c".to_string()], vec!["a".to_string()]))
+        );
+        assert_eq!(blacklist_changes(&new, &new), None);
+    }
+
+    #[test]
+    fn equal_blacklists_have_equal_versions() {
+        let identities = (0..100)
+            .map(|i| format!("identity{}", i))
+            .collect::<Vec<_>>();
+        let blacklist = identities.iter().cloned().collect::<BTreeSet<_>>();
+        let reversed = identities.iter().rev().cloned().collect::<BTreeSet<_>>();
+
+        assert_eq!(
+            Cache::new(blacklist.clone()).version(),
+            Cache::new(reversed).version()
+        );
+
+        let mut changed = blacklist.clone();
+        changed.remove("identity42");
+        assert_ne!(
+            Cache::new(blacklist).version(),
+            Cache::new(changed).version()
+        );
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::contract_cache::Cache;
+use okapi::openapi3::Responses;
+use rocket::http::{ContentType, Header, MediaType, Status};
+use rocket::response::{self, Responder, Response};
+use rocket::serde::json::Json;
+use rocket::Request;
+use rocket_okapi::gen::OpenApiGenerator;
+use rocket_okapi::response::OpenApiResponderInner;
+use rocket_okapi::util::{add_schema_response, ensure_status_code_exists};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::io::Cursor;
+
+// versioned, so that any change to the binary encoding (or the encoded types) could be rolled out
+// under a new media type without breaking the existing clients
+const BINARY_MEDIA_TYPE_TOP: &str = "application";
+const BINARY_MEDIA_TYPE_SUB: &str = "vnd.nym.topology.v1+bincode";
+
+/// Cached value served with its `ETag`, so that clients could avoid re-downloading it with
+/// `If-None-Match` if it has not changed. If the client prefers exactly
+/// `application/vnd.nym.topology.v1+bincode`, the value is bincode encoded instead of being
+/// sent as json.
+pub(crate) struct VersionedResponse<T> {
+    value: T,
+    version: String,
+}
+
+impl<T> From<Cache<T>> for VersionedResponse<T> {
+    fn from(cache: Cache<T>) -> Self {
+        VersionedResponse {
+            value: cache.value,
+            version: cache.version,
+        }
+    }
+}
+
+impl<T> VersionedResponse<T> {
+    // different encodings of the same value must not share the entity tag
+    fn etag(&self, binary: bool) -> String {
+        if binary {
+            format!("\"{}-bincode-v1\"", self.version)
+        } else {
+            format!("\"{}\"", self.version)
+        }
+    }
+}
+
+fn is_binary_media_type(media_type: &MediaType) -> bool {
+    media_type.top() == BINARY_MEDIA_TYPE_TOP && media_type.sub() == BINARY_MEDIA_TYPE_SUB
+}
+
+fn matches_etag(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for VersionedResponse<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let binary = request
+            .accept()
+            .map(|accept| is_binary_media_type(accept.preferred().media_type()))
+            .unwrap_or_default();
+        let etag = self.etag(binary);
+
+        let not_modified = request
+            .headers()
+            .get("If-None-Match")
+            .any(|if_none_match| matches_etag(if_none_match, &etag));
+
+        let mut response = if not_modified {
+            Response::build().status(Status::NotModified).finalize()
+        } else if binary {
+            let bytes = bincode::serialize(&self.value).map_err(|err| {
+                error!("Failed to serialize cached value - {}", err);
+                Status::InternalServerError
+            })?;
+            Response::build()
+                .header(ContentType::new(
+                    BINARY_MEDIA_TYPE_TOP,
+                    BINARY_MEDIA_TYPE_SUB,
+                ))
+                .sized_body(bytes.len(), Cursor::new(bytes))
+                .finalize()
+        } else {
+            Json(self.value).respond_to(request)?
+        };
+
+        response.set_header(Header::new("ETag", etag));
+        response.set_header(Header::new("Vary", "Accept"));
+        Ok(response)
+    }
+}
+
+impl<T: Serialize + JsonSchema> OpenApiResponderInner for VersionedResponse<T> {
+    fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+        let mut responses = Json::<T>::responses(gen)?;
+        add_schema_response(
+            &mut responses,
+            200,
+            &format!("{}/{}", BINARY_MEDIA_TYPE_TOP, BINARY_MEDIA_TYPE_SUB),
+            gen.json_schema::<Vec<u8>>(),
+        )?;
+        ensure_status_code_exists(&mut responses, 304);
+        Ok(responses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_etags() {
+        assert!(matches_etag("\"abc\"", "\"abc\""));
+        assert!(matches_etag("W/\"abc\"", "\"abc\""));
+        assert!(matches_etag("\"foo\", \"abc\"", "\"abc\""));
+        assert!(matches_etag("*", "\"abc\""));
+        assert!(!matches_etag("\"abc-bincode-v1\"", "\"abc\""));
+        assert!(!matches_etag("", "\"abc\""));
+    }
+
+    #[test]
+    fn only_exact_binary_media_type_is_accepted() {
+        assert!(is_binary_media_type(&MediaType::new(
+            "application",
+            "vnd.nym.topology.v1+bincode"
+        )));
+        assert!(!is_binary_media_type(&MediaType::new(
+            "application",
+            "vnd.nym.topology.v2+bincode"
+        )));
+        assert!(!is_binary_media_type(&MediaType::Binary));
+        assert!(!is_binary_media_type(&MediaType::Any));
+        assert!(!is_binary_media_type(&MediaType::JSON));
+    }
+}
@@ -1,43 +1,50 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::contract_cache::models::VersionedResponse;
 use crate::contract_cache::ValidatorCache;
 use mixnet_contract_common::reward_params::EpochRewardParams;
 use mixnet_contract_common::{GatewayBond, Interval, MixNodeBond};
+use rocket::response::stream::{Event, EventStream};
 use rocket::serde::json::Json;
-use rocket::State;
+use rocket::tokio::select;
+use rocket::tokio::sync::broadcast::error::RecvError;
+use rocket::{Shutdown, State};
 use rocket_okapi::openapi;
-use std::collections::HashSet;
+use std::collections::BTreeSet;
+use validator_api_requests::models::ValidatorCacheEvent;
 
 #[openapi(tag = "contract-cache")]
 #[get("/mixnodes")]
-pub async fn get_mixnodes(cache: &State<ValidatorCache>) -> Json<Vec<MixNodeBond>> {
-    Json(cache.mixnodes().await)
+pub async fn get_mixnodes(cache: &State<ValidatorCache>) -> VersionedResponse<Vec<MixNodeBond>> {
+    cache.versioned_mixnodes().await.into()
 }
 
 #[openapi(tag = "contract-cache")]
 #[get("/gateways")]
-pub async fn get_gateways(cache: &State<ValidatorCache>) -> Json<Vec<GatewayBond>> {
-    Json(cache.gateways().await)
+pub async fn get_gateways(cache: &State<ValidatorCache>) -> VersionedResponse<Vec<GatewayBond>> {
+    cache.versioned_gateways().await.into()
 }
 
 #[openapi(tag = "contract-cache")]
 #[get("/mixnodes/rewarded")]
-pub async fn get_rewarded_set(cache: &State<ValidatorCache>) -> Json<Vec<MixNodeBond>> {
-    Json(cache.rewarded_set().await.value)
+pub async fn get_rewarded_set(
+    cache: &State<ValidatorCache>,
+) -> VersionedResponse<Vec<MixNodeBond>> {
+    cache.rewarded_set().await.into()
 }
 
 #[openapi(tag = "contract-cache")]
 #[get("/mixnodes/active")]
-pub async fn get_active_set(cache: &State<ValidatorCache>) -> Json<Vec<MixNodeBond>> {
-    Json(cache.active_set().await.value)
+pub async fn get_active_set(cache: &State<ValidatorCache>) -> VersionedResponse<Vec<MixNodeBond>> {
+    cache.active_set().await.into()
 }
 
 #[openapi(tag = "contract-cache")]
 #[get("/mixnodes/blacklisted")]
 pub async fn get_blacklisted_mixnodes(
     cache: &State<ValidatorCache>,
-) -> Json<Option<HashSet<String>>> {
+) -> Json<Option<BTreeSet<String>>> {
     Json(cache.mixnodes_blacklist().await.map(|c| c.value))
 }
 
@@ -45,7 +52,7 @@ pub async fn get_blacklisted_mixnodes(
 #[get("/gateways/blacklisted")]
 pub async fn get_blacklisted_gateways(
     cache: &State<ValidatorCache>,
-) -> Json<Option<HashSet<String>>> {
+) -> Json<Option<BTreeSet<String>>> {
     Json(cache.gateways_blacklist().await.map(|c| c.value))
 }
 
@@ -60,3 +67,23 @@ pub async fn get_epoch_reward_params(cache: &State<ValidatorCache>) -> Json<Epoc
 pub async fn get_current_epoch(cache: &State<ValidatorCache>) -> Json<Option<Interval>> {
     Json(cache.current_epoch().await.value)
 }
+
+// server-sent events are not representable in the openapi spec
+#[openapi(skip)]
+#[get("/events")]
+pub fn get_cache_events(cache: &State<ValidatorCache>, mut shutdown: Shutdown) -> EventStream![] {
+    let mut events = cache.subscribe();
+    EventStream! {
+        loop {
+            let event = select! {
+                event = events.recv() => match event {
+                    Ok(event) => event,
+                    Err(RecvError::Closed) => break,
+                    Err(RecvError::Lagged(missed)) => ValidatorCacheEvent::Lagged { missed },
+                },
+                _ = &mut shutdown => break,
+            };
+            yield Event::json(&event);
+        }
+    }
+}
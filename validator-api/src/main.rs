@@ -31,7 +31,9 @@ use url::Url;
 // use validator_client::nymd::SigningNymdClient;
 // use validator_client::ValidatorClientError;
 
+use crate::rewarded_set_updater::selection::RewardedSetSelector;
 use crate::rewarded_set_updater::RewardedSetUpdater;
+use crate::topology_signer::TopologySigner;
 #[cfg(feature = "coconut")]
 use coconut::InternalSignRequest;
 use validator_client::nymd::SigningNymdClient;
@@ -44,6 +46,7 @@ pub(crate) mod nymd_client;
 mod rewarded_set_updater;
 pub(crate) mod storage;
 mod swagger;
+mod topology_signer;
 
 #[cfg(feature = "coconut")]
 mod coconut;
@@ -416,22 +419,29 @@ async fn setup_rocket(
         "/" => custom_route_spec,
         "" => contract_cache::validator_cache_routes(&openapi_settings),
         "/status" => node_status_api::node_status_routes(&openapi_settings, config.get_network_monitor_enabled()),
+        "/rewarded-set" => rewarded_set_updater::rewarded_set_routes(&openapi_settings, config.get_network_monitor_enabled()),
+        "/topology" => topology_signer::topology_routes(&openapi_settings),
     }
 
+    let topology_signer = TopologySigner::load_or_generate(config)?;
+
     let rocket = rocket
         .mount("/swagger", make_swagger_ui(&swagger::get_docs()))
         .attach(setup_cors()?)
         .attach(setup_liftoff_notify(liftoff_notify))
-        .attach(ValidatorCache::stage());
+        .attach(ValidatorCache::stage())
+        .attach(topology_signer.stage());
 
     // This is not a very nice approach. A lazy value would be more suitable, but that's still
     // a nightly feature: https://github.com/rust-lang/rust/issues/74465
     let storage = if cfg!(feature = "coconut") || config.get_network_monitor_enabled() {
-        Some(ValidatorApiStorage::init(config.get_node_status_api_database_path()).await?)
+        Some(ValidatorApiStorage::init(config).await?)
     } else {
         None
     };
 
+    let rocket = rocket.attach(RewardedSetSelector::new(config, storage.clone()).stage());
+
     #[cfg(feature = "coconut")]
     let rocket = if config.get_coconut_signer_enabled() {
         rocket.attach(InternalSignRequest::stage(
@@ -539,14 +549,24 @@ async fn run_validator_api(matches: ArgMatches<'static>) -> Result<()> {
         // spawn our cacher
         tokio::spawn(async move { validator_cache_refresher.run().await });
 
-        // setup our daily uptime updater. Note that if network monitor is disabled, then we have
-        // no data for the updates and hence we don't need to start it up
+        // setup our daily uptime updater, which also takes care of purging old monitor data.
+        // Note that if network monitor is disabled, then we have no data for the updates
+        // and hence we don't need to start it up
         let storage = rocket.state::<ValidatorApiStorage>().unwrap().clone();
-        let uptime_updater = HistoricalUptimeUpdater::new(storage.clone());
+        let uptime_updater = HistoricalUptimeUpdater::new(&config, storage.clone());
         tokio::spawn(async move { uptime_updater.run().await });
 
-        let mut rewarded_set_updater =
-            RewardedSetUpdater::new(nymd_client, validator_cache.clone(), storage).await?;
+        let selector = rocket.state::<RewardedSetSelector>().unwrap().clone();
+        let mut rewarded_set_updater = RewardedSetUpdater::new(
+            nymd_client,
+            validator_cache.clone(),
+            storage,
+            selector,
+            config.get_record_epoch_snapshots(),
+            config.get_jailing_uptime_threshold(),
+            config.get_jailing_epochs(),
+        )
+        .await?;
 
         // spawn rewarded set updater
         tokio::spawn(async move { rewarded_set_updater.run().await.unwrap() });
@@ -39,4 +39,27 @@ impl GatewaysReader {
         self.stream_map.remove(id);
         self.ack_map.remove(id);
     }
+
+    // the inbox clients are connected to the same gateways as the "main" clients,
+    // so they need to be kept under different keys
+    fn inbox_channel_id(id: &identity::PublicKey) -> String {
+        format!("inbox-{}", id)
+    }
+
+    pub fn add_inbox_receivers(
+        &mut self,
+        id: identity::PublicKey,
+        message_receiver: MixnetMessageReceiver,
+        ack_receiver: AcknowledgementReceiver,
+    ) {
+        let channel_id = Self::inbox_channel_id(&id);
+        self.stream_map.insert(channel_id.clone(), message_receiver);
+        self.ack_map.insert(channel_id, ack_receiver);
+    }
+
+    pub fn remove_inbox_receivers(&mut self, id: &identity::PublicKey) {
+        let channel_id = Self::inbox_channel_id(id);
+        self.stream_map.remove(&channel_id);
+        self.ack_map.remove(&channel_id);
+    }
 }
@@ -29,6 +29,14 @@ pub(crate) mod test_route;
 
 pub(crate) const ROUTE_TESTING_TEST_NONCE: u64 = 0;
 
+// packets sent during the gateway inbox test use the nonce of the current run with the highest bit set,
+// so that neither late packets from the regular test nor inbox packets from previous runs would be counted
+const INBOX_TEST_NONCE_FLAG: u64 = 1 << 63;
+
+pub(crate) fn inbox_test_nonce(test_nonce: u64) -> u64 {
+    test_nonce | INBOX_TEST_NONCE_FLAG
+}
+
 pub(crate) struct NetworkMonitorBuilder<'a> {
     config: &'a Config,
     system_version: String,
@@ -60,6 +68,10 @@ impl<'a> NetworkMonitorBuilder<'a> {
         let identity_keypair = Arc::new(identity::KeyPair::new(&mut rng));
         let encryption_keypair = Arc::new(encryption::KeyPair::new(&mut rng));
 
+        // note that the inbox identity shares the encryption key with the "main" one so that
+        // all the received packets could be processed in the same way
+        let inbox_identity_keypair = Arc::new(identity::KeyPair::new(&mut rng));
+
         let (gateway_status_update_sender, gateway_status_update_receiver) = mpsc::unbounded();
         let (received_processor_sender_channel, received_processor_receiver_channel) =
             mpsc::unbounded();
@@ -69,6 +81,7 @@ impl<'a> NetworkMonitorBuilder<'a> {
             self.validator_cache,
             self.config.get_per_node_test_packets(),
             *identity_keypair.public_key(),
+            *inbox_identity_keypair.public_key(),
             *encryption_keypair.public_key(),
         );
 
@@ -91,6 +104,7 @@ impl<'a> NetworkMonitorBuilder<'a> {
             self.config,
             gateway_status_update_sender,
             Arc::clone(&identity_keypair),
+            inbox_identity_keypair,
             self.config.get_gateway_sending_rate(),
             bandwidth_controller,
             self.config.get_disabled_credentials_mode(),
@@ -100,7 +114,10 @@ impl<'a> NetworkMonitorBuilder<'a> {
             received_processor_receiver_channel,
             Arc::clone(&encryption_keypair),
         );
-        let summary_producer = new_summary_producer(self.config.get_per_node_test_packets());
+        let summary_producer = new_summary_producer(
+            self.config.get_per_node_test_packets(),
+            self.config.get_inbox_test_packets(),
+        );
         let packet_receiver = new_packet_receiver(
             gateway_status_update_receiver,
             received_processor_sender_channel,
@@ -144,6 +161,7 @@ fn new_packet_preparer(
     validator_cache: ValidatorCache,
     per_node_test_packets: usize,
     self_public_identity: identity::PublicKey,
+    self_inbox_identity: identity::PublicKey,
     self_public_encryption: encryption::PublicKey,
 ) -> PacketPreparer {
     PacketPreparer::new(
@@ -151,6 +169,7 @@ fn new_packet_preparer(
         validator_cache,
         per_node_test_packets,
         self_public_identity,
+        self_inbox_identity,
         self_public_encryption,
     )
 }
@@ -159,6 +178,7 @@ fn new_packet_sender(
     config: &Config,
     gateways_status_updater: GatewayClientUpdateSender,
     local_identity: Arc<identity::KeyPair>,
+    inbox_identity: Arc<identity::KeyPair>,
     max_sending_rate: usize,
     bandwidth_controller: BandwidthController<PersistentStorage>,
     disabled_credentials_mode: bool,
@@ -166,6 +186,7 @@ fn new_packet_sender(
     PacketSender::new(
         gateways_status_updater,
         local_identity,
+        inbox_identity,
         config.get_gateway_response_timeout(),
         config.get_gateway_connection_timeout(),
         config.get_max_concurrent_gateway_clients(),
@@ -182,10 +203,13 @@ fn new_received_processor(
     ReceivedProcessor::new(packets_receiver, client_encryption_keypair)
 }
 
-fn new_summary_producer(per_node_test_packets: usize) -> SummaryProducer {
+fn new_summary_producer(
+    per_node_test_packets: usize,
+    inbox_test_packets: usize,
+) -> SummaryProducer {
     // right now always print the basic report. If we feel like we need to change it, it can
     // be easily adjusted by adding some flag or something
-    SummaryProducer::new(per_node_test_packets).with_report()
+    SummaryProducer::new(per_node_test_packets, inbox_test_packets).with_report()
 }
 
 fn new_packet_receiver(
@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::config::Config;
+use crate::network_monitor::inbox_test_nonce;
 use crate::network_monitor::monitor::preparer::PacketPreparer;
-use crate::network_monitor::monitor::processor::ReceivedProcessor;
+use crate::network_monitor::monitor::processor::{
+    average_round_trip_time, round_trip_times_per_route, ReceivedPacket, ReceivedProcessor,
+};
 use crate::network_monitor::monitor::sender::PacketSender;
+use crate::network_monitor::monitor::summary_producer::NodeResult;
 use crate::network_monitor::monitor::summary_producer::{SummaryProducer, TestSummary};
-use crate::network_monitor::test_packet::TestPacket;
 use crate::network_monitor::test_route::TestRoute;
 use crate::storage::ValidatorApiStorage;
 use log::{debug, error, info};
@@ -42,6 +45,12 @@ pub(super) struct Monitor {
     /// The minimum number of test routes that need to be constructed (and working) in order for
     /// a monitor test run to be valid.
     minimum_test_routes: usize,
+
+    /// Number of test packets sent via each test route to the offline inbox client of each gateway.
+    inbox_test_packets: usize,
+
+    /// Duration of waiting for the gateways to deliver the stored test packets after reconnecting.
+    inbox_retrieval_timeout: Duration,
 }
 
 impl Monitor {
@@ -66,6 +75,8 @@ impl Monitor {
             route_test_packets: config.get_route_test_packets(),
             test_routes: config.get_test_routes(),
             minimum_test_routes: config.get_minimum_test_routes(),
+            inbox_test_packets: config.get_inbox_test_packets(),
+            inbox_retrieval_timeout: config.get_inbox_retrieval_timeout(),
         }
     }
 
@@ -79,6 +90,7 @@ impl Monitor {
             .insert_monitor_run_results(
                 test_summary.mixnode_results,
                 test_summary.gateway_results,
+                test_summary.gateway_inbox_results,
                 test_summary
                     .route_results
                     .into_iter()
@@ -97,16 +109,26 @@ impl Monitor {
         }
     }
 
-    fn analyse_received_test_route_packets(&self, packets: &[TestPacket]) -> HashMap<u64, usize> {
+    fn analyse_received_test_route_packets(
+        &self,
+        packets: &[ReceivedPacket],
+    ) -> HashMap<u64, usize> {
         let mut received = HashMap::new();
-        for packet in packets {
-            *received.entry(packet.route_id).or_insert(0usize) += 1usize
+        for received_packet in packets {
+            *received
+                .entry(received_packet.packet.route_id)
+                .or_insert(0usize) += 1usize
         }
 
         received
     }
 
-    async fn test_chosen_test_routes(&mut self, routes: &[TestRoute]) -> HashMap<u64, bool> {
+    /// Returns average round-trip times of the routes that managed to deliver all the test packets,
+    /// or `None` for the ones that did not.
+    async fn test_chosen_test_routes(
+        &mut self,
+        routes: &[TestRoute],
+    ) -> HashMap<u64, Option<Duration>> {
         // notes for the future improvements:
         /*
            - gateway authentication failure should only 'blacklist' gateways, not mixnodes
@@ -136,26 +158,33 @@ impl Monitor {
 
         let received = self.received_processor.return_received().await;
         let mut results = self.analyse_received_test_route_packets(&received);
+        let round_trip_times = round_trip_times_per_route(&received);
 
         // create entry for routes that might have not forwarded a single packet
         for route in routes {
             results.entry(route.id()).or_insert(0);
         }
 
-        for entry in results.iter() {
-            if *entry.1 == self.route_test_packets {
-                info!("✔️ {} succeeded", entry.0)
-            } else {
-                info!(
-                    "❌️ {} failed ({}/{} received)",
-                    entry.0, entry.1, self.route_test_packets
-                )
-            }
-        }
-
         results
             .into_iter()
-            .map(|(k, v)| (k, v == self.route_test_packets))
+            .map(|(route_id, received)| {
+                if received == self.route_test_packets {
+                    let round_trip_time = round_trip_times
+                        .get(&route_id)
+                        .and_then(|times| average_round_trip_time(times));
+                    info!(
+                        "✔️ {} succeeded (average round-trip time: {:?})",
+                        route_id, round_trip_time
+                    );
+                    (route_id, round_trip_time)
+                } else {
+                    info!(
+                        "❌️ {} failed ({}/{} received)",
+                        route_id, received, self.route_test_packets
+                    );
+                    (route_id, None)
+                }
+            })
             .collect()
     }
 
@@ -208,12 +237,14 @@ impl Monitor {
                 // ideally we would blacklist all nodes regardless of the result so we would not use them anymore
                 // however, currently we have huge imbalance of gateways to mixnodes so we might accidentally
                 // discard working gateway because it was paired with broken mixnode
-                if *results.get(&candidate.id()).unwrap() {
+                if let Some(round_trip_time) = *results.get(&candidate.id()).unwrap() {
                     // if the path is fully working, blacklist those nodes so we wouldn't construct
                     // any other path through any of those nodes
                     self.blacklist_route_nodes(&candidate, &mut blacklist);
 
-                    verified_routes.push(candidate);
+                    // remember how fast the route is on its own to estimate latencies of the nodes
+                    // tested against it
+                    verified_routes.push(candidate.with_round_trip_time(round_trip_time));
                     if verified_routes.len() == self.test_routes {
                         break 'outer;
                     }
@@ -262,7 +293,7 @@ impl Monitor {
         info!("Test routes: {:?}", routes);
         info!("Received {}/{} packets", total_received, total_sent);
 
-        let summary = self.summary_producer.produce_summary(
+        let mut summary = self.summary_producer.produce_summary(
             prepared_packets.tested_mixnodes,
             prepared_packets.tested_gateways,
             received,
@@ -274,9 +305,69 @@ impl Monitor {
         let report = summary.create_report(total_sent, total_received);
         info!("{}", report);
 
+        summary.gateway_inbox_results = self.test_gateway_inboxes(routes).await;
+
         self.submit_new_node_statuses(summary).await;
     }
 
+    // checks whether gateways store messages for offline clients and deliver them once they reconnect
+    async fn test_gateway_inboxes(&mut self, routes: &[TestRoute]) -> Vec<NodeResult> {
+        if self.inbox_test_packets == 0 {
+            return Vec::new();
+        }
+
+        info!("Generating test mix packets for the gateway inbox test...");
+        let test_nonce = inbox_test_nonce(self.test_nonce);
+        let prepared_packets = self
+            .packet_preparer
+            .prepare_inbox_test_packets(test_nonce, routes, self.inbox_test_packets)
+            .await;
+
+        let total_sent = prepared_packets
+            .packets
+            .iter()
+            .map(|packets| packets.packets.len())
+            .sum::<usize>();
+
+        self.received_processor.set_new_test_nonce(test_nonce).await;
+
+        // we're not connected with the inbox identity to any gateway at this point,
+        // so all of those packets should end up in the gateways' storage
+        info!("Sending packets to the offline inbox clients...");
+        self.packet_sender
+            .send_packets(prepared_packets.packets)
+            .await;
+
+        sleep(self.packet_delivery_timeout).await;
+
+        info!("Reconnecting to all gateways to retrieve the stored packets...");
+        let inbox_clients = self
+            .packet_sender
+            .connect_inbox_clients(&prepared_packets.tested_gateways)
+            .await;
+
+        sleep(self.inbox_retrieval_timeout).await;
+        self.packet_sender
+            .disconnect_inbox_clients(inbox_clients)
+            .await;
+
+        let received = self.received_processor.return_received().await;
+        info!(
+            "Retrieved {}/{} packets stored by the gateways",
+            received.len(),
+            total_sent
+        );
+
+        let tested_gateways = prepared_packets
+            .tested_gateways
+            .iter()
+            .map(|gateway| gateway.into())
+            .collect();
+
+        self.summary_producer
+            .produce_inbox_results(tested_gateways, received, routes.len())
+    }
+
     async fn test_run(&mut self) {
         info!("Starting test run no. {}", self.test_nonce);
         let start = Instant::now();
@@ -2,22 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::contract_cache::ValidatorCache;
-use crate::network_monitor::chunker::Chunker;
-use crate::network_monitor::monitor::sender::GatewayPackets;
+use crate::network_monitor::monitor::sender::{GatewayPackets, PendingTestPacket};
 use crate::network_monitor::test_packet::{NodeType, TestPacket};
 use crate::network_monitor::test_route::TestRoute;
 use crypto::asymmetric::{encryption, identity};
 use log::info;
 use mixnet_contract_common::{Addr, GatewayBond, Layer, MixNodeBond};
 use nymsphinx::addressing::clients::Recipient;
-use nymsphinx::forwarding::packet::MixPacket;
 use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
 use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
 use std::time::Duration;
-use topology::{gateway, mix, NymTopology};
+use topology::{gateway, mix};
 
 // declared type aliases for easier code reasoning
 type Version = String;
@@ -117,9 +116,17 @@ pub(crate) struct PreparedPackets {
     pub(super) invalid_gateways: Vec<InvalidNode>,
 }
 
+pub(crate) struct PreparedInboxPackets {
+    /// All packets addressed to the (offline) inbox clients of the tested gateways as well as
+    /// the gateways through which they ought to be sent.
+    pub(super) packets: Vec<GatewayPackets>,
+
+    /// All gateways to which the inbox client will have to reconnect to retrieve the stored packets.
+    pub(super) tested_gateways: Vec<gateway::Node>,
+}
+
 pub(crate) struct PacketPreparer {
     system_version: String,
-    chunker: Option<Chunker>,
     validator_cache: ValidatorCache,
 
     /// Number of test packets sent to each node
@@ -130,6 +137,7 @@ pub(crate) struct PacketPreparer {
     // gateways might recognise our "test" keys and take special care to always forward those packets
     // even if otherwise they are malicious.
     self_public_identity: identity::PublicKey,
+    self_inbox_identity: identity::PublicKey,
     self_public_encryption: encryption::PublicKey,
 }
 
@@ -139,44 +147,19 @@ impl PacketPreparer {
         validator_cache: ValidatorCache,
         per_node_test_packets: usize,
         self_public_identity: identity::PublicKey,
+        self_inbox_identity: identity::PublicKey,
         self_public_encryption: encryption::PublicKey,
     ) -> Self {
         PacketPreparer {
             system_version: system_version.to_owned(),
-            chunker: None,
             validator_cache,
             per_node_test_packets,
             self_public_identity,
+            self_inbox_identity,
             self_public_encryption,
         }
     }
 
-    async fn wrap_test_packet(
-        &mut self,
-        packet: &TestPacket,
-        topology: &NymTopology,
-        packet_recipient: Recipient,
-    ) -> MixPacket {
-        // this should be done only once. We can't really do it at construction time
-        // as there's no sane Default for Recipient
-        if self.chunker.is_none() {
-            self.chunker = Some(Chunker::new(packet_recipient));
-        }
-        let mut mix_packets = self
-            .chunker
-            .as_mut()
-            .unwrap()
-            .prepare_packets_from(packet.to_bytes(), topology, packet_recipient)
-            .await;
-        assert_eq!(
-            mix_packets.len(),
-            1,
-            "Our test packets data is longer than a single sphinx packet!"
-        );
-
-        mix_packets.pop().unwrap()
-    }
-
     pub(crate) async fn wait_for_validator_cache_initial_values(&self, minimum_full_routes: usize) {
         // wait for the cache to get initialised
         self.validator_cache.wait_for_initial_values().await;
@@ -351,20 +334,25 @@ impl PacketPreparer {
         )
     }
 
+    fn create_inbox_recipient(&self, gateway: &gateway::Node) -> Recipient {
+        Recipient::new(
+            self.self_inbox_identity,
+            self.self_public_encryption,
+            gateway.identity_key,
+        )
+    }
+
     pub(crate) async fn prepare_test_route_viability_packets(
         &mut self,
         route: &TestRoute,
         num: usize,
     ) -> GatewayPackets {
-        let mut mix_packets = Vec::with_capacity(num);
         let test_packet = route.self_test_packet();
+        let topology = Arc::new(route.topology().clone());
         let recipient = self.create_packet_sender(route.gateway());
-        for _ in 0..num {
-            let mix_packet = self
-                .wrap_test_packet(&test_packet, route.topology(), recipient)
-                .await;
-            mix_packets.push(mix_packet)
-        }
+        let mix_packets = (0..num)
+            .map(|_| PendingTestPacket::new(test_packet.clone(), Arc::clone(&topology), recipient))
+            .collect();
 
         GatewayPackets::new(
             route.gateway_clients_address(),
@@ -448,13 +436,14 @@ impl PacketPreparer {
             // and for each mixnode...
             for mixnode in &mixnodes {
                 let test_packet = TestPacket::from_mixnode(mixnode, test_route.id(), test_nonce);
-                let topology = test_route.substitute_mix(mixnode);
+                let topology = Arc::new(test_route.substitute_mix(mixnode));
                 // produce n mix packets
                 for _ in 0..self.per_node_test_packets {
-                    let mix_packet = self
-                        .wrap_test_packet(&test_packet, &topology, recipient)
-                        .await;
-                    mix_packets.push(mix_packet);
+                    mix_packets.push(PendingTestPacket::new(
+                        test_packet.clone(),
+                        Arc::clone(&topology),
+                        recipient,
+                    ));
                 }
             }
 
@@ -473,13 +462,14 @@ impl PacketPreparer {
                 let gateway_address = gateway.clients_address();
                 let gateway_owner = gateway.owner.clone();
                 let recipient = self.create_packet_sender(gateway);
-                let topology = test_route.substitute_gateway(gateway);
+                let topology = Arc::new(test_route.substitute_gateway(gateway));
                 // produce n mix packets
                 for _ in 0..self.per_node_test_packets {
-                    let mix_packet = self
-                        .wrap_test_packet(&test_packet, &topology, recipient)
-                        .await;
-                    gateway_mix_packets.push(mix_packet);
+                    gateway_mix_packets.push(PendingTestPacket::new(
+                        test_packet.clone(),
+                        Arc::clone(&topology),
+                        recipient,
+                    ));
                 }
 
                 // and push it into existing struct (if it's a "core" gateway being tested against another route)
@@ -504,4 +494,52 @@ impl PacketPreparer {
             invalid_gateways,
         }
     }
+
+    pub(super) async fn prepare_inbox_test_packets(
+        &mut self,
+        test_nonce: u64,
+        test_routes: &[TestRoute],
+        inbox_test_packets: usize,
+    ) -> PreparedInboxPackets {
+        let (_, gateways) = self.all_mixnodes_and_gateways().await;
+        let (gateways, _) = self.filter_outdated_and_malformed_gateways(gateways);
+
+        info!(
+            "Need to create {} mix packets for the gateway inbox test",
+            test_routes.len() * inbox_test_packets * gateways.len()
+        );
+
+        let mut packets = Vec::with_capacity(gateways.len());
+        for gateway in &gateways {
+            let recipient = self.create_inbox_recipient(gateway);
+            let mut gateway_mix_packets =
+                Vec::with_capacity(test_routes.len() * inbox_test_packets);
+
+            // the packets are sent through the very gateway being tested, the same way as during
+            // the regular test, except nobody is going to be there to receive them
+            for test_route in test_routes {
+                let test_packet = TestPacket::from_gateway(gateway, test_route.id(), test_nonce);
+                let topology = Arc::new(test_route.substitute_gateway(gateway));
+                for _ in 0..inbox_test_packets {
+                    gateway_mix_packets.push(PendingTestPacket::new(
+                        test_packet.clone(),
+                        Arc::clone(&topology),
+                        recipient,
+                    ));
+                }
+            }
+
+            packets.push(GatewayPackets::new(
+                gateway.clients_address(),
+                gateway.identity_key,
+                gateway.owner.clone(),
+                gateway_mix_packets,
+            ));
+        }
+
+        PreparedInboxPackets {
+            packets,
+            tested_gateways: gateways,
+        }
+    }
 }
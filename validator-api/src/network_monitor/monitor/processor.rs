@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::network_monitor::gateways_reader::GatewayMessages;
-use crate::network_monitor::test_packet::TestPacket;
+use crate::network_monitor::test_packet::{unix_timestamp_millis, TestPacket};
 use crate::network_monitor::ROUTE_TESTING_TEST_NONCE;
 use crypto::asymmetric::encryption;
 use futures::channel::mpsc;
@@ -10,9 +10,11 @@ use futures::lock::{Mutex, MutexGuard};
 use futures::{SinkExt, StreamExt};
 use log::warn;
 use nymsphinx::receiver::MessageReceiver;
+use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
 use std::mem;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub(crate) type ReceivedProcessorSender = mpsc::UnboundedSender<GatewayMessages>;
 pub(crate) type ReceivedProcessorReceiver = mpsc::UnboundedReceiver<GatewayMessages>;
@@ -43,6 +45,68 @@ impl Display for ProcessingError {
     }
 }
 
+// number of nodes on each test route, i.e. three mixnodes and a gateway
+const TEST_ROUTE_NODES: u32 = 4;
+
+/// Test packet received during a test run alongside the measured round-trip time of the route it took.
+#[derive(Debug, Clone)]
+pub(crate) struct ReceivedPacket {
+    pub(crate) packet: TestPacket,
+    pub(crate) round_trip_time: Duration,
+}
+
+impl ReceivedPacket {
+    fn new(packet: TestPacket, received_timestamp: u64) -> Self {
+        ReceivedPacket {
+            round_trip_time: packet.round_trip_time(received_timestamp),
+            packet,
+        }
+    }
+}
+
+/// Groups round-trip times of the received packets by the test route they were sent through.
+pub(crate) fn round_trip_times_per_route(
+    received: &[ReceivedPacket],
+) -> HashMap<u64, Vec<Duration>> {
+    let mut round_trip_times: HashMap<_, Vec<_>> = HashMap::new();
+    for received_packet in received {
+        round_trip_times
+            .entry(received_packet.packet.route_id)
+            .or_default()
+            .push(received_packet.round_trip_time);
+    }
+    round_trip_times
+}
+
+/// Average of the provided round-trip times, or `None` if nothing was measured.
+pub(crate) fn average_round_trip_time(round_trip_times: &[Duration]) -> Option<Duration> {
+    if round_trip_times.is_empty() {
+        return None;
+    }
+    let total: Duration = round_trip_times.iter().sum();
+    Some(total / round_trip_times.len() as u32)
+}
+
+/// Estimates latency of a node from the round-trip times of the test packets sent through it,
+/// alongside the ids of the routes they took, and the round-trip times of those routes on their own.
+/// Each packet went over a route that only differs from the original one in the tested node,
+/// so the difference between their round-trip times is attributed to that node, on top of
+/// the latency of the node it replaced, which is assumed to be an even share of the route's time.
+pub(crate) fn estimate_node_latency(
+    round_trip_times: &[(u64, Duration)],
+    route_round_trip_times: &HashMap<u64, Duration>,
+) -> Option<Duration> {
+    let estimates = round_trip_times
+        .iter()
+        .filter_map(|(route_id, round_trip_time)| {
+            let route_round_trip_time = *route_round_trip_times.get(route_id)?;
+            let replaced_node_latency = route_round_trip_time / TEST_ROUTE_NODES;
+            Some((*round_trip_time + replaced_node_latency).saturating_sub(route_round_trip_time))
+        })
+        .collect::<Vec<_>>();
+    average_round_trip_time(&estimates)
+}
+
 // we can't use Notify due to possible edge case where both notification are consumed at once
 enum LockPermit {
     Release,
@@ -64,11 +128,13 @@ struct ReceivedProcessorInner {
     message_receiver: MessageReceiver,
 
     /// Vector containing all received (and decrypted) packets in the current test run.
-    received_packets: Vec<TestPacket>,
+    received_packets: Vec<ReceivedPacket>,
 }
 
 impl ReceivedProcessorInner {
     fn on_message(&mut self, message: Vec<u8>) -> Result<(), ProcessingError> {
+        let received_timestamp = unix_timestamp_millis();
+
         // if the nonce is none it means the packet was received during the 'waiting' for the
         // next test run
         if self.test_nonce.is_none() {
@@ -96,12 +162,13 @@ impl ReceivedProcessorInner {
             return Err(ProcessingError::NonMatchingNonce(test_packet.test_nonce()));
         }
 
-        self.received_packets.push(test_packet);
+        self.received_packets
+            .push(ReceivedPacket::new(test_packet, received_timestamp));
 
         Ok(())
     }
 
-    fn finish_run(&mut self) -> Vec<TestPacket> {
+    fn finish_run(&mut self) -> Vec<ReceivedPacket> {
         self.test_nonce = None;
         mem::take(&mut self.received_packets)
     }
@@ -212,7 +279,7 @@ impl ReceivedProcessor {
             .expect("processing task has died!");
     }
 
-    pub(super) async fn return_received(&mut self) -> Vec<TestPacket> {
+    pub(super) async fn return_received(&mut self) -> Vec<ReceivedPacket> {
         // ask for the lock back
         self.permit_changer
             .as_mut()
@@ -236,3 +303,85 @@ impl ReceivedProcessor {
         received
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network_monitor::test_packet::NodeType;
+    use crypto::asymmetric::identity;
+
+    fn received_packet(route_id: u64, round_trip_millis: u64) -> ReceivedPacket {
+        let mut rng = rand_07::thread_rng();
+        let dummy_keypair = identity::KeyPair::new(&mut rng);
+        let mut packet = TestPacket::new(
+            *dummy_keypair.public_key(),
+            "some owner".to_string(),
+            route_id,
+            1,
+            NodeType::Mixnode,
+        );
+        packet.sent_timestamp = 1_000;
+        ReceivedPacket::new(packet, 1_000 + round_trip_millis)
+    }
+
+    #[test]
+    fn round_trip_times_are_grouped_per_route() {
+        let received = vec![
+            received_packet(1, 100),
+            received_packet(2, 500),
+            received_packet(1, 300),
+        ];
+
+        let per_route = round_trip_times_per_route(&received);
+        assert_eq!(2, per_route.len());
+        assert_eq!(
+            Some(Duration::from_millis(200)),
+            average_round_trip_time(&per_route[&1])
+        );
+        assert_eq!(
+            Some(Duration::from_millis(500)),
+            average_round_trip_time(&per_route[&2])
+        );
+    }
+
+    #[test]
+    fn average_of_no_measurements_is_none() {
+        assert!(average_round_trip_time(&[]).is_none());
+    }
+
+    #[test]
+    fn node_latency_is_estimated_against_the_route_round_trip_times() {
+        let route_round_trip_times = [
+            (1, Duration::from_millis(400)),
+            (2, Duration::from_millis(800)),
+        ]
+        .into_iter()
+        .collect();
+
+        // 100ms + 50ms and 200ms + 100ms over the respective routes
+        let round_trip_times = [
+            (1, Duration::from_millis(450)),
+            (2, Duration::from_millis(900)),
+        ];
+        assert_eq!(
+            Some(Duration::from_millis(225)),
+            estimate_node_latency(&round_trip_times, &route_round_trip_times)
+        );
+
+        // node that is faster than the one it replaced can't go below zero
+        assert_eq!(
+            Some(Duration::ZERO),
+            estimate_node_latency(&[(1, Duration::from_millis(200))], &route_round_trip_times)
+        );
+    }
+
+    #[test]
+    fn node_latency_is_not_estimated_without_known_route_round_trip_times() {
+        let route_round_trip_times = [(1, Duration::from_millis(400))].into_iter().collect();
+        assert!(estimate_node_latency(&[], &route_round_trip_times).is_none());
+        assert!(
+            estimate_node_latency(&[(2, Duration::from_millis(400))], &route_round_trip_times)
+                .is_none()
+        );
+    }
+}
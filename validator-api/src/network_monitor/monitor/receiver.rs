@@ -17,6 +17,13 @@ pub(crate) enum GatewayClientUpdate {
         identity::PublicKey,
         (MixnetMessageReceiver, AcknowledgementReceiver),
     ),
+
+    /// Short-lived client used for retrieving messages stored by the gateway during the inbox test.
+    NewInbox(
+        identity::PublicKey,
+        (MixnetMessageReceiver, AcknowledgementReceiver),
+    ),
+    InboxDone(identity::PublicKey),
 }
 
 pub(crate) struct PacketReceiver {
@@ -46,6 +53,13 @@ impl PacketReceiver {
             GatewayClientUpdate::Failure(id) => {
                 self.gateways_reader.remove_recievers(&id.to_string());
             }
+            GatewayClientUpdate::NewInbox(id, (message_receiver, ack_receiver)) => {
+                self.gateways_reader
+                    .add_inbox_receivers(id, message_receiver, ack_receiver);
+            }
+            GatewayClientUpdate::InboxDone(id) => {
+                self.gateways_reader.remove_inbox_receivers(&id);
+            }
         }
     }
 
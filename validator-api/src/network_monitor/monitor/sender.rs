@@ -1,11 +1,13 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::network_monitor::chunker::Chunker;
 use crate::network_monitor::monitor::gateway_clients_cache::{
     ActiveGatewayClients, GatewayClientHandle,
 };
 use crate::network_monitor::monitor::gateways_pinger::GatewayPinger;
 use crate::network_monitor::monitor::receiver::{GatewayClientUpdate, GatewayClientUpdateSender};
+use crate::network_monitor::test_packet::{unix_timestamp_millis, TestPacket};
 use config::defaults::REMAINING_BANDWIDTH_THRESHOLD;
 use credential_storage::PersistentStorage;
 use crypto::asymmetric::identity::{self, PUBLIC_KEY_LENGTH};
@@ -16,6 +18,7 @@ use futures::{Future, Stream};
 use gateway_client::error::GatewayClientError;
 use gateway_client::{AcknowledgementReceiver, GatewayClient, MixnetMessageReceiver};
 use log::{debug, info, trace, warn};
+use nymsphinx::addressing::clients::Recipient;
 use nymsphinx::forwarding::packet::MixPacket;
 use pin_project::pin_project;
 use std::mem;
@@ -24,11 +27,49 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::Poll;
 use std::time::Duration;
+use topology::{gateway, NymTopology};
 
 use gateway_client::bandwidth::BandwidthController;
 
 const TIME_CHUNK_SIZE: Duration = Duration::from_millis(50);
 
+/// Test packet alongside everything required to wrap it into a sphinx packet.
+/// The wrapping is deferred until right before the packet is sent, so that its `sent_timestamp`
+/// would not include the time spent in our rate-limited sending queue.
+pub(crate) struct PendingTestPacket {
+    pub(crate) packet: TestPacket,
+    pub(crate) topology: Arc<NymTopology>,
+    pub(crate) recipient: Recipient,
+}
+
+impl PendingTestPacket {
+    pub(crate) fn new(
+        packet: TestPacket,
+        topology: Arc<NymTopology>,
+        recipient: Recipient,
+    ) -> Self {
+        PendingTestPacket {
+            packet,
+            topology,
+            recipient,
+        }
+    }
+
+    async fn into_mix_packet(mut self, chunker: &mut Chunker) -> MixPacket {
+        self.packet.sent_timestamp = unix_timestamp_millis();
+        let mut mix_packets = chunker
+            .prepare_packets_from(self.packet.to_bytes(), &self.topology, self.recipient)
+            .await;
+        assert_eq!(
+            mix_packets.len(),
+            1,
+            "Our test packets data is longer than a single sphinx packet!"
+        );
+
+        mix_packets.pop().unwrap()
+    }
+}
+
 pub(crate) struct GatewayPackets {
     /// Network address of the target gateway if wanted to be accessed by the client.
     /// It is a websocket address.
@@ -41,7 +82,7 @@ pub(crate) struct GatewayPackets {
     pub(crate) gateway_owner: String,
 
     /// All the packets that are going to get sent to the gateway.
-    pub(crate) packets: Vec<MixPacket>,
+    pub(crate) packets: Vec<PendingTestPacket>,
 }
 
 impl GatewayPackets {
@@ -49,7 +90,7 @@ impl GatewayPackets {
         clients_address: String,
         pub_key: identity::PublicKey,
         gateway_owner: String,
-        packets: Vec<MixPacket>,
+        packets: Vec<PendingTestPacket>,
     ) -> Self {
         GatewayPackets {
             clients_address,
@@ -72,7 +113,7 @@ impl GatewayPackets {
         }
     }
 
-    pub(super) fn push_packets(&mut self, mut packets: Vec<MixPacket>) {
+    pub(super) fn push_packets(&mut self, mut packets: Vec<PendingTestPacket>) {
         if self.packets.is_empty() {
             self.packets = packets
         } else if self.packets.len() > packets.len() {
@@ -88,6 +129,10 @@ impl GatewayPackets {
 struct FreshGatewayClientData {
     gateways_status_updater: GatewayClientUpdateSender,
     local_identity: Arc<identity::KeyPair>,
+
+    /// Identity used exclusively for the gateway inbox test. We never stay connected with it,
+    /// so that the gateways would have to store all the packets sent to it.
+    inbox_identity: Arc<identity::KeyPair>,
     gateway_response_timeout: Duration,
 
     // I guess in the future this struct will require aggregated verification key and....
@@ -154,6 +199,7 @@ impl PacketSender {
     pub(crate) fn new(
         gateways_status_updater: GatewayClientUpdateSender,
         local_identity: Arc<identity::KeyPair>,
+        inbox_identity: Arc<identity::KeyPair>,
         gateway_response_timeout: Duration,
         gateway_connection_timeout: Duration,
         max_concurrent_clients: usize,
@@ -166,6 +212,7 @@ impl PacketSender {
             fresh_gateway_client_data: Arc::new(FreshGatewayClientData {
                 gateways_status_updater,
                 local_identity,
+                inbox_identity,
                 gateway_response_timeout,
                 bandwidth_controller,
                 disabled_credentials_mode,
@@ -192,6 +239,7 @@ impl PacketSender {
         address: String,
         identity: identity::PublicKey,
         owner: String,
+        local_identity: Arc<identity::KeyPair>,
         fresh_gateway_client_data: &FreshGatewayClientData,
     ) -> (
         GatewayClientHandle,
@@ -206,7 +254,7 @@ impl PacketSender {
         let (ack_sender, ack_receiver) = mpsc::unbounded();
         let mut gateway_client = GatewayClient::new(
             address,
-            Arc::clone(&fresh_gateway_client_data.local_identity),
+            local_identity,
             identity,
             owner,
             None,
@@ -226,33 +274,50 @@ impl PacketSender {
         )
     }
 
+    async fn wrap_pending_packets(
+        chunker: &mut Chunker,
+        packets: Vec<PendingTestPacket>,
+    ) -> Vec<MixPacket> {
+        let mut mix_packets = Vec::with_capacity(packets.len());
+        for packet in packets {
+            mix_packets.push(packet.into_mix_packet(chunker).await);
+        }
+        mix_packets
+    }
+
     async fn attempt_to_send_packets(
         client: &mut GatewayClient,
-        mut mix_packets: Vec<MixPacket>,
+        mut packets: Vec<PendingTestPacket>,
         max_sending_rate: usize,
     ) -> Result<(), GatewayClientError> {
         let gateway_id = client.gateway_identity().to_base58_string();
         info!(
             "Got {} packets to send to gateway {}",
-            mix_packets.len(),
+            packets.len(),
             gateway_id
         );
 
-        if mix_packets.len() <= max_sending_rate {
+        let mut chunker = match packets.first() {
+            Some(packet) => Chunker::new(packet.recipient),
+            None => return Ok(()),
+        };
+
+        if packets.len() <= max_sending_rate {
             debug!("Everything is going to get sent as one.");
+            let mix_packets = Self::wrap_pending_packets(&mut chunker, packets).await;
             client.batch_send_mix_packets(mix_packets).await?;
         } else {
             let packets_per_time_chunk =
                 (max_sending_rate as f64 * TIME_CHUNK_SIZE.as_secs_f64()) as usize;
 
             let total_expected_time =
-                Duration::from_secs_f64(mix_packets.len() as f64 / max_sending_rate as f64);
+                Duration::from_secs_f64(packets.len() as f64 / max_sending_rate as f64);
             info!(
                 "With our rate of {} packets/s it should take around {:?} to send it all to {} ...",
                 max_sending_rate, total_expected_time, gateway_id
             );
 
-            fn split_off_vec(vec: &mut Vec<MixPacket>, at: usize) -> Option<Vec<MixPacket>> {
+            fn split_off_vec<T>(vec: &mut Vec<T>, at: usize) -> Option<Vec<T>> {
                 if vec.is_empty() {
                     None
                 } else {
@@ -263,12 +328,11 @@ impl PacketSender {
                 }
             }
 
-            // TODO future consideration: perhaps allow gateway client to take the packets by reference?
-            // this way we won't have to do reallocations in here as they're unavoidable when
-            // splitting a vector into multiple vectors
-            while let Some(retained) = split_off_vec(&mut mix_packets, packets_per_time_chunk) {
-                trace!("Sending {} packets...", mix_packets.len());
+            // the packets are only wrapped (and timestamped) right before they're sent
+            while let Some(retained) = split_off_vec(&mut packets, packets_per_time_chunk) {
+                trace!("Sending {} packets...", packets.len());
 
+                let mut mix_packets = Self::wrap_pending_packets(&mut chunker, packets).await;
                 if mix_packets.len() == 1 {
                     client.send_mix_packet(mix_packets.pop().unwrap()).await?;
                 } else {
@@ -277,7 +341,7 @@ impl PacketSender {
 
                 tokio::time::sleep(TIME_CHUNK_SIZE).await;
 
-                mix_packets = retained;
+                packets = retained;
             }
             debug!("Done sending");
         }
@@ -289,14 +353,20 @@ impl PacketSender {
         address: String,
         identity: identity::PublicKey,
         owner: String,
+        local_identity: Arc<identity::KeyPair>,
         fresh_gateway_client_data: &FreshGatewayClientData,
         gateway_connection_timeout: Duration,
     ) -> Option<(
         GatewayClientHandle,
         (MixnetMessageReceiver, AcknowledgementReceiver),
     )> {
-        let (new_client, (message_receiver, ack_receiver)) =
-            Self::new_gateway_client_handle(address, identity, owner, fresh_gateway_client_data);
+        let (new_client, (message_receiver, ack_receiver)) = Self::new_gateway_client_handle(
+            address,
+            identity,
+            owner,
+            local_identity,
+            fresh_gateway_client_data,
+        );
 
         // Put this in timeout in case the gateway has incorrectly set their ulimit and our connection
         // gets stuck in their TCP queue and just hangs on our end but does not terminate
@@ -374,6 +444,7 @@ impl PacketSender {
                     packets.clients_address,
                     packets.pub_key,
                     packets.gateway_owner,
+                    Arc::clone(&fresh_gateway_client_data.local_identity),
                     &fresh_gateway_client_data,
                     gateway_connection_timeout,
                 )
@@ -467,6 +538,74 @@ impl PacketSender {
         }
     }
 
+    /// Connects to the provided gateways with the inbox identity so that they would push us all the
+    /// messages they have stored for it while it was offline. The received messages are forwarded
+    /// to the packet receiver like any other.
+    pub(super) async fn connect_inbox_clients(
+        &self,
+        gateways: &[gateway::Node],
+    ) -> Vec<GatewayClientHandle> {
+        let fresh_gateway_client_data = &self.fresh_gateway_client_data;
+        let gateway_connection_timeout = self.gateway_connection_timeout;
+        let max_concurrent_clients = if self.max_concurrent_clients > 0 {
+            self.max_concurrent_clients
+        } else {
+            gateways.len().max(1)
+        };
+
+        stream::iter(gateways)
+            .map(|gateway| async move {
+                let (client, gateway_channels) =
+                    Self::create_new_gateway_client_handle_and_authenticate(
+                        gateway.clients_address(),
+                        gateway.identity_key,
+                        gateway.owner.clone(),
+                        Arc::clone(&fresh_gateway_client_data.inbox_identity),
+                        fresh_gateway_client_data,
+                        gateway_connection_timeout,
+                    )
+                    .await?;
+
+                fresh_gateway_client_data
+                    .gateways_status_updater
+                    .unbounded_send(GatewayClientUpdate::NewInbox(
+                        gateway.identity_key,
+                        gateway_channels,
+                    ))
+                    .expect("packet receiver seems to have died!");
+                Some(client)
+            })
+            .buffer_unordered(max_concurrent_clients)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Closes the connections of the inbox clients so that any subsequent packets sent to
+    /// the inbox identity would have to be stored by the gateways again.
+    pub(super) async fn disconnect_inbox_clients(&self, clients: Vec<GatewayClientHandle>) {
+        for client in clients {
+            let mut guard = client.lock_client().await;
+            if let Some(inner) = guard.inner_mut() {
+                let gateway_identity = inner.gateway_identity();
+                if let Err(err) = inner.close_connection().await {
+                    debug!(
+                        "failed to cleanly close the inbox connection to {} - {}",
+                        gateway_identity.to_base58_string(),
+                        err
+                    );
+                }
+                self.fresh_gateway_client_data
+                    .gateways_status_updater
+                    .unbounded_send(GatewayClientUpdate::InboxDone(gateway_identity))
+                    .expect("packet receiver seems to have died!");
+            }
+            guard.invalidate();
+        }
+    }
+
     pub(super) async fn send_packets(&mut self, packets: Vec<GatewayPackets>) {
         // we know that each of the elements in the packets array will only ever access a single,
         // unique element from the existing clients
@@ -2,10 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::network_monitor::monitor::preparer::{InvalidNode, TestedNode};
-use crate::network_monitor::test_packet::TestPacket;
+use crate::network_monitor::monitor::processor::{
+    average_round_trip_time, estimate_node_latency, round_trip_times_per_route, ReceivedPacket,
+};
 use crate::network_monitor::test_route::TestRoute;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::time::Duration;
 
 // just some approximate measures to print to stdout (well, technically stderr since it's being printed via log)
 const EXCEPTIONAL_THRESHOLD: u8 = 95; // 95 - 100
@@ -23,14 +26,25 @@ pub(crate) struct NodeResult {
     pub(crate) identity: String,
     pub(crate) owner: String,
     pub(crate) reliability: u8,
+
+    /// Estimated latency (in milliseconds) of the node, based on how much slower the test
+    /// packets sent through it were compared to the test routes on their own.
+    /// `None` if none of the packets got back to us.
+    pub(crate) latency: Option<u32>,
 }
 
 impl NodeResult {
-    pub(crate) fn new(identity: String, owner: String, reliability: u8) -> Self {
+    pub(crate) fn new(
+        identity: String,
+        owner: String,
+        reliability: u8,
+        latency: Option<Duration>,
+    ) -> Self {
         NodeResult {
             identity,
             owner,
             reliability,
+            latency: latency.map(|latency| latency.as_millis() as u32),
         }
     }
 }
@@ -39,11 +53,20 @@ impl NodeResult {
 pub(crate) struct RouteResult {
     pub(crate) route: TestRoute,
     reliability: u8,
+    average_round_trip_time: Option<Duration>,
 }
 
 impl RouteResult {
-    pub(crate) fn new(route: TestRoute, reliability: u8) -> Self {
-        RouteResult { route, reliability }
+    pub(crate) fn new(
+        route: TestRoute,
+        reliability: u8,
+        average_round_trip_time: Option<Duration>,
+    ) -> Self {
+        RouteResult {
+            route,
+            reliability,
+            average_round_trip_time,
+        }
     }
 }
 
@@ -156,8 +179,8 @@ impl Display for TestReport {
         for route_result in &self.route_results {
             writeln!(
                 f,
-                "{:?}, reliability: {:.2}",
-                route_result.route, route_result.reliability
+                "{:?}, reliability: {:.2}, average round-trip time: {:?}",
+                route_result.route, route_result.reliability, route_result.average_round_trip_time
             )?;
         }
 
@@ -224,6 +247,10 @@ pub(crate) struct TestSummary {
     pub(crate) mixnode_results: Vec<NodeResult>,
     pub(crate) gateway_results: Vec<NodeResult>,
     pub(crate) route_results: Vec<RouteResult>,
+
+    /// Reliability of the gateways when it comes to storing and delivering messages
+    /// of offline clients. Empty if the gateway inbox test was not performed.
+    pub(crate) gateway_inbox_results: Vec<NodeResult>,
 }
 
 impl TestSummary {
@@ -241,13 +268,15 @@ impl TestSummary {
 #[derive(Default)]
 pub(crate) struct SummaryProducer {
     per_node_test_packets: usize,
+    inbox_test_packets: usize,
     print_report: bool,
 }
 
 impl SummaryProducer {
-    pub(crate) fn new(per_node_test_packets: usize) -> Self {
+    pub(crate) fn new(per_node_test_packets: usize, inbox_test_packets: usize) -> Self {
         SummaryProducer {
             per_node_test_packets,
+            inbox_test_packets,
             print_report: false,
         }
     }
@@ -261,7 +290,7 @@ impl SummaryProducer {
         &self,
         tested_mixnodes: Vec<TestedNode>,
         tested_gateways: Vec<TestedNode>,
-        received_packets: Vec<TestPacket>,
+        received_packets: Vec<ReceivedPacket>,
         invalid_mixnodes: Vec<InvalidNode>,
         invalid_gateways: Vec<InvalidNode>,
         test_routes: &[TestRoute],
@@ -276,33 +305,55 @@ impl SummaryProducer {
             (tested_mixnodes.len() + tested_gateways.len()) * self.per_node_test_packets;
         let per_node_expected = test_routes.len() * self.per_node_test_packets;
 
+        // for each node keep track of the round-trip times of all the received packets alongside
+        // the routes they took (the number of which determines the reliability)
         for tested_mixnode in tested_mixnodes {
-            raw_mixnode_results.insert((tested_mixnode.identity, tested_mixnode.owner), 0);
+            raw_mixnode_results.insert((tested_mixnode.identity, tested_mixnode.owner), Vec::new());
         }
 
         for tested_gateway in tested_gateways {
-            raw_gateway_results.insert((tested_gateway.identity, tested_gateway.owner), 0);
+            raw_gateway_results.insert((tested_gateway.identity, tested_gateway.owner), Vec::new());
         }
 
         for invalid_mixnode in invalid_mixnodes {
-            raw_mixnode_results.insert((invalid_mixnode.identity(), invalid_mixnode.owner()), 0);
+            raw_mixnode_results.insert(
+                (invalid_mixnode.identity(), invalid_mixnode.owner()),
+                Vec::new(),
+            );
         }
 
         for invalid_gateway in invalid_gateways {
-            raw_gateway_results.insert((invalid_gateway.identity(), invalid_gateway.owner()), 0);
+            raw_gateway_results.insert(
+                (invalid_gateway.identity(), invalid_gateway.owner()),
+                Vec::new(),
+            );
         }
 
         for test_route in test_routes {
             raw_route_results.insert(test_route.id(), 0);
         }
 
+        let route_round_trip_times = round_trip_times_per_route(&received_packets);
+        let verified_round_trip_times: HashMap<_, _> = test_routes
+            .iter()
+            .filter_map(|route| Some((route.id(), route.round_trip_time()?)))
+            .collect();
+
         for received in received_packets {
+            let round_trip_time = received.round_trip_time;
+            let received = received.packet;
             let id_owner = (received.pub_key.to_base58_string(), received.owner);
 
             if received.node_type.is_mixnode() {
-                *raw_mixnode_results.entry(id_owner).or_default() += 1usize;
+                raw_mixnode_results
+                    .entry(id_owner)
+                    .or_default()
+                    .push((received.route_id, round_trip_time));
             } else {
-                *raw_gateway_results.entry(id_owner).or_default() += 1usize;
+                raw_gateway_results
+                    .entry(id_owner)
+                    .or_default()
+                    .push((received.route_id, round_trip_time));
             }
 
             *raw_route_results.entry(received.route_id).or_default() += 1usize;
@@ -310,19 +361,21 @@ impl SummaryProducer {
 
         let mixnode_results = raw_mixnode_results
             .into_iter()
-            .map(|((id, owner), received)| {
-                let reliability =
-                    (received as f32 / per_node_expected as f32 * 100.0).round() as u8;
-                NodeResult::new(id, owner, reliability)
+            .map(|((id, owner), round_trip_times)| {
+                let reliability = (round_trip_times.len() as f32 / per_node_expected as f32 * 100.0)
+                    .round() as u8;
+                let latency = estimate_node_latency(&round_trip_times, &verified_round_trip_times);
+                NodeResult::new(id, owner, reliability, latency)
             })
             .collect();
 
         let gateway_results = raw_gateway_results
             .into_iter()
-            .map(|((id, owner), received)| {
-                let reliability =
-                    (received as f32 / per_node_expected as f32 * 100.0).round() as u8;
-                NodeResult::new(id, owner, reliability)
+            .map(|((id, owner), round_trip_times)| {
+                let reliability = (round_trip_times.len() as f32 / per_node_expected as f32 * 100.0)
+                    .round() as u8;
+                let latency = estimate_node_latency(&round_trip_times, &verified_round_trip_times);
+                NodeResult::new(id, owner, reliability, latency)
             })
             .collect();
 
@@ -337,7 +390,12 @@ impl SummaryProducer {
                 test_routes
                     .iter()
                     .find(|route| route.id() == id)
-                    .map(|route| RouteResult::new(route.clone(), reliability))
+                    .map(|route| {
+                        let average_round_trip_time = route_round_trip_times
+                            .get(&id)
+                            .and_then(|times| average_round_trip_time(times));
+                        RouteResult::new(route.clone(), reliability, average_round_trip_time)
+                    })
             })
             .collect();
 
@@ -345,6 +403,45 @@ impl SummaryProducer {
             mixnode_results,
             gateway_results,
             route_results,
+            gateway_inbox_results: Vec::new(),
+        }
+    }
+
+    pub(super) fn produce_inbox_results(
+        &self,
+        tested_gateways: Vec<TestedNode>,
+        received_packets: Vec<ReceivedPacket>,
+        test_routes: usize,
+    ) -> Vec<NodeResult> {
+        let per_gateway_expected = test_routes * self.inbox_test_packets;
+
+        let mut raw_results = HashMap::new();
+        for tested_gateway in tested_gateways {
+            raw_results.insert((tested_gateway.identity, tested_gateway.owner), 0usize);
+        }
+
+        for received in received_packets {
+            let received = received.packet;
+            // we only ever send gateway packets during the inbox test
+            if received.node_type.is_mixnode() {
+                continue;
+            }
+            *raw_results
+                .entry((received.pub_key.to_base58_string(), received.owner))
+                .or_default() += 1;
         }
+
+        raw_results
+            .into_iter()
+            .map(|((id, owner), received)| {
+                // bound it in case the gateway decided to deliver the same message multiple times
+                let reliability = (received as f32 / per_gateway_expected as f32 * 100.0)
+                    .min(100.0)
+                    .round() as u8;
+                // the round-trip times of stored messages only tell us how long we have waited
+                // before reconnecting, so they're not included
+                NodeResult::new(id, owner, reliability, None)
+            })
+            .collect()
     }
 }
@@ -8,6 +8,7 @@ use std::fmt::{self, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::mem;
 use std::str::Utf8Error;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use topology::{gateway, mix};
 
 #[repr(u8)]
@@ -55,10 +56,22 @@ impl From<Utf8Error> for TestPacketError {
     }
 }
 
+/// Current unix timestamp in milliseconds.
+pub(crate) fn unix_timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
 #[derive(Eq, Clone, Debug)]
 pub(crate) struct TestPacket {
     pub(crate) route_id: u64,
     pub(crate) test_nonce: u64,
+
+    /// Unix timestamp (in milliseconds) of when the packet was sent through the network.
+    /// It's overwritten by the packet sender right before the packet gets wrapped and sent.
+    pub(crate) sent_timestamp: u64,
     pub(crate) pub_key: identity::PublicKey,
     pub(crate) owner: String,
     pub(crate) node_type: NodeType,
@@ -68,11 +81,12 @@ impl Display for TestPacket {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "TestPacket {{ pub_key: {}, owner: {}, route: {} test nonce: {} }}",
+            "TestPacket {{ pub_key: {}, owner: {}, route: {} test nonce: {} sent at: {} }}",
             self.pub_key.to_base58_string(),
             self.owner,
             self.route_id,
-            self.test_nonce
+            self.test_nonce,
+            self.sent_timestamp
         )
     }
 }
@@ -81,6 +95,7 @@ impl Hash for TestPacket {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.route_id.hash(state);
         self.test_nonce.hash(state);
+        self.sent_timestamp.hash(state);
         self.pub_key.to_bytes().hash(state);
         self.owner.hash(state);
         self.node_type.hash(state);
@@ -91,6 +106,7 @@ impl PartialEq for TestPacket {
     fn eq(&self, other: &Self) -> bool {
         self.route_id == other.route_id
             && self.test_nonce == other.test_nonce
+            && self.sent_timestamp == other.sent_timestamp
             && self.pub_key.to_bytes() == other.pub_key.to_bytes()
             && self.owner == other.owner
             && self.node_type == other.node_type
@@ -104,6 +120,7 @@ impl TestPacket {
             owner: mix.owner.clone(),
             route_id,
             test_nonce,
+            sent_timestamp: unix_timestamp_millis(),
             node_type: NodeType::Mixnode,
         }
     }
@@ -114,6 +131,7 @@ impl TestPacket {
             owner: gateway.owner.clone(),
             route_id,
             test_nonce,
+            sent_timestamp: unix_timestamp_millis(),
             node_type: NodeType::Gateway,
         }
     }
@@ -128,6 +146,7 @@ impl TestPacket {
         TestPacket {
             route_id,
             test_nonce,
+            sent_timestamp: unix_timestamp_millis(),
             pub_key,
             owner,
             node_type,
@@ -138,9 +157,16 @@ impl TestPacket {
         self.test_nonce
     }
 
+    /// Time it took the packet to get back to us, assuming it was received at the provided
+    /// unix timestamp (in milliseconds).
+    pub(crate) fn round_trip_time(&self, received_timestamp: u64) -> Duration {
+        Duration::from_millis(received_timestamp.saturating_sub(self.sent_timestamp))
+    }
+
     pub(crate) fn to_bytes(&self) -> Vec<u8> {
         IntoIterator::into_iter(self.route_id.to_be_bytes())
             .chain(IntoIterator::into_iter(self.test_nonce.to_be_bytes()))
+            .chain(IntoIterator::into_iter(self.sent_timestamp.to_be_bytes()))
             .chain(std::iter::once(self.node_type as u8))
             .chain(self.pub_key.to_bytes().iter().cloned())
             .chain(self.owner.as_bytes().iter().cloned())
@@ -148,27 +174,29 @@ impl TestPacket {
     }
 
     pub(crate) fn try_from_bytes(b: &[u8]) -> Result<Self, TestPacketError> {
-        // route id + test nonce size
+        // route id, test nonce and sent timestamp size
         let n = mem::size_of::<u64>();
 
-        if b.len() < 2 * n + 1 + identity::PUBLIC_KEY_LENGTH {
+        if b.len() < 3 * n + 1 + identity::PUBLIC_KEY_LENGTH {
             return Err(TestPacketError::IncompletePacket);
         }
 
         // those unwraps can't fail as we've already checked for the size
         let route_id = u64::from_be_bytes(b[0..n].try_into().unwrap());
         let test_nonce = u64::from_be_bytes(b[n..2 * n].try_into().unwrap());
-        let node_type = NodeType::try_from(b[2 * n])?;
+        let sent_timestamp = u64::from_be_bytes(b[2 * n..3 * n].try_into().unwrap());
+        let node_type = NodeType::try_from(b[3 * n])?;
 
         let pub_key = identity::PublicKey::from_bytes(
-            &b[2 * n + 1..2 * n + 1 + identity::PUBLIC_KEY_LENGTH],
+            &b[3 * n + 1..3 * n + 1 + identity::PUBLIC_KEY_LENGTH],
         )?;
-        let owner = std::str::from_utf8(&b[2 * n + 1 + identity::PUBLIC_KEY_LENGTH..])?;
+        let owner = std::str::from_utf8(&b[3 * n + 1 + identity::PUBLIC_KEY_LENGTH..])?;
 
         Ok(TestPacket {
             route_id,
             node_type,
             test_nonce,
+            sent_timestamp,
             pub_key,
             owner: owner.to_owned(),
         })
@@ -206,4 +234,22 @@ mod tests {
         let recovered = TestPacket::try_from_bytes(&bytes).unwrap();
         assert_eq!(packet, recovered);
     }
+
+    #[test]
+    fn round_trip_time_is_measured_from_sent_timestamp() {
+        let mut rng = rand_07::thread_rng();
+        let dummy_keypair = identity::KeyPair::new(&mut rng);
+        let mut packet = TestPacket::new(
+            *dummy_keypair.public_key(),
+            "some owner".to_string(),
+            42,
+            123,
+            NodeType::Gateway,
+        );
+        packet.sent_timestamp = 1_000;
+
+        assert_eq!(Duration::from_millis(250), packet.round_trip_time(1_250));
+        // clock going backwards should not cause any issues
+        assert_eq!(Duration::ZERO, packet.round_trip_time(500));
+    }
 }
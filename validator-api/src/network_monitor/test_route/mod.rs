@@ -5,6 +5,7 @@ use crate::network_monitor::test_packet::{NodeType, TestPacket};
 use crate::network_monitor::ROUTE_TESTING_TEST_NONCE;
 use crypto::asymmetric::identity;
 use std::fmt::{Debug, Formatter};
+use std::time::Duration;
 use topology::{gateway, mix, NymTopology};
 
 #[derive(Clone)]
@@ -12,6 +13,9 @@ pub(crate) struct TestRoute {
     id: u64,
     system_version: String,
     nodes: NymTopology,
+
+    /// Average round-trip time of the packets sent through this route when it was verified.
+    round_trip_time: Option<Duration>,
 }
 
 impl TestRoute {
@@ -35,13 +39,23 @@ impl TestRoute {
             id,
             system_version: system_version.to_string(),
             nodes: NymTopology::new(layered_mixes, vec![gateway]),
+            round_trip_time: None,
         }
     }
 
+    pub(crate) fn with_round_trip_time(mut self, round_trip_time: Duration) -> Self {
+        self.round_trip_time = Some(round_trip_time);
+        self
+    }
+
     pub(crate) fn id(&self) -> u64 {
         self.id
     }
 
+    pub(crate) fn round_trip_time(&self) -> Option<Duration> {
+        self.round_trip_time
+    }
+
     pub(crate) fn gateway(&self) -> &gateway::Node {
         &self.nodes.gateways()[0]
     }
@@ -24,6 +24,9 @@ pub(crate) fn node_status_routes(
         openapi_get_routes_spec![
             settings: routes::mixnode_report,
             routes::gateway_report,
+            routes::mixnode_latency_report,
+            routes::gateway_latency_report,
+            routes::gateway_inbox_report,
             routes::mixnode_uptime_history,
             routes::gateway_uptime_history,
             routes::mixnode_core_status_count,
@@ -34,6 +37,10 @@ pub(crate) fn node_status_routes(
             routes::get_mixnode_inclusion_probability,
             routes::get_mixnode_avg_uptime,
             routes::get_mixnode_avg_uptimes,
+            routes::export_mixnode_statuses,
+            routes::export_gateway_statuses,
+            routes::export_monitor_runs,
+            routes::export_rewarding_reports,
         ]
     } else {
         // in the minimal variant we would not have access to endpoints relying on existence
@@ -1,15 +1,17 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::node_status_api::utils::NodeUptimes;
-use crate::storage::models::NodeStatus;
+use crate::node_status_api::utils::{NodeInboxReliabilities, NodeLatencies, NodeUptimes};
+use crate::storage::models::{NodeLatency, NodeStatus};
+use log::error;
 use okapi::openapi3::{Responses, SchemaObject};
-use rocket::http::{ContentType, Status};
+use rocket::http::{ContentType, Header, Status};
 use rocket::response::{self, Responder, Response};
+use rocket::serde::json::Json;
 use rocket::Request;
 use rocket_okapi::gen::OpenApiGenerator;
 use rocket_okapi::response::OpenApiResponderInner;
-use rocket_okapi::util::ensure_status_code_exists;
+use rocket_okapi::util::{add_schema_response, ensure_status_code_exists};
 use schemars::gen::SchemaGenerator;
 use schemars::schema::{InstanceType, Schema};
 use schemars::JsonSchema;
@@ -17,6 +19,7 @@ use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 use std::fmt::{self, Display, Formatter};
 use std::io::Cursor;
+use std::str::FromStr;
 use time::OffsetDateTime;
 
 // todo: put into some error enum
@@ -178,6 +181,102 @@ impl GatewayStatusReport {
     }
 }
 
+// all latencies are expressed in milliseconds and are `None` if no measurements are available
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct MixnodeLatencyReport {
+    pub(crate) identity: String,
+    pub(crate) owner: String,
+
+    pub(crate) most_recent: Option<u32>,
+
+    pub(crate) last_hour: Option<u32>,
+    pub(crate) last_day: Option<u32>,
+}
+
+impl MixnodeLatencyReport {
+    pub(crate) fn construct_from_last_day_latencies(
+        report_time: OffsetDateTime,
+        identity: String,
+        owner: String,
+        last_day: Vec<NodeLatency>,
+    ) -> Self {
+        let node_latencies =
+            NodeLatencies::calculate_from_last_day_latencies(report_time, last_day);
+
+        MixnodeLatencyReport {
+            identity,
+            owner,
+            most_recent: node_latencies.most_recent,
+            last_hour: node_latencies.last_hour,
+            last_day: node_latencies.last_day,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GatewayLatencyReport {
+    pub(crate) identity: String,
+    pub(crate) owner: String,
+
+    pub(crate) most_recent: Option<u32>,
+
+    pub(crate) last_hour: Option<u32>,
+    pub(crate) last_day: Option<u32>,
+}
+
+impl GatewayLatencyReport {
+    pub(crate) fn construct_from_last_day_latencies(
+        report_time: OffsetDateTime,
+        identity: String,
+        owner: String,
+        last_day: Vec<NodeLatency>,
+    ) -> Self {
+        let node_latencies =
+            NodeLatencies::calculate_from_last_day_latencies(report_time, last_day);
+
+        GatewayLatencyReport {
+            identity,
+            owner,
+            most_recent: node_latencies.most_recent,
+            last_hour: node_latencies.last_hour,
+            last_day: node_latencies.last_day,
+        }
+    }
+}
+
+// reliability of the gateway when it comes to storing messages for offline clients and delivering
+// them once the client reconnects. It's `None` if the gateway hasn't been tested in the particular period
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GatewayInboxReport {
+    pub(crate) identity: String,
+    pub(crate) owner: String,
+
+    pub(crate) most_recent: Option<Uptime>,
+
+    pub(crate) last_hour: Option<Uptime>,
+    pub(crate) last_day: Option<Uptime>,
+}
+
+impl GatewayInboxReport {
+    pub(crate) fn construct_from_last_day_statuses(
+        report_time: OffsetDateTime,
+        identity: String,
+        owner: String,
+        last_day: Vec<NodeStatus>,
+    ) -> Self {
+        let inbox_reliabilities =
+            NodeInboxReliabilities::calculate_from_last_day_statuses(report_time, last_day);
+
+        GatewayInboxReport {
+            identity,
+            owner,
+            most_recent: inbox_reliabilities.most_recent,
+            last_hour: inbox_reliabilities.last_hour,
+            last_day: inbox_reliabilities.last_day,
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
 pub struct MixnodeUptimeHistory {
     pub(crate) identity: String,
@@ -223,6 +322,166 @@ pub struct HistoricalUptime {
     pub(crate) uptime: Uptime,
 }
 
+pub(crate) const DEFAULT_EXPORT_PAGE_SIZE: u32 = 100;
+pub(crate) const MAX_EXPORT_PAGE_SIZE: u32 = 1000;
+
+// a single raw status produced by the network monitor
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct NodeStatusEntry {
+    pub(crate) identity: String,
+    pub(crate) owner: String,
+    pub(crate) timestamp: i64,
+    pub(crate) reliability: u8,
+
+    // in milliseconds
+    pub(crate) latency: Option<u32>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct MonitorRunEntry {
+    pub(crate) id: i64,
+    pub(crate) timestamp: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct RewardingReportEntry {
+    pub(crate) interval_rewarding_id: i64,
+    pub(crate) timestamp: i64,
+    pub(crate) eligible_mixnodes: i64,
+    pub(crate) possibly_unrewarded_mixnodes: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ExportFormat {
+    Json,
+    Csv,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_ref() {
+            "json" => Ok(ExportFormat::Json),
+            "csv" => Ok(ExportFormat::Csv),
+            other => Err(format!(
+                "'{}' is not a supported export format. Use either 'json' or 'csv'",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PageRequest {
+    pub(crate) page: u32,
+    pub(crate) per_page: u32,
+}
+
+impl PageRequest {
+    pub(crate) fn new(page: Option<u32>, per_page: Option<u32>) -> Self {
+        PageRequest {
+            page: page.unwrap_or_default(),
+            per_page: per_page
+                .unwrap_or(DEFAULT_EXPORT_PAGE_SIZE)
+                .clamp(1, MAX_EXPORT_PAGE_SIZE),
+        }
+    }
+
+    // we always ask for one entry more than requested to know whether there's another page
+    pub(crate) fn limit(&self) -> i64 {
+        self.per_page as i64 + 1
+    }
+
+    pub(crate) fn offset(&self) -> i64 {
+        self.page as i64 * self.per_page as i64
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ExportPage<T> {
+    pub(crate) page: u32,
+    pub(crate) per_page: u32,
+    pub(crate) next_page: Option<u32>,
+    pub(crate) items: Vec<T>,
+}
+
+impl<T> ExportPage<T> {
+    // `items` are expected to have been retrieved with `PageRequest::limit`
+    pub(crate) fn new(request: PageRequest, mut items: Vec<T>) -> Self {
+        let next_page = if items.len() > request.per_page as usize {
+            items.truncate(request.per_page as usize);
+            Some(request.page + 1)
+        } else {
+            None
+        };
+
+        ExportPage {
+            page: request.page,
+            per_page: request.per_page,
+            next_page,
+            items,
+        }
+    }
+}
+
+pub(crate) struct ExportResponse<T> {
+    page: ExportPage<T>,
+    format: ExportFormat,
+}
+
+impl<T> ExportResponse<T> {
+    pub(crate) fn new(page: ExportPage<T>, format: ExportFormat) -> Self {
+        ExportResponse { page, format }
+    }
+}
+
+impl<T: Serialize> ExportResponse<T> {
+    fn to_csv(&self) -> Result<String, csv::Error> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for item in &self.page.items {
+            writer.serialize(item)?;
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|err| csv::Error::from(err.into_error()))?;
+        // all the serialized values are valid utf8 strings or numbers
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for ExportResponse<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        match self.format {
+            ExportFormat::Json => Json(self.page).respond_to(request),
+            ExportFormat::Csv => {
+                let csv = self.to_csv().map_err(|err| {
+                    error!("Failed to serialize exported data - {}", err);
+                    Status::InternalServerError
+                })?;
+
+                let mut response = Response::build();
+                response
+                    .header(ContentType::CSV)
+                    .sized_body(csv.len(), Cursor::new(csv));
+                // there's no place for pagination information in the csv itself
+                if let Some(next_page) = self.page.next_page {
+                    response.header(Header::new("X-Next-Page", next_page.to_string()));
+                }
+                response.ok()
+            }
+        }
+    }
+}
+
+impl<T: Serialize + JsonSchema> OpenApiResponderInner for ExportResponse<T> {
+    fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+        let mut responses = Json::<ExportPage<T>>::responses(gen)?;
+        add_schema_response(&mut responses, 200, "text/csv", gen.json_schema::<String>())?;
+        Ok(responses)
+    }
+}
+
 pub(crate) struct ErrorResponse {
     error_message: String,
     status: Status,
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::node_status_api::models::{
-    ErrorResponse, GatewayStatusReport, GatewayUptimeHistory, MixnodeStatusReport,
-    MixnodeUptimeHistory,
+    ErrorResponse, ExportFormat, ExportResponse, GatewayInboxReport, GatewayLatencyReport,
+    GatewayStatusReport, GatewayUptimeHistory, MixnodeLatencyReport, MixnodeStatusReport,
+    MixnodeUptimeHistory, MonitorRunEntry, NodeStatusEntry, PageRequest, RewardingReportEntry,
 };
 use crate::storage::ValidatorApiStorage;
 use crate::ValidatorCache;
@@ -13,6 +14,7 @@ use rocket::http::Status;
 use rocket::serde::json::Json;
 use rocket::State;
 use rocket_okapi::openapi;
+use time::OffsetDateTime;
 use validator_api_requests::models::{
     CoreNodeStatusResponse, InclusionProbabilityResponse, MixnodeStatusResponse,
     RewardEstimationResponse, StakeSaturationResponse, UptimeResponse,
@@ -46,6 +48,45 @@ pub(crate) async fn gateway_report(
         .map_err(|err| ErrorResponse::new(err.to_string(), Status::NotFound))
 }
 
+#[openapi(tag = "status")]
+#[get("/mixnode/<identity>/latency")]
+pub(crate) async fn mixnode_latency_report(
+    storage: &State<ValidatorApiStorage>,
+    identity: &str,
+) -> Result<Json<MixnodeLatencyReport>, ErrorResponse> {
+    storage
+        .construct_mixnode_latency_report(identity)
+        .await
+        .map(Json)
+        .map_err(|err| ErrorResponse::new(err.to_string(), Status::NotFound))
+}
+
+#[openapi(tag = "status")]
+#[get("/gateway/<identity>/latency")]
+pub(crate) async fn gateway_latency_report(
+    storage: &State<ValidatorApiStorage>,
+    identity: &str,
+) -> Result<Json<GatewayLatencyReport>, ErrorResponse> {
+    storage
+        .construct_gateway_latency_report(identity)
+        .await
+        .map(Json)
+        .map_err(|err| ErrorResponse::new(err.to_string(), Status::NotFound))
+}
+
+#[openapi(tag = "status")]
+#[get("/gateway/<identity>/inbox")]
+pub(crate) async fn gateway_inbox_report(
+    storage: &State<ValidatorApiStorage>,
+    identity: &str,
+) -> Result<Json<GatewayInboxReport>, ErrorResponse> {
+    storage
+        .construct_gateway_inbox_report(identity)
+        .await
+        .map(Json)
+        .map_err(|err| ErrorResponse::new(err.to_string(), Status::NotFound))
+}
+
 #[openapi(tag = "status")]
 #[get("/mixnode/<identity>/history")]
 pub(crate) async fn mixnode_uptime_history(
@@ -291,3 +332,117 @@ pub(crate) async fn get_mixnode_avg_uptimes(
 
     Ok(Json(response))
 }
+
+// the time range of the exported data defaults to everything up until now
+fn export_range(since: Option<i64>, until: Option<i64>) -> (i64, i64) {
+    (
+        since.unwrap_or_default(),
+        until.unwrap_or_else(|| OffsetDateTime::now_utc().unix_timestamp() + 1),
+    )
+}
+
+fn export_format(format: Option<String>) -> Result<ExportFormat, ErrorResponse> {
+    format
+        .map(|format| format.parse())
+        .transpose()
+        .map(|format| format.unwrap_or(ExportFormat::Json))
+        .map_err(|err| ErrorResponse::new(err, Status::BadRequest))
+}
+
+#[openapi(tag = "status")]
+#[get("/export/mixnodes/statuses?<since>&<until>&<identity>&<owner>&<page>&<per_page>&<format>")]
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn export_mixnode_statuses(
+    storage: &State<ValidatorApiStorage>,
+    since: Option<i64>,
+    until: Option<i64>,
+    identity: Option<String>,
+    owner: Option<String>,
+    page: Option<u32>,
+    per_page: Option<u32>,
+    format: Option<String>,
+) -> Result<ExportResponse<NodeStatusEntry>, ErrorResponse> {
+    let format = export_format(format)?;
+    let (since, until) = export_range(since, until);
+
+    storage
+        .get_mixnode_status_entries(
+            since,
+            until,
+            identity.as_deref(),
+            owner.as_deref(),
+            PageRequest::new(page, per_page),
+        )
+        .await
+        .map(|page| ExportResponse::new(page, format))
+        .map_err(|err| ErrorResponse::new(err.to_string(), Status::InternalServerError))
+}
+
+#[openapi(tag = "status")]
+#[get("/export/gateways/statuses?<since>&<until>&<identity>&<owner>&<page>&<per_page>&<format>")]
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn export_gateway_statuses(
+    storage: &State<ValidatorApiStorage>,
+    since: Option<i64>,
+    until: Option<i64>,
+    identity: Option<String>,
+    owner: Option<String>,
+    page: Option<u32>,
+    per_page: Option<u32>,
+    format: Option<String>,
+) -> Result<ExportResponse<NodeStatusEntry>, ErrorResponse> {
+    let format = export_format(format)?;
+    let (since, until) = export_range(since, until);
+
+    storage
+        .get_gateway_status_entries(
+            since,
+            until,
+            identity.as_deref(),
+            owner.as_deref(),
+            PageRequest::new(page, per_page),
+        )
+        .await
+        .map(|page| ExportResponse::new(page, format))
+        .map_err(|err| ErrorResponse::new(err.to_string(), Status::InternalServerError))
+}
+
+#[openapi(tag = "status")]
+#[get("/export/monitor-runs?<since>&<until>&<page>&<per_page>&<format>")]
+pub(crate) async fn export_monitor_runs(
+    storage: &State<ValidatorApiStorage>,
+    since: Option<i64>,
+    until: Option<i64>,
+    page: Option<u32>,
+    per_page: Option<u32>,
+    format: Option<String>,
+) -> Result<ExportResponse<MonitorRunEntry>, ErrorResponse> {
+    let format = export_format(format)?;
+    let (since, until) = export_range(since, until);
+
+    storage
+        .get_monitor_run_entries(since, until, PageRequest::new(page, per_page))
+        .await
+        .map(|page| ExportResponse::new(page, format))
+        .map_err(|err| ErrorResponse::new(err.to_string(), Status::InternalServerError))
+}
+
+#[openapi(tag = "status")]
+#[get("/export/rewarding-reports?<since>&<until>&<page>&<per_page>&<format>")]
+pub(crate) async fn export_rewarding_reports(
+    storage: &State<ValidatorApiStorage>,
+    since: Option<i64>,
+    until: Option<i64>,
+    page: Option<u32>,
+    per_page: Option<u32>,
+    format: Option<String>,
+) -> Result<ExportResponse<RewardingReportEntry>, ErrorResponse> {
+    let format = export_format(format)?;
+    let (since, until) = export_range(since, until);
+
+    storage
+        .get_rewarding_report_entries(since, until, PageRequest::new(page, per_page))
+        .await
+        .map(|page| ExportResponse::new(page, format))
+        .map_err(|err| ErrorResponse::new(err.to_string(), Status::InternalServerError))
+}
@@ -1,76 +1,69 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::node_status_api::models::{
-    GatewayStatusReport, MixnodeStatusReport, ValidatorApiStorageError,
-};
+use crate::config::Config;
+use crate::node_status_api::models::ValidatorApiStorageError;
 use crate::node_status_api::ONE_DAY;
 use crate::storage::ValidatorApiStorage;
-use log::error;
+use log::{debug, error, info, warn};
+use std::time::Duration;
 use time::OffsetDateTime;
-use tokio::time::sleep;
+use tokio::time::interval;
 
+// the raw data is required for constructing the daily reports and for rewarding,
+// so it can't be purged any sooner than that. Note that each day is always rolled up
+// before any of its data could get purged, as the compaction happens before the purge.
+const MINIMUM_RAW_DATA_RETENTION: Duration = ONE_DAY;
+
+/// Rolls up the raw network monitor data into the daily (historical) uptimes
+/// and purges the data past its retention period.
 pub(crate) struct HistoricalUptimeUpdater {
     storage: ValidatorApiStorage,
+    raw_data_retention: Duration,
+    compaction_interval: Duration,
 }
 
 impl HistoricalUptimeUpdater {
-    pub(crate) fn new(storage: ValidatorApiStorage) -> Self {
-        HistoricalUptimeUpdater { storage }
-    }
-
-    /// Obtains the lists of all mixnodes and gateways that were tested at least a single time
-    /// in the last 24h interval.
-    ///
-    /// # Arguments
-    ///
-    /// * `now`: current time.
-    async fn get_active_nodes(
-        &self,
-        now: OffsetDateTime,
-    ) -> Result<(Vec<MixnodeStatusReport>, Vec<GatewayStatusReport>), ValidatorApiStorageError>
-    {
-        let day_ago = (now - ONE_DAY).unix_timestamp();
-        let active_mixnodes = self
-            .storage
-            .get_all_active_mixnode_reports_in_interval(day_ago, now.unix_timestamp())
-            .await?;
-
-        let active_gateways = self
-            .storage
-            .get_all_active_gateway_reports_in_interval(day_ago, now.unix_timestamp())
-            .await?;
+    pub(crate) fn new(config: &Config, storage: ValidatorApiStorage) -> Self {
+        let mut raw_data_retention = config.get_raw_data_retention();
+        if raw_data_retention < MINIMUM_RAW_DATA_RETENTION {
+            warn!(
+                "The specified raw data retention ({:?}) is lower than the allowed minimum. {:?} is going to be used instead",
+                raw_data_retention, MINIMUM_RAW_DATA_RETENTION
+            );
+            raw_data_retention = MINIMUM_RAW_DATA_RETENTION;
+        }
 
-        Ok((active_mixnodes, active_gateways))
+        HistoricalUptimeUpdater {
+            storage,
+            raw_data_retention,
+            compaction_interval: config.get_compaction_interval(),
+        }
     }
 
     async fn update_uptimes(&self) -> Result<(), ValidatorApiStorageError> {
         let now = OffsetDateTime::now_utc();
-        let today_iso_8601 = now.date().to_string();
-
-        // get nodes that were active in last 24h
-        let (active_mixnodes, active_gateways) = self.get_active_nodes(now).await?;
 
-        if self
-            .storage
-            .check_if_historical_uptimes_exist_for_date(&today_iso_8601)
-            .await?
-        {
-            warn!("We have already updated uptimes for all nodes this day.")
-        } else {
-            info!("Updating historical daily uptimes of all nodes...");
-            self.storage
-                .update_historical_uptimes(&today_iso_8601, &active_mixnodes, &active_gateways)
-                .await?;
+        // the data must be compacted before it's purged, otherwise it'd be lost for good
+        let compacted_days = self.storage.compact_statuses(now).await?;
+        if compacted_days > 0 {
+            info!(
+                "Rolled up raw statuses of {} day(s) into the historical daily uptimes",
+                compacted_days
+            );
         }
 
-        Ok(())
+        let cutoff = (now - self.raw_data_retention).unix_timestamp();
+        debug!("Purging raw monitor data older than {}", cutoff);
+        self.storage.purge_raw_data(cutoff).await
     }
 
     pub(crate) async fn run(&self) {
+        // note that the first tick happens immediately, so that any data missed while we were offline
+        // would get compacted straight away
+        let mut compaction_interval = interval(self.compaction_interval.min(ONE_DAY));
         loop {
-            // start any updates a day after starting the task so that we would have complete data
-            sleep(ONE_DAY).await;
+            compaction_interval.tick().await;
             if let Err(err) = self.update_uptimes().await {
                 // normally that would have been a warning rather than an error,
                 // however, in this case it implies some underlying issues with our database
@@ -3,18 +3,11 @@
 
 use crate::node_status_api::models::Uptime;
 use crate::node_status_api::{FIFTEEN_MINUTES, ONE_HOUR};
-use crate::storage::models::NodeStatus;
+use crate::storage::models::{NodeLatency, NodeStatus};
 use log::warn;
 use std::convert::TryInto;
 use time::OffsetDateTime;
 
-// A temporary helper struct used to produce reports for active nodes.
-pub(crate) struct ActiveNodeStatuses {
-    pub(crate) identity: String,
-    pub(crate) owner: String,
-    pub(crate) statuses: Vec<NodeStatus>,
-}
-
 // A helper intermediate struct to remove duplicate code for construction of mixnode and gateway reports
 pub(crate) struct NodeUptimes {
     pub(crate) most_recent: Uptime,
@@ -107,3 +100,102 @@ impl NodeUptimes {
         }
     }
 }
+
+// A helper intermediate struct to remove duplicate code for construction of mixnode and gateway latency reports
+pub(crate) struct NodeLatencies {
+    pub(crate) most_recent: Option<u32>,
+
+    pub(crate) last_hour: Option<u32>,
+    pub(crate) last_day: Option<u32>,
+}
+
+impl NodeLatencies {
+    pub(crate) fn calculate_from_last_day_latencies(
+        report_time: OffsetDateTime,
+        last_day: Vec<NodeLatency>,
+    ) -> Self {
+        let hour_ago = (report_time - ONE_HOUR).unix_timestamp();
+        let fifteen_minutes_ago = (report_time - FIFTEEN_MINUTES).unix_timestamp();
+
+        // unlike uptimes, runs in which the node hasn't returned any packets don't tell us
+        // anything about its latency, so they're not included in the averages
+        fn average<'a>(latencies: impl Iterator<Item = &'a NodeLatency>) -> Option<u32> {
+            let (sum, count) = latencies
+                .filter_map(|measurement| measurement.latency)
+                .fold((0u64, 0u64), |(sum, count), latency| {
+                    (sum + latency as u64, count + 1)
+                });
+            if count == 0 {
+                None
+            } else {
+                Some((sum / count) as u32)
+            }
+        }
+
+        // find the most recent measurement and make sure its within last 15min
+        let most_recent = last_day
+            .iter()
+            .filter(|measurement| measurement.latency.is_some())
+            .max_by_key(|measurement| measurement.timestamp())
+            .filter(|measurement| measurement.timestamp() >= fifteen_minutes_ago)
+            .and_then(|measurement| measurement.latency);
+
+        NodeLatencies {
+            most_recent,
+            last_hour: average(
+                last_day
+                    .iter()
+                    .filter(|measurement| measurement.timestamp() >= hour_ago),
+            ),
+            last_day: average(last_day.iter()),
+        }
+    }
+}
+
+// A helper intermediate struct for construction of gateway inbox reports
+pub(crate) struct NodeInboxReliabilities {
+    pub(crate) most_recent: Option<Uptime>,
+
+    pub(crate) last_hour: Option<Uptime>,
+    pub(crate) last_day: Option<Uptime>,
+}
+
+impl NodeInboxReliabilities {
+    pub(crate) fn calculate_from_last_day_statuses(
+        report_time: OffsetDateTime,
+        last_day: Vec<NodeStatus>,
+    ) -> Self {
+        let hour_ago = (report_time - ONE_HOUR).unix_timestamp();
+        let fifteen_minutes_ago = (report_time - FIFTEEN_MINUTES).unix_timestamp();
+
+        // the inbox test might be disabled on some of the monitor runs, so unlike with the uptimes,
+        // we only average over the runs that have actually tested the gateway
+        fn average<'a>(statuses: impl Iterator<Item = &'a NodeStatus>) -> Option<Uptime> {
+            let (sum, count) = statuses.fold((0f32, 0usize), |(sum, count), status| {
+                (sum + status.reliability() as f32, count + 1)
+            });
+            if count == 0 {
+                None
+            } else {
+                Uptime::from_uptime_sum(sum, count).ok()
+            }
+        }
+
+        // find the most recent status and make sure its within last 15min
+        let most_recent = last_day
+            .iter()
+            .max_by_key(|status| status.timestamp())
+            .filter(|status| status.timestamp() >= fifteen_minutes_ago)
+            .and_then(|status| status.reliability().try_into().ok());
+
+        NodeInboxReliabilities {
+            most_recent,
+            last_hour: average(
+                last_day
+                    .iter()
+                    .filter(|status| status.timestamp() >= hour_ago),
+            ),
+            last_day: average(last_day.iter()),
+        }
+    }
+}
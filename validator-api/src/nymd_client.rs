@@ -239,7 +239,6 @@ impl<C> Client<C> {
         Ok(hash)
     }
 
-    #[allow(dead_code)]
     pub(crate) async fn get_mixnode_delegations(
         &self,
         identity: IdentityKey,
@@ -317,6 +316,7 @@ impl<C> Client<C> {
         rewarded_set: Vec<IdentityKey>,
         expected_active_set_size: u32,
         reward_msgs: Vec<(ExecuteMsg, Vec<CosmosCoin>)>,
+        to_jail: Vec<IdentityKey>,
     ) -> Result<(), RewardingError>
     where
         C: SigningCosmWasmClient + Sync,
@@ -324,6 +324,12 @@ impl<C> Client<C> {
         // // First we create the checkpoint, all subsequent changes to a node will be made to the checkpoint
         let mut msgs = vec![(ExecuteMsg::CheckpointMixnodes {}, vec![])];
         msgs.extend(reward_msgs);
+        // jailing has to happen before the new rewarded set is written
+        msgs.extend(
+            to_jail
+                .into_iter()
+                .map(|mix_identity| (ExecuteMsg::JailMixnode { mix_identity }, vec![])),
+        );
 
         let epoch_msgs = vec![
             (ExecuteMsg::ReconcileDelegations {}, vec![]),
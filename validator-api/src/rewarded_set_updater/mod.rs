@@ -14,23 +14,47 @@
 
 use crate::contract_cache::ValidatorCache;
 use crate::nymd_client::Client;
+use crate::rewarded_set_updater::models::{
+    DelegationSnapshot, EpochRewardingSnapshot, MixnodeRewardingSnapshot,
+};
+use crate::rewarded_set_updater::selection::RewardedSetSelector;
 use crate::storage::models::RewardingReport;
 use crate::storage::ValidatorApiStorage;
-use mixnet_contract_common::reward_params::NodeRewardParams;
+use mixnet_contract_common::reward_params::{EpochRewardParams, NodeRewardParams};
 use mixnet_contract_common::ExecuteMsg;
 use mixnet_contract_common::{IdentityKey, Interval, MixNodeBond};
-use rand::prelude::SliceRandom;
-use rand::rngs::OsRng;
-use std::collections::HashSet;
+use okapi::openapi3::OpenApi;
+use rocket::Route;
+use rocket_okapi::{openapi_get_routes_spec, settings::OpenApiSettings};
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 use time::OffsetDateTime;
 use tokio::time::sleep;
 use validator_client::nymd::{CosmosCoin, SigningNymdClient};
 
 pub(crate) mod error;
+pub(crate) mod models;
+pub(crate) mod routes;
+pub(crate) mod selection;
+pub(crate) mod simulation;
 
 use error::RewardingError;
 
+pub(crate) fn rewarded_set_routes(
+    settings: &OpenApiSettings,
+    enabled: bool,
+) -> (Vec<Route>, OpenApi) {
+    if enabled {
+        openapi_get_routes_spec![
+            settings: routes::rewarded_set_dry_run,
+            routes::simulate_rewarding,
+        ]
+    } else {
+        // simulations require the epoch snapshots kept in the network monitor storage
+        openapi_get_routes_spec![settings: routes::rewarded_set_dry_run]
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct MixnodeToReward {
     pub(crate) identity: IdentityKey,
@@ -60,10 +84,62 @@ impl MixnodeToReward {
 // Epoch has all the same semantics as interval, but has a lower set duration
 type Epoch = Interval;
 
+// Determines the not yet jailed mixnodes whose uptime stayed below the jailing threshold
+// in each of the last `jailing_epochs` epochs. Epochs during which the node was not tested
+// at all (for example because it was not bonded yet) are not held against it.
+// Note that the blacklisted nodes are considered as well, as persistently failing the tests
+// is exactly what gets them blacklisted in the first place.
+async fn nodes_to_jail(
+    validator_cache: &ValidatorCache,
+    storage: &ValidatorApiStorage,
+    epoch: Epoch,
+    jailing_uptime_threshold: u8,
+    jailing_epochs: u32,
+) -> Result<Vec<IdentityKey>, RewardingError> {
+    if jailing_epochs == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mixnodes = validator_cache.mixnodes_all().await;
+
+    let mut to_jail = Vec::new();
+    for bond in mixnodes.iter().filter(|bond| !bond.is_jailed()) {
+        let mut poor_epochs = 0;
+        let mut checked_epoch = Some(epoch);
+        while let Some(current) = checked_epoch {
+            if poor_epochs == jailing_epochs {
+                break;
+            }
+            let uptime = storage
+                .get_measured_mixnode_uptime_in_interval(
+                    bond.identity(),
+                    current.start_unix_timestamp(),
+                    current.end_unix_timestamp(),
+                )
+                .await?;
+            match uptime {
+                Some(uptime) if uptime.u8() < jailing_uptime_threshold => poor_epochs += 1,
+                _ => break,
+            }
+            checked_epoch = current.previous();
+        }
+
+        if poor_epochs == jailing_epochs {
+            to_jail.push(bond.identity().clone())
+        }
+    }
+
+    Ok(to_jail)
+}
+
 pub struct RewardedSetUpdater {
     nymd_client: Client<SigningNymdClient>,
     validator_cache: ValidatorCache,
     storage: ValidatorApiStorage,
+    selector: RewardedSetSelector,
+    record_epoch_snapshots: bool,
+    jailing_uptime_threshold: u8,
+    jailing_epochs: u32,
 }
 
 impl RewardedSetUpdater {
@@ -75,68 +151,100 @@ impl RewardedSetUpdater {
         nymd_client: Client<SigningNymdClient>,
         validator_cache: ValidatorCache,
         storage: ValidatorApiStorage,
+        selector: RewardedSetSelector,
+        record_epoch_snapshots: bool,
+        jailing_uptime_threshold: u8,
+        jailing_epochs: u32,
     ) -> Result<Self, RewardingError> {
         Ok(RewardedSetUpdater {
             nymd_client,
             validator_cache,
             storage,
+            selector,
+            record_epoch_snapshots,
+            jailing_uptime_threshold,
+            jailing_epochs,
         })
     }
 
-    fn determine_rewarded_set(
-        &self,
-        mixnodes: Vec<MixNodeBond>,
-        nodes_to_select: u32,
-    ) -> Vec<IdentityKey> {
-        if mixnodes.is_empty() {
-            return Vec::new();
-        }
-
-        let mut rng = OsRng;
-
-        // generate list of mixnodes and their relatively weight (by total stake)
-        let choices = mixnodes
-            .into_iter()
-            .map(|mix| {
-                // note that the theoretical maximum possible stake is equal to the total
-                // supply of all tokens, i.e. 1B (which is 1 quadrillion of native tokens, i.e. 10^15 ~ 2^50)
-                // which is way below maximum value of f64, so the cast is fine
-                let total_stake = mix.total_bond().unwrap_or_default() as f64;
-                (mix.mix_node.identity_key, total_stake)
-            }) // if for some reason node is invalid, treat it as 0 stake/weight
-            .collect::<Vec<_>>();
-
-        // the unwrap here is fine as an error can only be thrown under one of the following conditions:
-        // - our mixnode list is empty - we have already checked for that
-        // - we have invalid weights, i.e. less than zero or NaNs - it shouldn't happen in our case as we safely cast down from u128
-        // - all weights are zero - it's impossible in our case as the list of nodes is not empty and weight is proportional to stake. You must have non-zero stake in order to bond
-        // - we have more than u32::MAX values (which is incredibly unrealistic to have 4B mixnodes bonded... literally every other person on the planet would need one)
-        choices
-            .choose_multiple_weighted(&mut rng, nodes_to_select as usize, |item| item.1)
-            .unwrap()
-            .map(|(identity, _weight)| identity.clone())
-            .collect()
-    }
-
     async fn reward_current_rewarded_set(
         &self,
+        epoch: &Epoch,
+        to_reward: &[MixnodeToReward],
     ) -> Result<Vec<(ExecuteMsg, Vec<CosmosCoin>)>, RewardingError> {
-        let to_reward = self.nodes_to_reward().await?;
-        let epoch = self.epoch().await?;
-
         // self.storage.insert_started_epoch_rewarding(epoch).await?;
 
         let rewarding_report = RewardingReport {
             interval_rewarding_id: epoch.id() as i64,
             eligible_mixnodes: to_reward.len() as i64,
             possibly_unrewarded_mixnodes: 0,
+            timestamp: OffsetDateTime::now_utc().unix_timestamp(),
         };
 
         self.storage
             .insert_rewarding_report(rewarding_report)
             .await?;
 
-        self.generate_reward_messages(&to_reward).await
+        self.generate_reward_messages(to_reward).await
+    }
+
+    // Note: the rewarded set and the reward params have to be obtained before the epoch operations
+    // are executed, as afterwards the cache might already contain values for the new epoch.
+    async fn record_epoch_snapshot(
+        &self,
+        epoch: &Epoch,
+        epoch_reward_params: EpochRewardParams,
+        rewarded_set: Vec<MixNodeBond>,
+        to_reward: &[MixnodeToReward],
+    ) -> Result<(), RewardingError> {
+        let node_params = to_reward
+            .iter()
+            .map(|node| (&node.identity, node.params))
+            .collect::<HashMap<_, _>>();
+
+        let mut mixnodes = Vec::with_capacity(to_reward.len());
+        let mut delegations = Vec::new();
+        for bond in rewarded_set {
+            let params = match node_params.get(bond.identity()) {
+                Some(params) => *params,
+                None => continue,
+            };
+
+            // the same delegator might have delegated to the same node multiple times
+            let mut delegated: HashMap<String, u128> = HashMap::new();
+            for delegation in self
+                .nymd_client
+                .get_mixnode_delegations(bond.identity().clone())
+                .await?
+            {
+                *delegated.entry(delegation.owner.to_string()).or_default() +=
+                    delegation.amount.amount.u128();
+            }
+            delegations.extend(delegated.into_iter().map(|(delegator, amount)| {
+                DelegationSnapshot {
+                    mix_identity: bond.identity().clone(),
+                    delegator,
+                    amount,
+                }
+            }));
+
+            mixnodes.push(MixnodeRewardingSnapshot {
+                bond,
+                uptime: params.uptime() as u8,
+                in_active_set: params.in_active_set(),
+            });
+        }
+
+        self.storage
+            .insert_epoch_snapshot(EpochRewardingSnapshot {
+                epoch_id: epoch.id(),
+                epoch_reward_params,
+                mixnodes,
+                delegations,
+            })
+            .await?;
+
+        Ok(())
     }
 
     #[allow(unused_variables)]
@@ -197,7 +305,6 @@ impl RewardedSetUpdater {
 
     // This is where the epoch gets advanced, and all epoch related transactions originate
     async fn update(&self) -> Result<(), RewardingError> {
-        let epoch = self.epoch().await?;
         log::info!("Starting rewarded set update");
         // we know the entries are not stale, as a matter of fact they were JUST updated, since we got notified
         let all_nodes = self.validator_cache.mixnodes().await;
@@ -214,26 +321,61 @@ impl RewardedSetUpdater {
         //     log::info!("Rewarded current rewarded set... SUCCESS");
         // }
 
-        let reward_msgs = self.reward_current_rewarded_set().await?;
+        let epoch = self.epoch().await?;
+        let to_reward = self.nodes_to_reward().await?;
+        let rewarded_set = self.validator_cache.rewarded_set().await.into_inner();
+        let reward_msgs = self.reward_current_rewarded_set(&epoch, &to_reward).await?;
+
+        // nodes jailed in this epoch can't be put in the new rewarded set
+        let to_jail = nodes_to_jail(
+            &self.validator_cache,
+            &self.storage,
+            epoch,
+            self.jailing_uptime_threshold,
+            self.jailing_epochs,
+        )
+        .await?;
+        if !to_jail.is_empty() {
+            log::info!("Jailing {} mixnodes due to poor uptime", to_jail.len());
+        }
+        let all_nodes = all_nodes
+            .into_iter()
+            .filter(|bond| !to_jail.contains(bond.identity()))
+            .collect();
 
         let rewarded_set_size = epoch_reward_params.rewarded_set_size() as u32;
         let active_set_size = epoch_reward_params.active_set_size() as u32;
 
         // note that top k nodes are in the active set
-        let new_rewarded_set = self.determine_rewarded_set(all_nodes, rewarded_set_size);
+        let new_rewarded_set = self
+            .selector
+            .determine_rewarded_set(all_nodes, rewarded_set_size, epoch.end_unix_timestamp())
+            .await?;
 
         if let Err(err) = self
             .nymd_client
-            .epoch_operations(new_rewarded_set, active_set_size, reward_msgs)
+            .epoch_operations(new_rewarded_set, active_set_size, reward_msgs, to_jail)
             .await
         {
             log::error!("FAILED epoch operations - {}", err);
         } else {
             log::info!("Epoch operations... SUCCESS");
-        }
 
-        let cutoff = (epoch.end() - Duration::from_secs(86400)).unix_timestamp();
-        self.storage.purge_old_statuses(cutoff).await?;
+            // the snapshot is only recorded once the epoch operations got through,
+            // so that querying all the delegations wouldn't delay them
+            if self.record_epoch_snapshots {
+                if let Err(err) = self
+                    .record_epoch_snapshot(&epoch, epoch_reward_params, rewarded_set, &to_reward)
+                    .await
+                {
+                    log::warn!(
+                        "Failed to record snapshot of epoch {} - {}",
+                        epoch.id(),
+                        err
+                    );
+                }
+            }
+        }
 
         Ok(())
     }
@@ -313,3 +455,89 @@ impl RewardedSetUpdater {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network_monitor::monitor::summary_producer::NodeResult;
+    use mixnet_contract_common::{Addr, Coin, Layer, MixNode};
+
+    // 2022-05-10 00:00:00 UTC
+    const EPOCH_START: i64 = 1652140800;
+
+    fn bond(identity: &str) -> MixNodeBond {
+        MixNodeBond {
+            pledge_amount: Coin::new(10_000_000_000, "unym"),
+            total_delegation: Coin::new(0, "unym"),
+            owner: Addr::unchecked(format!("owner-{}", identity)),
+            layer: Layer::One,
+            block_height: 1,
+            mix_node: MixNode {
+                host: "1.1.1.1".to_string(),
+                mix_port: 1789,
+                verloc_port: 1790,
+                http_api_port: 8000,
+                sphinx_key: "sphinxkey".to_string(),
+                identity_key: identity.to_string(),
+                version: "0.12.1".to_string(),
+                profit_margin_percent: 10,
+                operating_cost: None,
+            },
+            proxy: None,
+            accumulated_rewards: None,
+            family: None,
+            jailed: None,
+        }
+    }
+
+    fn node_result(identity: &str, reliability: u8) -> NodeResult {
+        NodeResult {
+            identity: identity.to_string(),
+            owner: format!("owner-{}", identity),
+            reliability,
+            latency: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn blacklisted_nodes_with_persistently_poor_uptime_get_jailed() {
+        let storage = crate::storage::test_storage().await;
+        let validator_cache =
+            ValidatorCache::with_mixnodes(vec![bond("failing"), bond("healthy")]).await;
+        validator_cache
+            .update_mixnodes_blacklist(
+                vec!["failing".to_string()].into_iter().collect(),
+                HashSet::new(),
+            )
+            .await;
+        assert!(validator_cache
+            .mixnodes()
+            .await
+            .iter()
+            .all(|bond| bond.identity() != "failing"));
+
+        let epoch: Epoch = serde_json::from_value(serde_json::json!({
+            "id": 2,
+            "start": "2022-05-10T00:00:00Z",
+            "length": { "secs": 3600, "nanos": 0 },
+        }))
+        .unwrap();
+
+        // the node kept failing the tests during both the current and the previous epoch
+        for timestamp in [EPOCH_START - 1800, EPOCH_START + 1800] {
+            storage
+                .manager
+                .submit_mixnode_statuses(
+                    timestamp,
+                    vec![node_result("failing", 0), node_result("healthy", 100)],
+                )
+                .await
+                .unwrap();
+        }
+
+        let to_jail = nodes_to_jail(&validator_cache, &storage, epoch, 50, 2)
+            .await
+            .unwrap();
+        assert_eq!(to_jail, vec!["failing".to_string()]);
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use mixnet_contract_common::reward_params::EpochRewardParams;
+use mixnet_contract_common::{IdentityKey, Interval, Layer, MixNodeBond};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub(crate) struct NodeSelectionChance {
+    pub(crate) identity: IdentityKey,
+    pub(crate) owner: String,
+    pub(crate) layer: Layer,
+    pub(crate) total_stake: String,
+
+    /// Average reliability of the node in the last 24h, if it was taken into account.
+    pub(crate) performance: Option<u8>,
+
+    /// Weight of the node used during the sampling.
+    pub(crate) weight: String,
+
+    /// Estimated probability of the node being chosen for the rewarded set.
+    pub(crate) rewarded_probability: f64,
+
+    /// Estimated probability of the node being chosen for the active set.
+    pub(crate) active_probability: f64,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub(crate) struct RewardedSetDryRun {
+    pub(crate) next_epoch: Option<Interval>,
+    pub(crate) rewarded_set_size: u32,
+    pub(crate) active_set_size: u32,
+
+    /// Number of simulated selections used for estimating the probabilities.
+    pub(crate) samples: usize,
+
+    /// Example rewarded set that could have been chosen for the next epoch, where the first
+    /// `active_set_size` nodes form the active set.
+    pub(crate) rewarded_set: Vec<IdentityKey>,
+
+    /// All the candidate nodes sorted by their probability of getting into the rewarded set.
+    pub(crate) nodes: Vec<NodeSelectionChance>,
+}
+
+/// State of the rewarded set at the time of particular epoch getting rewarded.
+#[derive(Clone, Debug)]
+pub(crate) struct EpochRewardingSnapshot {
+    pub(crate) epoch_id: u32,
+    pub(crate) epoch_reward_params: EpochRewardParams,
+    pub(crate) mixnodes: Vec<MixnodeRewardingSnapshot>,
+    pub(crate) delegations: Vec<DelegationSnapshot>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct MixnodeRewardingSnapshot {
+    pub(crate) bond: MixNodeBond,
+    pub(crate) uptime: u8,
+    pub(crate) in_active_set: bool,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct DelegationSnapshot {
+    pub(crate) mix_identity: IdentityKey,
+    pub(crate) delegator: String,
+    pub(crate) amount: u128,
+}
+
+/// Parameters overriding the recorded ones during the rewarding simulation. Anything left
+/// unspecified retains the value that was in effect during the particular epoch.
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema)]
+pub(crate) struct RewardingSimulationRequest {
+    /// The id of the first simulated epoch.
+    pub(crate) from_epoch: u32,
+
+    /// The id of the last simulated epoch (inclusive).
+    pub(crate) to_epoch: u32,
+
+    pub(crate) epoch_reward_pool: Option<u64>,
+    pub(crate) circulating_supply: Option<u64>,
+
+    /// Note that changing the size of the rewarded or the active set only affects the reward
+    /// calculation. The simulated nodes are still the ones that were actually rewarded.
+    pub(crate) rewarded_set_size: Option<u32>,
+    pub(crate) active_set_size: Option<u32>,
+    pub(crate) sybil_resistance_percent: Option<u8>,
+    pub(crate) active_set_work_factor: Option<u8>,
+
+    /// Uptime assumed for every node instead of its measured one.
+    pub(crate) uptime: Option<u8>,
+
+    /// Profit margin assumed for every node instead of its actual one.
+    pub(crate) profit_margin_percent: Option<u8>,
+
+    /// If specified, only the rewards of this mixnode are going to be returned.
+    pub(crate) identity: Option<IdentityKey>,
+
+    /// If specified, only the rewards of this delegator are going to be returned.
+    pub(crate) delegator: Option<String>,
+}
+
+/// Rewards accumulated over all the simulated epochs, where the `recorded` values are calculated
+/// with the parameters that were in effect during those epochs.
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub(crate) struct SimulatedNodeRewards {
+    pub(crate) identity: IdentityKey,
+    pub(crate) owner: String,
+    pub(crate) epochs: u32,
+
+    pub(crate) recorded_operator_reward: u128,
+    pub(crate) simulated_operator_reward: u128,
+    pub(crate) operator_reward_difference: i128,
+
+    pub(crate) recorded_delegators_reward: u128,
+    pub(crate) simulated_delegators_reward: u128,
+    pub(crate) delegators_reward_difference: i128,
+}
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub(crate) struct SimulatedDelegatorRewards {
+    pub(crate) delegator: String,
+    pub(crate) mix_identity: IdentityKey,
+    pub(crate) epochs: u32,
+
+    pub(crate) recorded_reward: u128,
+    pub(crate) simulated_reward: u128,
+    pub(crate) reward_difference: i128,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub(crate) struct RewardingSimulationResponse {
+    /// Ids of all the epochs for which the snapshots were available.
+    pub(crate) simulated_epochs: Vec<u32>,
+    pub(crate) nodes: Vec<SimulatedNodeRewards>,
+    pub(crate) delegators: Vec<SimulatedDelegatorRewards>,
+}
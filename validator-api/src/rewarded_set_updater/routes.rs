@@ -0,0 +1,95 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::contract_cache::ValidatorCache;
+use crate::node_status_api::models::ErrorResponse;
+use crate::rewarded_set_updater::models::{
+    RewardedSetDryRun, RewardingSimulationRequest, RewardingSimulationResponse,
+};
+use crate::rewarded_set_updater::selection::RewardedSetSelector;
+use crate::rewarded_set_updater::simulation;
+use crate::storage::ValidatorApiStorage;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::State;
+use rocket_okapi::openapi;
+use time::OffsetDateTime;
+
+// a week worth of hourly epochs
+const MAX_SIMULATED_EPOCHS: u32 = 168;
+
+#[openapi(tag = "rewarded-set")]
+#[get("/dry-run")]
+pub(crate) async fn rewarded_set_dry_run(
+    cache: &State<ValidatorCache>,
+    selector: &State<RewardedSetSelector>,
+) -> Result<Json<RewardedSetDryRun>, ErrorResponse> {
+    let mixnodes = cache.versioned_mixnodes().await;
+    let epoch_reward_params = cache.epoch_reward_params().await;
+    let current_epoch = cache.current_epoch().await;
+
+    // the simulation only has to be redone if anything it's based on has changed
+    let version = format!(
+        "{}-{}-{}",
+        mixnodes.version(),
+        epoch_reward_params.version(),
+        current_epoch.version()
+    );
+    let epoch_reward_params = epoch_reward_params.into_inner();
+    let current_epoch = current_epoch.into_inner();
+
+    // the monitor data is looked at as of the end of the current epoch, i.e. the same way
+    // it's going to be done during the actual epoch transition
+    let end_ts = current_epoch
+        .map(|epoch| epoch.end_unix_timestamp())
+        .unwrap_or_else(|| OffsetDateTime::now_utc().unix_timestamp())
+        .min(OffsetDateTime::now_utc().unix_timestamp());
+
+    selector
+        .dry_run(
+            version,
+            mixnodes.into_inner(),
+            epoch_reward_params.rewarded_set_size() as u32,
+            epoch_reward_params.active_set_size() as u32,
+            current_epoch.map(|epoch| epoch.next()),
+            end_ts,
+        )
+        .await
+        .map(Json)
+        .map_err(|err| ErrorResponse::new(err.to_string(), Status::InternalServerError))
+}
+
+#[openapi(tag = "rewarded-set")]
+#[post("/simulate", data = "<request>")]
+pub(crate) async fn simulate_rewarding(
+    storage: &State<ValidatorApiStorage>,
+    request: Json<RewardingSimulationRequest>,
+) -> Result<Json<RewardingSimulationResponse>, ErrorResponse> {
+    let request = request.into_inner();
+    if request.from_epoch > request.to_epoch {
+        return Err(ErrorResponse::new(
+            "the simulated epoch range is empty",
+            Status::BadRequest,
+        ));
+    }
+    if request.to_epoch - request.from_epoch >= MAX_SIMULATED_EPOCHS {
+        return Err(ErrorResponse::new(
+            format!(
+                "at most {} epochs can be simulated at once",
+                MAX_SIMULATED_EPOCHS
+            ),
+            Status::BadRequest,
+        ));
+    }
+
+    let snapshots = storage
+        .get_epoch_snapshots(
+            request.from_epoch,
+            request.to_epoch,
+            request.delegator.as_deref(),
+        )
+        .await
+        .map_err(|err| ErrorResponse::new(err.to_string(), Status::InternalServerError))?;
+
+    Ok(Json(simulation::simulate_rewarding(&request, &snapshots)))
+}
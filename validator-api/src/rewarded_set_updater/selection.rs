@@ -0,0 +1,775 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{Config, RewardedSetWeighting};
+use crate::node_status_api::models::ValidatorApiStorageError;
+use crate::rewarded_set_updater::models::{NodeSelectionChance, RewardedSetDryRun};
+use crate::storage::ValidatorApiStorage;
+use log::warn;
+use mixnet_contract_common::{IdentityKey, Interval, Layer, MixNodeBond};
+use rand::rngs::OsRng;
+use rand::Rng;
+use rocket::fairing::AdHoc;
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::task;
+
+// weight given to nodes for which we don't have any monitor data (yet), i.e. they're treated
+// as if they were performing perfectly
+const UNKNOWN_PERFORMANCE: u128 = 100;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct NodeLocation {
+    pub(crate) country: Option<String>,
+    pub(crate) asn: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SelectionCandidate {
+    pub(crate) identity: IdentityKey,
+    pub(crate) owner: String,
+    pub(crate) layer: Layer,
+    pub(crate) total_stake: u128,
+
+    /// Average reliability of the node in the last 24h, if known.
+    pub(crate) performance: Option<u8>,
+    pub(crate) location: NodeLocation,
+}
+
+/// Policy used for choosing the rewarded set out of all bonded mixnodes. Nodes are sampled,
+/// without replacement, proportionally to their weights while respecting all the configured limits.
+/// The order of the selected nodes matters as the first nodes are going to form the active set.
+#[derive(Debug, Clone)]
+pub(crate) struct SelectionPolicy {
+    weighting: RewardedSetWeighting,
+    max_per_operator: Option<usize>,
+    max_per_country: Option<usize>,
+    max_per_asn: Option<usize>,
+    balance_layers: bool,
+}
+
+fn limit(value: usize) -> Option<usize> {
+    if value == 0 {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+impl SelectionPolicy {
+    pub(crate) fn new(config: &Config) -> Self {
+        SelectionPolicy {
+            weighting: config.get_rewarded_set_weighting(),
+            max_per_operator: limit(config.get_max_rewarded_nodes_per_operator()),
+            max_per_country: limit(config.get_max_rewarded_nodes_per_country()),
+            max_per_asn: limit(config.get_max_rewarded_nodes_per_asn()),
+            balance_layers: config.get_balance_rewarded_set_layers(),
+        }
+    }
+
+    pub(crate) fn weight(&self, candidate: &SelectionCandidate) -> u128 {
+        match self.weighting {
+            RewardedSetWeighting::Stake => candidate.total_stake,
+            // the theoretical maximum possible stake is equal to the total supply of all tokens,
+            // i.e. 10^15 of native tokens, so multiplying it by at most 100 can't possibly overflow
+            RewardedSetWeighting::Performance => {
+                candidate.total_stake
+                    * candidate
+                        .performance
+                        .map(u128::from)
+                        .unwrap_or(UNKNOWN_PERFORMANCE)
+            }
+        }
+    }
+
+    /// Chooses up to `nodes_to_select` candidates and returns their indices in the order they
+    /// were selected.
+    pub(crate) fn select<R: Rng + ?Sized>(
+        &self,
+        candidates: &[SelectionCandidate],
+        nodes_to_select: usize,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        let weights = candidates
+            .iter()
+            .map(|candidate| self.weight(candidate))
+            .collect::<Vec<_>>();
+        let groups = SelectionGroups::new(candidates);
+
+        Selection::new(self, &weights, &groups, nodes_to_select).run(rng)
+    }
+
+    /// Estimates the probability of each candidate getting into the rewarded and the active set
+    /// by running the selection `samples` times.
+    pub(crate) fn estimate_probabilities<R: Rng + ?Sized>(
+        &self,
+        candidates: &[SelectionCandidate],
+        rewarded_set_size: usize,
+        active_set_size: usize,
+        samples: usize,
+        rng: &mut R,
+    ) -> Vec<(f64, f64)> {
+        let weights = candidates
+            .iter()
+            .map(|candidate| self.weight(candidate))
+            .collect::<Vec<_>>();
+        let groups = SelectionGroups::new(candidates);
+
+        let mut rewarded = vec![0usize; candidates.len()];
+        let mut active = vec![0usize; candidates.len()];
+        for _ in 0..samples {
+            let selected = Selection::new(self, &weights, &groups, rewarded_set_size).run(rng);
+            for (position, index) in selected.into_iter().enumerate() {
+                rewarded[index] += 1;
+                if position < active_set_size {
+                    active[index] += 1;
+                }
+            }
+        }
+
+        let samples = samples.max(1) as f64;
+        rewarded
+            .into_iter()
+            .zip(active)
+            .map(|(rewarded, active)| (rewarded as f64 / samples, active as f64 / samples))
+            .collect()
+    }
+}
+
+// indices of candidates sharing the same operator, location or layer
+struct SelectionGroups {
+    node_groups: Vec<NodeGroups>,
+    operators: Vec<Vec<usize>>,
+    countries: Vec<Vec<usize>>,
+    asns: Vec<Vec<usize>>,
+    layers: Vec<Vec<usize>>,
+}
+
+struct NodeGroups {
+    operator: usize,
+    country: Option<usize>,
+    asn: Option<usize>,
+    layer: usize,
+}
+
+fn group_index<K: Eq + Hash>(
+    key: K,
+    node: usize,
+    lookup: &mut HashMap<K, usize>,
+    groups: &mut Vec<Vec<usize>>,
+) -> usize {
+    let index = *lookup.entry(key).or_insert_with(|| {
+        groups.push(Vec::new());
+        groups.len() - 1
+    });
+    groups[index].push(node);
+    index
+}
+
+impl SelectionGroups {
+    fn new(candidates: &[SelectionCandidate]) -> Self {
+        let mut operators = Vec::new();
+        let mut countries = Vec::new();
+        let mut asns = Vec::new();
+        let mut layers = Vec::new();
+
+        let mut operator_lookup = HashMap::new();
+        let mut country_lookup = HashMap::new();
+        let mut asn_lookup = HashMap::new();
+        let mut layer_lookup = HashMap::new();
+
+        let mut node_groups = Vec::with_capacity(candidates.len());
+        for (i, candidate) in candidates.iter().enumerate() {
+            let location = &candidate.location;
+            node_groups.push(NodeGroups {
+                operator: group_index(&candidate.owner, i, &mut operator_lookup, &mut operators),
+                country: location
+                    .country
+                    .as_ref()
+                    .map(|country| group_index(country, i, &mut country_lookup, &mut countries)),
+                asn: location
+                    .asn
+                    .map(|asn| group_index(asn, i, &mut asn_lookup, &mut asns)),
+                layer: group_index(candidate.layer, i, &mut layer_lookup, &mut layers),
+            });
+        }
+
+        SelectionGroups {
+            node_groups,
+            operators,
+            countries,
+            asns,
+            layers,
+        }
+    }
+}
+
+// Fenwick tree over the weights of candidates that can still be selected.
+// Integer weights are used so that removing a candidate always leaves the remaining sums exact.
+struct WeightTree {
+    tree: Vec<u128>,
+    weights: Vec<u128>,
+}
+
+impl WeightTree {
+    fn new(weights: &[u128]) -> Self {
+        let mut tree = WeightTree {
+            tree: vec![0; weights.len() + 1],
+            weights: vec![0; weights.len()],
+        };
+        for (i, weight) in weights.iter().enumerate() {
+            tree.insert(i, *weight);
+        }
+        tree
+    }
+
+    fn insert(&mut self, index: usize, weight: u128) {
+        self.weights[index] += weight;
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] += weight;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        let weight = self.weights[index];
+        self.weights[index] = 0;
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] -= weight;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn total(&self) -> u128 {
+        let mut total = 0;
+        let mut i = self.weights.len();
+        while i > 0 {
+            total += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        total
+    }
+
+    // finds the index whose cumulative weight range contains the provided point,
+    // i.e. the smallest index such that the sum of weights up to and including it exceeds `point`
+    fn find(&self, mut point: u128) -> usize {
+        let mut position = 0;
+        let mut step = self.tree.len().next_power_of_two();
+        while step > 0 {
+            let next = position + step;
+            if next < self.tree.len() && self.tree[next] <= point {
+                position = next;
+                point -= self.tree[next];
+            }
+            step >>= 1;
+        }
+        position
+    }
+}
+
+struct Selection<'a> {
+    policy: &'a SelectionPolicy,
+    weights: &'a [u128],
+    groups: &'a SelectionGroups,
+    nodes_to_select: usize,
+
+    available: WeightTree,
+    selected: Vec<bool>,
+    // nodes that can't be selected anymore due to the operator, country or asn limits
+    capped: Vec<bool>,
+    // nodes that can't be selected anymore only because their layer is full
+    layer_capped: Vec<bool>,
+
+    operator_counts: Vec<usize>,
+    country_counts: Vec<usize>,
+    asn_counts: Vec<usize>,
+    layer_counts: Vec<usize>,
+    layer_limit: Option<usize>,
+}
+
+impl<'a> Selection<'a> {
+    fn new(
+        policy: &'a SelectionPolicy,
+        weights: &'a [u128],
+        groups: &'a SelectionGroups,
+        nodes_to_select: usize,
+    ) -> Self {
+        let layer_limit = if policy.balance_layers && !groups.layers.is_empty() {
+            // round it up so that we'd still be able to fill the whole set
+            Some((nodes_to_select + groups.layers.len() - 1) / groups.layers.len())
+        } else {
+            None
+        };
+
+        Selection {
+            policy,
+            weights,
+            groups,
+            nodes_to_select,
+            available: WeightTree::new(weights),
+            selected: vec![false; weights.len()],
+            capped: vec![false; weights.len()],
+            layer_capped: vec![false; weights.len()],
+            operator_counts: vec![0; groups.operators.len()],
+            country_counts: vec![0; groups.countries.len()],
+            asn_counts: vec![0; groups.asns.len()],
+            layer_counts: vec![0; groups.layers.len()],
+            layer_limit,
+        }
+    }
+
+    fn cap_group(&mut self, group: &[usize]) {
+        for &node in group {
+            if !self.capped[node] && !self.selected[node] {
+                self.capped[node] = true;
+                self.available.remove(node);
+            }
+        }
+    }
+
+    fn cap_layer(&mut self, group: &[usize]) {
+        for &node in group {
+            if !self.capped[node] && !self.selected[node] && !self.layer_capped[node] {
+                self.layer_capped[node] = true;
+                self.available.remove(node);
+            }
+        }
+    }
+
+    // if the layers can't be balanced, for example because there are not enough nodes in one
+    // of them, fill the rest of the set with nodes from any layer
+    fn lift_layer_limit(&mut self) -> bool {
+        if self.layer_limit.take().is_none() {
+            return false;
+        }
+        for node in 0..self.weights.len() {
+            if self.layer_capped[node] && !self.capped[node] && !self.selected[node] {
+                self.layer_capped[node] = false;
+                self.available.insert(node, self.weights[node]);
+            }
+        }
+        true
+    }
+
+    fn mark_selected(&mut self, node: usize) {
+        self.selected[node] = true;
+        self.available.remove(node);
+
+        let groups = self.groups;
+        let node_groups = &groups.node_groups[node];
+
+        self.operator_counts[node_groups.operator] += 1;
+        if Some(self.operator_counts[node_groups.operator]) == self.policy.max_per_operator {
+            self.cap_group(&groups.operators[node_groups.operator]);
+        }
+
+        if let Some(country) = node_groups.country {
+            self.country_counts[country] += 1;
+            if Some(self.country_counts[country]) == self.policy.max_per_country {
+                self.cap_group(&groups.countries[country]);
+            }
+        }
+
+        if let Some(asn) = node_groups.asn {
+            self.asn_counts[asn] += 1;
+            if Some(self.asn_counts[asn]) == self.policy.max_per_asn {
+                self.cap_group(&groups.asns[asn]);
+            }
+        }
+
+        self.layer_counts[node_groups.layer] += 1;
+        if Some(self.layer_counts[node_groups.layer]) == self.layer_limit {
+            self.cap_layer(&groups.layers[node_groups.layer]);
+        }
+    }
+
+    fn run<R: Rng + ?Sized>(mut self, rng: &mut R) -> Vec<usize> {
+        let mut chosen = Vec::with_capacity(self.nodes_to_select);
+        while chosen.len() < self.nodes_to_select {
+            let total = self.available.total();
+            if total == 0 {
+                if self.lift_layer_limit() {
+                    continue;
+                }
+                // we ran out of nodes that could be selected
+                break;
+            }
+
+            let node = self.available.find(rng.gen_range(0..total));
+            self.mark_selected(node);
+            chosen.push(node);
+        }
+        chosen
+    }
+}
+
+fn load_node_locations(path: &Path) -> HashMap<IdentityKey, NodeLocation> {
+    let locations = fs::read_to_string(path)
+        .map_err(|err| err.to_string())
+        .and_then(|raw| serde_json::from_str(&raw).map_err(|err| err.to_string()));
+
+    match locations {
+        Ok(locations) => locations,
+        Err(err) => {
+            warn!(
+                "Failed to load the node locations from {} - {}. The country and asn limits are not going to be applied",
+                path.display(),
+                err
+            );
+            HashMap::new()
+        }
+    }
+}
+
+/// Result of the last dry run alongside the version of the data it was based on.
+struct CachedDryRun {
+    version: String,
+    result: RewardedSetDryRun,
+}
+
+/// Chooses the rewarded set according to the configured [`SelectionPolicy`]. It's used both for
+/// the actual epoch transitions and for the dry runs exposed via the http api.
+#[derive(Clone)]
+pub(crate) struct RewardedSetSelector {
+    policy: Arc<SelectionPolicy>,
+    locations: Arc<HashMap<IdentityKey, NodeLocation>>,
+    storage: Option<ValidatorApiStorage>,
+    dry_run_samples: usize,
+    last_dry_run: Arc<Mutex<Option<CachedDryRun>>>,
+}
+
+impl RewardedSetSelector {
+    pub(crate) fn new(config: &Config, storage: Option<ValidatorApiStorage>) -> Self {
+        let policy = SelectionPolicy::new(config);
+        if policy.weighting == RewardedSetWeighting::Performance && storage.is_none() {
+            warn!("The rewarded set is meant to be weighted by node performance, but the monitor data is not available. Only the stake is going to be taken into account");
+        }
+
+        let locations = config
+            .get_node_locations_path()
+            .map(|path| load_node_locations(&path))
+            .unwrap_or_default();
+
+        RewardedSetSelector {
+            policy: Arc::new(policy),
+            locations: Arc::new(locations),
+            storage,
+            dry_run_samples: config.get_selection_dry_run_samples(),
+            last_dry_run: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub(crate) fn stage(self) -> AdHoc {
+        AdHoc::on_ignite("Rewarded Set Selector", |rocket| async {
+            rocket.manage(self)
+        })
+    }
+
+    async fn candidates(
+        &self,
+        mixnodes: Vec<MixNodeBond>,
+        end_ts_secs: i64,
+    ) -> Result<Vec<SelectionCandidate>, ValidatorApiStorageError> {
+        let performance = match (&self.storage, self.policy.weighting) {
+            (Some(storage), RewardedSetWeighting::Performance) => storage
+                .get_all_avg_mix_reliability_in_last_24hr(end_ts_secs)
+                .await?
+                .into_iter()
+                .map(|avg| (avg.identity().to_string(), avg.value().round() as u8))
+                .collect(),
+            _ => HashMap::new(),
+        };
+
+        Ok(mixnodes
+            .into_iter()
+            // jailed nodes are not eligible for the rewarded set
+            .filter(|bond| !bond.is_jailed())
+            .map(|bond| {
+                let identity = bond.mix_node.identity_key.clone();
+                SelectionCandidate {
+                    performance: performance.get(&identity).copied(),
+                    location: self.locations.get(&identity).cloned().unwrap_or_default(),
+                    // if for some reason node is invalid, treat it as 0 stake/weight
+                    total_stake: bond.total_bond().unwrap_or_default(),
+                    owner: bond.owner.into_string(),
+                    layer: bond.layer,
+                    identity,
+                }
+            })
+            .collect())
+    }
+
+    /// Determines the new rewarded set, where the first `active_set_size` nodes
+    /// are going to form the active set.
+    pub(crate) async fn determine_rewarded_set(
+        &self,
+        mixnodes: Vec<MixNodeBond>,
+        nodes_to_select: u32,
+        end_ts_secs: i64,
+    ) -> Result<Vec<IdentityKey>, ValidatorApiStorageError> {
+        let candidates = self.candidates(mixnodes, end_ts_secs).await?;
+
+        Ok(self
+            .policy
+            .select(&candidates, nodes_to_select as usize, &mut OsRng)
+            .into_iter()
+            .map(|index| candidates[index].identity.clone())
+            .collect())
+    }
+
+    /// Simulates the selection of the rewarded set for the next epoch. As estimating the selection
+    /// probabilities is rather expensive, the result is reused for as long as the `version`
+    /// of the underlying data doesn't change.
+    pub(crate) async fn dry_run(
+        &self,
+        version: String,
+        mixnodes: Vec<MixNodeBond>,
+        rewarded_set_size: u32,
+        active_set_size: u32,
+        next_epoch: Option<Interval>,
+        end_ts_secs: i64,
+    ) -> Result<RewardedSetDryRun, ValidatorApiStorageError> {
+        // the lock is held for the entire simulation so that concurrent requests would just
+        // wait for its result rather than repeat it
+        let mut last_dry_run = self.last_dry_run.lock().await;
+        if let Some(last_dry_run) = &*last_dry_run {
+            if last_dry_run.version == version {
+                return Ok(last_dry_run.result.clone());
+            }
+        }
+
+        let candidates = self.candidates(mixnodes, end_ts_secs).await?;
+        let policy = Arc::clone(&self.policy);
+        let samples = self.dry_run_samples;
+
+        // the simulation is purely cpu-bound, so don't block the async runtime while it's running
+        let (rewarded_set, nodes) = task::spawn_blocking(move || {
+            simulate_selection(
+                &policy,
+                candidates,
+                rewarded_set_size as usize,
+                active_set_size as usize,
+                samples,
+            )
+        })
+        .await
+        .expect("the rewarded set selection simulation has panicked");
+
+        let result = RewardedSetDryRun {
+            next_epoch,
+            rewarded_set_size,
+            active_set_size,
+            samples,
+            rewarded_set,
+            nodes,
+        };
+        *last_dry_run = Some(CachedDryRun {
+            version,
+            result: result.clone(),
+        });
+
+        Ok(result)
+    }
+}
+
+// chooses an example rewarded set and estimates the chances of each candidate of getting into it
+fn simulate_selection(
+    policy: &SelectionPolicy,
+    candidates: Vec<SelectionCandidate>,
+    rewarded_set_size: usize,
+    active_set_size: usize,
+    samples: usize,
+) -> (Vec<IdentityKey>, Vec<NodeSelectionChance>) {
+    let mut rng = OsRng;
+
+    let rewarded_set = policy
+        .select(&candidates, rewarded_set_size, &mut rng)
+        .into_iter()
+        .map(|index| candidates[index].identity.clone())
+        .collect();
+
+    let probabilities = policy.estimate_probabilities(
+        &candidates,
+        rewarded_set_size,
+        active_set_size,
+        samples,
+        &mut rng,
+    );
+
+    let mut nodes = candidates
+        .into_iter()
+        .zip(probabilities)
+        .map(
+            |(candidate, (rewarded_probability, active_probability))| NodeSelectionChance {
+                weight: policy.weight(&candidate).to_string(),
+                total_stake: candidate.total_stake.to_string(),
+                identity: candidate.identity,
+                owner: candidate.owner,
+                layer: candidate.layer,
+                performance: candidate.performance,
+                rewarded_probability,
+                active_probability,
+            },
+        )
+        .collect::<Vec<_>>();
+    nodes.sort_by(|a, b| {
+        b.rewarded_probability
+            .partial_cmp(&a.rewarded_probability)
+            .unwrap_or(Ordering::Equal)
+    });
+
+    (rewarded_set, nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn candidate(
+        identity: &str,
+        owner: &str,
+        layer: Layer,
+        total_stake: u128,
+    ) -> SelectionCandidate {
+        SelectionCandidate {
+            identity: identity.to_string(),
+            owner: owner.to_string(),
+            layer,
+            total_stake,
+            performance: None,
+            location: NodeLocation::default(),
+        }
+    }
+
+    fn policy() -> SelectionPolicy {
+        SelectionPolicy {
+            weighting: RewardedSetWeighting::Stake,
+            max_per_operator: None,
+            max_per_country: None,
+            max_per_asn: None,
+            balance_layers: false,
+        }
+    }
+
+    #[test]
+    fn weight_tree_finds_correct_nodes() {
+        let mut tree = WeightTree::new(&[5, 0, 3, 2]);
+        assert_eq!(tree.total(), 10);
+        assert_eq!(tree.find(0), 0);
+        assert_eq!(tree.find(4), 0);
+        assert_eq!(tree.find(5), 2);
+        assert_eq!(tree.find(7), 2);
+        assert_eq!(tree.find(8), 3);
+        assert_eq!(tree.find(9), 3);
+
+        tree.remove(0);
+        assert_eq!(tree.total(), 5);
+        assert_eq!(tree.find(0), 2);
+        assert_eq!(tree.find(3), 3);
+    }
+
+    #[test]
+    fn nodes_without_stake_are_never_selected() {
+        let candidates = vec![
+            candidate("a", "owner1", Layer::One, 100),
+            candidate("b", "owner2", Layer::Two, 0),
+            candidate("c", "owner3", Layer::Three, 100),
+        ];
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let mut selected = policy().select(&candidates, 3, &mut rng);
+        selected.sort_unstable();
+        assert_eq!(selected, vec![0, 2]);
+    }
+
+    #[test]
+    fn operator_limit_is_respected() {
+        let candidates = vec![
+            candidate("a", "owner1", Layer::One, 1000),
+            candidate("b", "owner1", Layer::Two, 1000),
+            candidate("c", "owner1", Layer::Three, 1000),
+            candidate("d", "owner2", Layer::One, 1),
+            candidate("e", "owner3", Layer::Two, 1),
+        ];
+        let policy = SelectionPolicy {
+            max_per_operator: Some(1),
+            ..policy()
+        };
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..100 {
+            let selected = policy.select(&candidates, 4, &mut rng);
+            assert_eq!(selected.len(), 3);
+            assert_eq!(selected.iter().filter(|&&node| node < 3).count(), 1);
+        }
+    }
+
+    #[test]
+    fn layers_are_balanced_when_possible() {
+        let mut candidates = (0..10)
+            .map(|i| {
+                candidate(
+                    &format!("big{}", i),
+                    &format!("owner{}", i),
+                    Layer::One,
+                    1000,
+                )
+            })
+            .collect::<Vec<_>>();
+        candidates.push(candidate("l2", "owner-l2", Layer::Two, 1));
+        candidates.push(candidate("l3a", "owner-l3a", Layer::Three, 1));
+        candidates.push(candidate("l3b", "owner-l3b", Layer::Three, 1));
+        let policy = SelectionPolicy {
+            balance_layers: true,
+            ..policy()
+        };
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let selected = policy.select(&candidates, 6, &mut rng);
+        assert_eq!(selected.len(), 6);
+        // layer two only has a single node so the remaining spot gets filled by layer one
+        let layer_one = selected
+            .iter()
+            .filter(|&&node| candidates[node].layer == Layer::One)
+            .count();
+        assert_eq!(layer_one, 3);
+        for node in 10..13 {
+            assert!(selected.contains(&node));
+        }
+    }
+
+    #[test]
+    fn performance_affects_weights() {
+        let mut good = candidate("a", "owner1", Layer::One, 100);
+        good.performance = Some(100);
+        let mut bad = candidate("b", "owner2", Layer::One, 100);
+        bad.performance = Some(0);
+        let unknown = candidate("c", "owner3", Layer::One, 100);
+
+        let policy = SelectionPolicy {
+            weighting: RewardedSetWeighting::Performance,
+            ..policy()
+        };
+        assert_eq!(policy.weight(&good), 10000);
+        assert_eq!(policy.weight(&bad), 0);
+        assert_eq!(policy.weight(&unknown), 10000);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let probabilities =
+            policy.estimate_probabilities(&[good, bad, unknown], 2, 1, 100, &mut rng);
+        assert_eq!(probabilities[0].0, 1.0);
+        assert_eq!(probabilities[1], (0.0, 0.0));
+        assert_eq!(probabilities[2].0, 1.0);
+        assert_eq!(probabilities[0].1 + probabilities[2].1, 1.0);
+    }
+}
@@ -0,0 +1,323 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::rewarded_set_updater::models::{
+    EpochRewardingSnapshot, MixnodeRewardingSnapshot, RewardingSimulationRequest,
+    RewardingSimulationResponse, SimulatedDelegatorRewards, SimulatedNodeRewards,
+};
+use mixnet_contract_common::reward_params::{EpochRewardParams, NodeRewardParams, RewardParams};
+use mixnet_contract_common::{IdentityKey, MixNodeBond};
+use std::collections::BTreeMap;
+
+struct NodeRewards {
+    operator: u128,
+    delegators: u128,
+}
+
+fn difference(recorded: u128, simulated: u128) -> i128 {
+    simulated as i128 - recorded as i128
+}
+
+impl RewardingSimulationRequest {
+    fn epoch_reward_params(&self, recorded: &EpochRewardParams) -> EpochRewardParams {
+        EpochRewardParams::new(
+            self.epoch_reward_pool
+                .map(u128::from)
+                .unwrap_or_else(|| recorded.epoch_reward_pool()),
+            self.rewarded_set_size
+                .map(u128::from)
+                .unwrap_or_else(|| recorded.rewarded_set_size()),
+            self.active_set_size
+                .map(u128::from)
+                .unwrap_or_else(|| recorded.active_set_size()),
+            self.circulating_supply
+                .map(u128::from)
+                .unwrap_or_else(|| recorded.circulating_supply()),
+            self.sybil_resistance_percent
+                .unwrap_or_else(|| recorded.sybil_resistance_percent()),
+            self.active_set_work_factor
+                .unwrap_or_else(|| recorded.active_set_work_factor()),
+        )
+    }
+
+    fn bond(&self, recorded: &MixNodeBond) -> MixNodeBond {
+        let mut bond = recorded.clone();
+        if let Some(profit_margin_percent) = self.profit_margin_percent {
+            bond.mix_node.profit_margin_percent = profit_margin_percent;
+        }
+        bond
+    }
+
+    fn node_reward_params(&self, mixnode: &MixnodeRewardingSnapshot) -> NodeRewardParams {
+        NodeRewardParams::new(
+            0,
+            self.uptime.unwrap_or(mixnode.uptime) as u128,
+            mixnode.in_active_set,
+        )
+    }
+}
+
+// uses exactly the same calculations as the mixnet contract
+fn node_rewards(bond: &MixNodeBond, params: &RewardParams) -> NodeRewards {
+    let total = bond
+        .reward(params)
+        .reward()
+        .checked_to_num::<u128>()
+        .unwrap_or_default();
+    let operator = bond.operator_reward(params);
+
+    NodeRewards {
+        operator,
+        delegators: total.saturating_sub(operator),
+    }
+}
+
+/// Replays the rewarding of the provided epochs using both the recorded parameters
+/// and the ones modified according to the request.
+pub(crate) fn simulate_rewarding(
+    request: &RewardingSimulationRequest,
+    snapshots: &[EpochRewardingSnapshot],
+) -> RewardingSimulationResponse {
+    let mut nodes: BTreeMap<IdentityKey, SimulatedNodeRewards> = BTreeMap::new();
+    let mut delegators: BTreeMap<(String, IdentityKey), SimulatedDelegatorRewards> =
+        BTreeMap::new();
+
+    for snapshot in snapshots {
+        let simulated_epoch_params = request.epoch_reward_params(&snapshot.epoch_reward_params);
+
+        let mut epoch_params = BTreeMap::new();
+        for mixnode in &snapshot.mixnodes {
+            let identity = mixnode.bond.mix_node.identity_key.clone();
+            if let Some(requested) = &request.identity {
+                if requested != &identity {
+                    continue;
+                }
+            }
+
+            let recorded_params = RewardParams::new(
+                snapshot.epoch_reward_params,
+                NodeRewardParams::new(0, mixnode.uptime as u128, mixnode.in_active_set),
+            );
+            let simulated_params =
+                RewardParams::new(simulated_epoch_params, request.node_reward_params(mixnode));
+            let simulated_bond = request.bond(&mixnode.bond);
+
+            let recorded = node_rewards(&mixnode.bond, &recorded_params);
+            let simulated = node_rewards(&simulated_bond, &simulated_params);
+
+            let entry = nodes
+                .entry(identity.clone())
+                .or_insert_with(|| SimulatedNodeRewards {
+                    identity: identity.clone(),
+                    owner: mixnode.bond.owner.to_string(),
+                    ..Default::default()
+                });
+            entry.epochs += 1;
+            entry.recorded_operator_reward += recorded.operator;
+            entry.simulated_operator_reward += simulated.operator;
+            entry.recorded_delegators_reward += recorded.delegators;
+            entry.simulated_delegators_reward += simulated.delegators;
+
+            epoch_params.insert(
+                identity,
+                (mixnode, recorded_params, simulated_bond, simulated_params),
+            );
+        }
+
+        for delegation in &snapshot.delegations {
+            if let Some(requested) = &request.delegator {
+                if requested != &delegation.delegator {
+                    continue;
+                }
+            }
+            // the node might not have been rewarded (or was filtered out)
+            let (mixnode, recorded_params, simulated_bond, simulated_params) =
+                match epoch_params.get(&delegation.mix_identity) {
+                    Some(params) => params,
+                    None => continue,
+                };
+
+            let recorded = mixnode
+                .bond
+                .reward_delegation(delegation.amount.into(), recorded_params);
+            let simulated =
+                simulated_bond.reward_delegation(delegation.amount.into(), simulated_params);
+
+            let entry = delegators
+                .entry((
+                    delegation.delegator.clone(),
+                    delegation.mix_identity.clone(),
+                ))
+                .or_insert_with(|| SimulatedDelegatorRewards {
+                    delegator: delegation.delegator.clone(),
+                    mix_identity: delegation.mix_identity.clone(),
+                    ..Default::default()
+                });
+            entry.epochs += 1;
+            entry.recorded_reward += recorded;
+            entry.simulated_reward += simulated;
+        }
+    }
+
+    let nodes = nodes
+        .into_values()
+        .map(|mut node| {
+            node.operator_reward_difference = difference(
+                node.recorded_operator_reward,
+                node.simulated_operator_reward,
+            );
+            node.delegators_reward_difference = difference(
+                node.recorded_delegators_reward,
+                node.simulated_delegators_reward,
+            );
+            node
+        })
+        .collect();
+
+    let delegators = delegators
+        .into_values()
+        .map(|mut delegator| {
+            delegator.reward_difference =
+                difference(delegator.recorded_reward, delegator.simulated_reward);
+            delegator
+        })
+        .collect();
+
+    RewardingSimulationResponse {
+        simulated_epochs: snapshots.iter().map(|snapshot| snapshot.epoch_id).collect(),
+        nodes,
+        delegators,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rewarded_set_updater::models::DelegationSnapshot;
+    use mixnet_contract_common::{Addr, Coin, Layer, MixNode};
+
+    fn bond(identity: &str, profit_margin_percent: u8) -> MixNodeBond {
+        MixNodeBond {
+            pledge_amount: Coin::new(10_000_000_000, "unym"),
+            total_delegation: Coin::new(5_000_000_000, "unym"),
+            owner: Addr::unchecked(format!("owner-{}", identity)),
+            layer: Layer::One,
+            block_height: 1,
+            mix_node: MixNode {
+                host: "1.1.1.1".to_string(),
+                mix_port: 1789,
+                verloc_port: 1790,
+                http_api_port: 8000,
+                sphinx_key: "sphinxkey".to_string(),
+                identity_key: identity.to_string(),
+                version: "0.12.1".to_string(),
+                profit_margin_percent,
+                operating_cost: None,
+            },
+            proxy: None,
+            accumulated_rewards: None,
+            family: None,
+            jailed: None,
+        }
+    }
+
+    fn snapshot(epoch_id: u32) -> EpochRewardingSnapshot {
+        EpochRewardingSnapshot {
+            epoch_id,
+            epoch_reward_params: EpochRewardParams::new(
+                5_000_000_000,
+                720,
+                240,
+                250_000_000_000_000,
+                30,
+                10,
+            ),
+            mixnodes: vec![
+                MixnodeRewardingSnapshot {
+                    bond: bond("node1", 10),
+                    uptime: 100,
+                    in_active_set: true,
+                },
+                MixnodeRewardingSnapshot {
+                    bond: bond("node2", 10),
+                    uptime: 90,
+                    in_active_set: false,
+                },
+            ],
+            delegations: vec![
+                DelegationSnapshot {
+                    mix_identity: "node1".to_string(),
+                    delegator: "delegator".to_string(),
+                    amount: 5_000_000_000,
+                },
+                DelegationSnapshot {
+                    mix_identity: "unrewarded".to_string(),
+                    delegator: "delegator".to_string(),
+                    amount: 5_000_000_000,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn simulation_without_overrides_matches_recorded_rewards() {
+        let response = simulate_rewarding(
+            &RewardingSimulationRequest::default(),
+            &[snapshot(1), snapshot(2)],
+        );
+
+        assert_eq!(response.simulated_epochs, vec![1, 2]);
+        assert_eq!(response.nodes.len(), 2);
+        for node in &response.nodes {
+            assert_eq!(node.epochs, 2);
+            assert!(node.recorded_operator_reward > 0);
+            assert_eq!(
+                node.recorded_operator_reward,
+                node.simulated_operator_reward
+            );
+            assert_eq!(node.operator_reward_difference, 0);
+            assert_eq!(node.delegators_reward_difference, 0);
+        }
+
+        // delegations towards nodes that were not rewarded are ignored
+        assert_eq!(response.delegators.len(), 1);
+        let delegator = &response.delegators[0];
+        assert_eq!(delegator.mix_identity, "node1");
+        assert_eq!(delegator.epochs, 2);
+        assert!(delegator.recorded_reward > 0);
+        assert_eq!(delegator.reward_difference, 0);
+    }
+
+    #[test]
+    fn lower_profit_margin_benefits_delegators() {
+        let request = RewardingSimulationRequest {
+            profit_margin_percent: Some(0),
+            identity: Some("node1".to_string()),
+            ..Default::default()
+        };
+        let response = simulate_rewarding(&request, &[snapshot(1)]);
+
+        assert_eq!(response.nodes.len(), 1);
+        let node = &response.nodes[0];
+        assert_eq!(node.identity, "node1");
+        assert!(node.operator_reward_difference < 0);
+        assert!(node.delegators_reward_difference > 0);
+
+        assert_eq!(response.delegators.len(), 1);
+        assert!(response.delegators[0].reward_difference > 0);
+    }
+
+    #[test]
+    fn lower_uptime_reduces_rewards() {
+        let request = RewardingSimulationRequest {
+            uptime: Some(50),
+            ..Default::default()
+        };
+        let response = simulate_rewarding(&request, &[snapshot(1)]);
+
+        for node in &response.nodes {
+            assert!(node.simulated_operator_reward < node.recorded_operator_reward);
+        }
+        assert!(response.delegators[0].reward_difference < 0);
+    }
+}
@@ -0,0 +1,452 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::network_monitor::monitor::summary_producer::NodeResult;
+use crate::node_status_api::models::{
+    HistoricalUptime, MonitorRunEntry, NodeStatusEntry, RewardingReportEntry,
+};
+use crate::storage::models::{
+    AvgReliability, EpochSnapshot, EpochSnapshotDelegation, EpochSnapshotMixnode, NodeLatency,
+    NodeStatus, RewardingReport, TestingRoute,
+};
+use async_trait::async_trait;
+
+/// Database backend holding all the network monitor and rewarding data.
+#[async_trait]
+pub(crate) trait StorageBackend: Send + Sync {
+    /// Gets average reliability of all mixnodes in the 24h preceding the specified unix timestamp.
+    async fn get_all_avg_mix_reliability_in_last_24hr(
+        &self,
+        end_ts_secs: i64,
+    ) -> Result<Vec<AvgReliability>, sqlx::Error> {
+        let start_ts_secs = end_ts_secs - 86400;
+        self.get_all_avg_mix_reliability_in_interval(start_ts_secs, end_ts_secs)
+            .await
+    }
+
+    /// Gets average reliability of all gateways in the 24h preceding the specified unix timestamp.
+    async fn get_all_avg_gateway_reliability_in_last_24hr(
+        &self,
+        end_ts_secs: i64,
+    ) -> Result<Vec<AvgReliability>, sqlx::Error> {
+        let start_ts_secs = end_ts_secs - 86400;
+        self.get_all_avg_gateway_reliability_in_interval(start_ts_secs, end_ts_secs)
+            .await
+    }
+
+    /// Gets average reliability of all mixnodes within the specified time interval.
+    async fn get_all_avg_mix_reliability_in_interval(
+        &self,
+        start_ts_secs: i64,
+        end_ts_secs: i64,
+    ) -> Result<Vec<AvgReliability>, sqlx::Error>;
+
+    /// Gets average reliability of all gateways within the specified time interval.
+    async fn get_all_avg_gateway_reliability_in_interval(
+        &self,
+        start_ts_secs: i64,
+        end_ts_secs: i64,
+    ) -> Result<Vec<AvgReliability>, sqlx::Error>;
+
+    /// Tries to obtain row id of given mixnode given its identity.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity (base58-encoded public key) of the mixnode.
+    async fn get_mixnode_id(&self, identity: &str) -> Result<Option<i64>, sqlx::Error>;
+
+    /// Tries to obtain row id of given gateway given its identity
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity (base58-encoded public key) of the gateway.
+    async fn get_gateway_id(&self, identity: &str) -> Result<Option<i64>, sqlx::Error>;
+
+    /// Tries to obtain owner value of given mixnode given its identity
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity (base58-encoded public key) of the mixnode.
+    async fn get_mixnode_owner(&self, identity: &str) -> Result<Option<String>, sqlx::Error>;
+
+    /// Tries to obtain owner value of given gateway given its identity
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity (base58-encoded public key) of the gateway.
+    async fn get_gateway_owner(&self, identity: &str) -> Result<Option<String>, sqlx::Error>;
+
+    /// Gets all reliability statuses for mixnode with particular identity that were inserted
+    /// into the database after the specified unix timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity (base58-encoded public key) of the mixnode.
+    /// * `timestamp`: unix timestamp of the lower bound of the selection.
+    async fn get_mixnode_statuses_since(
+        &self,
+        identity: &str,
+        timestamp: i64,
+    ) -> Result<Vec<NodeStatus>, sqlx::Error>;
+
+    /// Gets all reliability statuses for gateway with particular identity that were inserted
+    /// into the database after the specified unix timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity (base58-encoded public key) of the gateway.
+    /// * `timestamp`: unix timestamp of the lower bound of the selection.
+    async fn get_gateway_statuses_since(
+        &self,
+        identity: &str,
+        timestamp: i64,
+    ) -> Result<Vec<NodeStatus>, sqlx::Error>;
+
+    /// Gets all inbox (stored messages delivery) reliability statuses for gateway with particular
+    /// identity that were inserted into the database after the specified unix timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity (base58-encoded public key) of the gateway.
+    /// * `timestamp`: unix timestamp of the lower bound of the selection.
+    async fn get_gateway_inbox_statuses_since(
+        &self,
+        identity: &str,
+        timestamp: i64,
+    ) -> Result<Vec<NodeStatus>, sqlx::Error>;
+
+    /// Gets all latency measurements for mixnode with particular identity that were inserted
+    /// into the database after the specified unix timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity (base58-encoded public key) of the mixnode.
+    /// * `timestamp`: unix timestamp of the lower bound of the selection.
+    async fn get_mixnode_latencies_since(
+        &self,
+        identity: &str,
+        timestamp: i64,
+    ) -> Result<Vec<NodeLatency>, sqlx::Error>;
+
+    /// Gets the historical daily uptime associated with the particular mixnode,
+    /// as rolled up into the daily aggregates.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity (base58-encoded public key) of the mixnode.
+    async fn get_mixnode_historical_uptimes(
+        &self,
+        identity: &str,
+    ) -> Result<Vec<HistoricalUptime>, sqlx::Error>;
+
+    /// Gets all latency measurements for gateway with particular identity that were inserted
+    /// into the database after the specified unix timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity (base58-encoded public key) of the gateway.
+    /// * `timestamp`: unix timestamp of the lower bound of the selection.
+    async fn get_gateway_latencies_since(
+        &self,
+        identity: &str,
+        timestamp: i64,
+    ) -> Result<Vec<NodeLatency>, sqlx::Error>;
+
+    /// Gets the historical daily uptime associated with the particular gateway,
+    /// as rolled up into the daily aggregates.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity`: identity (base58-encoded public key) of the gateway.
+    async fn get_gateway_historical_uptimes(
+        &self,
+        identity: &str,
+    ) -> Result<Vec<HistoricalUptime>, sqlx::Error>;
+
+    /// Gets average reliability of mixnode with particular id within the specified time interval.
+    async fn get_average_reliability_in_interval(
+        &self,
+        id: i64,
+        start: i64,
+        end: i64,
+    ) -> Result<Option<f32>, sqlx::Error>;
+
+    /// Tries to submit mixnode [`NodeResult`] from the network monitor to the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp`: unix timestamp indicating when the measurements took place.
+    /// * `mixnode_results`: reliability results of each node that got tested.
+    async fn submit_mixnode_statuses(
+        &self,
+        timestamp: i64,
+        mixnode_results: Vec<NodeResult>,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Tries to submit gateway [`NodeResult`] from the network monitor to the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp`: unix timestamp indicating when the measurements took place.
+    /// * `gateway_results`: reliability results of each node that got tested.
+    async fn submit_gateway_statuses(
+        &self,
+        timestamp: i64,
+        gateway_results: Vec<NodeResult>,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Tries to submit the results of the gateway inbox test from the network monitor to the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp`: unix timestamp indicating when the measurements took place.
+    /// * `inbox_results`: stored messages delivery reliability of each gateway that got tested.
+    async fn submit_gateway_inbox_statuses(
+        &self,
+        timestamp: i64,
+        inbox_results: Vec<NodeResult>,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Saves the information about which nodes were used as core nodes during this particular
+    /// network monitor test run.
+    ///
+    /// # Arguments
+    ///
+    /// * `testing_route`: test route used for this particular network monitor run.
+    async fn submit_testing_route_used(
+        &self,
+        testing_route: TestingRoute,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Get the number of times mixnode with the particular id is present in any `testing_route`
+    /// since the provided unix timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `mixnode_id`: id (as saved in the database) of the mixnode.
+    /// * `since`: unix timestamp indicating the lower bound interval of the selection.
+    async fn get_mixnode_testing_route_presence_count_since(
+        &self,
+        mixnode_id: i64,
+        since: i64,
+    ) -> Result<i32, sqlx::Error>;
+
+    /// Get the number of times gateway with the particular id is present in any `testing_route`
+    /// since the provided unix timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `gateway_id`: id (as saved in the database) of the gateway.
+    /// * `since`: unix timestamp indicating the lower bound interval of the selection.
+    async fn get_gateway_testing_route_presence_count_since(
+        &self,
+        gateway_id: i64,
+        since: i64,
+    ) -> Result<i32, sqlx::Error>;
+
+    /// Creates a database entry for a finished network monitor test run.
+    /// Returns id of the newly created entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp`: unix timestamp at which the monitor test run has occurred
+    async fn insert_monitor_run(&self, timestamp: i64) -> Result<i64, sqlx::Error>;
+
+    /// Obtains number of network monitor test runs that have occurred within the specified interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `since`: unix timestamp indicating the lower bound interval of the selection.
+    /// * `until`: unix timestamp indicating the upper bound interval of the selection.
+    async fn get_monitor_runs_count(&self, since: i64, until: i64) -> Result<i32, sqlx::Error>;
+
+    /// Obtains the unix timestamp of the start of the most recent day for which the raw statuses
+    /// have already been rolled up into the daily aggregates.
+    async fn get_last_compacted_day_start(&self) -> Result<Option<i64>, sqlx::Error>;
+
+    /// Obtains the unix timestamp of the oldest raw monitor data currently stored.
+    async fn get_earliest_raw_timestamp(&self) -> Result<Option<i64>, sqlx::Error>;
+
+    /// Rolls up all raw mixnode and gateway statuses from the specified day into the daily aggregates.
+    ///
+    /// # Arguments
+    ///
+    /// * `date`: date of the day represented in ISO 8601, i.e. YYYY-MM-DD.
+    /// * `day_start`: unix timestamp of the start of the day.
+    /// * `day_end`: unix timestamp of the end of the day (exclusive).
+    async fn compact_day(
+        &self,
+        date: &str,
+        day_start: i64,
+        day_end: i64,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Removes all raw (including inbox) statuses of mixnodes and gateways alongside all network monitor runs
+    /// and their testing routes as well as all epoch snapshots that are older than the provided timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp`: timestamp specifying the purge cutoff.
+    async fn purge_raw_data(&self, timestamp: i64) -> Result<(), sqlx::Error>;
+
+    /// Inserts new rewarding report into the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `report`: report to insert into the database
+    async fn insert_rewarding_report(&self, report: RewardingReport) -> Result<(), sqlx::Error>;
+
+    /// Inserts new snapshot of the rewarded set alongside its delegations at the time of
+    /// the particular epoch getting rewarded. Returns the id of the created snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch_id`: id of the rewarded epoch.
+    /// * `timestamp`: unix timestamp of the snapshot.
+    /// * `epoch_reward_params`: json encoded reward parameters of the epoch.
+    /// * `mixnodes`: the rewarded mixnodes.
+    /// * `delegations`: delegations towards the rewarded mixnodes.
+    async fn insert_epoch_snapshot(
+        &self,
+        epoch_id: i64,
+        timestamp: i64,
+        epoch_reward_params: &str,
+        mixnodes: Vec<EpochSnapshotMixnode>,
+        delegations: Vec<EpochSnapshotDelegation>,
+    ) -> Result<i64, sqlx::Error>;
+
+    /// Gets all snapshots of epochs with ids within the specified (inclusive) range.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_epoch`: the id of the first epoch.
+    /// * `to_epoch`: the id of the last epoch.
+    async fn get_epoch_snapshots(
+        &self,
+        from_epoch: i64,
+        to_epoch: i64,
+    ) -> Result<Vec<EpochSnapshot>, sqlx::Error>;
+
+    /// Gets all mixnodes included in the particular epoch snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch_snapshot_id`: id of the snapshot.
+    async fn get_epoch_snapshot_mixnodes(
+        &self,
+        epoch_snapshot_id: i64,
+    ) -> Result<Vec<EpochSnapshotMixnode>, sqlx::Error>;
+
+    /// Gets all delegations included in the particular epoch snapshot,
+    /// optionally only of the particular delegator.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch_snapshot_id`: id of the snapshot.
+    /// * `delegator`: optional address of the delegator.
+    async fn get_epoch_snapshot_delegations(
+        &self,
+        epoch_snapshot_id: i64,
+        delegator: Option<&str>,
+    ) -> Result<Vec<EpochSnapshotDelegation>, sqlx::Error>;
+
+    /// Gets a page of raw reliability statuses of mixnodes inserted within the specified
+    /// time interval, optionally only of the mixnode with particular identity and/or owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `since`: unix timestamp indicating the lower bound interval of the selection.
+    /// * `until`: unix timestamp indicating the upper bound interval of the selection.
+    /// * `identity`: optional identity (base58-encoded public key) of the mixnode.
+    /// * `owner`: optional address of the owner of the mixnode.
+    /// * `limit`: maximum number of statuses to return.
+    /// * `offset`: number of statuses to skip.
+    #[allow(clippy::too_many_arguments)]
+    async fn get_mixnode_status_entries(
+        &self,
+        since: i64,
+        until: i64,
+        identity: Option<&str>,
+        owner: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<NodeStatusEntry>, sqlx::Error>;
+
+    /// Gets a page of raw reliability statuses of gateways inserted within the specified
+    /// time interval, optionally only of the gateway with particular identity and/or owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `since`: unix timestamp indicating the lower bound interval of the selection.
+    /// * `until`: unix timestamp indicating the upper bound interval of the selection.
+    /// * `identity`: optional identity (base58-encoded public key) of the gateway.
+    /// * `owner`: optional address of the owner of the gateway.
+    /// * `limit`: maximum number of statuses to return.
+    /// * `offset`: number of statuses to skip.
+    #[allow(clippy::too_many_arguments)]
+    async fn get_gateway_status_entries(
+        &self,
+        since: i64,
+        until: i64,
+        identity: Option<&str>,
+        owner: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<NodeStatusEntry>, sqlx::Error>;
+
+    /// Gets a page of network monitor runs that have occurred within the specified time interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `since`: unix timestamp indicating the lower bound interval of the selection.
+    /// * `until`: unix timestamp indicating the upper bound interval of the selection.
+    /// * `limit`: maximum number of runs to return.
+    /// * `offset`: number of runs to skip.
+    async fn get_monitor_run_entries(
+        &self,
+        since: i64,
+        until: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<MonitorRunEntry>, sqlx::Error>;
+
+    /// Gets a page of rewarding reports created within the specified time interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `since`: unix timestamp indicating the lower bound interval of the selection.
+    /// * `until`: unix timestamp indicating the upper bound interval of the selection.
+    /// * `limit`: maximum number of reports to return.
+    /// * `offset`: number of reports to skip.
+    async fn get_rewarding_report_entries(
+        &self,
+        since: i64,
+        until: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<RewardingReportEntry>, sqlx::Error>;
+
+    /// Creates new encrypted blinded signature response entry for a given deposit tx hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx_hash`: hash of the deposit transaction.
+    /// * `blinded_signature_response`: the encrypted blinded signature response.
+    #[cfg(feature = "coconut")]
+    async fn insert_blinded_signature_response(
+        &self,
+        tx_hash: &str,
+        blinded_signature_response: &str,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Tries to obtain encrypted blinded signature response for a given transaction hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx_hash`: transaction hash of the deposit.
+    #[cfg(feature = "coconut")]
+    async fn get_blinded_signature_response(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Option<String>, sqlx::Error>;
+}
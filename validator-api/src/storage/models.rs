@@ -1,6 +1,21 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+pub struct AvgReliability {
+    pub(crate) identity: String,
+    pub(crate) value: Option<f32>,
+}
+
+impl AvgReliability {
+    pub fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value.unwrap_or_default()
+    }
+}
+
 // Internally used struct to catch results from the database to calculate uptimes for given mixnode/gateway
 pub(crate) struct NodeStatus {
     pub timestamp: Option<i64>,
@@ -17,11 +32,16 @@ impl NodeStatus {
     }
 }
 
-// Internally used struct to catch results from the database to find active mixnodes/gateways
-pub(crate) struct ActiveNode {
-    pub(crate) id: i64,
-    pub(crate) identity: String,
-    pub(crate) owner: String,
+// Internally used struct to catch results from the database to calculate latencies for given mixnode/gateway
+pub(crate) struct NodeLatency {
+    pub timestamp: Option<i64>,
+    pub latency: Option<u32>,
+}
+
+impl NodeLatency {
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp.unwrap_or_default()
+    }
 }
 
 pub(crate) struct TestingRoute {
@@ -39,4 +59,30 @@ pub(crate) struct RewardingReport {
     pub(crate) eligible_mixnodes: i64,
 
     pub(crate) possibly_unrewarded_mixnodes: i64,
+
+    // unix timestamp of when the report got created
+    pub(crate) timestamp: i64,
+}
+
+pub(crate) struct EpochSnapshot {
+    pub(crate) id: i64,
+    pub(crate) epoch_id: i64,
+    // unix timestamp of when the snapshot got created
+    pub(crate) timestamp: i64,
+    // json encoded `EpochRewardParams`
+    pub(crate) epoch_reward_params: String,
+}
+
+pub(crate) struct EpochSnapshotMixnode {
+    pub(crate) identity: String,
+    // json encoded `MixNodeBond`
+    pub(crate) bond: String,
+    pub(crate) uptime: u8,
+    pub(crate) in_active_set: bool,
+}
+
+pub(crate) struct EpochSnapshotDelegation {
+    pub(crate) mix_identity: String,
+    pub(crate) delegator: String,
+    pub(crate) amount: String,
 }
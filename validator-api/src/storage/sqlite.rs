@@ -0,0 +1,1029 @@
+// Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::network_monitor::monitor::summary_producer::NodeResult;
+use crate::node_status_api::models::{
+    HistoricalUptime, MonitorRunEntry, NodeStatusEntry, RewardingReportEntry, Uptime,
+};
+use crate::storage::backend::StorageBackend;
+use crate::storage::models::{
+    AvgReliability, EpochSnapshot, EpochSnapshotDelegation, EpochSnapshotMixnode, NodeLatency,
+    NodeStatus, RewardingReport, TestingRoute,
+};
+use async_trait::async_trait;
+use sqlx::ConnectOptions;
+use std::convert::TryFrom;
+use std::path::Path;
+
+#[derive(Clone)]
+pub(crate) struct SqliteStorage {
+    connection_pool: sqlx::SqlitePool,
+}
+
+impl SqliteStorage {
+    /// Connects to the sqlite database at the provided path, creating it if it doesn't exist,
+    /// and runs all the migrations on it.
+    ///
+    /// # Arguments
+    ///
+    /// * `database_path`: path to the database.
+    pub(crate) async fn init<P: AsRef<Path>>(database_path: P) -> Result<Self, sqlx::Error> {
+        // TODO: we can inject here more stuff based on our validator-api global config
+        // struct. Maybe different pool size or timeout intervals?
+        let mut opts = sqlx::sqlite::SqliteConnectOptions::new()
+            .filename(database_path)
+            .create_if_missing(true);
+
+        // TODO: do we want auto_vacuum ?
+
+        opts.disable_statement_logging();
+
+        let connection_pool = sqlx::SqlitePool::connect_with(opts).await?;
+        sqlx::migrate!("./migrations").run(&connection_pool).await?;
+
+        Ok(SqliteStorage { connection_pool })
+    }
+}
+
+// all SQL goes here
+#[async_trait]
+impl StorageBackend for SqliteStorage {
+    async fn get_all_avg_mix_reliability_in_interval(
+        &self,
+        start_ts_secs: i64,
+        end_ts_secs: i64,
+    ) -> Result<Vec<AvgReliability>, sqlx::Error> {
+        let result = sqlx::query_as!(
+            AvgReliability,
+            r#"
+            SELECT
+                d.identity as "identity: String",
+                AVG(s.reliability) as "value: f32"
+            FROM
+                mixnode_details d
+            JOIN
+                mixnode_status s on d.id = s.mixnode_details_id
+            WHERE
+                timestamp >= ? AND
+                timestamp <= ?
+            GROUP BY 1
+            "#,
+            start_ts_secs,
+            end_ts_secs
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(result)
+    }
+
+    async fn get_all_avg_gateway_reliability_in_interval(
+        &self,
+        start_ts_secs: i64,
+        end_ts_secs: i64,
+    ) -> Result<Vec<AvgReliability>, sqlx::Error> {
+        let result = sqlx::query_as!(
+            AvgReliability,
+            r#"
+            SELECT
+                d.identity as "identity: String",
+                AVG(reliability) as "value: f32"
+            FROM
+                gateway_details d
+            JOIN
+                gateway_status s on d.id = s.gateway_details_id
+            WHERE
+                timestamp >= ? AND
+                timestamp <= ?
+            GROUP BY 1
+            "#,
+            start_ts_secs,
+            end_ts_secs
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(result)
+    }
+
+    async fn get_mixnode_id(&self, identity: &str) -> Result<Option<i64>, sqlx::Error> {
+        let id = sqlx::query!(
+            "SELECT id FROM mixnode_details WHERE identity = ?",
+            identity
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(|row| row.id);
+
+        Ok(id)
+    }
+
+    async fn get_gateway_id(&self, identity: &str) -> Result<Option<i64>, sqlx::Error> {
+        let id = sqlx::query!(
+            "SELECT id FROM gateway_details WHERE identity = ?",
+            identity
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(|row| row.id);
+
+        Ok(id)
+    }
+
+    async fn get_mixnode_owner(&self, identity: &str) -> Result<Option<String>, sqlx::Error> {
+        let owner = sqlx::query!(
+            "SELECT owner FROM mixnode_details WHERE identity = ?",
+            identity
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(|row| row.owner);
+
+        Ok(owner)
+    }
+
+    async fn get_gateway_owner(&self, identity: &str) -> Result<Option<String>, sqlx::Error> {
+        let owner = sqlx::query!(
+            "SELECT owner FROM gateway_details WHERE identity = ?",
+            identity
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(|row| row.owner);
+
+        Ok(owner)
+    }
+
+    async fn get_mixnode_statuses_since(
+        &self,
+        identity: &str,
+        timestamp: i64,
+    ) -> Result<Vec<NodeStatus>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeStatus,
+            r#"
+                SELECT timestamp, reliability as "reliability: u8"
+                    FROM mixnode_status
+                    JOIN mixnode_details
+                    ON mixnode_status.mixnode_details_id = mixnode_details.id
+                    WHERE mixnode_details.identity=? AND mixnode_status.timestamp > ?;
+            "#,
+            identity,
+            timestamp,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_gateway_statuses_since(
+        &self,
+        identity: &str,
+        timestamp: i64,
+    ) -> Result<Vec<NodeStatus>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeStatus,
+            r#"
+                SELECT timestamp, reliability as "reliability: u8"
+                    FROM gateway_status
+                    JOIN gateway_details
+                    ON gateway_status.gateway_details_id = gateway_details.id
+                    WHERE gateway_details.identity=? AND gateway_status.timestamp > ?;
+            "#,
+            identity,
+            timestamp,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_gateway_inbox_statuses_since(
+        &self,
+        identity: &str,
+        timestamp: i64,
+    ) -> Result<Vec<NodeStatus>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeStatus,
+            r#"
+                SELECT timestamp, reliability as "reliability: u8"
+                    FROM gateway_inbox_status
+                    JOIN gateway_details
+                    ON gateway_inbox_status.gateway_details_id = gateway_details.id
+                    WHERE gateway_details.identity=? AND gateway_inbox_status.timestamp > ?;
+            "#,
+            identity,
+            timestamp,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_mixnode_latencies_since(
+        &self,
+        identity: &str,
+        timestamp: i64,
+    ) -> Result<Vec<NodeLatency>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeLatency,
+            r#"
+                SELECT timestamp, latency as "latency: u32"
+                    FROM mixnode_status
+                    JOIN mixnode_details
+                    ON mixnode_status.mixnode_details_id = mixnode_details.id
+                    WHERE mixnode_details.identity=? AND mixnode_status.timestamp > ?;
+            "#,
+            identity,
+            timestamp,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_mixnode_historical_uptimes(
+        &self,
+        identity: &str,
+    ) -> Result<Vec<HistoricalUptime>, sqlx::Error> {
+        let uptimes = sqlx::query!(
+            r#"
+                SELECT date as "date!", uptime as "uptime!"
+                    FROM mixnode_daily_status
+                    JOIN mixnode_details
+                    ON mixnode_daily_status.mixnode_details_id = mixnode_details.id
+                    WHERE mixnode_details.identity = ?
+                    ORDER BY date ASC
+            "#,
+            identity
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        // filter out nodes with valid uptime (in theory all should be 100% valid since we insert them ourselves, but
+        // better safe than sorry and not use an unwrap)
+        .filter_map(|row| {
+            Uptime::try_from(row.uptime)
+                .map(|uptime| HistoricalUptime {
+                    date: row.date,
+                    uptime,
+                })
+                .ok()
+        })
+        .collect();
+
+        Ok(uptimes)
+    }
+
+    async fn get_gateway_latencies_since(
+        &self,
+        identity: &str,
+        timestamp: i64,
+    ) -> Result<Vec<NodeLatency>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeLatency,
+            r#"
+                SELECT timestamp, latency as "latency: u32"
+                    FROM gateway_status
+                    JOIN gateway_details
+                    ON gateway_status.gateway_details_id = gateway_details.id
+                    WHERE gateway_details.identity=? AND gateway_status.timestamp > ?;
+            "#,
+            identity,
+            timestamp,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_gateway_historical_uptimes(
+        &self,
+        identity: &str,
+    ) -> Result<Vec<HistoricalUptime>, sqlx::Error> {
+        let uptimes = sqlx::query!(
+            r#"
+                SELECT date as "date!", uptime as "uptime!"
+                    FROM gateway_daily_status
+                    JOIN gateway_details
+                    ON gateway_daily_status.gateway_details_id = gateway_details.id
+                    WHERE gateway_details.identity = ?
+                    ORDER BY date ASC
+            "#,
+            identity
+        )
+        .fetch_all(&self.connection_pool)
+        .await?
+        .into_iter()
+        // filter out nodes with valid uptime (in theory all should be 100% valid since we insert them ourselves, but
+        // better safe than sorry and not use an unwrap)
+        .filter_map(|row| {
+            Uptime::try_from(row.uptime)
+                .map(|uptime| HistoricalUptime {
+                    date: row.date,
+                    uptime,
+                })
+                .ok()
+        })
+        .collect();
+
+        Ok(uptimes)
+    }
+
+    async fn get_average_reliability_in_interval(
+        &self,
+        id: i64,
+        start: i64,
+        end: i64,
+    ) -> Result<Option<f32>, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            SELECT AVG(reliability) as "reliability: f32" FROM mixnode_status
+            WHERE mixnode_details_id= ? AND timestamp >= ? AND timestamp <= ?
+            "#,
+            id,
+            start,
+            end
+        )
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(result.reliability)
+    }
+
+    async fn submit_mixnode_statuses(
+        &self,
+        timestamp: i64,
+        mixnode_results: Vec<NodeResult>,
+    ) -> Result<(), sqlx::Error> {
+        // insert it all in a transaction to make sure all nodes are updated at the same time
+        // (plus it's a nice guard against new nodes)
+        let mut tx = self.connection_pool.begin().await?;
+        for mixnode_result in mixnode_results {
+            let mixnode_id = sqlx::query!(
+                r#"
+                    INSERT OR IGNORE INTO mixnode_details(identity, owner) VALUES (?, ?);
+                    SELECT id FROM mixnode_details WHERE identity = ?;
+                "#,
+                mixnode_result.identity,
+                mixnode_result.owner,
+                mixnode_result.identity,
+            )
+            .fetch_one(&mut tx)
+            .await?
+            .id;
+
+            // insert the actual status
+            sqlx::query!(
+                    r#"
+                        INSERT INTO mixnode_status (mixnode_details_id, reliability, latency, timestamp) VALUES (?, ?, ?, ?);
+                    "#,
+                    mixnode_id,
+                    mixnode_result.reliability,
+                    mixnode_result.latency,
+                    timestamp
+                )
+                .execute(&mut tx)
+                .await?;
+        }
+
+        // finally commit the transaction
+        tx.commit().await
+    }
+
+    async fn submit_gateway_statuses(
+        &self,
+        timestamp: i64,
+        gateway_results: Vec<NodeResult>,
+    ) -> Result<(), sqlx::Error> {
+        // insert it all in a transaction to make sure all nodes are updated at the same time
+        // (plus it's a nice guard against new nodes)
+        let mut tx = self.connection_pool.begin().await?;
+
+        for gateway_result in gateway_results {
+            // if gateway info doesn't exist, insert it and get its id
+
+            // same ID "problem" as described for mixnode insertion
+            let gateway_id = sqlx::query!(
+                r#"
+                    INSERT OR IGNORE INTO gateway_details(identity, owner) VALUES (?, ?);
+                    SELECT id FROM gateway_details WHERE identity = ?;
+                "#,
+                gateway_result.identity,
+                gateway_result.owner,
+                gateway_result.identity,
+            )
+            .fetch_one(&mut tx)
+            .await?
+            .id;
+
+            // insert the actual status
+            sqlx::query!(
+                    r#"
+                        INSERT INTO gateway_status (gateway_details_id, reliability, latency, timestamp) VALUES (?, ?, ?, ?);
+                    "#,
+                    gateway_id,
+                    gateway_result.reliability,
+                    gateway_result.latency,
+                    timestamp
+                )
+                .execute(&mut tx)
+                .await?;
+        }
+
+        // finally commit the transaction
+        tx.commit().await
+    }
+
+    async fn submit_gateway_inbox_statuses(
+        &self,
+        timestamp: i64,
+        inbox_results: Vec<NodeResult>,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.connection_pool.begin().await?;
+
+        for inbox_result in inbox_results {
+            let gateway_id = sqlx::query!(
+                r#"
+                    INSERT OR IGNORE INTO gateway_details(identity, owner) VALUES (?, ?);
+                    SELECT id FROM gateway_details WHERE identity = ?;
+                "#,
+                inbox_result.identity,
+                inbox_result.owner,
+                inbox_result.identity,
+            )
+            .fetch_one(&mut tx)
+            .await?
+            .id;
+
+            sqlx::query!(
+                "INSERT INTO gateway_inbox_status (gateway_details_id, reliability, timestamp) VALUES (?, ?, ?)",
+                gateway_id,
+                inbox_result.reliability,
+                timestamp
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+
+    async fn submit_testing_route_used(
+        &self,
+        testing_route: TestingRoute,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO testing_route 
+                (gateway_id, layer1_mix_id, layer2_mix_id, layer3_mix_id, monitor_run_id) 
+                VALUES (?, ?, ?, ?, ?);
+            "#,
+            testing_route.gateway_id,
+            testing_route.layer1_mix_id,
+            testing_route.layer2_mix_id,
+            testing_route.layer3_mix_id,
+            testing_route.monitor_run_id,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_mixnode_testing_route_presence_count_since(
+        &self,
+        mixnode_id: i64,
+        since: i64,
+    ) -> Result<i32, sqlx::Error> {
+        let count = sqlx::query!(
+            r#"
+                SELECT COUNT(*) as count FROM
+                (
+                    SELECT monitor_run_id 
+                    FROM testing_route 
+                    WHERE testing_route.layer1_mix_id = ? OR testing_route.layer2_mix_id = ? OR testing_route.layer3_mix_id = ?
+                ) testing_route
+                JOIN 
+                (
+                    SELECT id 
+                    FROM monitor_run 
+                    WHERE monitor_run.timestamp > ?
+                ) monitor_run
+                ON monitor_run.id = testing_route.monitor_run_id;
+            "#,
+            mixnode_id,
+            mixnode_id,
+            mixnode_id,
+            since,
+        ).fetch_one(&self.connection_pool)
+            .await?
+            .count;
+
+        Ok(count)
+    }
+
+    async fn get_gateway_testing_route_presence_count_since(
+        &self,
+        gateway_id: i64,
+        since: i64,
+    ) -> Result<i32, sqlx::Error> {
+        let count = sqlx::query!(
+            r#"
+                SELECT COUNT(*) as count FROM
+                (
+                    SELECT monitor_run_id 
+                    FROM testing_route 
+                    WHERE testing_route.gateway_id = ?
+                ) testing_route
+                JOIN 
+                (
+                    SELECT id 
+                    FROM monitor_run 
+                    WHERE monitor_run.timestamp > ?
+                ) monitor_run
+                ON monitor_run.id = testing_route.monitor_run_id;
+            "#,
+            gateway_id,
+            since,
+        )
+        .fetch_one(&self.connection_pool)
+        .await?
+        .count;
+
+        Ok(count)
+    }
+
+    async fn insert_monitor_run(&self, timestamp: i64) -> Result<i64, sqlx::Error> {
+        let res = sqlx::query!("INSERT INTO monitor_run(timestamp) VALUES (?)", timestamp)
+            .execute(&self.connection_pool)
+            .await?;
+        Ok(res.last_insert_rowid())
+    }
+
+    async fn get_monitor_runs_count(&self, since: i64, until: i64) -> Result<i32, sqlx::Error> {
+        let count = sqlx::query!(
+            "SELECT COUNT(*) as count FROM monitor_run WHERE timestamp > ? AND timestamp < ?",
+            since,
+            until,
+        )
+        .fetch_one(&self.connection_pool)
+        .await?
+        .count;
+        Ok(count)
+    }
+
+    async fn get_last_compacted_day_start(&self) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query!(r#"SELECT MAX(day_start) as "day_start?: i64" FROM compacted_day"#)
+            .fetch_one(&self.connection_pool)
+            .await
+            .map(|result| result.day_start)
+    }
+
+    async fn get_earliest_raw_timestamp(&self) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query!(
+            r#"
+                SELECT MIN(timestamp) as "timestamp?: i64" FROM (
+                    SELECT MIN(timestamp) as timestamp FROM monitor_run
+                    UNION ALL
+                    SELECT MIN(timestamp) as timestamp FROM mixnode_status
+                    UNION ALL
+                    SELECT MIN(timestamp) as timestamp FROM gateway_status
+                )
+            "#
+        )
+        .fetch_one(&self.connection_pool)
+        .await
+        .map(|result| result.timestamp)
+    }
+
+    async fn compact_day(
+        &self,
+        date: &str,
+        day_start: i64,
+        day_end: i64,
+    ) -> Result<(), sqlx::Error> {
+        // make sure we'd never end up with a partially compacted day
+        let mut tx = self.connection_pool.begin().await?;
+
+        let monitor_runs = sqlx::query!(
+            "SELECT COUNT(*) as count FROM monitor_run WHERE timestamp >= ? AND timestamp < ?",
+            day_start,
+            day_end,
+        )
+        .fetch_one(&mut tx)
+        .await?
+        .count;
+
+        // we average over the number of monitor runs so if a node was not online for some of them
+        // it's treated as if it had a "zero" status. However, if somehow there are more statuses
+        // than runs, bound the uptime instead.
+        sqlx::query!(
+            r#"
+                INSERT OR REPLACE INTO mixnode_daily_status (mixnode_details_id, date, uptime, average_latency, status_count)
+                SELECT mixnode_details_id, ?, MIN(SUM(reliability) / MAX(?, COUNT(*)), 100), CAST(AVG(latency) AS INTEGER), COUNT(*)
+                    FROM mixnode_status
+                    WHERE timestamp >= ? AND timestamp < ?
+                    GROUP BY mixnode_details_id
+            "#,
+            date,
+            monitor_runs,
+            day_start,
+            day_end,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                INSERT OR REPLACE INTO gateway_daily_status (gateway_details_id, date, uptime, average_latency, status_count)
+                SELECT gateway_details_id, ?, MIN(SUM(reliability) / MAX(?, COUNT(*)), 100), CAST(AVG(latency) AS INTEGER), COUNT(*)
+                    FROM gateway_status
+                    WHERE timestamp >= ? AND timestamp < ?
+                    GROUP BY gateway_details_id
+            "#,
+            date,
+            monitor_runs,
+            day_start,
+            day_end,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO compacted_day (date, day_start, monitor_runs) VALUES (?, ?, ?)",
+            date,
+            day_start,
+            monitor_runs,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await
+    }
+
+    async fn purge_raw_data(&self, timestamp: i64) -> Result<(), sqlx::Error> {
+        let mut tx = self.connection_pool.begin().await?;
+
+        sqlx::query!("DELETE FROM mixnode_status WHERE timestamp < ?", timestamp)
+            .execute(&mut tx)
+            .await?;
+        sqlx::query!("DELETE FROM gateway_status WHERE timestamp < ?", timestamp)
+            .execute(&mut tx)
+            .await?;
+        sqlx::query!(
+            "DELETE FROM gateway_inbox_status WHERE timestamp < ?",
+            timestamp
+        )
+        .execute(&mut tx)
+        .await?;
+        sqlx::query!(
+            r#"
+                DELETE FROM testing_route WHERE monitor_run_id IN (
+                    SELECT id FROM monitor_run WHERE timestamp < ?
+                )
+            "#,
+            timestamp
+        )
+        .execute(&mut tx)
+        .await?;
+        sqlx::query!("DELETE FROM monitor_run WHERE timestamp < ?", timestamp)
+            .execute(&mut tx)
+            .await?;
+        sqlx::query!(
+            r#"
+                DELETE FROM epoch_snapshot_mixnode WHERE epoch_snapshot_id IN (
+                    SELECT id FROM epoch_snapshot WHERE timestamp < ?
+                )
+            "#,
+            timestamp
+        )
+        .execute(&mut tx)
+        .await?;
+        sqlx::query!(
+            r#"
+                DELETE FROM epoch_snapshot_delegation WHERE epoch_snapshot_id IN (
+                    SELECT id FROM epoch_snapshot WHERE timestamp < ?
+                )
+            "#,
+            timestamp
+        )
+        .execute(&mut tx)
+        .await?;
+        sqlx::query!("DELETE FROM epoch_snapshot WHERE timestamp < ?", timestamp)
+            .execute(&mut tx)
+            .await?;
+
+        tx.commit().await
+    }
+
+    // /// Tries to obtain the most recent interval rewarding entry currently stored.
+    // ///
+    // /// Returns None if no data exists.
+    // pub(super) async fn get_most_recent_interval_rewarding_entry(
+    //     &self,
+    // ) -> Result<Option<IntervalRewarding>, sqlx::Error> {
+    //     sqlx::query_as!(
+    //         IntervalRewarding,
+    //         r#"
+    //             SELECT * FROM interval_rewarding
+    //             ORDER BY interval_timestamp DESC
+    //             LIMIT 1
+    //         "#,
+    //     )
+    //     .fetch_optional(&self.connection_pool)
+    //     .await
+    // }
+
+    async fn insert_rewarding_report(&self, report: RewardingReport) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO rewarding_report
+                (interval_rewarding_id, eligible_mixnodes, possibly_unrewarded_mixnodes, timestamp)
+                VALUES (?, ?, ?, ?);
+            "#,
+            report.interval_rewarding_id,
+            report.eligible_mixnodes,
+            report.possibly_unrewarded_mixnodes,
+            report.timestamp,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_epoch_snapshot(
+        &self,
+        epoch_id: i64,
+        timestamp: i64,
+        epoch_reward_params: &str,
+        mixnodes: Vec<EpochSnapshotMixnode>,
+        delegations: Vec<EpochSnapshotDelegation>,
+    ) -> Result<i64, sqlx::Error> {
+        let mut tx = self.connection_pool.begin().await?;
+
+        let epoch_snapshot_id = sqlx::query!(
+            "INSERT INTO epoch_snapshot (epoch_id, timestamp, epoch_reward_params) VALUES (?, ?, ?)",
+            epoch_id,
+            timestamp,
+            epoch_reward_params,
+        )
+        .execute(&mut tx)
+        .await?
+        .last_insert_rowid();
+
+        for mixnode in mixnodes {
+            sqlx::query!(
+                r#"
+                    INSERT INTO epoch_snapshot_mixnode
+                    (epoch_snapshot_id, identity, bond, uptime, in_active_set)
+                    VALUES (?, ?, ?, ?, ?);
+                "#,
+                epoch_snapshot_id,
+                mixnode.identity,
+                mixnode.bond,
+                mixnode.uptime,
+                mixnode.in_active_set,
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        for delegation in delegations {
+            sqlx::query!(
+                r#"
+                    INSERT INTO epoch_snapshot_delegation
+                    (epoch_snapshot_id, mix_identity, delegator, amount)
+                    VALUES (?, ?, ?, ?);
+                "#,
+                epoch_snapshot_id,
+                delegation.mix_identity,
+                delegation.delegator,
+                delegation.amount,
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(epoch_snapshot_id)
+    }
+
+    async fn get_epoch_snapshots(
+        &self,
+        from_epoch: i64,
+        to_epoch: i64,
+    ) -> Result<Vec<EpochSnapshot>, sqlx::Error> {
+        sqlx::query_as!(
+            EpochSnapshot,
+            r#"
+                SELECT id as "id!", epoch_id, timestamp, epoch_reward_params
+                FROM epoch_snapshot
+                WHERE epoch_id >= ? AND epoch_id <= ?
+                ORDER BY epoch_id, id;
+            "#,
+            from_epoch,
+            to_epoch,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_epoch_snapshot_mixnodes(
+        &self,
+        epoch_snapshot_id: i64,
+    ) -> Result<Vec<EpochSnapshotMixnode>, sqlx::Error> {
+        sqlx::query_as!(
+            EpochSnapshotMixnode,
+            r#"
+                SELECT
+                    identity,
+                    bond,
+                    uptime as "uptime: u8",
+                    in_active_set as "in_active_set: bool"
+                FROM epoch_snapshot_mixnode
+                WHERE epoch_snapshot_id = ?;
+            "#,
+            epoch_snapshot_id,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_epoch_snapshot_delegations(
+        &self,
+        epoch_snapshot_id: i64,
+        delegator: Option<&str>,
+    ) -> Result<Vec<EpochSnapshotDelegation>, sqlx::Error> {
+        sqlx::query_as!(
+            EpochSnapshotDelegation,
+            r#"
+                SELECT mix_identity, delegator, amount
+                FROM epoch_snapshot_delegation
+                WHERE epoch_snapshot_id = ? AND (? IS NULL OR delegator = ?);
+            "#,
+            epoch_snapshot_id,
+            delegator,
+            delegator,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_mixnode_status_entries(
+        &self,
+        since: i64,
+        until: i64,
+        identity: Option<&str>,
+        owner: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<NodeStatusEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeStatusEntry,
+            r#"
+                SELECT
+                    mixnode_details.identity as "identity!",
+                    mixnode_details.owner as "owner!",
+                    mixnode_status.timestamp as "timestamp!",
+                    mixnode_status.reliability as "reliability!: u8",
+                    mixnode_status.latency as "latency: u32"
+                FROM mixnode_status
+                JOIN mixnode_details
+                ON mixnode_status.mixnode_details_id = mixnode_details.id
+                WHERE mixnode_status.timestamp >= ? AND mixnode_status.timestamp < ?
+                    AND (? IS NULL OR mixnode_details.identity = ?)
+                    AND (? IS NULL OR mixnode_details.owner = ?)
+                ORDER BY mixnode_status.timestamp, mixnode_details.id
+                LIMIT ? OFFSET ?;
+            "#,
+            since,
+            until,
+            identity,
+            identity,
+            owner,
+            owner,
+            limit,
+            offset,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_gateway_status_entries(
+        &self,
+        since: i64,
+        until: i64,
+        identity: Option<&str>,
+        owner: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<NodeStatusEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            NodeStatusEntry,
+            r#"
+                SELECT
+                    gateway_details.identity as "identity!",
+                    gateway_details.owner as "owner!",
+                    gateway_status.timestamp as "timestamp!",
+                    gateway_status.reliability as "reliability!: u8",
+                    gateway_status.latency as "latency: u32"
+                FROM gateway_status
+                JOIN gateway_details
+                ON gateway_status.gateway_details_id = gateway_details.id
+                WHERE gateway_status.timestamp >= ? AND gateway_status.timestamp < ?
+                    AND (? IS NULL OR gateway_details.identity = ?)
+                    AND (? IS NULL OR gateway_details.owner = ?)
+                ORDER BY gateway_status.timestamp, gateway_details.id
+                LIMIT ? OFFSET ?;
+            "#,
+            since,
+            until,
+            identity,
+            identity,
+            owner,
+            owner,
+            limit,
+            offset,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_monitor_run_entries(
+        &self,
+        since: i64,
+        until: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<MonitorRunEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            MonitorRunEntry,
+            r#"
+                SELECT id as "id!", timestamp as "timestamp!"
+                    FROM monitor_run
+                    WHERE timestamp >= ? AND timestamp < ?
+                    ORDER BY id
+                    LIMIT ? OFFSET ?;
+            "#,
+            since,
+            until,
+            limit,
+            offset,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_rewarding_report_entries(
+        &self,
+        since: i64,
+        until: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<RewardingReportEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            RewardingReportEntry,
+            r#"
+                SELECT
+                    interval_rewarding_id as "interval_rewarding_id!",
+                    timestamp as "timestamp!",
+                    eligible_mixnodes as "eligible_mixnodes!",
+                    possibly_unrewarded_mixnodes as "possibly_unrewarded_mixnodes!"
+                FROM rewarding_report
+                WHERE timestamp >= ? AND timestamp < ?
+                ORDER BY timestamp, interval_rewarding_id
+                LIMIT ? OFFSET ?;
+            "#,
+            since,
+            until,
+            limit,
+            offset,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    #[cfg(feature = "coconut")]
+    async fn insert_blinded_signature_response(
+        &self,
+        tx_hash: &str,
+        blinded_signature_response: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO signed_deposit(tx_hash, blinded_signature_response) VALUES (?, ?)",
+            tx_hash,
+            blinded_signature_response
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    #[cfg(feature = "coconut")]
+    async fn get_blinded_signature_response(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let blinded_signature_response = sqlx::query!(
+            "SELECT blinded_signature_response FROM signed_deposit WHERE tx_hash = ?",
+            tx_hash
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(|row| row.blinded_signature_response);
+
+        Ok(blinded_signature_response)
+    }
+}
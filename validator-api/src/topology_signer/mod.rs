@@ -0,0 +1,85 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::Config;
+use crypto::asymmetric::identity;
+use log::info;
+use okapi::openapi3::OpenApi;
+use rand_07::rngs::OsRng;
+use rocket::fairing::AdHoc;
+use rocket::Route;
+use rocket_okapi::openapi_get_routes_spec;
+use rocket_okapi::settings::OpenApiSettings;
+use std::io;
+use std::sync::Arc;
+use validator_api_requests::models::{SignedTopologySnapshot, TopologySnapshot};
+
+pub(crate) mod routes;
+
+/// Signs the topology snapshots served by this validator API, so that the clients could verify
+/// they have not been tampered with in transit and, if they wish to, require several independent
+/// validator APIs to agree on the network topology.
+#[derive(Clone)]
+pub(crate) struct TopologySigner {
+    identity_keypair: Arc<identity::KeyPair>,
+}
+
+impl TopologySigner {
+    /// Loads the identity keys from the paths specified in the config or generates and stores
+    /// a fresh pair if they do not exist yet.
+    pub(crate) fn load_or_generate(config: &Config) -> io::Result<Self> {
+        let private_key_file = config.get_private_identity_key_file();
+        let public_key_file = config.get_public_identity_key_file();
+        let paths = pemstore::KeyPairPath::new(private_key_file.clone(), public_key_file.clone());
+
+        let identity_keypair = if private_key_file.exists() && public_key_file.exists() {
+            pemstore::load_keypair(&paths)?
+        } else {
+            let keypair = identity::KeyPair::new(&mut OsRng);
+            pemstore::store_keypair(&keypair, &paths)?;
+            info!(
+                "Generated new topology signing identity key: {}",
+                keypair.public_key().to_base58_string()
+            );
+            keypair
+        };
+
+        Ok(TopologySigner {
+            identity_keypair: Arc::new(identity_keypair),
+        })
+    }
+
+    pub(crate) fn identity_key(&self) -> String {
+        self.identity_keypair.public_key().to_base58_string()
+    }
+
+    pub(crate) fn sign(
+        &self,
+        snapshot: &TopologySnapshot,
+    ) -> Result<SignedTopologySnapshot, serde_json::Error> {
+        let snapshot = serde_json::to_string(snapshot)?;
+        let signature = self
+            .identity_keypair
+            .private_key()
+            .sign(snapshot.as_bytes());
+
+        Ok(SignedTopologySnapshot {
+            identity_key: self.identity_key(),
+            snapshot,
+            signature: signature.to_base58_string(),
+        })
+    }
+
+    pub(crate) fn stage(self) -> AdHoc {
+        AdHoc::on_ignite("Topology Signer Stage", |rocket| async {
+            rocket.manage(self)
+        })
+    }
+}
+
+pub(crate) fn topology_routes(settings: &OpenApiSettings) -> (Vec<Route>, OpenApi) {
+    openapi_get_routes_spec![
+        settings: routes::get_signed_topology,
+        routes::get_identity
+    ]
+}
@@ -0,0 +1,55 @@
+// Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::contract_cache::ValidatorCache;
+use crate::node_status_api::models::ErrorResponse;
+use crate::topology_signer::TopologySigner;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::State;
+use rocket_okapi::openapi;
+use time::OffsetDateTime;
+use validator_api_requests::models::{
+    SignedTopologySnapshot, TopologySnapshot, ValidatorApiIdentity,
+};
+
+#[openapi(tag = "topology")]
+#[get("/signed")]
+pub(crate) async fn get_signed_topology(
+    cache: &State<ValidatorCache>,
+    signer: &State<TopologySigner>,
+) -> Result<Json<SignedTopologySnapshot>, ErrorResponse> {
+    let snapshot = TopologySnapshot {
+        timestamp: OffsetDateTime::now_utc().unix_timestamp(),
+        epoch: cache.current_epoch().await.into_inner(),
+        mixnodes: cache.mixnodes().await,
+        gateways: cache.gateways().await,
+        rewarded_set: cache
+            .rewarded_set()
+            .await
+            .into_inner()
+            .into_iter()
+            .map(|bond| bond.mix_node.identity_key)
+            .collect(),
+        active_set: cache
+            .active_set()
+            .await
+            .into_inner()
+            .into_iter()
+            .map(|bond| bond.mix_node.identity_key)
+            .collect(),
+    };
+
+    signer
+        .sign(&snapshot)
+        .map(Json)
+        .map_err(|err| ErrorResponse::new(err.to_string(), Status::InternalServerError))
+}
+
+#[openapi(tag = "topology")]
+#[get("/identity")]
+pub(crate) async fn get_identity(signer: &State<TopologySigner>) -> Json<ValidatorApiIdentity> {
+    Json(ValidatorApiIdentity {
+        identity_key: signer.identity_key(),
+    })
+}
@@ -1,7 +1,8 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use mixnet_contract_common::reward_params::RewardParams;
+use mixnet_contract_common::reward_params::{EpochRewardParams, RewardParams};
+use mixnet_contract_common::{GatewayBond, IdentityKey, Interval, MixNodeBond};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -147,3 +148,75 @@ impl fmt::Display for InclusionProbabilityResponse {
         )
     }
 }
+
+/// Change of the validator API view of the network, as pushed to the subscribers of its event
+/// stream whenever the underlying contract cache gets refreshed.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ValidatorCacheEvent {
+    MixnodesBonded {
+        mixnodes: Vec<MixNodeBond>,
+    },
+    MixnodesUnbonded {
+        identities: Vec<IdentityKey>,
+    },
+    GatewaysBonded {
+        gateways: Vec<GatewayBond>,
+    },
+    GatewaysUnbonded {
+        identities: Vec<IdentityKey>,
+    },
+    RewardedSetChanged {
+        rewarded_set: Vec<IdentityKey>,
+        active_set: Vec<IdentityKey>,
+    },
+    EpochAdvanced {
+        epoch: Interval,
+        epoch_reward_params: EpochRewardParams,
+    },
+    MixnodesBlacklistChanged {
+        added: Vec<IdentityKey>,
+        removed: Vec<IdentityKey>,
+    },
+    GatewaysBlacklistChanged {
+        added: Vec<IdentityKey>,
+        removed: Vec<IdentityKey>,
+    },
+
+    /// The subscriber was too slow to keep up and missed some events. Its view of the network
+    /// should be refreshed via the regular endpoints.
+    Lagged {
+        missed: u64,
+    },
+}
+
+/// View of the network of a particular validator API at the given time.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TopologySnapshot {
+    pub timestamp: i64,
+    pub epoch: Option<Interval>,
+    /// All bonded mixnodes, excluding the blacklisted ones.
+    pub mixnodes: Vec<MixNodeBond>,
+    /// All bonded gateways, excluding the blacklisted ones.
+    pub gateways: Vec<GatewayBond>,
+    pub rewarded_set: Vec<IdentityKey>,
+    pub active_set: Vec<IdentityKey>,
+}
+
+/// [`TopologySnapshot`] signed with the identity key of the validator API that produced it.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignedTopologySnapshot {
+    /// Base58 encoded ed25519 public key of the signer.
+    pub identity_key: String,
+    /// Json encoded [`TopologySnapshot`]. It is kept in its serialized form so that the signature
+    /// could be verified against the exact signed bytes.
+    pub snapshot: String,
+    /// Base58 encoded ed25519 signature of the `snapshot`.
+    pub signature: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ValidatorApiIdentity {
+    /// Base58 encoded ed25519 public key used for signing the topology snapshots.
+    pub identity_key: String,
+}